@@ -7,27 +7,42 @@
 //! - Darwinian: Fitness-based resource allocation
 
 use axum::{
-    extract::{Path, State, WebSocketUpgrade, ws::{Message, WebSocket}},
+    body::Body,
+    extract::{Extension, Path, Query, Request, State, WebSocketUpgrade, ws::{Message, WebSocket}},
     http::{Method, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::{HashMap, HashSet}, sync::Arc};
 use tokio::sync::{broadcast, RwLock};
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
     compression::CompressionLayer,
 };
-use tracing::info;
+use tracing::{info, warn};
 use chrono::{DateTime, Utc};
 use rand::Rng;
+use actoris_protocol_dna::{DelegatePrimitive, DelegationContract, DnaEvent, DnaMachine, OutcomeAnchor, TransitionError};
+use actoris_protocol_dna::primitives::delegate::{
+    AcceptanceCriteria, DelegationStatus, DisputeRuling, EscrowError, MilestoneSpec,
+};
+use actoris_common::crypto::did::ParsedDid;
+use actoris_common::crypto::frost::{generate_key_shares_trusted, FrostCoordinator, FrostKeyShare, FrostSigner};
+use actoris_common::crypto::merkle::MerkleTree;
+use actoris_common::types::outcome_record::{FrostSignature, OracleVote, OutcomeRecord, VerificationResult};
+use actoris_common::security::{RequestAuthenticator, RequestParts};
+use actoris_common::{HcWallet, Statement, Tranche, TrancheSource};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
 
 // ============ ERROR TYPE ============
 
+#[derive(Debug)]
 struct AppError(StatusCode, String);
 
 impl IntoResponse for AppError {
@@ -36,6 +51,238 @@ impl IntoResponse for AppError {
     }
 }
 
+impl From<TransitionError> for AppError {
+    fn from(e: TransitionError) -> Self {
+        AppError(StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+impl From<EscrowError> for AppError {
+    fn from(e: EscrowError) -> Self {
+        AppError(StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+// ============ HC AMOUNTS ============
+
+/// Decimal places HC amounts are kept to
+const HC_SCALE: u32 = 6;
+
+/// HC amount from a request, at wallet precision
+fn hc(amount: f64) -> Result<Decimal, AppError> {
+    Decimal::from_f64(amount)
+        .map(|amount| amount.round_dp(HC_SCALE))
+        .ok_or(AppError(StatusCode::BAD_REQUEST, format!("Invalid HC amount: {}", amount)))
+}
+
+/// HC amount for JSON responses
+fn hc_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+fn from_millis(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_default()
+}
+
+// ============ REQUEST AUTHENTICATION ============
+
+/// Largest body buffered for signature verification (axum's JSON limit)
+const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// DID that signed the request, if it was signed
+#[derive(Debug, Clone)]
+struct Caller(Option<String>);
+
+impl Caller {
+    /// Check the request was signed by `did`
+    fn require(&self, did: &str) -> Result<(), AppError> {
+        match &self.0 {
+            Some(caller) if caller == did => Ok(()),
+            Some(caller) => Err(AppError(StatusCode::FORBIDDEN, format!("{} cannot act as {}", caller, did))),
+            None => Err(AppError(StatusCode::UNAUTHORIZED, format!("Request must be signed by {}", did))),
+        }
+    }
+
+    /// Check the caller may act as `agent`
+    ///
+    /// Agents without a DID can only be driven by unsigned requests, which
+    /// the gateway accepts only when signatures are turned off.
+    fn authorize(&self, agent: &AgentV2) -> Result<(), AppError> {
+        match (&agent.did, &self.0) {
+            (Some(did), _) => self.require(did),
+            (None, None) => Ok(()),
+            (None, Some(caller)) => Err(AppError(StatusCode::FORBIDDEN, format!("{} cannot act as {}, which has no DID", caller, agent.id))),
+        }
+    }
+
+    /// Check the caller may act as one of `agents`
+    fn authorize_any(&self, agents: &[&AgentV2]) -> Result<(), AppError> {
+        let mut denied = Ok(());
+        for agent in agents {
+            match self.authorize(agent) {
+                Ok(()) => return Ok(()),
+                Err(e) => denied = Err(e),
+            }
+        }
+        denied
+    }
+
+    /// Check the request was signed by a configured arbiter
+    fn require_arbiter(&self, arbiters: &HashSet<String>) -> Result<(), AppError> {
+        match &self.0 {
+            Some(caller) if arbiters.contains(caller) => Ok(()),
+            Some(caller) => Err(AppError(StatusCode::FORBIDDEN, format!("{} is not an arbiter", caller))),
+            None => Err(AppError(StatusCode::UNAUTHORIZED, "Request must be signed by an arbiter".to_string())),
+        }
+    }
+}
+
+/// Verify DID signatures on mutating requests and record the [`Caller`]
+async fn authenticate_request(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(next.run(req).await);
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let body = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES)
+        .await
+        .map_err(|e| AppError(StatusCode::PAYLOAD_TOO_LARGE, e.to_string()))?;
+
+    let request = parts.headers.iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .fold(
+            RequestParts::new(parts.method.as_str(), parts.uri.path(), &body),
+            |request, (name, value)| request.with_header(name, value),
+        );
+
+    let caller = if request.is_signed() {
+        let did = state.request_auth.authenticate(&request)
+            .map_err(|e| AppError(StatusCode::UNAUTHORIZED, e.to_string()))?;
+        Caller(Some(did))
+    } else if state.require_signed_requests {
+        return Err(AppError(StatusCode::UNAUTHORIZED, "Request must be signed".to_string()));
+    } else {
+        Caller(None)
+    };
+
+    parts.extensions.insert(caller);
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+// ============ OUTCOME LEDGER ============
+
+/// Oracles in the verification committee and the threshold that signs
+const ORACLE_COUNT: u16 = 5;
+const ORACLE_THRESHOLD: u16 = 3;
+
+/// FROST-signed outcome records of verified actions, in a Merkle tree that
+/// delegation escrow is released against
+///
+/// The oracles are simulated: the gateway deals and holds every key share
+/// itself, so a signature proves only that this gateway recorded the
+/// verdict, not that independent oracles agreed on it. Each action is
+/// recorded at most once.
+struct OutcomeLedger {
+    oracles: Vec<FrostKeyShare>, // Trusted-dealer shares, one per oracle
+    coordinator: FrostCoordinator,
+    tree: MerkleTree,
+    records: HashMap<String, (OutcomeRecord, u64)>, // action_id -> (record, leaf index)
+}
+
+impl OutcomeLedger {
+    fn new() -> anyhow::Result<Self> {
+        let keys = generate_key_shares_trusted(ORACLE_THRESHOLD, ORACLE_COUNT)?;
+        Ok(Self {
+            oracles: keys.key_shares,
+            coordinator: FrostCoordinator::new(keys.public_key_package, ORACLE_THRESHOLD),
+            tree: MerkleTree::new(),
+            records: HashMap::new(),
+        })
+    }
+
+    /// Have the approving oracles threshold-sign the action's verdict and
+    /// append the record to the tree, returning the aggregate signature
+    async fn record(&mut self, action: &ActionV2, proof: &VerificationProofV2) -> Result<[u8; 64], AppError> {
+        if self.records.contains_key(&action.id) {
+            return Err(AppError(StatusCode::CONFLICT, "Action outcome already recorded".to_string()));
+        }
+        let approving: Vec<usize> = proof.oracle_votes.iter().enumerate()
+            .filter(|(_, vote)| vote.vote)
+            .map(|(i, _)| i)
+            .collect();
+        let votes = proof.oracle_votes.iter().map(|vote| OracleVote {
+            oracle_did: vote.oracle_id.clone(),
+            approved: vote.vote,
+            reason: None,
+            timestamp: vote.timestamp.timestamp_millis(),
+            vrf_proof: None,
+        }).collect();
+
+        // The gateway keeps no payloads; the record is keyed to the action
+        let digest = *blake3::hash(action.id.as_bytes()).as_bytes();
+        let mut record = OutcomeRecord::new(
+            action.producer_id.clone(),
+            action.consumer_id.clone(),
+            action.action_type.clone(),
+            digest,
+            digest,
+            hc(action.pricing.final_price)?,
+            VerificationResult::success(ORACLE_COUNT as u8, proof.latency_ms as u32, votes),
+            FrostSignature::new([0u8; 64], Vec::new(), [0u8; 32], ORACLE_THRESHOLD as u8, ORACLE_COUNT as u8),
+        );
+        record.submitted_at = action.created_at.timestamp_millis();
+
+        let signing_error = |e: actoris_common::error::CryptoError| AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+        let message = record.verdict_message();
+        let signers: Vec<FrostSigner> = approving.iter()
+            .map(|&i| FrostSigner::new(self.oracles[i].clone()))
+            .collect();
+        let mut commitments = Vec::new();
+        for signer in &signers {
+            commitments.push(signer.start_signing(&action.id, &message).await.map_err(signing_error)?);
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            for (j, commitment) in commitments.iter().enumerate() {
+                if i != j {
+                    signer.add_commitment(&action.id, commitment.clone()).await.map_err(signing_error)?;
+                }
+            }
+        }
+        let mut shares = Vec::new();
+        for signer in &signers {
+            shares.push(signer.sign(&action.id).await.map_err(signing_error)?);
+        }
+        let signature = self.coordinator.aggregate(&message, &commitments, &shares).map_err(signing_error)?;
+        record.signature = FrostSignature::new(
+            signature.to_bytes(),
+            approving.iter().map(|i| format!("oracle-{}", i)).collect(),
+            self.coordinator.group_public_key(),
+            ORACLE_THRESHOLD as u8,
+            ORACLE_COUNT as u8,
+        );
+
+        let index = self.tree.append(record.canonical_hash());
+        self.tree.commit();
+        let signature = record.signature.signature;
+        self.records.insert(action.id.clone(), (record, index));
+        Ok(signature)
+    }
+
+    /// An action's record, proven against the current root
+    fn proven(&self, action_id: &str) -> Option<(OutcomeRecord, OutcomeAnchor)> {
+        let (record, index) = self.records.get(action_id)?;
+        let proof = self.tree.generate_proof(*index)?;
+        let mut record = record.clone();
+        record.set_merkle_proof(proof.siblings, proof.root, *index);
+        let anchor = OutcomeAnchor::new(self.coordinator.group_public_key()).with_root(proof.root);
+        Some((record, anchor))
+    }
+}
+
 // ============ STATE ============
 
 #[derive(Clone)]
@@ -46,21 +293,56 @@ struct AppState {
     loans: Arc<RwLock<HashMap<String, Loan>>>,
     policies: Arc<RwLock<HashMap<String, InsurancePolicy>>>,
     delegations: Arc<RwLock<HashMap<String, Delegation>>>,
+    // Ledgered Protocol DNA state: every HC move is a DnaEvent, priced with
+    // the canonical pricing shared with TrustLedger
+    dna: Arc<RwLock<DnaMachine>>,
+    // Signed outcomes of verified actions, which release milestone escrow
+    outcomes: Arc<RwLock<OutcomeLedger>>,
     // External ID mappings (external_id -> internal_id)
     external_agent_map: Arc<RwLock<HashMap<String, String>>>,
     events_tx: broadcast::Sender<Event>,
+    // DID request signatures (nonce cache shared by all routes)
+    request_auth: Arc<RequestAuthenticator>,
+    require_signed_requests: bool,
+    // DIDs allowed to rule on disputed delegations
+    arbiters: Arc<HashSet<String>>,
+}
+
+impl AppState {
+    /// Empty state; unsigned mutations are refused when
+    /// `require_signed_requests` is set
+    fn new(require_signed_requests: bool, arbiters: HashSet<String>) -> anyhow::Result<Self> {
+        let (events_tx, _) = broadcast::channel::<Event>(1000);
+        Ok(Self {
+            agents: Arc::new(RwLock::new(HashMap::new())),
+            actions: Arc::new(RwLock::new(HashMap::new())),
+            loans: Arc::new(RwLock::new(HashMap::new())),
+            policies: Arc::new(RwLock::new(HashMap::new())),
+            delegations: Arc::new(RwLock::new(HashMap::new())),
+            dna: Arc::new(RwLock::new(DnaMachine::new())),
+            outcomes: Arc::new(RwLock::new(OutcomeLedger::new()?)),
+            external_agent_map: Arc::new(RwLock::new(HashMap::new())),
+            events_tx,
+            request_auth: Arc::new(RequestAuthenticator::default()),
+            require_signed_requests,
+            arbiters: Arc::new(arbiters),
+        })
+    }
 }
 
 // ============ MODELS - Matching UI v2 ============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 struct AgentV2 {
     id: String,
     name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    did: Option<String>, // Only requests signed by this DID may act as the agent
     #[serde(rename = "type")]
     agent_type: String, // "human" | "agent" | "organization"
     trust_score: TrustScoreV2,
-    wallet: HCWallet,
+    #[serde(serialize_with = "serialize_wallet")]
+    wallet: HcWallet,
     fitness: FitnessMetrics,
     status: String, // "active" | "warning" | "culled"
     created_at: DateTime<Utc>,
@@ -76,14 +358,96 @@ struct TrustScoreV2 {
     last_updated: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct HCWallet {
+/// Wallet in the shape the UI reads: HC as numbers, `balance` and `reserved`
+#[derive(Debug, Serialize)]
+struct WalletJson {
     id: String,
     balance: f64,    // PFLOP-hours
     reserved: f64,
+    expires_at: DateTime<Utc>, // Soonest tranche expiry
+    tranches: Vec<TrancheJson>, // Soonest expiry first
+    reserved_tranches: Vec<TrancheJson>,
+}
+
+#[derive(Debug, Serialize)]
+struct TrancheJson {
+    amount: f64,
+    source: TrancheSource, // "purchase" | "earnings" | "grant" | "loan"
+    issued_at: DateTime<Utc>,
     expires_at: DateTime<Utc>,
 }
 
+impl From<&Tranche> for TrancheJson {
+    fn from(tranche: &Tranche) -> Self {
+        Self {
+            amount: hc_f64(tranche.amount),
+            source: tranche.source,
+            issued_at: from_millis(tranche.issued_at),
+            expires_at: from_millis(tranche.expires_at),
+        }
+    }
+}
+
+impl From<&HcWallet> for WalletJson {
+    fn from(wallet: &HcWallet) -> Self {
+        Self {
+            id: wallet.owner_did.clone(),
+            balance: hc_f64(wallet.available),
+            reserved: hc_f64(wallet.locked),
+            expires_at: from_millis(wallet.expires_at),
+            tranches: wallet.tranches.iter().map(TrancheJson::from).collect(),
+            reserved_tranches: wallet.locked_tranches.iter().map(TrancheJson::from).collect(),
+        }
+    }
+}
+
+fn serialize_wallet<S: serde::Serializer>(wallet: &HcWallet, serializer: S) -> Result<S::Ok, S::Error> {
+    WalletJson::from(wallet).serialize(serializer)
+}
+
+/// Open an agent's wallet on the DNA machine, minting `balance` HC from
+/// `source`; returns the wallet snapshot kept on the agent
+fn open_wallet(
+    dna: &mut DnaMachine,
+    agent_id: &str,
+    balance: Decimal,
+    source: TrancheSource,
+) -> Result<HcWallet, AppError> {
+    if balance > Decimal::ZERO {
+        dna.mint_from(agent_id, balance, source)?;
+    }
+    Ok(dna.wallet(agent_id).cloned().unwrap_or_else(|| HcWallet::new(agent_id.to_string())))
+}
+
+/// Refresh the agents' wallet snapshots from the DNA machine
+fn sync_wallets(agents: &mut HashMap<String, AgentV2>, dna: &DnaMachine, ids: &[&str]) {
+    for id in ids {
+        if let (Some(agent), Some(wallet)) = (agents.get_mut(*id), dna.wallet(id)) {
+            agent.wallet = wallet.clone();
+        }
+    }
+}
+
+/// Id of the position a DNA command opened
+fn opened(event: &DnaEvent) -> String {
+    event.opened_position().unwrap_or_default().to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpcomingExpiration {
+    amount: f64,
+    source: TrancheSource,
+    expires_at: DateTime<Utc>,
+    days_until_expiry: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct WalletView {
+    agent_id: String,
+    wallet: WalletJson,
+    upcoming_expirations: Vec<UpcomingExpiration>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FitnessMetrics {
     eta: f64,        // η = τ × (Revenue/Cost)
@@ -168,6 +532,7 @@ struct Loan {
     lender_id: String,
     borrower_id: String,
     principal: f64,        // HC amount
+    collateral: f64,       // HC locked by the borrower
     interest_rate: f64,    // APR (based on trust)
     term_days: u32,
     status: String,        // "active" | "repaid" | "defaulted"
@@ -190,8 +555,21 @@ struct InsurancePolicy {
     expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Delegation escrowed in the DNA machine and settled by the protocol's
+/// milestone contract
+#[derive(Debug, Clone, Serialize)]
+#[serde(into = "DelegationJson")]
 struct Delegation {
+    contract: DelegationContract,
+    all_or_nothing: bool,            // One milestone, released on the client's acceptance
+    action_ids: Vec<Option<String>>, // Verified action that released each milestone
+    created_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+/// Delegation in the shape the UI reads
+#[derive(Debug, Serialize)]
+struct DelegationJson {
     id: String,
     client_id: String,
     agent_id: String,
@@ -201,6 +579,51 @@ struct Delegation {
     created_at: DateTime<Utc>,
     deadline: DateTime<Utc>,
     completed_at: Option<DateTime<Utc>>,
+    milestones: Vec<DelegationMilestone>, // Released in order; empty = all-or-nothing
+}
+
+#[derive(Debug, Serialize)]
+struct DelegationMilestone {
+    description: String,
+    escrow_amount: f64,
+    deadline: DateTime<Utc>,
+    action_type: Option<String>, // Required action type of the delivering action
+    status: String,              // "pending" | "released" | "refunded" | "frozen" | "settled"
+    action_id: Option<String>,   // Verified action that released the milestone
+}
+
+impl From<Delegation> for DelegationJson {
+    fn from(delegation: Delegation) -> Self {
+        let Delegation { contract, all_or_nothing, action_ids, created_at, completed_at } = delegation;
+        let status = match contract.status {
+            DelegationStatus::Active if all_or_nothing => "pending".to_string(),
+            status => format!("{:?}", status).to_lowercase(),
+        };
+        let milestones = if all_or_nothing {
+            Vec::new()
+        } else {
+            contract.milestones.into_iter().zip(action_ids).map(|(milestone, action_id)| DelegationMilestone {
+                description: milestone.description,
+                escrow_amount: hc_f64(milestone.escrow_amount),
+                deadline: from_millis(milestone.deadline),
+                action_type: milestone.acceptance.action_type,
+                status: format!("{:?}", milestone.status).to_lowercase(),
+                action_id,
+            }).collect()
+        };
+        DelegationJson {
+            id: contract.id,
+            client_id: contract.client_did,
+            agent_id: contract.agent_did,
+            task_description: contract.task_description,
+            escrow_amount: hc_f64(contract.escrow_amount),
+            status,
+            created_at,
+            deadline: from_millis(contract.deadline),
+            completed_at,
+            milestones,
+        }
+    }
 }
 
 // ============ REQUEST TYPES ============
@@ -210,6 +633,7 @@ struct CreateAgentRequest {
     name: String,
     #[serde(rename = "type")]
     agent_type: Option<String>,
+    did: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -230,6 +654,15 @@ struct VerifyActionRequest {
 struct SpawnAgentRequest {
     parent_id: String,
     name: String,
+    did: Option<String>, // DID the child acts as; defaults to the parent's
+    #[serde(default)]
+    stake: f64,     // HC the parent locks for the child's lifetime
+    #[serde(default = "default_endowment")]
+    endowment: f64, // HC the parent gives the child
+}
+
+fn default_endowment() -> f64 {
+    100.0
 }
 
 #[derive(Debug, Deserialize)]
@@ -238,6 +671,8 @@ struct CreateLoanRequest {
     borrower_id: String,
     principal: f64,
     term_days: u32,
+    #[serde(default)]
+    collateral_pct: f64, // Share of principal the borrower locks
 }
 
 #[derive(Debug, Deserialize)]
@@ -256,6 +691,27 @@ struct CreateDelegationRequest {
     task_description: String,
     escrow_amount: f64,
     deadline_days: u32,
+    #[serde(default)]
+    milestones: Vec<CreateMilestoneRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateMilestoneRequest {
+    description: String,
+    escrow_amount: f64,
+    deadline_days: u32,
+    action_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseMilestoneRequest {
+    action_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolveDisputeRequest {
+    to_agent: f64,   // HC paid to the agent
+    forfeited: f64,  // HC removed from escrow
 }
 
 // ============ EXTERNAL INTEGRATION (HUMAIN ONE / Grok) ============
@@ -384,11 +840,20 @@ async fn list_agents(State(state): State<AppState>) -> Json<Vec<AgentV2>> {
 
 async fn create_agent(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<CreateAgentRequest>,
-) -> Json<AgentV2> {
+) -> Result<Json<AgentV2>, AppError> {
+    // Registering a DID requires proving control of it; without one the
+    // agent is bound to the signer
+    if let Some(did) = &req.did {
+        ParsedDid::parse(did)
+            .map_err(|e| AppError(StatusCode::BAD_REQUEST, e.to_string()))?;
+        caller.require(did)?;
+    }
+    let did = req.did.or_else(|| caller.0.clone());
+
     // Generate all random values before any await points to avoid Send issues
     let id = format!("agent-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-    let wallet_id = format!("wallet-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
 
     let (initial_score, balance, revenue, cost, hc_allocation) = {
         let mut rng = rand::thread_rng();
@@ -407,6 +872,7 @@ async fn create_agent(
     let agent = AgentV2 {
         id: id.clone(),
         name: req.name,
+        did,
         agent_type: req.agent_type.unwrap_or_else(|| "agent".to_string()),
         trust_score: TrustScoreV2 {
             score: initial_score,
@@ -421,12 +887,7 @@ async fn create_agent(
             disputes: 0,
             last_updated: Utc::now(),
         },
-        wallet: HCWallet {
-            id: wallet_id,
-            balance,
-            reserved: 0.0,
-            expires_at: Utc::now() + chrono::Duration::days(30),
-        },
+        wallet: open_wallet(&mut *state.dna.write().await, &id, hc(balance)?, TrancheSource::Purchase)?,
         fitness: FitnessMetrics {
             eta: tau * (revenue / cost),
             revenue,
@@ -449,7 +910,7 @@ async fn create_agent(
     });
 
     info!("Created agent: {}", id);
-    Json(agent)
+    Ok(Json(agent))
 }
 
 async fn get_agent(
@@ -463,6 +924,48 @@ async fn get_agent(
         .ok_or(AppError(StatusCode::NOT_FOUND, "Agent not found".to_string()))
 }
 
+async fn get_agent_wallet(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<WalletView>, AppError> {
+    let agents = state.agents.read().await;
+    let agent = agents.get(&agent_id)
+        .ok_or(AppError(StatusCode::NOT_FOUND, "Agent not found".to_string()))?;
+    let now = Utc::now();
+    Ok(Json(WalletView {
+        agent_id,
+        upcoming_expirations: agent.wallet.upcoming_expirations(now.timestamp_millis())
+            .map(|t| UpcomingExpiration {
+                amount: hc_f64(t.amount),
+                source: t.source,
+                expires_at: from_millis(t.expires_at),
+                days_until_expiry: t.days_until_expiry(now.timestamp_millis()),
+            })
+            .collect(),
+        wallet: WalletJson::from(&agent.wallet),
+    }))
+}
+
+/// Statement period in Unix milliseconds; defaults to the whole history
+#[derive(Debug, Deserialize)]
+struct StatementQuery {
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+async fn get_agent_statement(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Query(period): Query<StatementQuery>,
+) -> Result<Json<Statement>, AppError> {
+    if !state.agents.read().await.contains_key(&agent_id) {
+        return Err(AppError(StatusCode::NOT_FOUND, "Agent not found".to_string()));
+    }
+    let from = period.from.unwrap_or(0);
+    let to = period.to.unwrap_or(i64::MAX);
+    Ok(Json(state.dna.read().await.statement(&agent_id, from, to)))
+}
+
 async fn list_actions(State(state): State<AppState>) -> Json<Vec<ActionV2>> {
     let actions = state.actions.read().await;
     let mut all: Vec<_> = actions.values().cloned().collect();
@@ -472,8 +975,16 @@ async fn list_actions(State(state): State<AppState>) -> Json<Vec<ActionV2>> {
 
 async fn submit_action(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<SubmitActionRequest>,
-) -> Json<ActionV2> {
+) -> Result<Json<ActionV2>, AppError> {
+    {
+        let agents = state.agents.read().await;
+        let producer = agents.get(&req.producer_id)
+            .ok_or(AppError(StatusCode::NOT_FOUND, "Producer not found".to_string()))?;
+        caller.authorize(producer)?;
+    }
+
     let id = format!("act-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
 
     // Calculate OneBill pricing: Price = Compute + Risk - Trust
@@ -513,11 +1024,16 @@ async fn submit_action(
     });
 
     info!("Submitted action: {}", id);
-    Json(action)
+    Ok(Json(action))
 }
 
+/// Put an action to the (simulated) oracle committee, once
+///
+/// Votes are drawn at random on behalf of the oracles; only the action's
+/// consumer may request them, and a verdict, verified or disputed, is final.
 async fn verify_action(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Path(action_id): Path<String>,
 ) -> Result<Json<ActionV2>, AppError> {
     // Generate all random values before any await points
@@ -539,20 +1055,35 @@ async fn verify_action(
     }).collect();
 
     let yes_votes = oracle_votes.iter().filter(|v| v.vote).count();
-    let quorum_reached = yes_votes >= 3;
+    let quorum_reached = yes_votes >= ORACLE_THRESHOLD as usize;
 
     let mut actions = state.actions.write().await;
 
     let action = actions.get_mut(&action_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Action not found".to_string()))?;
+    {
+        let agents = state.agents.read().await;
+        let consumer = agents.get(&action.consumer_id)
+            .ok_or(AppError(StatusCode::NOT_FOUND, "Consumer not found".to_string()))?;
+        caller.authorize(consumer)?;
+    }
+    if action.verification.is_some() {
+        return Err(AppError(StatusCode::CONFLICT, "Action already verified".to_string()));
+    }
 
-    action.verification = Some(VerificationProofV2 {
+    let mut proof = VerificationProofV2 {
         oracle_votes,
         quorum_reached,
-        quorum_threshold: "3-of-5".to_string(),
-        aggregate_signature: format!("0x{}", hex::encode(&rand::random::<[u8; 32]>())),
+        quorum_threshold: format!("{}-of-{}", ORACLE_THRESHOLD, ORACLE_COUNT),
+        aggregate_signature: String::new(),
         latency_ms,
-    });
+    };
+    // Only a quorum signs; its record is what delegation escrow releases on
+    if quorum_reached {
+        let signature = state.outcomes.write().await.record(action, &proof).await?;
+        proof.aggregate_signature = format!("0x{}", hex::encode(signature));
+    }
+    action.verification = Some(proof);
 
     action.status = if quorum_reached { "verified".to_string() } else { "disputed".to_string() };
     action.verified_at = Some(Utc::now());
@@ -600,27 +1131,41 @@ async fn get_leaderboard(State(state): State<AppState>) -> Json<Vec<AgentV2>> {
 // SPAWN: Create child agent with 30% trust inheritance
 async fn spawn_agent(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<SpawnAgentRequest>,
 ) -> Result<Json<AgentV2>, AppError> {
     // Generate random values before await
     let id = format!("agent-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-    let wallet_id = format!("wallet-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
     let hc_allocation = {
         let mut rng = rand::thread_rng();
         rng.gen_range(50.0..200.0f64)
     };
 
-    let agents = state.agents.read().await;
+    let mut agents = state.agents.write().await;
     let parent = agents.get(&req.parent_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Parent agent not found".to_string()))?;
+    caller.authorize(parent)?;
 
-    // 30% trust inheritance cap
-    let inherited_score = (parent.trust_score.score as f64 * 0.30) as u16;
+    // The child acts as its own DID if the parent names one, else the parent controls it
+    if let Some(did) = &req.did {
+        ParsedDid::parse(did)
+            .map_err(|e| AppError(StatusCode::BAD_REQUEST, e.to_string()))?;
+    }
+    let did = req.did.clone().or_else(|| parent.did.clone());
+
+    // The parent stakes and endows the child; trust is capped at 30% of the parent's
+    let mut dna = state.dna.write().await;
+    let spawned = dna.spawn(&req.parent_id, parent.trust_score.tau, &id, hc(req.stake)?, hc(req.endowment)?)?;
+    let DnaEvent::Spawned { inherited_tau, .. } = spawned else {
+        return Err(AppError(StatusCode::INTERNAL_SERVER_ERROR, "Unexpected spawn event".to_string()));
+    };
+    let inherited_score = (inherited_tau * 1000.0) as u16;
     let tau = inherited_score as f64 / 1000.0;
 
     let child = AgentV2 {
         id: id.clone(),
         name: req.name.clone(),
+        did,
         agent_type: "agent".to_string(),
         trust_score: TrustScoreV2 {
             score: inherited_score,
@@ -635,12 +1180,7 @@ async fn spawn_agent(
             disputes: 0,
             last_updated: Utc::now(),
         },
-        wallet: HCWallet {
-            id: wallet_id,
-            balance: 100.0, // Starting balance
-            reserved: 0.0,
-            expires_at: Utc::now() + chrono::Duration::days(30),
-        },
+        wallet: dna.wallet(&id).cloned().unwrap_or_else(|| HcWallet::new(id.clone())),
         fitness: FitnessMetrics {
             eta: tau * 0.5, // Start with low fitness
             revenue: 0.0,
@@ -652,8 +1192,10 @@ async fn spawn_agent(
         created_at: Utc::now(),
     };
 
+    sync_wallets(&mut agents, &dna, &[&req.parent_id]);
+    drop(dna);
+    agents.insert(id.clone(), child.clone());
     drop(agents);
-    state.agents.write().await.insert(id.clone(), child.clone());
 
     let _ = state.events_tx.send(Event {
         event_type: "agent_spawned".to_string(),
@@ -671,29 +1213,42 @@ async fn spawn_agent(
 // LEND: Create risk-priced loan based on TrustScore
 async fn create_loan(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<CreateLoanRequest>,
 ) -> Result<Json<Loan>, AppError> {
-    let agents = state.agents.read().await;
+    let mut agents = state.agents.write().await;
 
     let borrower = agents.get(&req.borrower_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Borrower not found".to_string()))?;
 
-    let _lender = agents.get(&req.lender_id)
+    let lender = agents.get(&req.lender_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Lender not found".to_string()))?;
-
-    // Calculate interest rate based on borrower's trust score
-    // Higher trust = lower rate. Base rate 3.2% APR
-    let base_rate = 0.032;
-    let tau = borrower.trust_score.tau;
-    let interest_rate = base_rate * (2.0 - tau); // Range: 3.2% to 6.4%
-
-    let id = format!("loan-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
+    caller.authorize(lender)?;
+
+    // Principal moves to the borrower against locked collateral, at a rate
+    // priced on the borrower's trust: higher trust = lower rate, 5% to 10% APR
+    let mut dna = state.dna.write().await;
+    let issued = dna.lend(
+        &req.lender_id,
+        &req.borrower_id,
+        borrower.trust_score.tau,
+        hc(req.principal)?,
+        req.collateral_pct,
+        req.term_days,
+    )?;
+    let id = opened(&issued);
+    let DnaEvent::LoanIssued { principal, collateral, interest_rate, .. } = issued else {
+        return Err(AppError(StatusCode::INTERNAL_SERVER_ERROR, "Unexpected loan event".to_string()));
+    };
+    sync_wallets(&mut agents, &dna, &[&req.lender_id, &req.borrower_id]);
+    drop(dna);
 
     let loan = Loan {
         id: id.clone(),
         lender_id: req.lender_id.clone(),
         borrower_id: req.borrower_id.clone(),
-        principal: req.principal,
+        principal: hc_f64(principal),
+        collateral: hc_f64(collateral),
         interest_rate,
         term_days: req.term_days,
         status: "active".to_string(),
@@ -723,25 +1278,30 @@ async fn list_loans(State(state): State<AppState>) -> Json<Vec<Loan>> {
 // INSURE: Create insurance policy with trust-based premium
 async fn create_insurance(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<CreateInsuranceRequest>,
 ) -> Result<Json<InsurancePolicy>, AppError> {
-    let agents = state.agents.read().await;
+    let mut agents = state.agents.write().await;
 
     let insured = agents.get(&req.insured_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Insured entity not found".to_string()))?;
 
-    let _insurer = agents.get(&req.insurer_id)
+    let insurer = agents.get(&req.insurer_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Insurer not found".to_string()))?;
-
-    // Calculate premium based on trust score
-    // Lower trust = higher premium. Base rate 8-12%
-    let tau = insured.trust_score.tau;
-    let risk_factor = 1.0 + (1.0 - tau); // 1.0 to 2.0
-    let base_failure_prob = 0.05; // 5% base failure probability
-    let premium_rate = base_failure_prob * risk_factor; // 5% to 10%
-    let premium = req.coverage * premium_rate;
-
-    let id = format!("policy-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
+    caller.authorize(insurer)?;
+
+    // The insured pays a premium priced on its trust score (lower trust =
+    // higher premium, 5% to 10% of coverage); the insurer locks the coverage
+    let mut dna = state.dna.write().await;
+    let premium_rate = dna.pricing().premium_rate(insured.trust_score.tau);
+    let issued = dna.insure(&req.insurer_id, &req.insured_id, insured.trust_score.tau, hc(req.coverage)?)?;
+    let id = opened(&issued);
+    let DnaEvent::PolicyIssued { premium, .. } = issued else {
+        return Err(AppError(StatusCode::INTERNAL_SERVER_ERROR, "Unexpected policy event".to_string()));
+    };
+    let premium = hc_f64(premium);
+    sync_wallets(&mut agents, &dna, &[&req.insurer_id, &req.insured_id]);
+    drop(dna);
 
     let policy = InsurancePolicy {
         id: id.clone(),
@@ -777,33 +1337,68 @@ async fn list_policies(State(state): State<AppState>) -> Json<Vec<InsurancePolic
 // DELEGATE: Create task delegation with escrow
 async fn create_delegation(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<CreateDelegationRequest>,
 ) -> Result<Json<Delegation>, AppError> {
+    let now = Utc::now();
+    let deadline = |days: u32| (now + chrono::Duration::days(days as i64)).timestamp_millis();
+
+    // An all-or-nothing delegation is a single milestone the client accepts
+    let all_or_nothing = req.milestones.is_empty();
+    let specs = if all_or_nothing {
+        vec![MilestoneSpec {
+            description: req.task_description.clone(),
+            escrow_amount: hc(req.escrow_amount)?,
+            deadline: deadline(req.deadline_days),
+            acceptance: AcceptanceCriteria::default(),
+        }]
+    } else {
+        req.milestones.into_iter().map(|m| Ok(MilestoneSpec {
+            description: m.description,
+            escrow_amount: hc(m.escrow_amount)?,
+            deadline: deadline(m.deadline_days),
+            acceptance: AcceptanceCriteria {
+                action_type: m.action_type,
+                ..Default::default()
+            },
+        })).collect::<Result<_, AppError>>()?
+    };
+    let milestone_count = specs.len();
+    let mut contract = DelegatePrimitive::open_milestone_contract(
+        String::new(),
+        req.client_id.clone(),
+        req.agent_id.clone(),
+        req.task_description,
+        specs,
+    )?;
+
     let mut agents = state.agents.write().await;
 
-    let client = agents.get_mut(&req.client_id)
+    let client = agents.get(&req.client_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Client not found".to_string()))?;
-
-    // Check if client has enough balance
-    if client.wallet.balance < req.escrow_amount {
-        return Err(AppError(StatusCode::BAD_REQUEST, "Insufficient balance".to_string()));
+    caller.authorize(client)?;
+    if !agents.contains_key(&req.agent_id) {
+        return Err(AppError(StatusCode::NOT_FOUND, "Agent not found".to_string()));
     }
 
-    // Lock escrow
-    client.wallet.balance -= req.escrow_amount;
-    client.wallet.reserved += req.escrow_amount;
-
-    let id = format!("delegation-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-
+    // Lock escrow from the client's soonest-expiring tranches; the contract
+    // settles the position it opens
+    let mut dna = state.dna.write().await;
+    let locked = dna.delegate(&req.client_id, &req.agent_id, contract.escrow_amount)
+        .map_err(|e| match e {
+            TransitionError::Wallet(_) => AppError(StatusCode::BAD_REQUEST, "Insufficient balance".to_string()),
+            e => e.into(),
+        })?;
+    contract.id = opened(&locked);
+    sync_wallets(&mut agents, &dna, &[&req.client_id]);
+    drop(dna);
+
+    let id = contract.id.clone();
     let delegation = Delegation {
-        id: id.clone(),
-        client_id: req.client_id.clone(),
-        agent_id: req.agent_id.clone(),
-        task_description: req.task_description,
-        escrow_amount: req.escrow_amount,
-        status: "pending".to_string(),
-        created_at: Utc::now(),
-        deadline: Utc::now() + chrono::Duration::days(req.deadline_days as i64),
+        contract,
+        all_or_nothing,
+        action_ids: vec![None; milestone_count],
+        created_at: now,
         completed_at: None,
     };
 
@@ -816,7 +1411,7 @@ async fn create_delegation(
         timestamp: Utc::now(),
     });
 
-    info!("Created delegation {} (escrow: {} HC)", id, req.escrow_amount);
+    info!("Created delegation {} (escrow: {} HC)", id, delegation.contract.escrow_amount);
     Ok(Json(delegation))
 }
 
@@ -825,8 +1420,10 @@ async fn list_delegations(State(state): State<AppState>) -> Json<Vec<Delegation>
     Json(delegations.values().cloned().collect())
 }
 
+/// Release an all-or-nothing delegation's escrow on the client's acceptance
 async fn complete_delegation(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Path(delegation_id): Path<String>,
 ) -> Result<Json<Delegation>, AppError> {
     let mut delegations = state.delegations.write().await;
@@ -834,22 +1431,24 @@ async fn complete_delegation(
 
     let delegation = delegations.get_mut(&delegation_id)
         .ok_or(AppError(StatusCode::NOT_FOUND, "Delegation not found".to_string()))?;
-
-    if delegation.status != "pending" && delegation.status != "active" {
-        return Err(AppError(StatusCode::BAD_REQUEST, "Delegation already completed".to_string()));
+    let (client_id, agent_id) = (delegation.contract.client_did.clone(), delegation.contract.agent_did.clone());
+    if let Some(client) = agents.get(&client_id) {
+        caller.authorize(client)?;
+    }
+    if !delegation.all_or_nothing {
+        return Err(AppError(StatusCode::BAD_REQUEST, "Milestone delegations are released per milestone".to_string()));
     }
 
     // Release escrow to agent
-    if let Some(client) = agents.get_mut(&delegation.client_id) {
-        client.wallet.reserved -= delegation.escrow_amount;
+    let now = Utc::now();
+    let mut dna = state.dna.write().await;
+    let released = delegation.contract.accept_in(now.timestamp_millis(), &mut dna.escrow(&delegation_id))?;
+    sync_wallets(&mut agents, &dna, &[&client_id, &agent_id]);
+    drop(dna);
+    if let Some(agent) = agents.get_mut(&agent_id) {
+        agent.fitness.revenue += hc_f64(released);
     }
-    if let Some(agent) = agents.get_mut(&delegation.agent_id) {
-        agent.wallet.balance += delegation.escrow_amount;
-        agent.fitness.revenue += delegation.escrow_amount;
-    }
-
-    delegation.status = "completed".to_string();
-    delegation.completed_at = Some(Utc::now());
+    delegation.completed_at = Some(now);
 
     let delegation_clone = delegation.clone();
 
@@ -863,15 +1462,192 @@ async fn complete_delegation(
     Ok(Json(delegation_clone))
 }
 
+/// Release the next milestone against the signed outcome of a verified
+/// action that delivers it
+async fn release_milestone(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Path(delegation_id): Path<String>,
+    Json(req): Json<ReleaseMilestoneRequest>,
+) -> Result<Json<Delegation>, AppError> {
+    let outcomes = state.outcomes.read().await;
+    let mut delegations = state.delegations.write().await;
+    let mut agents = state.agents.write().await;
+
+    // An outcome pays for one milestone, in one delegation
+    let spent = Some(req.action_id.clone());
+    if delegations.values().any(|d| d.action_ids.contains(&spent)) {
+        return Err(AppError(StatusCode::CONFLICT, "Action already released a milestone".to_string()));
+    }
+    let delegation = delegations.get_mut(&delegation_id)
+        .ok_or(AppError(StatusCode::NOT_FOUND, "Delegation not found".to_string()))?;
+    let (client_id, agent_id) = (delegation.contract.client_did.clone(), delegation.contract.agent_did.clone());
+    if let Some(client) = agents.get(&client_id) {
+        caller.authorize(client)?;
+    }
+    if delegation.all_or_nothing {
+        return Err(AppError(StatusCode::BAD_REQUEST, "All-or-nothing delegations are released on completion".to_string()));
+    }
+    let (record, anchor) = outcomes.proven(&req.action_id)
+        .ok_or(AppError(StatusCode::BAD_REQUEST, "Action has no signed outcome".to_string()))?;
+    let index = delegation.contract.next_milestone().map(|m| m.index);
+
+    // The contract checks the outcome's signature, ledger inclusion, parties,
+    // deadline and acceptance criteria before paying the agent
+    let now = Utc::now();
+    let mut dna = state.dna.write().await;
+    let amount = delegation.contract.release_milestone_in(
+        &record,
+        &anchor,
+        now.timestamp_millis(),
+        &mut dna.escrow(&delegation_id),
+    )?;
+    sync_wallets(&mut agents, &dna, &[&client_id, &agent_id]);
+    drop(dna);
+    if let Some(agent) = agents.get_mut(&agent_id) {
+        agent.fitness.revenue += hc_f64(amount);
+    }
+    if let Some(index) = index {
+        delegation.action_ids[index] = Some(req.action_id.clone());
+    }
+    if delegation.contract.status == DelegationStatus::Completed {
+        delegation.completed_at = Some(now);
+    }
+
+    let delegation_clone = delegation.clone();
+
+    let _ = state.events_tx.send(Event {
+        event_type: "delegation_milestone_released".to_string(),
+        payload: serde_json::to_value(&delegation_clone).unwrap(),
+        timestamp: Utc::now(),
+    });
+
+    info!("Released {} HC on delegation {} for action {}", amount, delegation_id, req.action_id);
+    Ok(Json(delegation_clone))
+}
+
+/// Cancel a delegation, refunding all escrow not yet released
+async fn cancel_delegation(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Path(delegation_id): Path<String>,
+) -> Result<Json<Delegation>, AppError> {
+    let mut delegations = state.delegations.write().await;
+    let mut agents = state.agents.write().await;
+
+    let delegation = delegations.get_mut(&delegation_id)
+        .ok_or(AppError(StatusCode::NOT_FOUND, "Delegation not found".to_string()))?;
+    let client_id = delegation.contract.client_did.clone();
+    if let Some(client) = agents.get(&client_id) {
+        caller.authorize(client)?;
+    }
+
+    let now = Utc::now();
+    let mut dna = state.dna.write().await;
+    let refund = delegation.contract.cancel_in(now.timestamp_millis(), &mut dna.escrow(&delegation_id))?;
+    sync_wallets(&mut agents, &dna, &[&client_id]);
+    drop(dna);
+    delegation.completed_at = Some(now);
+
+    let delegation_clone = delegation.clone();
+
+    let _ = state.events_tx.send(Event {
+        event_type: "delegation_cancelled".to_string(),
+        payload: serde_json::to_value(&delegation_clone).unwrap(),
+        timestamp: Utc::now(),
+    });
+
+    info!("Cancelled delegation {} (refunded: {} HC)", delegation_id, refund);
+    Ok(Json(delegation_clone))
+}
+
+/// Dispute a delegation, freezing the remaining escrow until an arbiter
+/// resolves it; either party may dispute
+async fn dispute_delegation(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Path(delegation_id): Path<String>,
+) -> Result<Json<Delegation>, AppError> {
+    let mut delegations = state.delegations.write().await;
+    let agents = state.agents.read().await;
+
+    let delegation = delegations.get_mut(&delegation_id)
+        .ok_or(AppError(StatusCode::NOT_FOUND, "Delegation not found".to_string()))?;
+    let parties: Vec<&AgentV2> = [&delegation.contract.client_did, &delegation.contract.agent_did]
+        .into_iter()
+        .filter_map(|id| agents.get(id))
+        .collect();
+    caller.authorize_any(&parties)?;
+    drop(agents);
+
+    delegation.contract.dispute()?;
+
+    let delegation_clone = delegation.clone();
+
+    let _ = state.events_tx.send(Event {
+        event_type: "delegation_disputed".to_string(),
+        payload: serde_json::to_value(&delegation_clone).unwrap(),
+        timestamp: Utc::now(),
+    });
+
+    info!("Disputed delegation {}", delegation_id);
+    Ok(Json(delegation_clone))
+}
+
+/// Settle a disputed delegation's frozen escrow; anything not paid to the
+/// agent or forfeited is refunded to the client. Only arbiters may rule.
+async fn resolve_delegation_dispute(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Path(delegation_id): Path<String>,
+    Json(req): Json<ResolveDisputeRequest>,
+) -> Result<Json<Delegation>, AppError> {
+    caller.require_arbiter(&state.arbiters)?;
+
+    let mut delegations = state.delegations.write().await;
+    let mut agents = state.agents.write().await;
+
+    let delegation = delegations.get_mut(&delegation_id)
+        .ok_or(AppError(StatusCode::NOT_FOUND, "Delegation not found".to_string()))?;
+    let (client_id, agent_id) = (delegation.contract.client_did.clone(), delegation.contract.agent_did.clone());
+    let frozen = delegation.contract.locked_amount();
+    let ruling = DisputeRuling {
+        to_agent: hc(req.to_agent)?,
+        forfeited: hc(req.forfeited)?,
+    };
+
+    let now = Utc::now();
+    let mut dna = state.dna.write().await;
+    delegation.contract.resolve_dispute_in(ruling, now.timestamp_millis(), &mut dna.escrow(&delegation_id))?;
+    sync_wallets(&mut agents, &dna, &[&client_id, &agent_id]);
+    drop(dna);
+    if let Some(agent) = agents.get_mut(&agent_id) {
+        agent.fitness.revenue += req.to_agent;
+    }
+    delegation.completed_at = Some(now);
+
+    let delegation_clone = delegation.clone();
+
+    let _ = state.events_tx.send(Event {
+        event_type: "delegation_dispute_resolved".to_string(),
+        payload: serde_json::to_value(&delegation_clone).unwrap(),
+        timestamp: Utc::now(),
+    });
+
+    info!("Resolved dispute on delegation {} (agent: {} HC, forfeited: {} HC, refunded: {} HC)",
+          delegation_id, ruling.to_agent, ruling.forfeited, frozen - ruling.to_agent - ruling.forfeited);
+    Ok(Json(delegation_clone))
+}
+
 // ============ EXTERNAL INTEGRATION HANDLERS ============
 
 /// Ingest agent from HUMAIN ONE, Grok, or other orchestration platforms
 async fn ingest_agent(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<IngestAgentRequest>,
-) -> Json<IngestResponse> {
+) -> Result<Json<IngestResponse>, AppError> {
     let id = format!("agent-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-    let wallet_id = format!("wallet-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
 
     let initial_score = req.initial_trust_score.unwrap_or(500);
     let tau = initial_score as f64 / 1000.0;
@@ -880,6 +1656,7 @@ async fn ingest_agent(
     let agent = AgentV2 {
         id: id.clone(),
         name: req.name.clone(),
+        did: caller.0.clone(), // The ingesting platform acts for the agent
         agent_type: req.agent_type.unwrap_or_else(|| "agent".to_string()),
         trust_score: TrustScoreV2 {
             score: initial_score,
@@ -894,12 +1671,7 @@ async fn ingest_agent(
             disputes: 0,
             last_updated: Utc::now(),
         },
-        wallet: HCWallet {
-            id: wallet_id,
-            balance,
-            reserved: 0.0,
-            expires_at: Utc::now() + chrono::Duration::days(30),
-        },
+        wallet: open_wallet(&mut *state.dna.write().await, &id, hc(balance)?, TrancheSource::Purchase)?,
         fitness: FitnessMetrics {
             eta: tau,
             revenue: 0.0,
@@ -927,17 +1699,18 @@ async fn ingest_agent(
 
     info!("Ingested agent {} from {} (external_id: {})", id, req.source, req.external_id);
 
-    Json(IngestResponse {
+    Ok(Json(IngestResponse {
         success: true,
         id,
         external_id: Some(req.external_id),
         message: format!("Agent ingested from {}", req.source),
-    })
+    }))
 }
 
 /// Ingest action from external platform
 async fn ingest_action(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<IngestActionRequest>,
 ) -> Result<Json<IngestResponse>, AppError> {
     // Resolve external IDs to internal IDs
@@ -955,6 +1728,13 @@ async fn ingest_action(
 
     drop(external_map);
 
+    {
+        let agents = state.agents.read().await;
+        let producer = agents.get(&producer_id)
+            .ok_or(AppError(StatusCode::NOT_FOUND, "Producer not found".to_string()))?;
+        caller.authorize(producer)?;
+    }
+
     let id = format!("act-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
 
     // Calculate pricing
@@ -1013,6 +1793,7 @@ async fn ingest_action(
 /// Batch ingest for bulk imports
 async fn batch_ingest(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(req): Json<BatchIngestRequest>,
 ) -> Json<BatchIngestResponse> {
     let mut agents_created = 0;
@@ -1023,13 +1804,24 @@ async fn batch_ingest(
     if let Some(agents) = req.agents {
         for agent_req in agents {
             let id = format!("agent-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-            let wallet_id = format!("wallet-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
+            let wallet = match hc(agent_req.initial_balance.unwrap_or(1000.0)) {
+                Ok(balance) => open_wallet(&mut *state.dna.write().await, &id, balance, TrancheSource::Purchase),
+                Err(e) => Err(e),
+            };
+            let wallet = match wallet {
+                Ok(wallet) => wallet,
+                Err(AppError(_, e)) => {
+                    errors.push(format!("Agent {}: {}", agent_req.external_id, e));
+                    continue;
+                }
+            };
             let initial_score = agent_req.initial_trust_score.unwrap_or(500);
             let tau = initial_score as f64 / 1000.0;
 
             let agent = AgentV2 {
                 id: id.clone(),
                 name: agent_req.name,
+                did: caller.0.clone(), // The ingesting platform acts for the agent
                 agent_type: agent_req.agent_type.unwrap_or_else(|| "agent".to_string()),
                 trust_score: TrustScoreV2 {
                     score: initial_score,
@@ -1039,12 +1831,7 @@ async fn batch_ingest(
                     disputes: 0,
                     last_updated: Utc::now(),
                 },
-                wallet: HCWallet {
-                    id: wallet_id,
-                    balance: agent_req.initial_balance.unwrap_or(1000.0),
-                    reserved: 0.0,
-                    expires_at: Utc::now() + chrono::Duration::days(30),
-                },
+                wallet,
                 fitness: FitnessMetrics {
                     eta: tau,
                     revenue: 0.0,
@@ -1085,6 +1872,15 @@ async fn batch_ingest(
 
             drop(external_map);
 
+            let authorized = match state.agents.read().await.get(&producer_id) {
+                Some(producer) => caller.authorize(producer),
+                None => Err(AppError(StatusCode::NOT_FOUND, "Producer not found".to_string())),
+            };
+            if let Err(AppError(_, e)) = authorized {
+                errors.push(format!("Producer {}: {}", action_req.producer_external_id, e));
+                continue;
+            }
+
             let id = format!("act-{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
             let base_compute = action_req.compute_cost.unwrap_or(0.08);
 
@@ -1187,6 +1983,7 @@ async fn seed_demo_data(state: &AppState) {
         let agent = AgentV2 {
             id: id.clone(),
             name: name.to_string(),
+            did: None,
             agent_type: agent_type.to_string(),
             trust_score: TrustScoreV2 {
                 score,
@@ -1201,11 +1998,11 @@ async fn seed_demo_data(state: &AppState) {
                 disputes: rng.gen_range(0..30),
                 last_updated: Utc::now(),
             },
-            wallet: HCWallet {
-                id: format!("wallet-{}", uuid::Uuid::new_v4().to_string()[..8].to_string()),
-                balance: rng.gen_range(500.0..50000.0),
-                reserved: rng.gen_range(0.0..500.0),
-                expires_at: Utc::now() + chrono::Duration::days(30),
+            wallet: {
+                let balance = Decimal::from(rng.gen_range(500..50000u32));
+                let mut dna = state.dna.write().await;
+                let _ = dna.mint(&id, balance);
+                dna.wallet(&id).cloned().unwrap_or_else(|| HcWallet::new(id.clone()))
             },
             fitness: FitnessMetrics {
                 eta,
@@ -1298,17 +2095,17 @@ async fn main() -> anyhow::Result<()> {
 
     dotenvy::dotenv().ok();
 
-    let (events_tx, _) = broadcast::channel::<Event>(1000);
-
-    let state = AppState {
-        agents: Arc::new(RwLock::new(HashMap::new())),
-        actions: Arc::new(RwLock::new(HashMap::new())),
-        loans: Arc::new(RwLock::new(HashMap::new())),
-        policies: Arc::new(RwLock::new(HashMap::new())),
-        delegations: Arc::new(RwLock::new(HashMap::new())),
-        external_agent_map: Arc::new(RwLock::new(HashMap::new())),
-        events_tx,
-    };
+    let state = AppState::new(
+        std::env::var("REQUIRE_SIGNED_REQUESTS")
+            .map(|v| v != "false")
+            .unwrap_or(true),
+        std::env::var("ARBITER_DIDS")
+            .map(|v| v.split(',').map(str::trim).filter(|did| !did.is_empty()).map(String::from).collect())
+            .unwrap_or_default(),
+    )?;
+    if !state.require_signed_requests {
+        warn!("Request signatures disabled: unsigned requests may act as any agent without a DID");
+    }
 
     // Seed demo data on startup
     seed_demo_data(&state).await;
@@ -1325,6 +2122,8 @@ async fn main() -> anyhow::Result<()> {
         // IdentityCloud
         .route("/agents", get(list_agents).post(create_agent))
         .route("/agents/:agent_id", get(get_agent))
+        .route("/agents/:agent_id/wallet", get(get_agent_wallet))
+        .route("/agents/:agent_id/statement", get(get_agent_statement))
         // TrustLedger
         .route("/actions", get(list_actions).post(submit_action))
         .route("/actions/:action_id/verify", post(verify_action))
@@ -1339,6 +2138,10 @@ async fn main() -> anyhow::Result<()> {
         // Protocol DNA - Delegate
         .route("/delegations", get(list_delegations).post(create_delegation))
         .route("/delegations/:delegation_id/complete", post(complete_delegation))
+        .route("/delegations/:delegation_id/milestones/release", post(release_milestone))
+        .route("/delegations/:delegation_id/cancel", post(cancel_delegation))
+        .route("/delegations/:delegation_id/dispute", post(dispute_delegation))
+        .route("/delegations/:delegation_id/resolve", post(resolve_delegation_dispute))
         // WebSocket
         .route("/ws", get(websocket_handler))
         // External Integration (HUMAIN ONE, Grok, etc.)
@@ -1347,6 +2150,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/ingest/batch", post(batch_ingest))
         .route("/ingest/mappings", get(get_external_mappings))
         // Middleware
+        .layer(middleware::from_fn_with_state(state.clone(), authenticate_request))
         .layer(TraceLayer::new_for_http())
         .layer(CompressionLayer::new())
         .layer(cors)
@@ -1364,3 +2168,264 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actoris_common::security::RequestSigner;
+    use ed25519_dalek::SigningKey;
+    use tower::ServiceExt;
+
+    fn state() -> AppState {
+        AppState::new(true, HashSet::new()).unwrap()
+    }
+
+    fn caller(did: &str) -> Extension<Caller> {
+        Extension(Caller(Some(did.to_string())))
+    }
+
+    /// Register an agent bound to `did`
+    async fn agent(state: &AppState, did: &str) -> AgentV2 {
+        create_agent(
+            State(state.clone()),
+            caller(did),
+            Json(CreateAgentRequest { name: did.to_string(), agent_type: None, did: None }),
+        )
+        .await
+        .unwrap()
+        .0
+    }
+
+    async fn submit(state: &AppState, did: &str, producer_id: &str, consumer_id: &str) -> Result<ActionV2, AppError> {
+        submit_action(
+            State(state.clone()),
+            caller(did),
+            Json(SubmitActionRequest {
+                producer_id: producer_id.to_string(),
+                consumer_id: consumer_id.to_string(),
+                action_type: "inference".to_string(),
+                input_data: "prompt".to_string(),
+            }),
+        )
+        .await
+        .map(|Json(action)| action)
+    }
+
+    /// The agent's wallet snapshot matches the DNA machine's ledgered wallet
+    async fn assert_synced(state: &AppState, agent_id: &str) -> HcWallet {
+        let agents = state.agents.read().await;
+        let dna = state.dna.read().await;
+        let (snapshot, ledgered) = (&agents[agent_id].wallet, dna.wallet(agent_id).unwrap());
+        assert_eq!(
+            (snapshot.available, snapshot.locked, snapshot.version),
+            (ledgered.available, ledgered.locked, ledgered.version),
+            "wallet of {} is stale",
+            agent_id
+        );
+        snapshot.clone()
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_and_impersonating_requests_are_rejected() {
+        let state = state();
+        let app = Router::new()
+            .route("/agents", post(create_agent))
+            .layer(middleware::from_fn_with_state(state.clone(), authenticate_request))
+            .with_state(state.clone());
+        let alice = RequestSigner::new(SigningKey::from_bytes(&[1u8; 32]));
+        let mallory = RequestSigner::new(SigningKey::from_bytes(&[2u8; 32]));
+        let body = serde_json::json!({ "name": "alice", "did": alice.did() }).to_string();
+
+        let post_agent = |signer: Option<&RequestSigner>| {
+            let mut request = axum::http::Request::builder()
+                .method(Method::POST)
+                .uri("/agents")
+                .header("content-type", "application/json");
+            if let Some(signer) = signer {
+                for (name, value) in signer.sign("POST", "/agents", body.as_bytes()).pairs() {
+                    request = request.header(name, value);
+                }
+            }
+            app.clone().oneshot(request.body(Body::from(body.clone())).unwrap())
+        };
+
+        assert_eq!(post_agent(None).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(post_agent(Some(&mallory)).await.unwrap().status(), StatusCode::FORBIDDEN);
+        assert_eq!(post_agent(Some(&alice)).await.unwrap().status(), StatusCode::OK);
+        assert!(state.agents.read().await.values().all(|a| a.did.as_deref() == Some(alice.did())));
+    }
+
+    #[tokio::test]
+    async fn test_actions_are_authorized_against_the_producer() {
+        let state = state();
+        let producer = agent(&state, "did:key:producer").await;
+        let consumer = agent(&state, "did:key:consumer").await;
+
+        let forged = submit(&state, "did:key:mallory", &producer.id, &consumer.id).await.unwrap_err();
+        assert_eq!(forged.0, StatusCode::FORBIDDEN);
+        let unknown = submit(&state, "did:key:mallory", "agent-missing", &consumer.id).await.unwrap_err();
+        assert_eq!(unknown.0, StatusCode::NOT_FOUND);
+        submit(&state, "did:key:producer", &producer.id, &consumer.id).await.unwrap();
+
+        {
+            let mut external = state.external_agent_map.write().await;
+            external.insert("ext-producer".to_string(), producer.id.clone());
+            external.insert("ext-consumer".to_string(), consumer.id.clone());
+        }
+        let ingest = |did: &str| {
+            ingest_action(
+                State(state.clone()),
+                caller(did),
+                Json(IngestActionRequest {
+                    external_id: None,
+                    producer_external_id: "ext-producer".to_string(),
+                    consumer_external_id: "ext-consumer".to_string(),
+                    action_type: "inference".to_string(),
+                    input_hash: None,
+                    output_hash: None,
+                    compute_cost: None,
+                    source: "test".to_string(),
+                    metadata: None,
+                }),
+            )
+        };
+        assert_eq!(ingest("did:key:mallory").await.unwrap_err().0, StatusCode::FORBIDDEN);
+        let Json(ingested) = ingest("did:key:producer").await.unwrap();
+        assert!(ingested.success);
+        assert_eq!(state.actions.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_an_outcome_releases_one_milestone_once() {
+        let state = state();
+        let client = agent(&state, "did:key:client").await;
+        let worker = agent(&state, "did:key:worker").await;
+
+        let milestone = |description: &str| CreateMilestoneRequest {
+            description: description.to_string(),
+            escrow_amount: 10.0,
+            deadline_days: 7,
+            action_type: None,
+        };
+        let Json(delegation) = create_delegation(
+            State(state.clone()),
+            caller("did:key:client"),
+            Json(CreateDelegationRequest {
+                client_id: client.id.clone(),
+                agent_id: worker.id.clone(),
+                task_description: "two steps".to_string(),
+                escrow_amount: 0.0,
+                deadline_days: 7,
+                milestones: vec![milestone("first"), milestone("second")],
+            }),
+        )
+        .await
+        .unwrap();
+        let delegation_id = delegation.contract.id.clone();
+
+        // A quorum-signed outcome for work the worker delivered to the client
+        let action = submit(&state, "did:key:worker", &worker.id, &client.id).await.unwrap();
+        let proof = VerificationProofV2 {
+            oracle_votes: (0..ORACLE_COUNT)
+                .map(|i| OracleVoteV2 {
+                    oracle_id: format!("oracle-{}", i),
+                    oracle_name: format!("Oracle-{}", i),
+                    vote: true,
+                    timestamp: Utc::now(),
+                })
+                .collect(),
+            quorum_reached: true,
+            quorum_threshold: format!("{}-of-{}", ORACLE_THRESHOLD, ORACLE_COUNT),
+            aggregate_signature: String::new(),
+            latency_ms: 800.0,
+        };
+        state.outcomes.write().await.record(&action, &proof).await.unwrap();
+
+        let release = |did: &str| {
+            release_milestone(
+                State(state.clone()),
+                caller(did),
+                Path(delegation_id.clone()),
+                Json(ReleaseMilestoneRequest { action_id: action.id.clone() }),
+            )
+        };
+
+        // Only the client releases, and the outcome pays for a single milestone
+        assert_eq!(release("did:key:worker").await.unwrap_err().0, StatusCode::FORBIDDEN);
+        let before = assert_synced(&state, &worker.id).await;
+        let Json(released) = release("did:key:client").await.unwrap();
+        assert_eq!(released.action_ids, vec![Some(action.id.clone()), None]);
+        let after = assert_synced(&state, &worker.id).await;
+        assert_eq!(after.available - before.available, Decimal::from(10));
+        assert_synced(&state, &client.id).await;
+
+        assert_eq!(release("did:key:client").await.unwrap_err().0, StatusCode::CONFLICT);
+        assert_eq!(assert_synced(&state, &worker.id).await.available, after.available);
+    }
+
+    #[tokio::test]
+    async fn test_wallets_follow_lend_insure_and_delegate() {
+        let state = state();
+        let alice = agent(&state, "did:key:alice").await;
+        let bob = agent(&state, "did:key:bob").await;
+        let carol = agent(&state, "did:key:carol").await;
+
+        let Json(loan) = create_loan(
+            State(state.clone()),
+            caller("did:key:alice"),
+            Json(CreateLoanRequest {
+                lender_id: alice.id.clone(),
+                borrower_id: bob.id.clone(),
+                principal: 50.0,
+                term_days: 30,
+                collateral_pct: 0.5,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loan.collateral, 25.0);
+        let lender = assert_synced(&state, &alice.id).await;
+        let borrower = assert_synced(&state, &bob.id).await;
+        assert_eq!(alice.wallet.available - lender.available, Decimal::from(50));
+        assert_eq!(borrower.available - bob.wallet.available, Decimal::from(25));
+        assert_eq!(borrower.locked, Decimal::from(25));
+
+        let Json(policy) = create_insurance(
+            State(state.clone()),
+            caller("did:key:alice"),
+            Json(CreateInsuranceRequest {
+                insurer_id: alice.id.clone(),
+                insured_id: bob.id.clone(),
+                coverage: 40.0,
+                action_type: "inference".to_string(),
+                duration_days: 30,
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(policy.premium > 0.0);
+        let insurer = assert_synced(&state, &alice.id).await;
+        let insured = assert_synced(&state, &bob.id).await;
+        assert_eq!(insurer.locked, Decimal::from(40));
+        assert!(insured.available < borrower.available);
+
+        let Json(delegation) = create_delegation(
+            State(state.clone()),
+            caller("did:key:bob"),
+            Json(CreateDelegationRequest {
+                client_id: bob.id.clone(),
+                agent_id: carol.id.clone(),
+                task_description: "summarize".to_string(),
+                escrow_amount: 30.0,
+                deadline_days: 7,
+                milestones: Vec::new(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(delegation.contract.escrow_amount, Decimal::from(30));
+        let client = assert_synced(&state, &bob.id).await;
+        assert_eq!(client.locked - insured.locked, Decimal::from(30));
+        assert_eq!(insured.available - client.available, Decimal::from(30));
+    }
+}
@@ -0,0 +1,592 @@
+//! Capability tokens for delegation chains
+//!
+//! A delegation is issued as a self-contained token signed by the
+//! delegator's did:key. A delegate allowed to sub-delegate issues a child
+//! token naming the parent by ID; every link may only narrow the scope it
+//! received. [`verify_chain`] checks a chain root-first without contacting
+//! the issuer, so any service holding a revocation list can authorize an
+//! action offline.
+//!
+//! Total HC caps cannot be metered offline: the verifier returns the
+//! tightest cap along the chain and usage accounting is left to the caller.
+
+use std::collections::{BTreeSet, HashSet};
+
+use ed25519_dalek::SigningKey;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::did::{encode_did_key, sign_with_key, verify_with_did, DidError};
+
+/// Domain separator for token signatures
+const SIGNING_DOMAIN: &[u8] = b"actoris-capability-v1";
+
+/// Maximum number of tokens in a chain
+pub const MAX_CHAIN_DEPTH: usize = 8;
+
+/// What a capability token permits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityScope {
+    /// Allowed actions (empty = all, `prefix.*` = any action under `prefix.`)
+    pub allowed_actions: Vec<String>,
+    /// Maximum HC per action
+    pub max_hc_per_action: Option<Decimal>,
+    /// Maximum total HC
+    pub max_total_hc: Option<Decimal>,
+    /// Start of validity (Unix ms)
+    pub not_before: i64,
+    /// End of validity (Unix ms)
+    pub expires_at: i64,
+    /// Whether the audience may issue child tokens
+    pub allow_subdelegation: bool,
+}
+
+impl CapabilityScope {
+    /// Whether this scope is no wider than `parent`
+    pub fn attenuates(&self, parent: &CapabilityScope) -> Result<(), CapabilityError> {
+        let widened = |what: &str| Err(CapabilityError::ScopeWidened(what.to_string()));
+
+        if !parent.allowed_actions.is_empty()
+            && (self.allowed_actions.is_empty()
+                || !self
+                    .allowed_actions
+                    .iter()
+                    .all(|a| action_allowed(&parent.allowed_actions, a)))
+        {
+            return widened("allowed actions");
+        }
+        if !cap_within(self.max_hc_per_action, parent.max_hc_per_action) {
+            return widened("per-action HC cap");
+        }
+        if !cap_within(self.max_total_hc, parent.max_total_hc) {
+            return widened("total HC cap");
+        }
+        if self.not_before < parent.not_before || self.expires_at > parent.expires_at {
+            return widened("validity period");
+        }
+        if self.allow_subdelegation && !parent.allow_subdelegation {
+            return widened("sub-delegation");
+        }
+        Ok(())
+    }
+
+    /// Whether `action` costing `hc_amount` falls inside this scope
+    pub fn permits(&self, action: &str, hc_amount: Decimal) -> Result<(), CapabilityError> {
+        if !action_allowed(&self.allowed_actions, action) {
+            return Err(CapabilityError::ActionNotAllowed(action.to_string()));
+        }
+        if let Some(max) = self.max_hc_per_action {
+            if hc_amount > max {
+                return Err(CapabilityError::HcLimitExceeded {
+                    limit: max,
+                    requested: hc_amount,
+                });
+            }
+        }
+        if let Some(max) = self.max_total_hc {
+            if hc_amount > max {
+                return Err(CapabilityError::HcLimitExceeded {
+                    limit: max,
+                    requested: hc_amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the scope is valid at `now`
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.not_before <= now && now <= self.expires_at
+    }
+}
+
+/// Whether `action` is covered by an allowed-actions list
+///
+/// An empty list allows everything; a pattern ending in `*` matches any action
+/// with that prefix, so `compute.*` covers `compute.run` and `compute.gpu.*`.
+pub fn action_allowed(allowed: &[String], action: &str) -> bool {
+    allowed.is_empty()
+        || allowed.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => action.starts_with(prefix),
+            None => pattern == action,
+        })
+}
+
+/// A child cap is within its parent's if the parent is unbounded or the
+/// child is bounded no higher
+fn cap_within(child: Option<Decimal>, parent: Option<Decimal>) -> bool {
+    match (child, parent) {
+        (_, None) => true,
+        (Some(c), Some(p)) => c <= p,
+        (None, Some(_)) => false,
+    }
+}
+
+/// Signed delegation of authority from `issuer_did` to `audience_did`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityToken {
+    /// Token ID, checked against revocation lists
+    pub id: String,
+    /// Delegator DID (did:key, signs the token)
+    pub issuer_did: String,
+    /// Delegate DID
+    pub audience_did: String,
+    pub scope: CapabilityScope,
+    /// Token this one was sub-delegated from
+    pub parent_id: Option<String>,
+    /// Ed25519 signature by the issuer over [`Self::signing_bytes`]
+    pub signature: Vec<u8>,
+}
+
+/// Fields covered by the signature
+#[derive(Serialize)]
+struct SignedPayload<'a> {
+    id: &'a str,
+    issuer_did: &'a str,
+    audience_did: &'a str,
+    scope: &'a CapabilityScope,
+    parent_id: Option<&'a str>,
+}
+
+impl CapabilityToken {
+    /// Issue a root token
+    pub fn issue(
+        signing_key: &SigningKey,
+        audience_did: impl Into<String>,
+        scope: CapabilityScope,
+    ) -> Result<Self, CapabilityError> {
+        Self::issue_with_id(
+            signing_key,
+            Uuid::now_v7().to_string(),
+            audience_did,
+            scope,
+            None,
+        )
+    }
+
+    /// Issue a child token narrowing `parent`
+    ///
+    /// The signer must be the parent's audience.
+    pub fn subdelegate(
+        signing_key: &SigningKey,
+        parent: &CapabilityToken,
+        audience_did: impl Into<String>,
+        scope: CapabilityScope,
+    ) -> Result<Self, CapabilityError> {
+        Self::issue_with_id(
+            signing_key,
+            Uuid::now_v7().to_string(),
+            audience_did,
+            scope,
+            Some(parent),
+        )
+    }
+
+    /// Issue a token with a caller-chosen ID
+    ///
+    /// Lets a ledger reuse its own delegation IDs so revoking a delegation
+    /// revokes its token.
+    pub fn issue_with_id(
+        signing_key: &SigningKey,
+        id: impl Into<String>,
+        audience_did: impl Into<String>,
+        scope: CapabilityScope,
+        parent: Option<&CapabilityToken>,
+    ) -> Result<Self, CapabilityError> {
+        let issuer_did = encode_did_key(&signing_key.verifying_key().to_bytes());
+
+        if let Some(parent) = parent {
+            if parent.audience_did != issuer_did {
+                return Err(CapabilityError::BrokenChain(
+                    "issuer is not the parent token's audience".into(),
+                ));
+            }
+            if !parent.scope.allow_subdelegation {
+                return Err(CapabilityError::SubdelegationNotAllowed);
+            }
+            scope.attenuates(&parent.scope)?;
+        }
+
+        let mut token = Self {
+            id: id.into(),
+            issuer_did,
+            audience_did: audience_did.into(),
+            scope,
+            parent_id: parent.map(|p| p.id.clone()),
+            signature: Vec::new(),
+        };
+        token.signature = sign_with_key(signing_key, &token.signing_bytes()).to_vec();
+        Ok(token)
+    }
+
+    /// Canonical bytes covered by the signature
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let payload = SignedPayload {
+            id: &self.id,
+            issuer_did: &self.issuer_did,
+            audience_did: &self.audience_did,
+            scope: &self.scope,
+            parent_id: self.parent_id.as_deref(),
+        };
+        let mut bytes = SIGNING_DOMAIN.to_vec();
+        bytes.extend(serde_json::to_vec(&payload).expect("payload serializes"));
+        bytes
+    }
+
+    /// Verify the issuer's signature
+    pub fn verify_signature(&self) -> Result<(), CapabilityError> {
+        let signature: [u8; 64] = self
+            .signature
+            .as_slice()
+            .try_into()
+            .map_err(|_| CapabilityError::InvalidSignature(self.id.clone()))?;
+        if verify_with_did(&self.issuer_did, &self.signing_bytes(), &signature)? {
+            Ok(())
+        } else {
+            Err(CapabilityError::InvalidSignature(self.id.clone()))
+        }
+    }
+}
+
+/// Source of revoked token IDs
+pub trait RevocationList {
+    fn is_revoked(&self, token_id: &str) -> bool;
+}
+
+impl RevocationList for HashSet<String> {
+    fn is_revoked(&self, token_id: &str) -> bool {
+        self.contains(token_id)
+    }
+}
+
+impl RevocationList for BTreeSet<String> {
+    fn is_revoked(&self, token_id: &str) -> bool {
+        self.contains(token_id)
+    }
+}
+
+/// Outcome of a successful chain verification
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizedCapability {
+    /// DID whose authority is being exercised
+    pub root_issuer: String,
+    /// Token IDs root-first
+    pub token_ids: Vec<String>,
+    /// Tightest total HC cap along the chain, to be metered by the caller
+    pub max_total_hc: Option<Decimal>,
+}
+
+/// Authorize `invoker_did` to perform `action` costing `hc_amount`
+///
+/// `chain` is ordered root-first. Every token must be correctly signed,
+/// unrevoked and valid at `now`; each child must be issued by its parent's
+/// audience, name the parent by ID and narrow its scope; the last token must
+/// be addressed to the invoker.
+pub fn verify_chain(
+    chain: &[CapabilityToken],
+    invoker_did: &str,
+    action: &str,
+    hc_amount: Decimal,
+    now: i64,
+    revocations: &impl RevocationList,
+) -> Result<AuthorizedCapability, CapabilityError> {
+    let (root, leaf) = match (chain.first(), chain.last()) {
+        (Some(root), Some(leaf)) => (root, leaf),
+        _ => return Err(CapabilityError::BrokenChain("empty chain".into())),
+    };
+    if chain.len() > MAX_CHAIN_DEPTH {
+        return Err(CapabilityError::ChainTooLong {
+            max: MAX_CHAIN_DEPTH,
+        });
+    }
+    if root.parent_id.is_some() {
+        return Err(CapabilityError::BrokenChain(
+            "root token has a parent".into(),
+        ));
+    }
+
+    let mut max_total_hc = None;
+    let mut parent: Option<&CapabilityToken> = None;
+    for token in chain {
+        token.verify_signature()?;
+        if revocations.is_revoked(&token.id) {
+            return Err(CapabilityError::Revoked(token.id.clone()));
+        }
+        if !token.scope.is_active_at(now) {
+            return Err(CapabilityError::Expired(token.id.clone()));
+        }
+
+        if let Some(parent) = parent {
+            if token.parent_id.as_deref() != Some(parent.id.as_str()) {
+                return Err(CapabilityError::BrokenChain(format!(
+                    "token {} does not name {} as parent",
+                    token.id, parent.id
+                )));
+            }
+            if token.issuer_did != parent.audience_did {
+                return Err(CapabilityError::BrokenChain(format!(
+                    "token {} is not issued by its parent's audience",
+                    token.id
+                )));
+            }
+            if !parent.scope.allow_subdelegation {
+                return Err(CapabilityError::SubdelegationNotAllowed);
+            }
+            token.scope.attenuates(&parent.scope)?;
+        }
+
+        max_total_hc = token.scope.max_total_hc.or(max_total_hc);
+        parent = Some(token);
+    }
+
+    if leaf.audience_did != invoker_did {
+        return Err(CapabilityError::WrongAudience {
+            expected: leaf.audience_did.clone(),
+            actual: invoker_did.to_string(),
+        });
+    }
+    leaf.scope.permits(action, hc_amount)?;
+
+    Ok(AuthorizedCapability {
+        root_issuer: root.issuer_did.clone(),
+        token_ids: chain.iter().map(|t| t.id.clone()).collect(),
+        max_total_hc,
+    })
+}
+
+/// Capability token errors
+#[derive(Debug, Error)]
+pub enum CapabilityError {
+    #[error("Invalid signature on token {0}")]
+    InvalidSignature(String),
+
+    #[error("Token {0} has been revoked")]
+    Revoked(String),
+
+    #[error("Token {0} is not valid at this time")]
+    Expired(String),
+
+    #[error("Broken delegation chain: {0}")]
+    BrokenChain(String),
+
+    #[error("Delegation chain exceeds {max} tokens")]
+    ChainTooLong { max: usize },
+
+    #[error("Sub-delegation not allowed")]
+    SubdelegationNotAllowed,
+
+    #[error("Sub-delegation widens {0}")]
+    ScopeWidened(String),
+
+    #[error("Token is addressed to {expected}, not {actual}")]
+    WrongAudience { expected: String, actual: String },
+
+    #[error("Action not allowed: {0}")]
+    ActionNotAllowed(String),
+
+    #[error("HC limit exceeded: limit {limit}, requested {requested}")]
+    HcLimitExceeded { limit: Decimal, requested: Decimal },
+
+    #[error(transparent)]
+    Did(#[from] DidError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use rust_decimal_macros::dec;
+
+    fn did_of(key: &SigningKey) -> String {
+        encode_did_key(&key.verifying_key().to_bytes())
+    }
+
+    fn scope(actions: &[&str], per_action: Option<Decimal>, subdelegate: bool) -> CapabilityScope {
+        CapabilityScope {
+            allowed_actions: actions.iter().map(|a| a.to_string()).collect(),
+            max_hc_per_action: per_action,
+            max_total_hc: Some(dec!(1000)),
+            not_before: 0,
+            expires_at: 10_000,
+            allow_subdelegation: subdelegate,
+        }
+    }
+
+    #[test]
+    fn test_chain_verifies_and_attenuates() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let carol = SigningKey::generate(&mut OsRng);
+        let none = HashSet::new();
+
+        let root = CapabilityToken::issue(
+            &alice,
+            did_of(&bob),
+            scope(&["compute", "storage"], Some(dec!(100)), true),
+        )
+        .unwrap();
+        let child = CapabilityToken::subdelegate(
+            &bob,
+            &root,
+            did_of(&carol),
+            CapabilityScope {
+                max_total_hc: Some(dec!(200)),
+                ..scope(&["compute"], Some(dec!(50)), false)
+            },
+        )
+        .unwrap();
+        let chain = vec![root.clone(), child.clone()];
+
+        let auth =
+            verify_chain(&chain, &did_of(&carol), "compute", dec!(40), 5_000, &none).unwrap();
+        assert_eq!(auth.root_issuer, did_of(&alice));
+        assert_eq!(auth.token_ids, vec![root.id.clone(), child.id.clone()]);
+        assert_eq!(auth.max_total_hc, Some(dec!(200)));
+
+        // The child narrowed both the actions and the per-action cap
+        assert!(matches!(
+            verify_chain(&chain, &did_of(&carol), "storage", dec!(40), 5_000, &none),
+            Err(CapabilityError::ActionNotAllowed(_))
+        ));
+        assert!(matches!(
+            verify_chain(&chain, &did_of(&carol), "compute", dec!(60), 5_000, &none),
+            Err(CapabilityError::HcLimitExceeded { .. })
+        ));
+        assert!(matches!(
+            verify_chain(&chain, &did_of(&bob), "compute", dec!(40), 5_000, &none),
+            Err(CapabilityError::WrongAudience { .. })
+        ));
+        assert!(matches!(
+            verify_chain(&chain, &did_of(&carol), "compute", dec!(40), 20_000, &none),
+            Err(CapabilityError::Expired(_))
+        ));
+
+        // Revoking any link kills the chain
+        let revoked: HashSet<String> = [root.id.clone()].into_iter().collect();
+        assert!(matches!(
+            verify_chain(
+                &chain,
+                &did_of(&carol),
+                "compute",
+                dec!(40),
+                5_000,
+                &revoked
+            ),
+            Err(CapabilityError::Revoked(_))
+        ));
+
+        // Carol may not sub-delegate further
+        assert!(matches!(
+            CapabilityToken::subdelegate(
+                &carol,
+                &child,
+                did_of(&bob),
+                scope(&["compute"], Some(dec!(10)), false)
+            ),
+            Err(CapabilityError::SubdelegationNotAllowed)
+        ));
+    }
+
+    #[test]
+    fn test_widened_or_forged_links_rejected() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let carol = SigningKey::generate(&mut OsRng);
+        let none = HashSet::new();
+
+        let root = CapabilityToken::issue(
+            &alice,
+            did_of(&bob),
+            scope(&["compute"], Some(dec!(100)), true),
+        )
+        .unwrap();
+
+        // Issuing a wider child is refused
+        assert!(matches!(
+            CapabilityToken::subdelegate(
+                &bob,
+                &root,
+                did_of(&carol),
+                scope(&[], Some(dec!(100)), false)
+            ),
+            Err(CapabilityError::ScopeWidened(_))
+        ));
+        assert!(matches!(
+            CapabilityToken::subdelegate(
+                &bob,
+                &root,
+                did_of(&carol),
+                scope(&["compute"], None, false)
+            ),
+            Err(CapabilityError::ScopeWidened(_))
+        ));
+        // Only the parent's audience may sub-delegate
+        assert!(matches!(
+            CapabilityToken::subdelegate(
+                &carol,
+                &root,
+                did_of(&carol),
+                scope(&["compute"], Some(dec!(1)), false)
+            ),
+            Err(CapabilityError::BrokenChain(_))
+        ));
+
+        // A child widened after signing fails verification
+        let mut child = CapabilityToken::subdelegate(
+            &bob,
+            &root,
+            did_of(&carol),
+            scope(&["compute"], Some(dec!(10)), false),
+        )
+        .unwrap();
+        child.scope.max_hc_per_action = Some(dec!(100));
+        assert!(matches!(
+            verify_chain(
+                &[root.clone(), child],
+                &did_of(&carol),
+                "compute",
+                dec!(50),
+                5_000,
+                &none
+            ),
+            Err(CapabilityError::InvalidSignature(_))
+        ));
+
+        // A correctly signed but unlinked token can't be spliced in
+        let stray = CapabilityToken::issue(
+            &bob,
+            did_of(&carol),
+            scope(&["compute"], Some(dec!(10)), false),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_chain(
+                &[root, stray],
+                &did_of(&carol),
+                "compute",
+                dec!(5),
+                5_000,
+                &none
+            ),
+            Err(CapabilityError::BrokenChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_wildcard_actions() {
+        let parent = scope(&["compute.*"], None, true);
+        assert!(parent.permits("compute.run", dec!(1)).is_ok());
+        assert!(parent.permits("compute.gpu.train", dec!(1)).is_ok());
+        assert!(parent.permits("storage.put", dec!(1)).is_err());
+
+        // Children may narrow a wildcard but not widen it
+        assert!(scope(&["compute.run"], None, false).attenuates(&parent).is_ok());
+        assert!(scope(&["compute.gpu.*"], None, false).attenuates(&parent).is_ok());
+        assert!(scope(&["*"], None, false).attenuates(&parent).is_err());
+        assert!(scope(&["compute.*"], None, false)
+            .attenuates(&scope(&["compute.run"], None, true))
+            .is_err());
+    }
+}
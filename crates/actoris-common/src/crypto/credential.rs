@@ -0,0 +1,822 @@
+//! W3C Verifiable Credentials
+//!
+//! Lets other platforms consume Actoris attestations without calling our
+//! API. Credentials follow the VC Data Model 2.0 and are secured with a
+//! Data Integrity proof using the `eddsa-jcs-2022` cryptosuite: the proof
+//! options and the credential are canonicalized as JSON (JCS), hashed with
+//! SHA-256 and signed with the issuer's Ed25519 key.
+//!
+//! Revocation uses a Bitstring Status List: each credential points at one
+//! bit of a list the issuer publishes as its own signed credential, so a
+//! verifier learns the status without revealing which credential it holds.
+//!
+//! Reference: https://www.w3.org/TR/vc-data-model-2.0/
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, SigningKey, Verifier};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::did::{encode_did_key, DidError};
+use super::did_resolver::{DidResolver, HttpClient};
+use crate::types::trust_score::{TrustComponents, TrustScore};
+
+/// Base context of every credential
+pub const CREDENTIALS_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+
+/// Actoris claim vocabulary
+pub const ACTORIS_CONTEXT: &str = "https://actoris.network/credentials/v1";
+
+/// Proof type and cryptosuite used for signing
+const PROOF_TYPE: &str = "DataIntegrityProof";
+const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+
+/// Bits in a new status list (16KB, the minimum for group privacy)
+pub const DEFAULT_STATUS_LIST_SIZE: usize = 131_072;
+
+/// Status purpose for revocation lists
+const REVOCATION: &str = "revocation";
+
+/// Snapshot of a trust score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustScoreClaim {
+    /// Composite score (0-1000)
+    pub score: u16,
+    pub components: TrustComponents,
+    pub verified_outcomes: u64,
+}
+
+impl From<&TrustScore> for TrustScoreClaim {
+    fn from(score: &TrustScore) -> Self {
+        Self {
+            score: score.score,
+            components: score.components.clone(),
+            verified_outcomes: score.verified_outcomes,
+        }
+    }
+}
+
+/// Membership of an agent in an organization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipClaim {
+    /// Organization DID
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+/// What a credential attests about its subject
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CredentialClaim {
+    TrustScore {
+        #[serde(rename = "trustScore")]
+        trust_score: TrustScoreClaim,
+    },
+    /// SyRA identity verification tier (0-3)
+    VerificationTier {
+        #[serde(rename = "verificationTier")]
+        verification_tier: u8,
+    },
+    Membership {
+        #[serde(rename = "memberOf")]
+        member_of: MembershipClaim,
+    },
+    /// Published revocation bits (subject of a status list credential)
+    StatusList {
+        #[serde(rename = "type")]
+        list_type: String,
+        #[serde(rename = "statusPurpose")]
+        status_purpose: String,
+        #[serde(rename = "encodedList")]
+        encoded_list: String,
+    },
+}
+
+impl CredentialClaim {
+    /// Attest a trust score snapshot
+    pub fn trust_score(score: &TrustScore) -> Self {
+        Self::TrustScore {
+            trust_score: score.into(),
+        }
+    }
+
+    /// Attest membership of an organization
+    pub fn membership(org_did: impl Into<String>, role: Option<String>) -> Self {
+        Self::Membership {
+            member_of: MembershipClaim {
+                id: org_did.into(),
+                role,
+            },
+        }
+    }
+
+    /// Credential type declared alongside `VerifiableCredential`
+    pub fn credential_type(&self) -> &'static str {
+        match self {
+            Self::TrustScore { .. } => "ActorisTrustScoreCredential",
+            Self::VerificationTier { .. } => "ActorisVerificationTierCredential",
+            Self::Membership { .. } => "ActorisMembershipCredential",
+            Self::StatusList { .. } => "BitstringStatusListCredential",
+        }
+    }
+}
+
+/// Subject of a credential and the claim about it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialSubject {
+    /// Subject DID (or status list URL)
+    pub id: String,
+    #[serde(flatten)]
+    pub claim: CredentialClaim,
+}
+
+/// Pointer to the status list bit of a credential
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    pub status_purpose: String,
+    /// Bit index, encoded as a string per the spec
+    pub status_list_index: String,
+    /// URL of the status list credential
+    pub status_list_credential: String,
+}
+
+/// Data Integrity proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub cryptosuite: String,
+    pub created: DateTime<Utc>,
+    /// Issuer verification method that signed
+    pub verification_method: String,
+    pub proof_purpose: String,
+    /// Multibase (base58btc) Ed25519 signature
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub proof_value: String,
+}
+
+/// Signed W3C Verifiable Credential
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    /// Issuer DID
+    pub issuer: String,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    pub credential_subject: CredentialSubject,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<CredentialProof>,
+}
+
+impl VerifiableCredential {
+    /// Bytes signed by the proof: SHA-256 of the canonical proof options
+    /// followed by SHA-256 of the canonical unsigned credential
+    fn hash_data(&self, proof: &CredentialProof) -> Result<Vec<u8>, CredentialError> {
+        let unsigned = Self {
+            proof: None,
+            ..self.clone()
+        };
+
+        let mut options = serde_json::to_value(CredentialProof {
+            proof_value: String::new(),
+            ..proof.clone()
+        })?;
+        options["@context"] = serde_json::to_value(&self.context)?;
+
+        let mut bytes = Sha256::digest(canonical_json(&options)).to_vec();
+        bytes.extend(Sha256::digest(canonical_json(&serde_json::to_value(
+            &unsigned,
+        )?)));
+        Ok(bytes)
+    }
+
+    /// Whether the credential is within its validity period at `now`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.valid_from <= now && now <= self.valid_until
+    }
+}
+
+/// JSON Canonicalization Scheme (RFC 8785) for the values credentials hold
+///
+/// Object keys are sorted and no whitespace is emitted. Keys here are ASCII,
+/// so byte order matches the UTF-16 order JCS specifies.
+fn canonical_json(value: &serde_json::Value) -> Vec<u8> {
+    fn write(value: &serde_json::Value, out: &mut Vec<u8>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                out.push(b'{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    out.extend(serde_json::to_vec(key).expect("string serializes"));
+                    out.push(b':');
+                    write(value, out);
+                }
+                out.push(b'}');
+            }
+            serde_json::Value::Array(items) => {
+                out.push(b'[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write(item, out);
+                }
+                out.push(b']');
+            }
+            scalar => out.extend(serde_json::to_vec(scalar).expect("scalar serializes")),
+        }
+    }
+
+    let mut out = Vec::new();
+    write(value, &mut out);
+    out
+}
+
+/// Signs credentials on behalf of an issuer DID
+pub struct CredentialIssuer {
+    did: String,
+    verification_method: String,
+    signing_key: SigningKey,
+}
+
+impl CredentialIssuer {
+    /// Issuer identified by the did:key of `signing_key`
+    pub fn from_did_key(signing_key: SigningKey) -> Self {
+        let did = encode_did_key(&signing_key.verifying_key().to_bytes());
+        let verification_method = format!("{}#{}", did, &did[8..]);
+        Self {
+            did,
+            verification_method,
+            signing_key,
+        }
+    }
+
+    /// Issuer signing with a listed method of its DID Document (e.g. did:web)
+    pub fn new(
+        did: impl Into<String>,
+        verification_method: impl Into<String>,
+        signing_key: SigningKey,
+    ) -> Self {
+        Self {
+            did: did.into(),
+            verification_method: verification_method.into(),
+            signing_key,
+        }
+    }
+
+    pub fn did(&self) -> &str {
+        &self.did
+    }
+
+    /// Issue a credential about `subject_did`, valid from now until `valid_until`
+    ///
+    /// With a status list the credential takes the list's next free bit and
+    /// can be revoked through it.
+    pub fn issue(
+        &self,
+        subject_did: impl Into<String>,
+        claim: CredentialClaim,
+        valid_until: DateTime<Utc>,
+        status_list: Option<&mut StatusList>,
+    ) -> Result<VerifiableCredential, CredentialError> {
+        let credential_status = status_list.map(StatusList::allocate).transpose()?;
+        let credential = VerifiableCredential {
+            context: vec![CREDENTIALS_CONTEXT.to_string(), ACTORIS_CONTEXT.to_string()],
+            id: format!("urn:uuid:{}", Uuid::new_v4()),
+            types: vec![
+                "VerifiableCredential".to_string(),
+                claim.credential_type().to_string(),
+            ],
+            issuer: self.did.clone(),
+            valid_from: Utc::now(),
+            valid_until,
+            credential_subject: CredentialSubject {
+                id: subject_did.into(),
+                claim,
+            },
+            credential_status,
+            proof: None,
+        };
+        self.sign(credential)
+    }
+
+    /// Attach a proof to an unsigned credential
+    pub fn sign(
+        &self,
+        mut credential: VerifiableCredential,
+    ) -> Result<VerifiableCredential, CredentialError> {
+        if credential.issuer != self.did {
+            return Err(CredentialError::UntrustedIssuer(credential.issuer));
+        }
+
+        let mut proof = CredentialProof {
+            proof_type: PROOF_TYPE.to_string(),
+            cryptosuite: CRYPTOSUITE.to_string(),
+            created: Utc::now(),
+            verification_method: self.verification_method.clone(),
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: String::new(),
+        };
+        credential.proof = None;
+        let signature =
+            super::did::sign_with_key(&self.signing_key, &credential.hash_data(&proof)?);
+        proof.proof_value = format!("z{}", bs58::encode(signature).into_string());
+        credential.proof = Some(proof);
+        Ok(credential)
+    }
+}
+
+/// Revocation bits kept by an issuer
+///
+/// Published as a status list credential at `id`; index 0 is the most
+/// significant bit of the first byte.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusList {
+    /// URL the list credential is published at
+    pub id: String,
+    bits: Vec<u8>,
+    next_index: usize,
+}
+
+impl StatusList {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self::with_size(id, DEFAULT_STATUS_LIST_SIZE)
+    }
+
+    /// List with room for `size` credentials (rounded up to whole bytes)
+    pub fn with_size(id: impl Into<String>, size: usize) -> Self {
+        Self {
+            id: id.into(),
+            bits: vec![0; size.div_ceil(8)],
+            next_index: 0,
+        }
+    }
+
+    /// Reserve the next bit for a new credential
+    pub fn allocate(&mut self) -> Result<CredentialStatus, CredentialError> {
+        let index = self.next_index;
+        if index >= self.bits.len() * 8 {
+            return Err(CredentialError::StatusListFull(self.id.clone()));
+        }
+        self.next_index += 1;
+
+        Ok(CredentialStatus {
+            id: format!("{}#{}", self.id, index),
+            status_type: "BitstringStatusListEntry".to_string(),
+            status_purpose: REVOCATION.to_string(),
+            status_list_index: index.to_string(),
+            status_list_credential: self.id.clone(),
+        })
+    }
+
+    /// Revoke the credential holding `status`
+    pub fn revoke(&mut self, status: &CredentialStatus) -> Result<(), CredentialError> {
+        let index = self.index_of(status)?;
+        self.bits[index / 8] |= 0x80 >> (index % 8);
+        Ok(())
+    }
+
+    pub fn is_revoked(&self, status: &CredentialStatus) -> Result<bool, CredentialError> {
+        let index = self.index_of(status)?;
+        Ok(bit_set(&self.bits, index))
+    }
+
+    fn index_of(&self, status: &CredentialStatus) -> Result<usize, CredentialError> {
+        if status.status_list_credential != self.id {
+            return Err(CredentialError::StatusList(format!(
+                "{} is not in list {}",
+                status.id, self.id
+            )));
+        }
+        status_index(status, self.bits.len())
+    }
+
+    /// Multibase (base64url) GZIP of the bits
+    pub fn encode(&self) -> String {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&self.bits).expect("in-memory write");
+        let compressed = encoder.finish().expect("in-memory write");
+        format!("u{}", URL_SAFE_NO_PAD.encode(compressed))
+    }
+
+    /// Sign the current bits as a status list credential
+    pub fn to_credential(
+        &self,
+        issuer: &CredentialIssuer,
+        valid_until: DateTime<Utc>,
+    ) -> Result<VerifiableCredential, CredentialError> {
+        let mut credential = issuer.issue(
+            self.id.clone(),
+            CredentialClaim::StatusList {
+                list_type: "BitstringStatusList".to_string(),
+                status_purpose: REVOCATION.to_string(),
+                encoded_list: self.encode(),
+            },
+            valid_until,
+            None,
+        )?;
+        credential.id = self.id.clone();
+        issuer.sign(credential)
+    }
+}
+
+fn bit_set(bits: &[u8], index: usize) -> bool {
+    bits[index / 8] & (0x80 >> (index % 8)) != 0
+}
+
+fn status_index(status: &CredentialStatus, list_bytes: usize) -> Result<usize, CredentialError> {
+    let index: usize = status.status_list_index.parse().map_err(|_| {
+        CredentialError::StatusList(format!("bad index {}", status.status_list_index))
+    })?;
+    if index >= list_bytes * 8 {
+        return Err(CredentialError::StatusList(format!(
+            "index {} outside list {}",
+            index, status.status_list_credential
+        )));
+    }
+    Ok(index)
+}
+
+/// Decode an `encodedList` into bits
+fn decode_status_list(encoded: &str) -> Result<Vec<u8>, CredentialError> {
+    let compressed = encoded
+        .strip_prefix('u')
+        .and_then(|b64| URL_SAFE_NO_PAD.decode(b64).ok())
+        .ok_or_else(|| CredentialError::StatusList("encodedList is not base64url".into()))?;
+
+    let mut bits = Vec::new();
+    flate2::read::GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut bits)
+        .map_err(|e| CredentialError::StatusList(format!("encodedList: {}", e)))?;
+    Ok(bits)
+}
+
+/// Where a verifier gets status list credentials from
+#[async_trait]
+pub trait StatusListSource: Send + Sync {
+    async fn fetch(&self, url: &str) -> Result<VerifiableCredential, CredentialError>;
+}
+
+#[async_trait]
+impl StatusListSource for HashMap<String, VerifiableCredential> {
+    async fn fetch(&self, url: &str) -> Result<VerifiableCredential, CredentialError> {
+        self.get(url)
+            .cloned()
+            .ok_or_else(|| CredentialError::StatusList(format!("{} not found", url)))
+    }
+}
+
+/// Status lists fetched from their published URLs
+pub struct WebStatusLists<C> {
+    client: C,
+}
+
+impl<C: HttpClient> WebStatusLists<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient> StatusListSource for WebStatusLists<C> {
+    async fn fetch(&self, url: &str) -> Result<VerifiableCredential, CredentialError> {
+        let body = self.client.get(url).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+/// Checks credentials from a set of trusted issuers
+///
+/// A credential is accepted when its issuer is trusted, it is within its
+/// validity period, its proof verifies against an assertion method of the
+/// issuer's resolved DID Document, and its status list bit is clear.
+pub struct CredentialVerifier {
+    resolver: Arc<dyn DidResolver>,
+    status_lists: Arc<dyn StatusListSource>,
+    trusted_issuers: HashSet<String>,
+}
+
+impl CredentialVerifier {
+    pub fn new(resolver: Arc<dyn DidResolver>, status_lists: Arc<dyn StatusListSource>) -> Self {
+        Self {
+            resolver,
+            status_lists,
+            trusted_issuers: HashSet::new(),
+        }
+    }
+
+    /// Accept credentials issued by `did`
+    pub fn with_trusted_issuer(mut self, did: impl Into<String>) -> Self {
+        self.trusted_issuers.insert(did.into());
+        self
+    }
+
+    /// Verify a credential at `now`, returning its subject
+    pub async fn verify<'a>(
+        &self,
+        credential: &'a VerifiableCredential,
+        now: DateTime<Utc>,
+    ) -> Result<&'a CredentialSubject, CredentialError> {
+        self.verify_issued(credential, now).await?;
+
+        if let Some(status) = &credential.credential_status {
+            if status.status_purpose != REVOCATION {
+                return Err(CredentialError::StatusList(format!(
+                    "unsupported status purpose {}",
+                    status.status_purpose
+                )));
+            }
+
+            let list = self
+                .status_lists
+                .fetch(&status.status_list_credential)
+                .await?;
+            self.verify_issued(&list, now).await?;
+            if list.issuer != credential.issuer || list.id != status.status_list_credential {
+                return Err(CredentialError::StatusList(format!(
+                    "{} is not a status list of {}",
+                    status.status_list_credential, credential.issuer
+                )));
+            }
+
+            let bits = match &list.credential_subject.claim {
+                CredentialClaim::StatusList {
+                    status_purpose,
+                    encoded_list,
+                    ..
+                } if status_purpose == REVOCATION => decode_status_list(encoded_list)?,
+                _ => {
+                    return Err(CredentialError::StatusList(format!(
+                        "{} is not a revocation list",
+                        list.id
+                    )))
+                }
+            };
+            if bit_set(&bits, status_index(status, bits.len())?) {
+                return Err(CredentialError::Revoked(credential.id.clone()));
+            }
+        }
+
+        Ok(&credential.credential_subject)
+    }
+
+    /// Issuer, validity period and proof
+    async fn verify_issued(
+        &self,
+        credential: &VerifiableCredential,
+        now: DateTime<Utc>,
+    ) -> Result<(), CredentialError> {
+        if !self.trusted_issuers.contains(&credential.issuer) {
+            return Err(CredentialError::UntrustedIssuer(credential.issuer.clone()));
+        }
+        if !credential.is_valid_at(now) {
+            return Err(CredentialError::Expired(credential.id.clone()));
+        }
+
+        let proof = credential
+            .proof
+            .as_ref()
+            .ok_or_else(|| CredentialError::InvalidProof(credential.id.clone()))?;
+        if proof.proof_type != PROOF_TYPE || proof.cryptosuite != CRYPTOSUITE {
+            return Err(CredentialError::UnsupportedProof(format!(
+                "{}/{}",
+                proof.proof_type, proof.cryptosuite
+            )));
+        }
+        if proof.proof_purpose != "assertionMethod" {
+            return Err(CredentialError::InvalidProof(credential.id.clone()));
+        }
+
+        let document = self.resolver.resolve(&credential.issuer).await?;
+        if !document.is_assertion_method(&proof.verification_method) {
+            return Err(CredentialError::InvalidProof(credential.id.clone()));
+        }
+        let key = document
+            .verification_method(&proof.verification_method)
+            .ok_or_else(|| DidError::KeyNotFound(proof.verification_method.clone()))?
+            .verifying_key()?;
+
+        let signature: [u8; 64] = proof
+            .proof_value
+            .strip_prefix('z')
+            .and_then(|s| bs58::decode(s).into_vec().ok())
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| CredentialError::InvalidProof(credential.id.clone()))?;
+        key.verify(
+            &credential.hash_data(proof)?,
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| CredentialError::InvalidProof(credential.id.clone()))
+    }
+}
+
+/// Verifiable credential errors
+#[derive(Debug, Error)]
+pub enum CredentialError {
+    #[error("Issuer is not trusted: {0}")]
+    UntrustedIssuer(String),
+
+    #[error("Credential {0} is not valid at this time")]
+    Expired(String),
+
+    #[error("Invalid proof on credential {0}")]
+    InvalidProof(String),
+
+    #[error("Unsupported proof: {0}")]
+    UnsupportedProof(String),
+
+    #[error("Credential {0} has been revoked")]
+    Revoked(String),
+
+    #[error("Status list error: {0}")]
+    StatusList(String),
+
+    #[error("Status list {0} is full")]
+    StatusListFull(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Did(#[from] DidError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::did_resolver::UniversalResolver;
+    use chrono::Duration;
+    use rand::rngs::OsRng;
+
+    const LIST_URL: &str = "https://actoris.network/status/1";
+
+    fn verifier(
+        issuer: &CredentialIssuer,
+        lists: HashMap<String, VerifiableCredential>,
+    ) -> CredentialVerifier {
+        CredentialVerifier::new(Arc::new(UniversalResolver::new()), Arc::new(lists))
+            .with_trusted_issuer(issuer.did())
+    }
+
+    #[tokio::test]
+    async fn test_trust_score_credential_round_trip() {
+        let issuer = CredentialIssuer::from_did_key(SigningKey::generate(&mut OsRng));
+        let mut list = StatusList::with_size(LIST_URL, 64);
+        let mut score = TrustScore::new();
+        score.verified_outcomes = 42;
+
+        let until = Utc::now() + Duration::days(30);
+        let credential = issuer
+            .issue(
+                "did:key:z6MkAgent",
+                CredentialClaim::trust_score(&score),
+                until,
+                Some(&mut list),
+            )
+            .unwrap();
+        let lists = HashMap::from([(
+            LIST_URL.to_string(),
+            list.to_credential(&issuer, until).unwrap(),
+        )]);
+
+        // Serialized form survives a round trip and still verifies
+        let json = serde_json::to_string(&credential).unwrap();
+        assert!(json.contains("\"credentialSubject\""));
+        assert!(json.contains("\"verifiedOutcomes\":42"));
+        let parsed: VerifiableCredential = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, credential);
+
+        let subject = verifier(&issuer, lists.clone())
+            .verify(&parsed, Utc::now())
+            .await
+            .unwrap();
+        assert!(matches!(
+            &subject.claim,
+            CredentialClaim::TrustScore { trust_score } if trust_score.verified_outcomes == 42
+        ));
+
+        // Tampering, expiry and unknown issuers are all rejected
+        let mut tampered = parsed.clone();
+        tampered.credential_subject.claim = CredentialClaim::VerificationTier {
+            verification_tier: 3,
+        };
+        let verifier = verifier(&issuer, lists);
+        assert!(matches!(
+            verifier.verify(&tampered, Utc::now()).await,
+            Err(CredentialError::InvalidProof(_))
+        ));
+        assert!(matches!(
+            verifier.verify(&parsed, until + Duration::seconds(1)).await,
+            Err(CredentialError::Expired(_))
+        ));
+        let stranger = CredentialIssuer::from_did_key(SigningKey::generate(&mut OsRng));
+        let foreign = stranger
+            .issue(
+                "did:key:z6MkAgent",
+                CredentialClaim::trust_score(&score),
+                until,
+                None,
+            )
+            .unwrap();
+        assert!(matches!(
+            verifier.verify(&foreign, Utc::now()).await,
+            Err(CredentialError::UntrustedIssuer(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_status_list_revocation() {
+        let issuer = CredentialIssuer::from_did_key(SigningKey::generate(&mut OsRng));
+        let mut list = StatusList::new(LIST_URL);
+        let until = Utc::now() + Duration::days(365);
+
+        let member = issuer
+            .issue(
+                "did:key:z6MkAgent",
+                CredentialClaim::membership(issuer.did(), Some("operator".to_string())),
+                until,
+                Some(&mut list),
+            )
+            .unwrap();
+        let tier = issuer
+            .issue(
+                "did:key:z6MkAgent",
+                CredentialClaim::VerificationTier {
+                    verification_tier: 2,
+                },
+                until,
+                Some(&mut list),
+            )
+            .unwrap();
+        assert_eq!(
+            tier.credential_status.as_ref().unwrap().status_list_index,
+            "1"
+        );
+
+        list.revoke(member.credential_status.as_ref().unwrap())
+            .unwrap();
+        let published = list.to_credential(&issuer, until).unwrap();
+        let verifier = verifier(
+            &issuer,
+            HashMap::from([(LIST_URL.to_string(), published.clone())]),
+        );
+
+        assert!(matches!(
+            verifier.verify(&member, Utc::now()).await,
+            Err(CredentialError::Revoked(_))
+        ));
+        verifier.verify(&tier, Utc::now()).await.unwrap();
+
+        // A list signed by someone else cannot clear a revocation
+        let forger = CredentialIssuer::from_did_key(SigningKey::generate(&mut OsRng));
+        let forged = StatusList::new(LIST_URL)
+            .to_credential(&forger, until)
+            .unwrap();
+        let fooled = CredentialVerifier::new(
+            Arc::new(UniversalResolver::new()),
+            Arc::new(HashMap::from([(LIST_URL.to_string(), forged)])),
+        )
+        .with_trusted_issuer(issuer.did())
+        .with_trusted_issuer(forger.did());
+        assert!(matches!(
+            fooled.verify(&member, Utc::now()).await,
+            Err(CredentialError::StatusList(_))
+        ));
+
+        let mut full = StatusList::with_size(LIST_URL, 1);
+        for _ in 0..8 {
+            full.allocate().unwrap();
+        }
+        assert!(matches!(
+            full.allocate(),
+            Err(CredentialError::StatusListFull(_))
+        ));
+    }
+}
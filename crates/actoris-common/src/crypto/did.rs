@@ -14,7 +14,7 @@ use thiserror::Error;
 const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
 
 /// DID method types supported
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DidMethod {
     /// Self-sovereign identity using Ed25519 key
@@ -69,6 +69,9 @@ impl ParsedDid {
     }
 
     /// Extract Ed25519 public key from did:key
+    ///
+    /// Other methods carry no key in the identifier; resolve their document
+    /// with a [`DidResolver`](super::did_resolver::DidResolver) instead.
     pub fn extract_public_key(&self) -> Result<[u8; 32], DidError> {
         if self.method != DidMethod::Key {
             return Err(DidError::WrongMethod {
@@ -80,6 +83,41 @@ impl ParsedDid {
         decode_did_key(&self.identifier)
     }
 
+    /// HTTPS URL of the DID Document for a did:web
+    ///
+    /// `did:web:example.com` maps to `https://example.com/.well-known/did.json`
+    /// and `did:web:example.com:users:alice` to
+    /// `https://example.com/users/alice/did.json`.
+    pub fn document_url(&self) -> Result<String, DidError> {
+        if self.method != DidMethod::Web {
+            return Err(DidError::WrongMethod {
+                expected: DidMethod::Web,
+                actual: self.method,
+            });
+        }
+
+        let mut segments = self.identifier.split(':');
+        let host = segments
+            .next()
+            .filter(|h| !h.is_empty())
+            .ok_or_else(|| DidError::InvalidFormat("did:web requires a domain".into()))?
+            .replace("%3A", ":")
+            .replace("%3a", ":");
+        let path: Vec<&str> = segments.collect();
+        if path
+            .iter()
+            .any(|s| s.is_empty() || *s == "." || *s == ".." || s.contains('/'))
+        {
+            return Err(DidError::InvalidFormat("invalid did:web path".into()));
+        }
+
+        if path.is_empty() {
+            Ok(format!("https://{}/.well-known/did.json", host))
+        } else {
+            Ok(format!("https://{}/{}/did.json", host, path.join("/")))
+        }
+    }
+
     /// Get verifying key for signature verification
     pub fn verifying_key(&self) -> Result<VerifyingKey, DidError> {
         let key_bytes = self.extract_public_key()?;
@@ -131,54 +169,398 @@ pub fn create_did_web(domain: &str, path: Option<&str>) -> String {
     }
 }
 
-/// DID Document (simplified)
+/// Verification method type for Ed25519 keys
+const ED25519_KEY_TYPE: &str = "Ed25519VerificationKey2020";
+
+/// Encode an Ed25519 public key as multibase (z = base58btc, multicodec-prefixed)
+fn ed25519_multibase(public_key: &[u8; 32]) -> String {
+    let mut prefixed = Vec::with_capacity(34);
+    prefixed.extend_from_slice(&ED25519_MULTICODEC);
+    prefixed.extend_from_slice(public_key);
+    format!("z{}", bs58::encode(&prefixed).into_string())
+}
+
+/// Accept either a single string or a list of strings
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+/// DID Document
+///
+/// Holds any number of verification methods. `authentication` and
+/// `assertion_method` reference methods by id, either absolute
+/// (`did:web:example.com#key-1`) or relative (`#key-1`). When `controller` is
+/// empty the subject controls its own document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DidDocument {
-    /// The DID subject
     #[serde(rename = "@context")]
     pub context: Vec<String>,
+    /// The DID subject
     pub id: String,
+    /// DIDs allowed to change this document
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub controller: Vec<String>,
+    #[serde(default)]
     pub verification_method: Vec<VerificationMethod>,
+    #[serde(default)]
     pub authentication: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_method: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service: Vec<ServiceEndpoint>,
 }
 
 /// Verification method in DID Document
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VerificationMethod {
     pub id: String,
     #[serde(rename = "type")]
     pub method_type: String,
     pub controller: String,
-    #[serde(rename = "publicKeyMultibase")]
-    pub public_key_multibase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_multibase: Option<String>,
+    /// Older Ed25519VerificationKey2018 encoding (raw key, base58btc)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_base58: Option<String>,
 }
 
-impl DidDocument {
-    /// Create a DID Document for a did:key
-    pub fn for_did_key(public_key: &[u8; 32]) -> Self {
-        let did = encode_did_key(public_key);
-        let vm_id = format!("{}#{}", did, &did[8..]);
+impl VerificationMethod {
+    /// Create an Ed25519 verification method
+    pub fn ed25519(id: String, controller: String, public_key: &[u8; 32]) -> Self {
+        Self {
+            id,
+            method_type: ED25519_KEY_TYPE.to_string(),
+            controller,
+            public_key_multibase: Some(ed25519_multibase(public_key)),
+            public_key_base58: None,
+        }
+    }
+
+    /// Raw Ed25519 public key
+    pub fn public_key(&self) -> Result<[u8; 32], DidError> {
+        if let Some(multibase) = &self.public_key_multibase {
+            if !multibase.starts_with('z') {
+                return Err(DidError::InvalidEncoding);
+            }
+            return decode_did_key(multibase);
+        }
+
+        let encoded = self
+            .public_key_base58
+            .as_ref()
+            .ok_or_else(|| DidError::KeyNotFound(self.id.clone()))?;
+        let decoded = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|_| DidError::InvalidEncoding)?;
+        decoded.try_into().map_err(|_| DidError::InvalidPublicKey)
+    }
+
+    /// Get verifying key for signature verification
+    pub fn verifying_key(&self) -> Result<VerifyingKey, DidError> {
+        VerifyingKey::from_bytes(&self.public_key()?).map_err(|_| DidError::InvalidPublicKey)
+    }
+}
 
-        // Encode public key as multibase (z = base58btc)
-        let mut prefixed = Vec::with_capacity(34);
-        prefixed.extend_from_slice(&ED25519_MULTICODEC);
-        prefixed.extend_from_slice(public_key);
-        let multibase = format!("z{}", bs58::encode(&prefixed).into_string());
+/// Service endpoint in DID Document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceEndpoint {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub service_endpoint: String,
+}
 
+impl DidDocument {
+    /// Create an empty DID Document controlled by its subject
+    pub fn new(did: impl Into<String>) -> Self {
         Self {
             context: vec![
                 "https://www.w3.org/ns/did/v1".to_string(),
                 "https://w3id.org/security/suites/ed25519-2020/v1".to_string(),
             ],
-            id: did.clone(),
-            verification_method: vec![VerificationMethod {
-                id: vm_id.clone(),
-                method_type: "Ed25519VerificationKey2020".to_string(),
-                controller: did,
-                public_key_multibase: multibase,
-            }],
-            authentication: vec![vm_id],
+            id: did.into(),
+            controller: Vec::new(),
+            verification_method: Vec::new(),
+            authentication: Vec::new(),
+            assertion_method: Vec::new(),
+            service: Vec::new(),
+        }
+    }
+
+    /// Create a DID Document for a did:key
+    pub fn for_did_key(public_key: &[u8; 32]) -> Self {
+        let did = encode_did_key(public_key);
+        let vm_id = format!("{}#{}", did, &did[8..]);
+
+        let mut doc = Self::new(did.clone());
+        doc.verification_method = vec![VerificationMethod::ed25519(vm_id.clone(), did, public_key)];
+        doc.authentication = vec![vm_id.clone()];
+        doc.assertion_method = vec![vm_id];
+        doc
+    }
+
+    /// Hand control of this document to another DID
+    pub fn with_controller(mut self, controller: impl Into<String>) -> Self {
+        self.controller.push(controller.into());
+        self
+    }
+
+    /// Add a service endpoint
+    pub fn with_service(mut self, service: ServiceEndpoint) -> Self {
+        self.service.push(service);
+        self
+    }
+
+    /// Add an Ed25519 key for authentication and assertion, returning its id
+    pub fn add_key(&mut self, public_key: &[u8; 32]) -> String {
+        let id = format!("{}#key-{}", self.id, self.next_key_index());
+        self.verification_method.push(VerificationMethod::ed25519(
+            id.clone(),
+            self.id.clone(),
+            public_key,
+        ));
+        self.authentication.push(id.clone());
+        self.assertion_method.push(id.clone());
+        id
+    }
+
+    /// DIDs allowed to change this document (the subject itself by default)
+    pub fn controllers(&self) -> Vec<&str> {
+        if self.controller.is_empty() {
+            vec![self.id.as_str()]
+        } else {
+            self.controller.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Look up a verification method by absolute or relative id
+    pub fn verification_method(&self, id: &str) -> Option<&VerificationMethod> {
+        let id = self.absolute_id(id);
+        self.verification_method
+            .iter()
+            .find(|vm| self.absolute_id(&vm.id) == id)
+    }
+
+    /// Verification methods usable for authentication
+    pub fn authentication_methods(&self) -> Vec<&VerificationMethod> {
+        self.authentication
+            .iter()
+            .filter_map(|id| self.verification_method(id))
+            .collect()
+    }
+
+    /// Check whether `id` is listed for authentication
+    pub fn is_authentication_method(&self, id: &str) -> bool {
+        let id = self.absolute_id(id);
+        self.authentication
+            .iter()
+            .any(|reference| self.absolute_id(reference) == id)
+    }
+
+    /// Check whether `id` is listed for issuing assertions (credentials)
+    pub fn is_assertion_method(&self, id: &str) -> bool {
+        let id = self.absolute_id(id);
+        self.assertion_method
+            .iter()
+            .any(|reference| self.absolute_id(reference) == id)
+    }
+
+    /// First service endpoint of the given type
+    pub fn service(&self, service_type: &str) -> Option<&ServiceEndpoint> {
+        self.service.iter().find(|s| s.service_type == service_type)
+    }
+
+    /// Verify a signature against any authentication key
+    pub fn verify(&self, message: &[u8], signature: &[u8; 64]) -> bool {
+        let sig = Signature::from_bytes(signature);
+        self.authentication_methods().into_iter().any(|vm| {
+            vm.verifying_key()
+                .map(|key| key.verify(message, &sig).is_ok())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Verify a signature against one authentication key
+    pub fn verify_with_method(
+        &self,
+        method_id: &str,
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, DidError> {
+        if !self.is_authentication_method(method_id) {
+            return Err(DidError::KeyNotFound(method_id.to_string()));
         }
+        let key = self
+            .verification_method(method_id)
+            .ok_or_else(|| DidError::KeyNotFound(method_id.to_string()))?
+            .verifying_key()?;
+
+        Ok(key
+            .verify(message, &Signature::from_bytes(signature))
+            .is_ok())
+    }
+
+    /// Replace a key as instructed by a signed rotation, returning the new key id
+    ///
+    /// `signer` is the resolved document of the DID that signed the rotation;
+    /// for self-controlled documents that is this document. The DID stays the
+    /// same, so credentials and lineage tied to it survive the rotation.
+    pub fn apply_rotation(
+        &mut self,
+        rotation: &KeyRotation,
+        signer: &DidDocument,
+    ) -> Result<String, DidError> {
+        if rotation.did != self.id {
+            return Err(DidError::InvalidFormat(format!(
+                "rotation targets {}, document is {}",
+                rotation.did, self.id
+            )));
+        }
+
+        // A did:key is its key; rotating it would change the DID
+        let parsed = ParsedDid::parse(&self.id)?;
+        if parsed.method == DidMethod::Key {
+            return Err(DidError::WrongMethod {
+                expected: DidMethod::Web,
+                actual: DidMethod::Key,
+            });
+        }
+
+        if !self.controllers().contains(&signer.id.as_str()) {
+            return Err(DidError::NotController {
+                did: self.id.clone(),
+                signer: signer.id.clone(),
+            });
+        }
+        let signature: [u8; 64] = rotation
+            .signature
+            .as_slice()
+            .try_into()
+            .map_err(|_| DidError::SignatureVerificationFailed)?;
+        if !signer.verify_with_method(
+            &rotation.signer_key,
+            &rotation.signing_payload(),
+            &signature,
+        )? {
+            return Err(DidError::SignatureVerificationFailed);
+        }
+
+        let retired = self.absolute_id(&rotation.retired_key);
+        if self.verification_method(&retired).is_none() {
+            return Err(DidError::KeyNotFound(rotation.retired_key.clone()));
+        }
+        let new_key = decode_did_key(&rotation.new_key_multibase)?;
+
+        let new_id = format!("{}#key-{}", self.id, self.next_key_index());
+        let controller = self.id.clone();
+        let self_id = self.id.clone();
+        let absolute = |id: &str| absolute_id(&self_id, id);
+
+        self.verification_method
+            .retain(|vm| absolute(&vm.id) != retired);
+        self.verification_method.push(VerificationMethod::ed25519(
+            new_id.clone(),
+            controller,
+            &new_key,
+        ));
+        for relationship in [&mut self.authentication, &mut self.assertion_method] {
+            for reference in relationship.iter_mut() {
+                if absolute(reference) == retired {
+                    *reference = new_id.clone();
+                }
+            }
+        }
+
+        Ok(new_id)
+    }
+
+    fn absolute_id(&self, id: &str) -> String {
+        absolute_id(&self.id, id)
+    }
+
+    /// Next free `#key-N` index (indices are never reused after rotation)
+    fn next_key_index(&self) -> u32 {
+        self.verification_method
+            .iter()
+            .filter_map(|vm| vm.id.rsplit_once("#key-"))
+            .filter_map(|(_, n)| n.parse::<u32>().ok())
+            .max()
+            .map_or(1, |n| n + 1)
+    }
+}
+
+fn absolute_id(did: &str, id: &str) -> String {
+    if id.starts_with('#') {
+        format!("{}{}", did, id)
+    } else {
+        id.to_string()
+    }
+}
+
+/// Signed instruction to replace a key in a DID Document
+///
+/// Signed by an authentication key of one of the document's controllers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotation {
+    /// Document whose key is replaced
+    pub did: String,
+    /// Verification method id being retired
+    pub retired_key: String,
+    /// Replacement Ed25519 key (multibase)
+    pub new_key_multibase: String,
+    /// Verification method id that signed the rotation
+    pub signer_key: String,
+    /// Ed25519 signature over [`KeyRotation::signing_payload`]
+    pub signature: Vec<u8>,
+}
+
+impl KeyRotation {
+    /// Create and sign a rotation
+    pub fn new(
+        did: impl Into<String>,
+        retired_key: impl Into<String>,
+        new_key: &[u8; 32],
+        signer_key: impl Into<String>,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let mut rotation = Self {
+            did: did.into(),
+            retired_key: retired_key.into(),
+            new_key_multibase: ed25519_multibase(new_key),
+            signer_key: signer_key.into(),
+            signature: Vec::new(),
+        };
+        rotation.signature = sign_with_key(signing_key, &rotation.signing_payload()).to_vec();
+        rotation
+    }
+
+    /// Bytes covered by the signature
+    pub fn signing_payload(&self) -> Vec<u8> {
+        format!(
+            "actoris:did-rotation:v1\n{}\n{}\n{}\n{}",
+            self.did, self.retired_key, self.new_key_multibase, self.signer_key
+        )
+        .into_bytes()
     }
 }
 
@@ -208,6 +590,12 @@ pub enum DidError {
 
     #[error("Resolution failed: {0}")]
     ResolutionFailed(String),
+
+    #[error("Verification method not found: {0}")]
+    KeyNotFound(String),
+
+    #[error("{signer} is not a controller of {did}")]
+    NotController { did: String, signer: String },
 }
 
 /// Sign data using a signing key, returning signature bytes
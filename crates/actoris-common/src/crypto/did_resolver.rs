@@ -0,0 +1,313 @@
+//! DID resolution
+//!
+//! Turns a DID into its [`DidDocument`]:
+//! - did:key documents are derived from the identifier itself
+//! - did:web documents are fetched from the domain over HTTPS
+//!
+//! HTTP is behind [`HttpClient`] so deployments bring their own client and
+//! tests can point resolution at a local server.
+//!
+//! Reference: https://w3c-ccg.github.io/did-method-web/
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::did::{DidDocument, DidError, DidMethod, ParsedDid};
+
+/// Resolves DIDs to DID Documents
+#[async_trait]
+pub trait DidResolver: Send + Sync {
+    /// Resolve a DID to its current document
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidError>;
+}
+
+/// Minimal HTTP client used by [`WebResolver`]
+#[async_trait]
+pub trait HttpClient: Send + Sync {
+    /// GET a URL, returning the body of a successful (2xx) response
+    async fn get(&self, url: &str) -> Result<Vec<u8>, DidError>;
+}
+
+/// Resolver for did:key
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyResolver;
+
+#[async_trait]
+impl DidResolver for KeyResolver {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidError> {
+        let parsed = ParsedDid::parse(did)?;
+        let public_key = parsed.extract_public_key()?;
+        Ok(DidDocument::for_did_key(&public_key))
+    }
+}
+
+/// Resolver for did:web
+pub struct WebResolver<C> {
+    client: C,
+}
+
+impl<C: HttpClient> WebResolver<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient> DidResolver for WebResolver<C> {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidError> {
+        let url = ParsedDid::parse(did)?.document_url()?;
+        let body = self.client.get(&url).await?;
+
+        let document: DidDocument = serde_json::from_slice(&body)
+            .map_err(|e| DidError::ResolutionFailed(format!("{}: {}", url, e)))?;
+
+        // A domain must not be able to serve a document for another DID
+        if document.id != did {
+            return Err(DidError::ResolutionFailed(format!(
+                "{} served a document for {}",
+                url, document.id
+            )));
+        }
+
+        tracing::debug!(did = %did, methods = document.verification_method.len(), "Resolved did:web");
+        Ok(document)
+    }
+}
+
+/// Resolver dispatching on DID method
+///
+/// did:key is always supported; other methods are registered with
+/// [`UniversalResolver::with_resolver`].
+pub struct UniversalResolver {
+    resolvers: HashMap<DidMethod, Arc<dyn DidResolver>>,
+}
+
+impl UniversalResolver {
+    pub fn new() -> Self {
+        let mut resolvers: HashMap<DidMethod, Arc<dyn DidResolver>> = HashMap::new();
+        resolvers.insert(DidMethod::Key, Arc::new(KeyResolver));
+        Self { resolvers }
+    }
+
+    /// Register (or replace) the resolver for a method
+    pub fn with_resolver(
+        mut self,
+        method: DidMethod,
+        resolver: impl DidResolver + 'static,
+    ) -> Self {
+        self.resolvers.insert(method, Arc::new(resolver));
+        self
+    }
+}
+
+impl Default for UniversalResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DidResolver for UniversalResolver {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidError> {
+        let parsed = ParsedDid::parse(did)?;
+        let resolver = self
+            .resolvers
+            .get(&parsed.method)
+            .ok_or_else(|| DidError::UnsupportedMethod(parsed.method.to_string()))?;
+        resolver.resolve(did).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::did::{encode_did_key, sign_with_key, KeyRotation, ServiceEndpoint};
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Plain-HTTP client that sends every request to a local server
+    struct LocalHttp {
+        addr: std::net::SocketAddr,
+    }
+
+    #[async_trait]
+    impl HttpClient for LocalHttp {
+        async fn get(&self, url: &str) -> Result<Vec<u8>, DidError> {
+            let rest = url.strip_prefix("https://").unwrap();
+            let (host, path) = rest.split_at(rest.find('/').unwrap());
+
+            let mut stream = TcpStream::connect(self.addr).await.unwrap();
+            let request = format!(
+                "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                path, host
+            );
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await.unwrap();
+
+            let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            if !response.starts_with(b"HTTP/1.1 200") {
+                return Err(DidError::ResolutionFailed(format!("{} not found", url)));
+            }
+            Ok(response[split + 4..].to_vec())
+        }
+    }
+
+    /// Serve `documents` (path → JSON) until the test ends
+    async fn serve(documents: HashMap<String, String>) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+                let response = match documents.get(&path) {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_resolve_did_key() {
+        let key = SigningKey::generate(&mut OsRng);
+        let did = encode_did_key(&key.verifying_key().to_bytes());
+
+        let doc = UniversalResolver::new().resolve(&did).await.unwrap();
+        assert_eq!(doc.id, did);
+        let signature = sign_with_key(&key, b"hello");
+        assert!(doc.verify(b"hello", &signature));
+
+        assert!(matches!(
+            UniversalResolver::new()
+                .resolve("did:web:example.com")
+                .await,
+            Err(DidError::UnsupportedMethod(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_did_web_and_rotate() {
+        let old_key = SigningKey::generate(&mut OsRng);
+        let new_key = SigningKey::generate(&mut OsRng);
+        let did = "did:web:example.com";
+
+        let mut doc = DidDocument::new(did).with_service(ServiceEndpoint {
+            id: format!("{}#agents", did),
+            service_type: "ActorisAgentRegistry".to_string(),
+            service_endpoint: "https://example.com/agents".to_string(),
+        });
+        let old_id = doc.add_key(&old_key.verifying_key().to_bytes());
+
+        let alice = DidDocument::new("did:web:example.com:users:alice").with_controller(did);
+        let documents = HashMap::from([
+            (
+                "/.well-known/did.json".to_string(),
+                serde_json::to_string(&doc).unwrap(),
+            ),
+            (
+                "/users/alice/did.json".to_string(),
+                serde_json::to_string(&alice).unwrap(),
+            ),
+        ]);
+        let addr = serve(documents).await;
+        let resolver = UniversalResolver::new()
+            .with_resolver(DidMethod::Web, WebResolver::new(LocalHttp { addr }));
+
+        let resolved = resolver.resolve(did).await.unwrap();
+        assert_eq!(
+            resolved
+                .service("ActorisAgentRegistry")
+                .unwrap()
+                .service_endpoint,
+            "https://example.com/agents"
+        );
+        assert!(resolved.verify(b"hello", &sign_with_key(&old_key, b"hello")));
+
+        let resolved_alice = resolver.resolve(&alice.id).await.unwrap();
+        assert_eq!(resolved_alice.controllers(), vec![did]);
+
+        // Unknown paths and mismatched ids fail resolution
+        assert!(resolver
+            .resolve("did:web:example.com:users:bob")
+            .await
+            .is_err());
+        let addr = serve(HashMap::from([(
+            "/.well-known/did.json".to_string(),
+            serde_json::to_string(&doc).unwrap(),
+        )]))
+        .await;
+        let forged = WebResolver::new(LocalHttp { addr });
+        assert!(forged.resolve("did:web:evil.com").await.is_err());
+
+        // Rotate: the DID is unchanged, only the new key verifies
+        let rotation = KeyRotation::new(
+            did,
+            &old_id,
+            &new_key.verifying_key().to_bytes(),
+            &old_id,
+            &old_key,
+        );
+        let mut rotated = resolved.clone();
+        let new_id = rotated.apply_rotation(&rotation, &resolved).unwrap();
+        assert_eq!(new_id, format!("{}#key-2", did));
+        assert_eq!(rotated.id, did);
+        assert!(rotated.verify(b"hello", &sign_with_key(&new_key, b"hello")));
+        assert!(!rotated.verify(b"hello", &sign_with_key(&old_key, b"hello")));
+
+        // The retired key can no longer rotate
+        let replay = KeyRotation::new(
+            did,
+            &new_id,
+            &old_key.verifying_key().to_bytes(),
+            &old_id,
+            &old_key,
+        );
+        let current = rotated.clone();
+        assert!(matches!(
+            rotated.apply_rotation(&replay, &current),
+            Err(DidError::KeyNotFound(_))
+        ));
+
+        // alice is controlled by example.com, not by herself
+        let mut alice_doc = resolved_alice.clone();
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let alice_id = alice_doc.add_key(&alice_key.verifying_key().to_bytes());
+        let self_signed = KeyRotation::new(
+            &alice_doc.id,
+            &alice_id,
+            &new_key.verifying_key().to_bytes(),
+            &alice_id,
+            &alice_key,
+        );
+        let alice_signer = alice_doc.clone();
+        assert!(matches!(
+            alice_doc.apply_rotation(&self_signed, &alice_signer),
+            Err(DidError::NotController { .. })
+        ));
+        let by_controller = KeyRotation::new(
+            &alice_doc.id,
+            &alice_id,
+            &new_key.verifying_key().to_bytes(),
+            &new_id,
+            &new_key,
+        );
+        alice_doc.apply_rotation(&by_controller, &rotated).unwrap();
+        assert!(alice_doc.verify(b"hi", &sign_with_key(&new_key, b"hi")));
+    }
+}
@@ -111,6 +111,26 @@ impl SigningCommitment {
         let commitments = frost::round1::SigningCommitments::new(hiding, binding);
         Ok((identifier, commitments))
     }
+
+    /// Wire encoding: identifier, hiding and binding (32 bytes each)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.identifier[..], &self.hiding[..], &self.binding[..]].concat()
+    }
+
+    /// Decode the wire encoding produced by [`SigningCommitment::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.len() != 96 {
+            return Err(CryptoError::FrostError(format!(
+                "Invalid commitment length: {}",
+                bytes.len()
+            )));
+        }
+        Ok(Self {
+            identifier: bytes[..32].to_vec(),
+            hiding: bytes[32..64].to_vec(),
+            binding: bytes[64..].to_vec(),
+        })
+    }
 }
 
 /// Signature share from round 2
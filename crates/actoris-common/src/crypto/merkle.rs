@@ -44,9 +44,11 @@ impl MerkleProof {
 }
 
 /// Binary Merkle tree implementation
+///
+/// A level with an odd number of nodes pairs its last node with itself.
 pub struct MerkleTree {
-    /// All nodes in the tree (leaves + internal nodes)
-    nodes: Vec<[u8; HASH_SIZE]>,
+    /// Tree levels, leaves first and the root last
+    levels: Vec<Vec<[u8; HASH_SIZE]>>,
     /// Number of leaves
     leaf_count: u64,
     /// Historic roots for rollback support
@@ -65,7 +67,7 @@ impl MerkleTree {
     /// Create a new empty Merkle tree
     pub fn new() -> Self {
         Self {
-            nodes: Vec::new(),
+            levels: Vec::new(),
             leaf_count: 0,
             historic_roots: HashMap::new(),
             version: 0,
@@ -84,11 +86,7 @@ impl MerkleTree {
 
     /// Get current root hash
     pub fn root(&self) -> Option<[u8; HASH_SIZE]> {
-        if self.nodes.is_empty() {
-            None
-        } else {
-            Some(self.nodes[0])
-        }
+        self.levels.last().and_then(|level| level.first()).copied()
     }
 
     /// Get number of leaves
@@ -106,6 +104,10 @@ impl MerkleTree {
         let index = self.leaf_count;
         self.leaf_count += 1;
 
+        match self.levels.first_mut() {
+            Some(leaves) => leaves.push(leaf_hash),
+            None => self.levels.push(vec![leaf_hash]),
+        }
         // For simplicity, rebuild the tree (production should use incremental update)
         self.rebuild();
 
@@ -138,49 +140,30 @@ impl MerkleTree {
 
     /// Get siblings for a leaf
     fn get_siblings(&self, leaf_index: u64) -> Vec<[u8; HASH_SIZE]> {
+        let mut index = leaf_index as usize;
         let mut siblings = Vec::new();
-        let mut index = leaf_index;
-        let mut level_size = self.leaf_count;
-        let mut level_start = self.nodes.len() - self.leaf_count as usize;
-
-        while level_size > 1 {
-            let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
-
-            if sibling_index < level_size {
-                let node_idx = level_start + sibling_index as usize;
-                if node_idx < self.nodes.len() {
-                    siblings.push(self.nodes[node_idx]);
-                }
-            } else {
-                // Odd leaf count - duplicate the last node
-                let node_idx = level_start + index as usize;
-                if node_idx < self.nodes.len() {
-                    siblings.push(self.nodes[node_idx]);
-                }
-            }
 
+        for level in &self.levels[..self.levels.len() - 1] {
+            // Odd level - the last node is paired with itself
+            let sibling = level.get(index ^ 1).unwrap_or(&level[index]);
+            siblings.push(*sibling);
             index /= 2;
-            level_size = (level_size + 1) / 2;
-            level_start -= level_size as usize;
         }
 
         siblings
     }
 
-    /// Rebuild tree from scratch
+    /// Rebuild the internal levels from the leaves
     fn rebuild(&mut self) {
-        // This is a simplified implementation
-        // Production should use an incremental update algorithm
-        self.nodes.clear();
-
-        if self.leaf_count == 0 {
-            return;
+        self.levels.truncate(1);
+
+        while let Some(level) = self.levels.last().filter(|level| level.len() > 1) {
+            let parents = level
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
+            self.levels.push(parents);
         }
-
-        // For now, just create dummy structure
-        // Real implementation would store leaves and compute internal nodes
-        let empty_hash = [0u8; HASH_SIZE];
-        self.nodes.push(empty_hash);
     }
 
     /// Get historic root at a specific version
@@ -344,4 +327,29 @@ mod tests {
         let wrong_leaf = hash_leaf(b"wrong data");
         assert!(!proof.verify(&wrong_leaf));
     }
+
+    #[test]
+    fn test_tree_proofs_verify() {
+        let leaves: Vec<_> = (0u8..5).map(|i| hash_leaf(&[i])).collect();
+        let mut tree = MerkleTree::from_leaves(leaves[..1].to_vec());
+        assert_eq!(tree.root(), Some(leaves[0]));
+        assert!(tree.generate_proof(0).unwrap().verify(&leaves[0]));
+
+        for leaf in &leaves[1..] {
+            tree.append(*leaf);
+        }
+        tree.commit();
+        let root = tree.root().unwrap();
+        assert_eq!(tree.get_historic_root(2), Some(root));
+
+        // Every leaf of an odd-sized tree proves against the same root
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.generate_proof(index as u64).unwrap();
+            assert_eq!(proof.root, root);
+            assert!(proof.verify(leaf));
+            assert!(MerkleTree::verify_proof(leaf, &proof.siblings, index, &root));
+            assert!(!proof.verify(&leaves[(index + 1) % leaves.len()]));
+        }
+        assert!(tree.generate_proof(5).is_none());
+    }
 }
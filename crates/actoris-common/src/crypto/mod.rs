@@ -4,11 +4,26 @@
 //! - FROST threshold signatures (3-of-N Schnorr)
 //! - Merkle tree operations for audit proofs
 //! - DID (Decentralized Identifier) operations
+//! - DID resolution for did:key and did:web
+//! - Capability tokens for offline-verifiable delegation chains
+//! - W3C Verifiable Credentials with status-list revocation
+//! - VRF proofs for verifiable oracle committee selection
 
+pub mod capability;
+pub mod credential;
 pub mod did;
+pub mod did_resolver;
 pub mod frost;
 pub mod merkle;
+pub mod vrf;
 
 // Re-export commonly used items
+pub use capability::{verify_chain, CapabilityScope, CapabilityToken, RevocationList};
+pub use credential::{
+    CredentialClaim, CredentialIssuer, CredentialVerifier, StatusList, VerifiableCredential,
+};
+pub use did::{DidDocument, KeyRotation, ServiceEndpoint, VerificationMethod};
+pub use did_resolver::{DidResolver, HttpClient, KeyResolver, UniversalResolver, WebResolver};
 pub use frost::{FrostKeyShare, FrostSigner, SignatureShare};
 pub use merkle::{MerkleProof, MerkleTree};
+pub use vrf::{VrfOutput, VrfProof};
@@ -0,0 +1,308 @@
+//! Verifiable Random Function (ECVRF-EDWARDS25519-SHA512-TAI)
+//!
+//! Implements the Edwards25519 VRF suite from RFC 9381 on top of the same
+//! Ed25519 keys used for DIDs. A VRF lets an oracle prove that a pseudo-random
+//! output was derived from its key and a public input, without anyone being
+//! able to predict the output before the key holder reveals it:
+//! - `prove` produces an 80-byte proof (Gamma || c || s)
+//! - `verify` checks the proof against the public key and returns the output
+//! - Outputs are unique per (key, input), so they cannot be ground
+//!
+//! Reference: RFC 9381 - https://www.rfc-editor.org/rfc/rfc9381.html
+
+use crate::error::CryptoError;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+/// Suite identifier for ECVRF-EDWARDS25519-SHA512-TAI
+const SUITE_STRING: u8 = 0x03;
+
+/// Challenge length in bytes (cLen)
+const CHALLENGE_LEN: usize = 16;
+
+/// Serialized proof length (ptLen + cLen + qLen)
+pub const PROOF_LEN: usize = 32 + CHALLENGE_LEN + 32;
+
+/// VRF output length (SHA-512 digest)
+pub const OUTPUT_LEN: usize = 64;
+
+/// VRF output (beta)
+pub type VrfOutput = [u8; OUTPUT_LEN];
+
+/// VRF proof (pi)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrfProof(#[serde(with = "proof_bytes")] pub [u8; PROOF_LEN]);
+
+/// Serde helper for [u8; 80] arrays
+mod proof_bytes {
+    use super::PROOF_LEN;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; PROOF_LEN], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        bytes.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; PROOF_LEN], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vec: Vec<u8> = Vec::deserialize(deserializer)?;
+        vec.try_into().map_err(|v: Vec<u8>| {
+            serde::de::Error::custom(format!("expected {} bytes, got {}", PROOF_LEN, v.len()))
+        })
+    }
+}
+
+impl VrfProof {
+    /// Parse a proof from raw bytes
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let arr: [u8; PROOF_LEN] = bytes
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+        Ok(Self(arr))
+    }
+
+    /// Raw proof bytes
+    pub fn to_bytes(&self) -> [u8; PROOF_LEN] {
+        self.0
+    }
+
+    /// Compute the VRF output from this proof
+    ///
+    /// Only meaningful after the proof has been checked with [`verify`].
+    pub fn to_output(&self) -> Result<VrfOutput, CryptoError> {
+        let (gamma, _, _) = decode_proof(&self.0)?;
+        Ok(gamma_to_hash(&gamma))
+    }
+}
+
+/// Produce a VRF proof for `alpha` using an Ed25519 signing key
+pub fn prove(signing_key: &SigningKey, alpha: &[u8]) -> VrfProof {
+    let x = signing_key.to_scalar();
+    let public_key = signing_key.verifying_key().to_bytes();
+
+    // Nonce prefix is the second half of the expanded secret key (RFC 8032)
+    let expanded = Sha512::digest(signing_key.to_bytes());
+
+    let h = encode_to_curve(&public_key, alpha)
+        .expect("try-and-increment fails with negligible probability");
+    let h_bytes = h.compress().to_bytes();
+    let gamma = h * x;
+
+    let k = {
+        let mut hasher = Sha512::new();
+        hasher.update(&expanded[32..]);
+        hasher.update(h_bytes);
+        Scalar::from_bytes_mod_order_wide(&digest_to_array(hasher))
+    };
+
+    let c = challenge(
+        &public_key,
+        &h_bytes,
+        &gamma,
+        &EdwardsPoint::mul_base(&k),
+        &(h * k),
+    );
+    let s = k + c * x;
+
+    let mut pi = [0u8; PROOF_LEN];
+    pi[..32].copy_from_slice(gamma.compress().as_bytes());
+    pi[32..32 + CHALLENGE_LEN].copy_from_slice(&c.to_bytes()[..CHALLENGE_LEN]);
+    pi[32 + CHALLENGE_LEN..].copy_from_slice(s.as_bytes());
+    VrfProof(pi)
+}
+
+/// Verify a VRF proof and return the output (beta) on success
+pub fn verify(
+    public_key: &[u8; 32],
+    alpha: &[u8],
+    proof: &VrfProof,
+) -> Result<VrfOutput, CryptoError> {
+    let y = CompressedEdwardsY(*public_key)
+        .decompress()
+        .ok_or(CryptoError::InvalidPublicKey)?;
+    if y.is_small_order() {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+
+    let (gamma, c, s) = decode_proof(&proof.0)?;
+    let h = encode_to_curve(public_key, alpha).ok_or(CryptoError::InvalidSignature)?;
+    let h_bytes = h.compress().to_bytes();
+
+    // U = s*B - c*Y, V = s*H - c*Gamma
+    let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&(-c), &y, &s);
+    let v = h * s - gamma * c;
+
+    if challenge(public_key, &h_bytes, &gamma, &u, &v) != c {
+        return Err(CryptoError::InvalidSignature);
+    }
+
+    Ok(gamma_to_hash(&gamma))
+}
+
+/// Interpret the first 8 bytes of a VRF output as a uniform fraction in [0, 1)
+pub fn output_to_unit(output: &VrfOutput) -> f64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&output[..8]);
+    (u64::from_be_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// ECVRF_encode_to_curve_try_and_increment
+fn encode_to_curve(public_key: &[u8; 32], alpha: &[u8]) -> Option<EdwardsPoint> {
+    (0u8..=255).find_map(|ctr| {
+        let mut hasher = Sha512::new();
+        hasher.update([SUITE_STRING, 0x01]);
+        hasher.update(public_key);
+        hasher.update(alpha);
+        hasher.update([ctr, 0x00]);
+        let digest = hasher.finalize();
+
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&digest[..32]);
+        CompressedEdwardsY(candidate)
+            .decompress()
+            .map(|p| p.mul_by_cofactor())
+    })
+}
+
+/// ECVRF_challenge_generation, truncated to cLen bytes
+fn challenge(
+    public_key: &[u8; 32],
+    h_bytes: &[u8; 32],
+    gamma: &EdwardsPoint,
+    u: &EdwardsPoint,
+    v: &EdwardsPoint,
+) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE_STRING, 0x02]);
+    hasher.update(public_key);
+    hasher.update(h_bytes);
+    hasher.update(gamma.compress().as_bytes());
+    hasher.update(u.compress().as_bytes());
+    hasher.update(v.compress().as_bytes());
+    hasher.update([0x00]);
+    let digest = hasher.finalize();
+
+    let mut c_bytes = [0u8; 32];
+    c_bytes[..CHALLENGE_LEN].copy_from_slice(&digest[..CHALLENGE_LEN]);
+    Scalar::from_bytes_mod_order(c_bytes)
+}
+
+/// ECVRF_decode_proof
+fn decode_proof(pi: &[u8; PROOF_LEN]) -> Result<(EdwardsPoint, Scalar, Scalar), CryptoError> {
+    let mut gamma_bytes = [0u8; 32];
+    gamma_bytes.copy_from_slice(&pi[..32]);
+    let gamma = CompressedEdwardsY(gamma_bytes)
+        .decompress()
+        .ok_or(CryptoError::InvalidSignature)?;
+
+    let mut c_bytes = [0u8; 32];
+    c_bytes[..CHALLENGE_LEN].copy_from_slice(&pi[32..32 + CHALLENGE_LEN]);
+    let c = Scalar::from_bytes_mod_order(c_bytes);
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&pi[32 + CHALLENGE_LEN..]);
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes))
+        .ok_or(CryptoError::InvalidSignature)?;
+
+    Ok((gamma, c, s))
+}
+
+/// ECVRF_proof_to_hash
+fn gamma_to_hash(gamma: &EdwardsPoint) -> VrfOutput {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE_STRING, 0x03]);
+    hasher.update(gamma.mul_by_cofactor().compress().as_bytes());
+    hasher.update([0x00]);
+    digest_to_array(hasher)
+}
+
+/// Finalize a SHA-512 hasher into a fixed-size array
+fn digest_to_array(hasher: Sha512) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_prove_and_verify() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let public_key = signing_key.verifying_key().to_bytes();
+
+        let proof = prove(&signing_key, b"request-1");
+        let output = verify(&public_key, b"request-1", &proof).unwrap();
+        assert_eq!(output, proof.to_output().unwrap());
+    }
+
+    #[test]
+    fn test_rfc9381_vector() {
+        // RFC 9381 Appendix B.3, Example 16 (empty alpha)
+        let sk: [u8; 32] =
+            hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let signing_key = SigningKey::from_bytes(&sk);
+
+        let proof = prove(&signing_key, b"");
+        assert_eq!(
+            hex::encode(proof.to_bytes()),
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+             26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12\
+             68a1b0db10836d9826a528ca76567805"
+        );
+
+        let output = verify(&signing_key.verifying_key().to_bytes(), b"", &proof).unwrap();
+        assert_eq!(
+            hex::encode(output),
+            "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+             66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae"
+        );
+    }
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+
+        let a = prove(&signing_key, b"seed");
+        let b = prove(&signing_key, b"seed");
+        assert_eq!(a, b);
+
+        let c = prove(&signing_key, b"other seed");
+        assert_ne!(a.to_output().unwrap(), c.to_output().unwrap());
+    }
+
+    #[test]
+    fn test_wrong_input_or_key_rejected() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+        let public_key = signing_key.verifying_key().to_bytes();
+
+        let proof = prove(&signing_key, b"seed");
+        assert!(verify(&public_key, b"different", &proof).is_err());
+        assert!(verify(&other_key.verifying_key().to_bytes(), b"seed", &proof).is_err());
+
+        let mut tampered = proof;
+        tampered.0[40] ^= 0x01;
+        assert!(verify(&public_key, b"seed", &tampered).is_err());
+    }
+
+    #[test]
+    fn test_output_to_unit_range() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let output = prove(&signing_key, b"seed").to_output().unwrap();
+        let unit = output_to_unit(&output);
+        assert!((0.0..1.0).contains(&unit));
+    }
+}
@@ -18,6 +18,9 @@ pub enum ActorisError {
     #[error("Wallet error: {0}")]
     Wallet(#[from] crate::types::hc_wallet::WalletError),
 
+    #[error("Journal error: {0}")]
+    Journal(#[from] crate::types::hc_journal::JournalError),
+
     // Crypto errors
     #[error("Cryptographic error: {0}")]
     Crypto(#[from] CryptoError),
@@ -38,6 +41,10 @@ pub enum ActorisError {
     #[error("Network error: {0}")]
     Network(String),
 
+    // Consensus errors
+    #[error("Consensus error: {0}")]
+    Consensus(String),
+
     // Configuration errors
     #[error("Configuration error: {0}")]
     Config(String),
@@ -121,6 +128,24 @@ pub enum VerificationError {
     #[error("Oracle unavailable: {oracle_did}")]
     OracleUnavailable { oracle_did: String },
 
+    #[error("Oracle not in committee for this request: {oracle_did}")]
+    NotInCommittee { oracle_did: String },
+
+    #[error("Committee for request {request_id} is still collecting tickets")]
+    CommitteePending { request_id: String },
+
+    #[error("Oracle not registered: {oracle_did}")]
+    UnknownOracle { oracle_did: String },
+
+    #[error("Oracle already voted on this request: {oracle_did}")]
+    DuplicateVote { oracle_did: String },
+
+    #[error("Verification request not found: {request_id}")]
+    RequestNotFound { request_id: String },
+
+    #[error("Reviewer not authorized: {reviewer_did}")]
+    UnknownReviewer { reviewer_did: String },
+
     #[error("Action semantics invalid: {reason}")]
     SemanticFailure { reason: String },
 
@@ -130,12 +155,15 @@ pub enum VerificationError {
     #[error("Action type not supported: {action_type}")]
     UnsupportedAction { action_type: String },
 
+    #[error("Action cannot be challenged: {reason}")]
+    ChallengeRejected { reason: String },
+
     #[error("Input validation failed: {0}")]
     InputValidation(String),
 }
 
 /// Pricing calculation errors
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum PricingError {
     #[error("Invalid compute amount: must be positive")]
     InvalidComputeAmount,
@@ -154,6 +182,9 @@ pub enum PricingError {
 
     #[error("Pricing calculation overflow")]
     Overflow,
+
+    #[error("Invalid rate: {0}")]
+    InvalidRate(f64),
 }
 
 // Implement From for common external error types
@@ -6,15 +6,23 @@
 //!
 //! - [`UnifiedID`]: DID-based identity for humans, agents, and organizations
 //! - [`TrustScore`]: 0-1000 score representing entity trustworthiness
+//! - [`EigenTrust`]: Network reputation from verified client/actor outcomes
 //! - [`HcWallet`]: HC (PFLOP-hours) credit balance management
+//! - [`HcJournal`]: Double-entry journal of wallet postings and statements
 //! - [`OutcomeRecord`]: Verified action record with oracle signatures
 //! - [`PricingRequest`]/[`PricingResponse`]: Pricing calculation types
+//! - [`PrincipalGraph`]: Org/human/agent hierarchy and financial accountability
+//! - [`Saga`]: Cross-service flows with compensating actions
 //!
 //! ## Crypto
 //!
 //! - [`crypto::frost`]: FROST threshold signature (3-of-N Schnorr)
 //! - [`crypto::merkle`]: Merkle tree for audit proofs
 //! - [`crypto::did`]: W3C DID operations
+//! - [`crypto::did_resolver`]: did:key and did:web resolution
+//! - [`crypto::capability`]: Signed capability tokens for delegation chains
+//! - [`crypto::credential`]: W3C Verifiable Credentials for trust attestations
+//! - [`crypto::vrf`]: ECVRF proofs for committee sortition
 //!
 //! ## Security
 //!
@@ -22,6 +30,7 @@
 //! - [`security::hsm`]: Hardware Security Module integration
 //! - [`security::policy`]: Security policy enforcement
 //! - [`security::audit`]: Audit logging
+//! - [`security::request_auth`]: DID-signed request authentication
 
 pub mod crypto;
 pub mod error;
@@ -32,13 +41,17 @@ pub mod types;
 pub use error::{ActorisError, Result};
 pub use types::{
     unified_id::{EntityType, UnifiedID},
-    trust_score::{TrustComponents, TrustScore},
-    hc_wallet::{HcWallet, WalletError},
-    outcome_record::{FrostSignature, OutcomeRecord, VerificationResult},
+    trust_score::{TrustComponents, TrustExplanation, TrustModel, TrustScore},
+    eigentrust::{EigenTrust, EigenTrustConfig},
+    hc_journal::{HcJournal, JournalError, Posting, Reference, Statement},
+    hc_wallet::{HcWallet, Tranche, TrancheSource, WalletError},
+    outcome_record::{FrostSignature, OutcomeRecord, VerificationMode, VerificationResult},
     pricing::{
         DataSensitivity, PricingBreakdown, PricingRequest, PricingResponse, RiskFactor,
         TaskComplexity,
     },
+    principal::{Accountability, BillingAccount, MemberRole, PrincipalGraph},
+    saga::{Saga, SagaError, SagaStep},
 };
 
 /// Actoris version
@@ -5,13 +5,18 @@
 //! - Hardware Security Module (HSM) integration for key management
 //! - Security policy enforcement
 //! - Audit logging
+//! - DID-signed request authentication
 
 pub mod mtls;
 pub mod hsm;
 pub mod policy;
 pub mod audit;
+pub mod request_auth;
 
 pub use mtls::{MtlsConfig, MtlsConnector, MtlsAcceptor, CertificateSource};
 pub use hsm::{HsmConfig, HsmProvider, HsmKeyHandle, KeyType};
 pub use policy::{SecurityPolicy, PolicyEnforcer, PolicyViolation};
 pub use audit::{AuditLogger, AuditEvent, AuditSeverity};
+pub use request_auth::{
+    AuthError, RequestAuthConfig, RequestAuthenticator, RequestParts, RequestSigner, SignedHeaders,
+};
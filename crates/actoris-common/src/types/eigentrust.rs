@@ -0,0 +1,426 @@
+//! EigenTrust network reputation
+//!
+//! Clients rate actors implicitly through verified outcomes: a passed,
+//! undisputed outcome is a positive interaction, a failed one or a lost
+//! dispute a negative one. Each client's net positive weight towards its
+//! actors forms a row of the local trust matrix, and the global trust vector
+//! is its stationary distribution mixed with a pre-trusted set, so ratings
+//! from trusted clients count for more than ratings from fresh identities.
+//!
+//! Interaction weights decay with a half-life, and [`EigenTrust::compute`]
+//! warm-starts from the previous vector so recomputing after a few new
+//! outcomes converges in a handful of iterations.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::outcome_record::OutcomeRecord;
+use super::trust_score::{TrustModel, TrustScore};
+
+/// Parameters of the EigenTrust computation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EigenTrustConfig {
+    /// Weight of the pre-trusted distribution in each iteration
+    pub pretrust_weight: f64,
+    /// L1 distance between iterations at which the vector has converged
+    pub epsilon: f64,
+    /// Iteration cap per computation
+    pub max_iterations: usize,
+    /// Days for an interaction to lose half its weight
+    pub half_life_days: f64,
+    /// Incoming interaction weight at which the network rating counts half
+    pub confidence_weight: f64,
+}
+
+impl Default for EigenTrustConfig {
+    fn default() -> Self {
+        Self {
+            pretrust_weight: 0.15,
+            epsilon: 1e-9,
+            max_iterations: 100,
+            half_life_days: 90.0,
+            confidence_weight: 5.0,
+        }
+    }
+}
+
+/// Decayed interaction weights from one client to one actor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+struct Interactions {
+    positive: f64,
+    negative: f64,
+    updated_at: i64,
+}
+
+impl Interactions {
+    fn decayed(&self, now: i64, half_life_days: f64) -> Self {
+        let factor = TrustModel::decay_factor(half_life_days, now - self.updated_at);
+        Self {
+            positive: self.positive * factor,
+            negative: self.negative * factor,
+            updated_at: self.updated_at.max(now),
+        }
+    }
+
+    /// Local trust: net positive weight, never below zero
+    fn local_trust(&self) -> f64 {
+        (self.positive - self.negative).max(0.0)
+    }
+}
+
+/// Why an entity has its network reputation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkTrustExplanation {
+    pub did: String,
+    /// Share of global trust held by the entity
+    pub global_trust: f64,
+    /// Global trust relative to the most trusted entity
+    pub relative_trust: f64,
+    /// Decayed weight of interactions the entity received
+    pub incoming_weight: f64,
+    /// Weight of the network rating versus the neutral prior
+    pub confidence: f64,
+    /// Reputation in [0, 1] fed into the network component
+    pub reputation: f64,
+    /// Largest contributors to the entity's global trust
+    pub top_sources: Vec<(String, f64)>,
+}
+
+/// EigenTrust over the client to actor interaction graph
+#[derive(Debug, Clone, Default)]
+pub struct EigenTrust {
+    config: EigenTrustConfig,
+    edges: HashMap<String, HashMap<String, Interactions>>,
+    pretrusted: HashSet<String>,
+    trust: HashMap<String, f64>,
+    computed_at: i64,
+}
+
+impl EigenTrust {
+    pub fn new(config: EigenTrustConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Build the graph from a batch of outcomes and compute trust as of `now`
+    pub fn from_outcomes<'a>(
+        config: EigenTrustConfig,
+        outcomes: impl IntoIterator<Item = &'a OutcomeRecord>,
+        now: i64,
+    ) -> Self {
+        let mut graph = Self::new(config);
+        for outcome in outcomes {
+            graph.record_outcome(outcome);
+        }
+        graph.compute(now);
+        graph
+    }
+
+    /// Seed trust with entities vouched for out of band (e.g. verified orgs)
+    pub fn with_pretrusted(mut self, did: impl Into<String>) -> Self {
+        self.pretrusted.insert(did.into());
+        self
+    }
+
+    pub fn config(&self) -> &EigenTrustConfig {
+        &self.config
+    }
+
+    /// Record an interaction between a client and the actor it hired
+    pub fn record_interaction(
+        &mut self,
+        client_did: &str,
+        actor_did: &str,
+        positive: bool,
+        at: i64,
+    ) {
+        if client_did == actor_did {
+            return;
+        }
+        let half_life = self.config.half_life_days;
+        let edge = self
+            .edges
+            .entry(client_did.to_string())
+            .or_default()
+            .entry(actor_did.to_string())
+            .or_insert(Interactions {
+                updated_at: at,
+                ..Default::default()
+            });
+        *edge = edge.decayed(at, half_life);
+        if positive {
+            edge.positive += 1.0;
+        } else {
+            edge.negative += 1.0;
+        }
+    }
+
+    /// Record a verified outcome; lost disputes count against the actor
+    pub fn record_outcome(&mut self, outcome: &OutcomeRecord) {
+        let positive = outcome.is_verified() && !outcome.dispute_upheld();
+        self.record_interaction(
+            &outcome.client_did,
+            &outcome.actor_did,
+            positive,
+            outcome.verified_at,
+        );
+    }
+
+    /// Recompute global trust as of `now`, returning the iterations used
+    pub fn compute(&mut self, now: i64) -> usize {
+        let mut nodes: Vec<&str> = self
+            .edges
+            .iter()
+            .flat_map(|(client, actors)| {
+                std::iter::once(client.as_str()).chain(actors.keys().map(String::as_str))
+            })
+            .chain(self.pretrusted.iter().map(String::as_str))
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        if nodes.is_empty() {
+            self.trust.clear();
+            self.computed_at = now;
+            return 0;
+        }
+        let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, d)| (*d, i)).collect();
+
+        // Pre-trusted distribution, uniform when nobody is pre-trusted
+        let mut pretrust = vec![0.0; nodes.len()];
+        if self.pretrusted.is_empty() {
+            pretrust.fill(1.0 / nodes.len() as f64);
+        } else {
+            let share = 1.0 / self.pretrusted.len() as f64;
+            for did in &self.pretrusted {
+                pretrust[index[did.as_str()]] = share;
+            }
+        }
+
+        // Row-normalized local trust; clients with no positive ratings
+        // defer to the pre-trusted distribution
+        let rows: Vec<Vec<(usize, f64)>> = nodes
+            .iter()
+            .map(|did| {
+                let Some(actors) = self.edges.get(*did) else {
+                    return Vec::new();
+                };
+                let weights: Vec<(usize, f64)> = actors
+                    .iter()
+                    .map(|(actor, edge)| {
+                        let local = edge.decayed(now, self.config.half_life_days).local_trust();
+                        (index[actor.as_str()], local)
+                    })
+                    .filter(|(_, w)| *w > 0.0)
+                    .collect();
+                let total: f64 = weights.iter().map(|(_, w)| w).sum();
+                weights.into_iter().map(|(j, w)| (j, w / total)).collect()
+            })
+            .collect();
+
+        // Warm start from the previous vector
+        let mut current: Vec<f64> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, did)| self.trust.get(*did).copied().unwrap_or(pretrust[i]))
+            .collect();
+        let total: f64 = current.iter().sum();
+        if total > 0.0 {
+            current.iter_mut().for_each(|t| *t /= total);
+        } else {
+            current.clone_from(&pretrust);
+        }
+
+        let alpha = self.config.pretrust_weight;
+        let mut iterations = 0;
+        while iterations < self.config.max_iterations {
+            iterations += 1;
+            let mut next: Vec<f64> = pretrust.iter().map(|p| alpha * p).collect();
+            for (i, row) in rows.iter().enumerate() {
+                let mass = (1.0 - alpha) * current[i];
+                if row.is_empty() {
+                    for (j, p) in pretrust.iter().enumerate() {
+                        next[j] += mass * p;
+                    }
+                } else {
+                    for (j, c) in row {
+                        next[*j] += mass * c;
+                    }
+                }
+            }
+            let delta: f64 = next.iter().zip(&current).map(|(a, b)| (a - b).abs()).sum();
+            current = next;
+            if delta < self.config.epsilon {
+                break;
+            }
+        }
+
+        self.trust = nodes
+            .iter()
+            .zip(current)
+            .map(|(did, t)| (did.to_string(), t))
+            .collect();
+        self.computed_at = now;
+        iterations
+    }
+
+    /// Share of global trust held by `did` as of the last computation
+    pub fn global_trust(&self, did: &str) -> f64 {
+        self.trust.get(did).copied().unwrap_or(0.0)
+    }
+
+    /// Reputation in [0, 1]: global trust relative to the most trusted
+    /// entity, shrunk towards 0.5 when `did` has received few interactions
+    pub fn reputation(&self, did: &str) -> f64 {
+        let (relative, confidence) = self.rating(did);
+        confidence * relative + (1.0 - confidence) * 0.5
+    }
+
+    /// Set the network component of `score` from the last computation
+    pub fn apply_to(&self, did: &str, score: &mut TrustScore) {
+        score.update_network_score(self.reputation(did));
+    }
+
+    /// Break down the network reputation of `did`
+    pub fn explain(&self, did: &str) -> NetworkTrustExplanation {
+        let (relative_trust, confidence) = self.rating(did);
+        let alpha = self.config.pretrust_weight;
+        let mut top_sources: Vec<(String, f64)> = self
+            .edges
+            .iter()
+            .filter_map(|(client, actors)| {
+                let edge = actors
+                    .get(did)?
+                    .decayed(self.computed_at, self.config.half_life_days);
+                let total: f64 = actors
+                    .values()
+                    .map(|e| {
+                        e.decayed(self.computed_at, self.config.half_life_days)
+                            .local_trust()
+                    })
+                    .sum();
+                let local = edge.local_trust();
+                if local <= 0.0 {
+                    return None;
+                }
+                let contribution = (1.0 - alpha) * self.global_trust(client) * local / total;
+                Some((client.clone(), contribution))
+            })
+            .collect();
+        top_sources.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_sources.truncate(5);
+
+        NetworkTrustExplanation {
+            did: did.to_string(),
+            global_trust: self.global_trust(did),
+            relative_trust,
+            incoming_weight: self.incoming_weight(did),
+            confidence,
+            reputation: confidence * relative_trust + (1.0 - confidence) * 0.5,
+            top_sources,
+        }
+    }
+
+    fn rating(&self, did: &str) -> (f64, f64) {
+        let max = self.trust.values().copied().fold(0.0, f64::max);
+        let relative = if max > 0.0 {
+            self.global_trust(did) / max
+        } else {
+            0.0
+        };
+        let incoming = self.incoming_weight(did);
+        let confidence = incoming / (incoming + self.config.confidence_weight);
+        (relative, confidence)
+    }
+
+    fn incoming_weight(&self, did: &str) -> f64 {
+        self.edges
+            .values()
+            .filter_map(|actors| actors.get(did))
+            .map(|edge| {
+                let edge = edge.decayed(self.computed_at, self.config.half_life_days);
+                edge.positive + edge.negative
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    fn graph() -> EigenTrust {
+        let mut graph = EigenTrust::new(EigenTrustConfig::default()).with_pretrusted("did:key:org");
+        for i in 0..20 {
+            graph.record_interaction("did:key:org", "did:key:good", true, NOW + i);
+            graph.record_interaction("did:key:org", "did:key:bad", i % 4 == 0, NOW + i);
+            graph.record_interaction("did:key:good", "did:key:helper", true, NOW + i);
+        }
+        // A sybil ring vouching for itself gets little trust
+        for i in 0..50 {
+            graph.record_interaction("did:key:sybil1", "did:key:sybil2", true, NOW + i);
+            graph.record_interaction("did:key:sybil2", "did:key:sybil1", true, NOW + i);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_trust_flows_from_pretrusted() {
+        let mut graph = graph();
+        assert!(graph.compute(NOW + 100) > 1);
+
+        let total: f64 = graph.trust.values().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        assert!(graph.global_trust("did:key:good") > graph.global_trust("did:key:bad"));
+        assert!(graph.global_trust("did:key:helper") > graph.global_trust("did:key:sybil1"));
+
+        // Newcomers without interactions sit at the neutral prior
+        assert_eq!(graph.reputation("did:key:newcomer"), 0.5);
+        assert!(graph.reputation("did:key:good") > 0.5);
+        assert!(graph.reputation("did:key:sybil1") < 0.5);
+
+        let mut score = TrustScore::new();
+        graph.apply_to("did:key:good", &mut score);
+        assert!(score.components.network_score > 100);
+
+        let explanation = graph.explain("did:key:helper");
+        assert_eq!(explanation.top_sources[0].0, "did:key:good");
+        assert_eq!(explanation.reputation, graph.reputation("did:key:helper"));
+    }
+
+    #[test]
+    fn test_incremental_recompute_warm_starts() {
+        let mut graph = graph();
+        let cold = graph.compute(NOW + 100);
+        graph.record_interaction("did:key:org", "did:key:good", true, NOW + 200);
+        let warm = graph.compute(NOW + 200);
+        assert!(warm < cold);
+
+        // Matches a batch computation over the same graph
+        let mut batch = graph.clone();
+        batch.trust.clear();
+        batch.compute(NOW + 200);
+        for (did, t) in &graph.trust {
+            assert!((batch.global_trust(did) - t).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_interactions_decay() {
+        let day = 86_400_000;
+        let mut graph = EigenTrust::new(EigenTrustConfig::default());
+        for _ in 0..10 {
+            graph.record_interaction("did:key:client", "did:key:actor", true, NOW);
+        }
+        graph.compute(NOW);
+        let fresh = graph.explain("did:key:actor");
+        graph.compute(NOW + 90 * day);
+        let stale = graph.explain("did:key:actor");
+        assert!((stale.incoming_weight - fresh.incoming_weight / 2.0).abs() < 1e-9);
+        assert!(stale.confidence < fresh.confidence);
+    }
+}
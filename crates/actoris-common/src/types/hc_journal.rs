@@ -0,0 +1,883 @@
+//! HC Journal - Double-entry record of wallet movements
+//!
+//! Every change to an [`HcWallet`] balance is a [`Posting`]: a set of legs
+//! across accounts that sums to zero. Each wallet has an available and a
+//! locked account, and HC enters and leaves circulation through system
+//! accounts (issuance, consumption, forfeiture, expiry), so the journal as a
+//! whole always balances and a wallet's balances can be rebuilt by replaying
+//! the postings that touched it.
+//!
+//! [`Statement`]s summarize a wallet's postings over a period and export as
+//! CSV or JSON.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::hc_wallet::{HcWallet, TrancheSource, WalletError};
+
+/// Journal errors
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum JournalError {
+    #[error("Posting {0} does not balance")]
+    Unbalanced(Uuid),
+
+    #[error("Posting {0} is already in the journal")]
+    Duplicate(Uuid),
+
+    #[error(transparent)]
+    Wallet(#[from] WalletError),
+
+    #[error("{account} holds {wallet} but the journal derives {journal}")]
+    BalanceMismatch {
+        account: Account,
+        wallet: Decimal,
+        journal: Decimal,
+    },
+}
+
+/// Accounts through which HC enters and leaves circulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemAccount {
+    /// Source of credited HC
+    Issuance,
+    /// HC spent on compute
+    Consumption,
+    /// Escrow forfeited in disputes
+    Forfeiture,
+    /// Balances swept after expiry
+    Expiry,
+}
+
+impl SystemAccount {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Issuance => "issuance",
+            Self::Consumption => "consumption",
+            Self::Forfeiture => "forfeiture",
+            Self::Expiry => "expiry",
+        }
+    }
+}
+
+/// Journal account
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "did", rename_all = "snake_case")]
+pub enum Account {
+    /// Spendable balance of a wallet
+    Available(String),
+    /// Escrowed balance of a wallet
+    Locked(String),
+    /// System account outside any wallet
+    System(SystemAccount),
+}
+
+impl Account {
+    /// DID of the wallet the account belongs to
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            Self::Available(did) | Self::Locked(did) => Some(did),
+            Self::System(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Available(did) => write!(f, "available:{}", did),
+            Self::Locked(did) => write!(f, "locked:{}", did),
+            Self::System(account) => write!(f, "system:{}", account.as_str()),
+        }
+    }
+}
+
+/// What a posting did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostingKind {
+    Credit,
+    Debit,
+    Lock,
+    Release,
+    Transfer,
+    Forfeit,
+    Expire,
+}
+
+impl PostingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Credit => "credit",
+            Self::Debit => "debit",
+            Self::Lock => "lock",
+            Self::Release => "release",
+            Self::Transfer => "transfer",
+            Self::Forfeit => "forfeit",
+            Self::Expire => "expire",
+        }
+    }
+}
+
+/// Business object a posting belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum Reference {
+    Outcome(String),
+    Invoice(String),
+    Loan(String),
+    Escrow(String),
+    Policy(String),
+    Other(String),
+}
+
+impl Reference {
+    /// Parse `kind:id` (e.g. `invoice:inv-42`); anything else is `Other`
+    pub fn parse(reference: &str) -> Self {
+        let Some((kind, id)) = reference.split_once(':') else {
+            return Self::Other(reference.to_string());
+        };
+        let id = id.to_string();
+        match kind {
+            "outcome" => Self::Outcome(id),
+            "invoice" => Self::Invoice(id),
+            "loan" => Self::Loan(id),
+            "escrow" => Self::Escrow(id),
+            "policy" => Self::Policy(id),
+            _ => Self::Other(reference.to_string()),
+        }
+    }
+
+    /// Tranche source of HC credited against this reference
+    pub fn tranche_source(&self) -> TrancheSource {
+        match self {
+            Self::Outcome(_) | Self::Escrow(_) | Self::Policy(_) => TrancheSource::Earnings,
+            Self::Loan(_) => TrancheSource::Loan,
+            Self::Invoice(_) | Self::Other(_) => TrancheSource::Purchase,
+        }
+    }
+}
+
+impl std::fmt::Display for Reference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Outcome(id) => write!(f, "outcome:{}", id),
+            Self::Invoice(id) => write!(f, "invoice:{}", id),
+            Self::Loan(id) => write!(f, "loan:{}", id),
+            Self::Escrow(id) => write!(f, "escrow:{}", id),
+            Self::Policy(id) => write!(f, "policy:{}", id),
+            Self::Other(id) => f.write_str(id),
+        }
+    }
+}
+
+/// Signed change to one account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Leg {
+    pub account: Account,
+    pub amount: Decimal,
+}
+
+/// Balanced set of legs recording one wallet operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Posting {
+    /// Unique posting ID (UUIDv7 for time-ordering)
+    pub id: Uuid,
+    pub kind: PostingKind,
+    /// Human-readable reason (e.g. "top-up", "usage")
+    pub reason: String,
+    pub reference: Option<Reference>,
+    pub legs: Vec<Leg>,
+    /// Posting timestamp (Unix milliseconds)
+    pub posted_at: i64,
+}
+
+impl Posting {
+    fn new(
+        kind: PostingKind,
+        reason: impl Into<String>,
+        amount: Decimal,
+        from: Account,
+        to: Account,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            kind,
+            reason: reason.into(),
+            reference: None,
+            legs: vec![
+                Leg {
+                    account: from,
+                    amount: -amount,
+                },
+                Leg {
+                    account: to,
+                    amount,
+                },
+            ],
+            posted_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Issue HC into a wallet
+    pub fn credit(did: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Credit,
+            reason,
+            amount,
+            Account::System(SystemAccount::Issuance),
+            Account::Available(did.to_string()),
+        )
+    }
+
+    /// Spend available HC
+    pub fn debit(did: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Debit,
+            reason,
+            amount,
+            Account::Available(did.to_string()),
+            Account::System(SystemAccount::Consumption),
+        )
+    }
+
+    /// Move available HC into escrow
+    pub fn lock(did: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Lock,
+            reason,
+            amount,
+            Account::Available(did.to_string()),
+            Account::Locked(did.to_string()),
+        )
+    }
+
+    /// Return escrowed HC to the owner
+    pub fn release(did: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Release,
+            reason,
+            amount,
+            Account::Locked(did.to_string()),
+            Account::Available(did.to_string()),
+        )
+    }
+
+    /// Pay available HC to another wallet
+    pub fn transfer(from: &str, to: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Transfer,
+            reason,
+            amount,
+            Account::Available(from.to_string()),
+            Account::Available(to.to_string()),
+        )
+    }
+
+    /// Settle escrowed HC to another wallet
+    pub fn transfer_locked(
+        from: &str,
+        to: &str,
+        amount: Decimal,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            PostingKind::Transfer,
+            reason,
+            amount,
+            Account::Locked(from.to_string()),
+            Account::Available(to.to_string()),
+        )
+    }
+
+    /// Forfeit escrowed HC
+    pub fn forfeit(did: &str, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self::new(
+            PostingKind::Forfeit,
+            reason,
+            amount,
+            Account::Locked(did.to_string()),
+            Account::System(SystemAccount::Forfeiture),
+        )
+    }
+
+    /// Sweep an expired available balance
+    pub fn expire(did: &str, amount: Decimal) -> Self {
+        Self::new(
+            PostingKind::Expire,
+            "expired",
+            amount,
+            Account::Available(did.to_string()),
+            Account::System(SystemAccount::Expiry),
+        )
+    }
+
+    /// Move HC across several accounts at once; the legs must sum to zero
+    pub fn adjustment(legs: Vec<Leg>, reason: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            kind: PostingKind::Transfer,
+            reason: reason.into(),
+            reference: None,
+            legs,
+            posted_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn with_reference(mut self, reference: Reference) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    pub fn with_posted_at(mut self, posted_at: i64) -> Self {
+        self.posted_at = posted_at;
+        self
+    }
+
+    /// Whether the legs are non-zero and sum to zero
+    pub fn is_balanced(&self) -> bool {
+        !self.legs.is_empty()
+            && self.legs.iter().all(|leg| !leg.amount.is_zero())
+            && self
+                .legs
+                .iter()
+                .map(|leg| leg.amount)
+                .sum::<Decimal>()
+                .is_zero()
+    }
+
+    /// Whether any leg belongs to the wallet of `did`
+    pub fn touches(&self, did: &str) -> bool {
+        self.legs.iter().any(|leg| leg.account.owner() == Some(did))
+    }
+
+    /// Net change to the available and locked balances of `did`
+    pub fn change(&self, did: &str) -> (Decimal, Decimal) {
+        let mut available = Decimal::ZERO;
+        let mut locked = Decimal::ZERO;
+        for leg in &self.legs {
+            match &leg.account {
+                Account::Available(owner) if owner == did => available += leg.amount,
+                Account::Locked(owner) if owner == did => locked += leg.amount,
+                _ => {}
+            }
+        }
+        (available, locked)
+    }
+
+    /// Posting that undoes this one
+    pub fn reversal(&self) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            kind: self.kind,
+            reason: format!("reversal of {}", self.reason),
+            reference: self.reference.clone(),
+            legs: self
+                .legs
+                .iter()
+                .map(|leg| Leg {
+                    account: leg.account.clone(),
+                    amount: -leg.amount,
+                })
+                .collect(),
+            posted_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Tranche source of HC this posting credits to a wallet
+    pub fn tranche_source(&self) -> TrancheSource {
+        match (&self.kind, &self.reference) {
+            (PostingKind::Transfer, _) => TrancheSource::Earnings,
+            (_, Some(reference)) => reference.tranche_source(),
+            (_, None) => TrancheSource::Purchase,
+        }
+    }
+
+    /// Apply this posting's change to the owner's wallet
+    ///
+    /// HC entering the locked balance is taken from available, and HC
+    /// leaving it returns to available before anything is forfeited, so the
+    /// wallet's tranches move the same way as with the direct wallet calls.
+    pub fn apply_to(&self, wallet: &mut HcWallet) -> Result<(), WalletError> {
+        let (available, locked) = self.change(&wallet.owner_did);
+        if locked > Decimal::ZERO {
+            let funded = available + locked;
+            if funded > Decimal::ZERO {
+                wallet.credit_from(funded, self.tranche_source())?;
+            }
+            wallet.lock(locked)?;
+            if funded < Decimal::ZERO {
+                wallet.debit(-funded)?;
+            }
+            return Ok(());
+        }
+
+        let mut available = available;
+        if locked < Decimal::ZERO {
+            let released = (-locked).min(available.max(Decimal::ZERO));
+            if released > Decimal::ZERO {
+                wallet.release(released)?;
+                available -= released;
+            }
+            if released < -locked {
+                wallet.forfeit_locked(-locked - released)?;
+            }
+        }
+        if available > Decimal::ZERO {
+            wallet.credit_from(available, self.tranche_source())?;
+        } else if available < Decimal::ZERO {
+            wallet.debit(-available)?;
+        }
+        Ok(())
+    }
+}
+
+/// Append-only double-entry journal of HC postings
+#[derive(Debug, Clone, Default)]
+pub struct HcJournal {
+    postings: Vec<Posting>,
+    ids: HashSet<Uuid>,
+    balances: HashMap<Account, Decimal>,
+}
+
+impl HcJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild a journal, and with it every balance, from its postings
+    pub fn replay(postings: impl IntoIterator<Item = Posting>) -> Result<Self, JournalError> {
+        let mut journal = Self::new();
+        for posting in postings {
+            journal.post(posting)?;
+        }
+        Ok(journal)
+    }
+
+    /// Append a posting
+    pub fn post(&mut self, posting: Posting) -> Result<(), JournalError> {
+        self.post_all(vec![posting])
+    }
+
+    /// Append postings atomically: all are recorded or none are
+    ///
+    /// Fails if a posting does not balance or would leave a wallet account
+    /// negative after the postings before it.
+    pub fn post_all(&mut self, postings: Vec<Posting>) -> Result<(), JournalError> {
+        self.post_with_wallets(postings, &[])
+    }
+
+    /// Append postings only if they leave `wallets` at the balances the
+    /// journal derives for them
+    ///
+    /// Lets a store write wallets and the postings that moved them together,
+    /// refusing writes the journal does not account for.
+    pub fn post_with_wallets(
+        &mut self,
+        postings: Vec<Posting>,
+        wallets: &[HcWallet],
+    ) -> Result<(), JournalError> {
+        let mut pending: HashMap<&Account, Decimal> = HashMap::new();
+        let mut ids = HashSet::new();
+        for posting in &postings {
+            if !posting.is_balanced() {
+                return Err(JournalError::Unbalanced(posting.id));
+            }
+            if self.ids.contains(&posting.id) || !ids.insert(posting.id) {
+                return Err(JournalError::Duplicate(posting.id));
+            }
+            for leg in &posting.legs {
+                let balance = pending
+                    .entry(&leg.account)
+                    .or_insert_with(|| self.balance(&leg.account));
+                *balance += leg.amount;
+                if leg.account.owner().is_some() && *balance < Decimal::ZERO {
+                    return Err(Self::overdrawn(&leg.account, *balance, leg.amount).into());
+                }
+            }
+        }
+        for wallet in wallets {
+            Self::check_wallet(wallet, |account| {
+                pending
+                    .get(account)
+                    .copied()
+                    .unwrap_or_else(|| self.balance(account))
+            })?;
+        }
+
+        let balances: Vec<(Account, Decimal)> = pending
+            .into_iter()
+            .map(|(account, balance)| (account.clone(), balance))
+            .collect();
+        self.balances.extend(balances);
+        self.ids.extend(ids);
+        self.postings.extend(postings);
+        Ok(())
+    }
+
+    fn overdrawn(account: &Account, balance: Decimal, amount: Decimal) -> WalletError {
+        let required = -amount;
+        let held = balance - amount;
+        match account {
+            Account::Locked(_) => WalletError::InsufficientLocked {
+                required,
+                locked: held,
+            },
+            _ => WalletError::InsufficientBalance {
+                required,
+                available: held,
+            },
+        }
+    }
+
+    /// Balance of an account; system accounts may be negative
+    pub fn balance(&self, account: &Account) -> Decimal {
+        self.balances.get(account).copied().unwrap_or_default()
+    }
+
+    /// Available and locked balances of the wallet of `did`
+    pub fn wallet_balances(&self, did: &str) -> (Decimal, Decimal) {
+        (
+            self.balance(&Account::Available(did.to_string())),
+            self.balance(&Account::Locked(did.to_string())),
+        )
+    }
+
+    /// Check a stored wallet against the balances the journal derives
+    pub fn verify(&self, wallet: &HcWallet) -> Result<(), JournalError> {
+        Self::check_wallet(wallet, |account| self.balance(account))
+    }
+
+    fn check_wallet(
+        wallet: &HcWallet,
+        balance: impl Fn(&Account) -> Decimal,
+    ) -> Result<(), JournalError> {
+        let checks = [
+            (
+                Account::Available(wallet.owner_did.clone()),
+                wallet.available,
+            ),
+            (Account::Locked(wallet.owner_did.clone()), wallet.locked),
+        ];
+        for (account, wallet) in checks {
+            let journal = balance(&account);
+            if wallet != journal {
+                return Err(JournalError::BalanceMismatch {
+                    account,
+                    wallet,
+                    journal,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// All postings, oldest first
+    pub fn postings(&self) -> &[Posting] {
+        &self.postings
+    }
+
+    /// Postings touching the wallet of `did`, oldest first
+    pub fn postings_for<'a>(&'a self, did: &'a str) -> impl Iterator<Item = &'a Posting> + 'a {
+        self.postings.iter().filter(move |p| p.touches(did))
+    }
+
+    /// Statement of the wallet of `did` for `[from, to)`
+    pub fn statement(&self, did: &str, from: i64, to: i64) -> Statement {
+        Statement::from_postings(did, self.postings_for(did), from, to)
+    }
+}
+
+/// One posting as seen from a wallet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub posting_id: Uuid,
+    pub posted_at: i64,
+    pub kind: PostingKind,
+    pub reason: String,
+    pub reference: Option<Reference>,
+    pub available_change: Decimal,
+    pub locked_change: Decimal,
+    /// Available balance after the posting
+    pub available: Decimal,
+    /// Locked balance after the posting
+    pub locked: Decimal,
+}
+
+/// Wallet activity over a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statement {
+    pub owner_did: String,
+    /// Period start (inclusive, Unix milliseconds)
+    pub from: i64,
+    /// Period end (exclusive, Unix milliseconds)
+    pub to: i64,
+    pub opening_available: Decimal,
+    pub opening_locked: Decimal,
+    pub closing_available: Decimal,
+    pub closing_locked: Decimal,
+    pub lines: Vec<StatementLine>,
+}
+
+impl Statement {
+    /// Build a statement from the wallet's postings
+    ///
+    /// Postings before `from` only contribute to the opening balances.
+    pub fn from_postings<'a>(
+        did: &str,
+        postings: impl IntoIterator<Item = &'a Posting>,
+        from: i64,
+        to: i64,
+    ) -> Self {
+        let mut postings: Vec<&Posting> = postings.into_iter().filter(|p| p.touches(did)).collect();
+        postings.sort_by_key(|p| p.posted_at);
+
+        let mut available = Decimal::ZERO;
+        let mut locked = Decimal::ZERO;
+        let mut opening = (Decimal::ZERO, Decimal::ZERO);
+        let mut lines = Vec::new();
+        for posting in postings {
+            if posting.posted_at >= to {
+                break;
+            }
+            let (available_change, locked_change) = posting.change(did);
+            available += available_change;
+            locked += locked_change;
+            if posting.posted_at < from {
+                opening = (available, locked);
+                continue;
+            }
+            lines.push(StatementLine {
+                posting_id: posting.id,
+                posted_at: posting.posted_at,
+                kind: posting.kind,
+                reason: posting.reason.clone(),
+                reference: posting.reference.clone(),
+                available_change,
+                locked_change,
+                available,
+                locked,
+            });
+        }
+
+        Self {
+            owner_did: did.to_string(),
+            from,
+            to,
+            opening_available: opening.0,
+            opening_locked: opening.1,
+            closing_available: available,
+            closing_locked: locked,
+            lines,
+        }
+    }
+
+    /// Export the lines as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "posting_id,posted_at,kind,reason,reference,available_change,locked_change,available,locked\n",
+        );
+        for line in &self.lines {
+            let reference = line
+                .reference
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                line.posting_id,
+                line.posted_at,
+                line.kind.as_str(),
+                csv_field(&line.reason),
+                csv_field(&reference),
+                line.available_change,
+                line.locked_change,
+                line.available,
+                line.locked
+            );
+        }
+        csv
+    }
+
+    /// Export the statement as JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const ALICE: &str = "did:key:alice";
+    const BOB: &str = "did:key:bob";
+
+    #[test]
+    fn test_balances_derive_from_postings() {
+        let mut journal = HcJournal::new();
+        journal
+            .post(Posting::credit(ALICE, dec!(100), "top-up").with_posted_at(1))
+            .unwrap();
+        journal
+            .post_all(vec![
+                Posting::lock(ALICE, dec!(40), "escrow")
+                    .with_reference(Reference::Escrow("e-1".into()))
+                    .with_posted_at(2),
+                Posting::transfer_locked(ALICE, BOB, dec!(30), "settle")
+                    .with_reference(Reference::Escrow("e-1".into()))
+                    .with_posted_at(3),
+                Posting::release(ALICE, dec!(10), "refund").with_posted_at(4),
+            ])
+            .unwrap();
+        journal
+            .post(Posting::debit(BOB, dec!(5), "usage").with_posted_at(5))
+            .unwrap();
+
+        assert_eq!(journal.wallet_balances(ALICE), (dec!(70), dec!(0)));
+        assert_eq!(journal.wallet_balances(BOB), (dec!(25), dec!(0)));
+        assert_eq!(
+            journal.balance(&Account::System(SystemAccount::Issuance)),
+            dec!(-100)
+        );
+
+        // The whole journal sums to zero
+        let total: Decimal = journal.balances.values().sum();
+        assert!(total.is_zero());
+
+        // Replaying the postings rebuilds the same balances
+        let replayed = HcJournal::replay(journal.postings().to_vec()).unwrap();
+        let mut wallet = HcWallet::with_balance(ALICE.to_string(), dec!(70));
+        replayed.verify(&wallet).unwrap();
+        wallet.available = dec!(71);
+        assert!(matches!(
+            replayed.verify(&wallet),
+            Err(JournalError::BalanceMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejected_batches_leave_journal_untouched() {
+        let mut journal = HcJournal::new();
+        journal
+            .post(Posting::credit(ALICE, dec!(10), "top-up"))
+            .unwrap();
+
+        let result = journal.post_all(vec![
+            Posting::debit(ALICE, dec!(5), "usage"),
+            Posting::debit(ALICE, dec!(6), "usage"),
+        ]);
+        assert!(matches!(
+            result,
+            Err(JournalError::Wallet(
+                WalletError::InsufficientBalance { .. }
+            ))
+        ));
+        assert_eq!(journal.postings().len(), 1);
+        assert_eq!(journal.wallet_balances(ALICE), (dec!(10), dec!(0)));
+
+        // A wallet write the postings do not account for is refused
+        let wallet = HcWallet::with_balance(ALICE.to_string(), dec!(4));
+        assert!(matches!(
+            journal.post_with_wallets(vec![Posting::debit(ALICE, dec!(5), "usage")], &[wallet]),
+            Err(JournalError::BalanceMismatch { .. })
+        ));
+        assert_eq!(journal.postings().len(), 1);
+
+        let mut unbalanced = Posting::credit(ALICE, dec!(1), "oops");
+        unbalanced.legs[0].amount = dec!(-2);
+        assert!(matches!(
+            journal.post(unbalanced),
+            Err(JournalError::Unbalanced(_))
+        ));
+    }
+
+    #[test]
+    fn test_statement_export() {
+        let postings = vec![
+            Posting::credit(ALICE, dec!(100), "top-up").with_posted_at(10),
+            Posting::debit(ALICE, dec!(20), "usage, batch 1")
+                .with_reference(Reference::parse("invoice:inv-7"))
+                .with_posted_at(20),
+            Posting::lock(ALICE, dec!(30), "escrow").with_posted_at(30),
+            Posting::credit(BOB, dec!(5), "top-up").with_posted_at(35),
+            Posting::expire(ALICE, dec!(50)).with_posted_at(40),
+        ];
+        let statement = Statement::from_postings(ALICE, &postings, 15, 40);
+
+        assert_eq!(
+            (statement.opening_available, statement.opening_locked),
+            (dec!(100), dec!(0))
+        );
+        assert_eq!(
+            (statement.closing_available, statement.closing_locked),
+            (dec!(50), dec!(30))
+        );
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(
+            statement.lines[0].reference,
+            Some(Reference::Invoice("inv-7".into()))
+        );
+
+        let csv = statement.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].ends_with(",debit,\"usage, batch 1\",invoice:inv-7,-20,0,80,0"));
+        assert!(rows[2].ends_with(",lock,escrow,,-30,30,50,30"));
+
+        let json: Statement = serde_json::from_str(&statement.to_json().unwrap()).unwrap();
+        assert_eq!(json, statement);
+    }
+
+    #[test]
+    fn test_postings_apply_to_wallets() {
+        let mut alice = HcWallet::new(ALICE.to_string());
+        let mut bob = HcWallet::new(BOB.to_string());
+        let settle = Posting::transfer_locked(ALICE, BOB, dec!(30), "settle");
+        let postings = vec![
+            Posting::credit(ALICE, dec!(100), "top-up"),
+            Posting::lock(ALICE, dec!(40), "escrow"),
+            settle.clone(),
+            Posting::release(ALICE, dec!(10), "refund"),
+        ];
+        for posting in &postings {
+            posting.apply_to(&mut alice).unwrap();
+            posting.apply_to(&mut bob).unwrap();
+        }
+        let mut journal = HcJournal::replay(postings).unwrap();
+        journal.verify(&alice).unwrap();
+        journal.verify(&bob).unwrap();
+        assert_eq!(bob.tranches[0].source, TrancheSource::Earnings);
+
+        // Reversing the settlement puts the HC back in escrow
+        let reversal = settle.reversal();
+        assert!(reversal.is_balanced());
+        reversal.apply_to(&mut alice).unwrap();
+        reversal.apply_to(&mut bob).unwrap();
+        journal.post(reversal).unwrap();
+        journal.verify(&alice).unwrap();
+        journal.verify(&bob).unwrap();
+        assert_eq!((alice.available, alice.locked), (dec!(70), dec!(30)));
+        assert_eq!(bob.total(), Decimal::ZERO);
+
+        assert!(matches!(
+            Posting::debit(BOB, dec!(1), "usage").apply_to(&mut bob),
+            Err(WalletError::InsufficientBalance { .. })
+        ));
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! HC (Harness Credits) represent compute capacity measured in PFLOP-hours.
 //! Key characteristics:
-//! - 30-day rolling expiration (encourages velocity)
+//! - Each credit is a [`Tranche`] expiring 30 days after it was issued
+//!   (encourages velocity); spending and locking consume tranches FIFO,
+//!   soonest expiry first, so a new credit never extends an old one
 //! - Can be locked in escrow (Delegate primitive)
 //! - Tracks available vs locked balance
 //! - Version field for optimistic concurrency
@@ -17,6 +19,8 @@ pub const EXPIRY_DAYS: i64 = 30;
 /// Minimum balance for operations
 pub const MIN_BALANCE: Decimal = Decimal::ZERO;
 
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
 /// Wallet operation errors
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum WalletError {
@@ -39,6 +43,54 @@ pub enum WalletError {
     VersionConflict { expected: u64, found: u64 },
 }
 
+/// Where a tranche of HC came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrancheSource {
+    /// Bought with external funds
+    #[default]
+    Purchase,
+    /// Paid for services or settled from escrow
+    Earnings,
+    /// Endowments and other grants
+    Grant,
+    /// Borrowed principal
+    Loan,
+}
+
+/// HC credited at one time, expiring on its own schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tranche {
+    /// Remaining amount (PFLOP-hours)
+    pub amount: Decimal,
+    pub source: TrancheSource,
+    /// Credit timestamp (Unix milliseconds)
+    pub issued_at: i64,
+    /// Expiration timestamp (Unix milliseconds)
+    pub expires_at: i64,
+}
+
+impl Tranche {
+    /// New tranche expiring [`EXPIRY_DAYS`] after `issued_at`
+    pub fn new(amount: Decimal, source: TrancheSource, issued_at: i64) -> Self {
+        Self {
+            amount,
+            source,
+            issued_at,
+            expires_at: issued_at + EXPIRY_DAYS * DAY_MS,
+        }
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Whole days left before expiry (negative once expired)
+    pub fn days_until_expiry(&self, now: i64) -> i64 {
+        (self.expires_at - now) / DAY_MS
+    }
+}
+
 /// HC Wallet for compute credit management
 ///
 /// Each entity in Actoris has an HC wallet that tracks their compute credits.
@@ -55,7 +107,7 @@ pub struct HcWallet {
     pub locked: Decimal,
 
     /// Expiration timestamp (Unix milliseconds)
-    /// Balance expires 30 days after last credit
+    /// Soonest expiry among the available tranches
     pub expires_at: i64,
 
     /// Version for optimistic concurrency control
@@ -63,6 +115,14 @@ pub struct HcWallet {
 
     /// Timestamp of last modification
     pub updated_at: i64,
+
+    /// Available balance by tranche, soonest expiry first
+    #[serde(default)]
+    pub tranches: Vec<Tranche>,
+
+    /// Locked balance by tranche, soonest expiry first
+    #[serde(default)]
+    pub locked_tranches: Vec<Tranche>,
 }
 
 impl HcWallet {
@@ -73,16 +133,22 @@ impl HcWallet {
             owner_did,
             available: Decimal::ZERO,
             locked: Decimal::ZERO,
-            expires_at: now + (EXPIRY_DAYS * 24 * 60 * 60 * 1000),
+            expires_at: now + (EXPIRY_DAYS * DAY_MS),
             version: 0,
             updated_at: now,
+            tranches: Vec::new(),
+            locked_tranches: Vec::new(),
         }
     }
 
     /// Create a wallet with initial balance
     pub fn with_balance(owner_did: String, initial_balance: Decimal) -> Self {
         let mut wallet = Self::new(owner_did);
-        wallet.available = initial_balance;
+        if initial_balance > Decimal::ZERO {
+            let tranche = Tranche::new(initial_balance, TrancheSource::Grant, wallet.updated_at);
+            wallet.available = initial_balance;
+            wallet.tranches.push(tranche);
+        }
         wallet
     }
 
@@ -92,41 +158,49 @@ impl HcWallet {
         self.available + self.locked
     }
 
-    /// Check if wallet has expired
+    /// Check if the soonest-expiring available tranche has expired
     pub fn is_expired(&self) -> bool {
         chrono::Utc::now().timestamp_millis() > self.expires_at
     }
 
-    /// Credit HC to the wallet (extend expiry)
+    /// Available HC that has not expired at `now`
+    pub fn spendable(&self, now: i64) -> Decimal {
+        self.tranches
+            .iter()
+            .filter(|t| !t.is_expired(now))
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    /// Credit purchased HC to the wallet as a new tranche
     pub fn credit(&mut self, amount: Decimal) -> Result<(), WalletError> {
+        self.credit_from(amount, TrancheSource::Purchase)
+    }
+
+    /// Credit HC from `source` as a new tranche
+    pub fn credit_from(
+        &mut self,
+        amount: Decimal,
+        source: TrancheSource,
+    ) -> Result<(), WalletError> {
         if amount <= Decimal::ZERO {
             return Err(WalletError::InvalidAmount);
         }
 
+        self.sync_tranches();
+        let now = chrono::Utc::now().timestamp_millis();
         self.available += amount;
+        insert_tranche(&mut self.tranches, Tranche::new(amount, source, now));
         self.refresh_expiry();
         self.touch();
         Ok(())
     }
 
-    /// Debit HC from available balance
+    /// Debit HC from available balance, soonest-expiring tranches first
     pub fn debit(&mut self, amount: Decimal) -> Result<(), WalletError> {
-        if amount <= Decimal::ZERO {
-            return Err(WalletError::InvalidAmount);
-        }
-
-        if self.is_expired() {
-            return Err(WalletError::Expired);
-        }
-
-        if self.available < amount {
-            return Err(WalletError::InsufficientBalance {
-                required: amount,
-                available: self.available,
-            });
-        }
-
+        self.take_spendable(amount)?;
         self.available -= amount;
+        self.refresh_expiry();
         self.touch();
         Ok(())
     }
@@ -135,24 +209,15 @@ impl HcWallet {
     ///
     /// Moves funds from available to locked, making them unavailable
     /// for regular spending but reserved for a specific operation.
+    /// Locked tranches keep their expiry.
     pub fn lock(&mut self, amount: Decimal) -> Result<(), WalletError> {
-        if amount <= Decimal::ZERO {
-            return Err(WalletError::InvalidAmount);
-        }
-
-        if self.is_expired() {
-            return Err(WalletError::Expired);
+        let taken = self.take_spendable(amount)?;
+        for tranche in taken {
+            insert_tranche(&mut self.locked_tranches, tranche);
         }
-
-        if self.available < amount {
-            return Err(WalletError::InsufficientBalance {
-                required: amount,
-                available: self.available,
-            });
-        }
-
         self.available -= amount;
         self.locked += amount;
+        self.refresh_expiry();
         self.touch();
         Ok(())
     }
@@ -160,21 +225,17 @@ impl HcWallet {
     /// Release HC from escrow back to available
     ///
     /// Called when an escrowed operation is cancelled or the agent
-    /// successfully completes the task.
+    /// successfully completes the task. Released tranches keep their
+    /// original expiry.
     pub fn release(&mut self, amount: Decimal) -> Result<(), WalletError> {
-        if amount <= Decimal::ZERO {
-            return Err(WalletError::InvalidAmount);
-        }
+        self.check_locked(amount)?;
 
-        if self.locked < amount {
-            return Err(WalletError::InsufficientLocked {
-                required: amount,
-                locked: self.locked,
-            });
+        for tranche in take_fifo(&mut self.locked_tranches, amount, None) {
+            insert_tranche(&mut self.tranches, tranche);
         }
-
         self.locked -= amount;
         self.available += amount;
+        self.refresh_expiry();
         self.touch();
         Ok(())
     }
@@ -182,34 +243,77 @@ impl HcWallet {
     /// Transfer locked HC to another wallet (escrow completion)
     ///
     /// Called when an escrowed operation completes successfully,
-    /// transferring the locked funds to the service provider.
+    /// transferring the locked funds to the service provider as earnings.
     pub fn transfer_locked(
         &mut self,
         amount: Decimal,
         recipient: &mut HcWallet,
     ) -> Result<(), WalletError> {
+        self.check_locked(amount)?;
+
+        take_fifo(&mut self.locked_tranches, amount, None);
+        self.locked -= amount;
+        recipient.credit_from(amount, TrancheSource::Earnings)?;
+
+        self.touch();
+        Ok(())
+    }
+
+    /// Forfeit locked HC (dispute resolution - funds burned or redistributed)
+    pub fn forfeit_locked(&mut self, amount: Decimal) -> Result<Decimal, WalletError> {
+        self.check_locked(amount)?;
+
+        take_fifo(&mut self.locked_tranches, amount, None);
+        self.locked -= amount;
+        self.touch();
+        Ok(amount) // Returns forfeited amount for redistribution
+    }
+
+    /// Sweep available tranches that expired before `now`
+    ///
+    /// Returns the amount swept. Locked HC stays in escrow until it settles.
+    pub fn sweep_expired(&mut self, now: i64) -> Decimal {
+        self.sync_tranches();
+        let swept: Decimal = self
+            .tranches
+            .iter()
+            .filter(|t| t.is_expired(now))
+            .map(|t| t.amount)
+            .sum();
+        if swept.is_zero() {
+            return Decimal::ZERO;
+        }
+
+        self.tranches.retain(|t| !t.is_expired(now));
+        self.available -= swept;
+        self.refresh_expiry();
+        self.touch();
+        swept
+    }
+
+    /// Validate an amount against the unexpired available tranches and
+    /// remove it from them
+    fn take_spendable(&mut self, amount: Decimal) -> Result<Vec<Tranche>, WalletError> {
         if amount <= Decimal::ZERO {
             return Err(WalletError::InvalidAmount);
         }
 
-        if self.locked < amount {
-            return Err(WalletError::InsufficientLocked {
+        if self.available < amount {
+            return Err(WalletError::InsufficientBalance {
                 required: amount,
-                locked: self.locked,
+                available: self.available,
             });
         }
 
-        self.locked -= amount;
-        recipient.available += amount;
-        recipient.refresh_expiry();
-
-        self.touch();
-        recipient.touch();
-        Ok(())
+        self.sync_tranches();
+        let now = chrono::Utc::now().timestamp_millis();
+        if self.spendable(now) < amount {
+            return Err(WalletError::Expired);
+        }
+        Ok(take_fifo(&mut self.tranches, amount, Some(now)))
     }
 
-    /// Forfeit locked HC (dispute resolution - funds burned or redistributed)
-    pub fn forfeit_locked(&mut self, amount: Decimal) -> Result<Decimal, WalletError> {
+    fn check_locked(&mut self, amount: Decimal) -> Result<(), WalletError> {
         if amount <= Decimal::ZERO {
             return Err(WalletError::InvalidAmount);
         }
@@ -221,15 +325,33 @@ impl HcWallet {
             });
         }
 
-        self.locked -= amount;
-        self.touch();
-        Ok(amount) // Returns forfeited amount for redistribution
+        self.sync_tranches();
+        Ok(())
     }
 
-    /// Refresh expiry timestamp (30 days from now)
+    /// Bring tranches in line with balances set without them
+    ///
+    /// Wallets stored before tranches existed hold their whole balance as a
+    /// single tranche expiring at the old wallet-level `expires_at`.
+    fn sync_tranches(&mut self) {
+        let untracked = Tranche {
+            amount: Decimal::ZERO,
+            source: TrancheSource::default(),
+            issued_at: self.updated_at,
+            expires_at: self.expires_at,
+        };
+        sync(&mut self.tranches, self.available, &untracked);
+        sync(&mut self.locked_tranches, self.locked, &untracked);
+    }
+
+    /// Point `expires_at` at the soonest available tranche expiry
     fn refresh_expiry(&mut self) {
-        let now = chrono::Utc::now().timestamp_millis();
-        self.expires_at = now + (EXPIRY_DAYS * 24 * 60 * 60 * 1000);
+        if let Some(first) = self.tranches.first() {
+            self.expires_at = first.expires_at;
+        } else {
+            let now = chrono::Utc::now().timestamp_millis();
+            self.expires_at = now + (EXPIRY_DAYS * DAY_MS);
+        }
     }
 
     /// Update version and timestamp
@@ -249,11 +371,73 @@ impl HcWallet {
         Ok(())
     }
 
-    /// Calculate remaining time until expiry in days
+    /// Calculate remaining time until the soonest tranche expires in days
     pub fn days_until_expiry(&self) -> i64 {
         let now = chrono::Utc::now().timestamp_millis();
         let remaining_ms = self.expires_at - now;
-        remaining_ms / (24 * 60 * 60 * 1000)
+        remaining_ms / DAY_MS
+    }
+
+    /// Unexpired available tranches, soonest expiry first
+    pub fn upcoming_expirations(&self, now: i64) -> impl Iterator<Item = &Tranche> {
+        self.tranches.iter().filter(move |t| !t.is_expired(now))
+    }
+}
+
+/// Insert keeping tranches ordered by expiry
+///
+/// A tranche from the same source expiring within a day of its neighbour is
+/// merged into it under the earlier expiry, so frequent small credits don't
+/// grow the list without bound.
+fn insert_tranche(tranches: &mut Vec<Tranche>, tranche: Tranche) {
+    let index = tranches.partition_point(|t| t.expires_at <= tranche.expires_at);
+    if let Some(previous) = index.checked_sub(1).map(|i| &mut tranches[i]) {
+        if previous.source == tranche.source && tranche.expires_at - previous.expires_at < DAY_MS {
+            previous.amount += tranche.amount;
+            return;
+        }
+    }
+    tranches.insert(index, tranche);
+}
+
+/// Take `amount` from the front of `tranches`, skipping tranches expired at
+/// `now` if given
+fn take_fifo(tranches: &mut Vec<Tranche>, amount: Decimal, now: Option<i64>) -> Vec<Tranche> {
+    let mut remaining = amount;
+    let mut taken = Vec::new();
+    for tranche in tranches.iter_mut() {
+        if remaining.is_zero() {
+            break;
+        }
+        if now.is_some_and(|now| tranche.is_expired(now)) {
+            continue;
+        }
+        let part = remaining.min(tranche.amount);
+        tranche.amount -= part;
+        remaining -= part;
+        taken.push(Tranche {
+            amount: part,
+            ..tranche.clone()
+        });
+    }
+    tranches.retain(|t| !t.amount.is_zero());
+    taken
+}
+
+/// Make `tranches` sum to `balance`, adding `untracked` for any shortfall
+/// and trimming the soonest-expiring tranches for any excess
+fn sync(tranches: &mut Vec<Tranche>, balance: Decimal, untracked: &Tranche) {
+    let tracked: Decimal = tranches.iter().map(|t| t.amount).sum();
+    if tracked < balance {
+        insert_tranche(
+            tranches,
+            Tranche {
+                amount: balance - tracked,
+                ..untracked.clone()
+            },
+        );
+    } else if tracked > balance {
+        take_fifo(tranches, tracked - balance, None);
     }
 }
 
@@ -340,4 +524,78 @@ mod tests {
         wallet.debit(dec!(5)).unwrap();
         assert_eq!(wallet.version, initial_version + 2);
     }
+
+    #[test]
+    fn test_sweep_expired() {
+        let mut wallet = HcWallet::with_balance("did:key:test".to_string(), dec!(100));
+        wallet.lock(dec!(30)).unwrap();
+
+        assert_eq!(wallet.sweep_expired(wallet.expires_at), Decimal::ZERO);
+        assert_eq!(wallet.sweep_expired(wallet.expires_at + 1), dec!(70));
+        assert_eq!(wallet.available, Decimal::ZERO);
+        assert_eq!(wallet.locked, dec!(30));
+    }
+
+    #[test]
+    fn test_tranches_consumed_fifo() {
+        let mut wallet = HcWallet::new("did:key:test".to_string());
+        wallet.credit(dec!(50)).unwrap();
+        wallet.tranches[0].expires_at -= 10 * DAY_MS;
+        wallet
+            .credit_from(dec!(30), TrancheSource::Earnings)
+            .unwrap();
+        assert_eq!(wallet.tranches.len(), 2);
+        assert_eq!(wallet.expires_at, wallet.tranches[0].expires_at);
+
+        // The older purchase goes first and a new credit doesn't extend it
+        wallet.debit(dec!(60)).unwrap();
+        assert_eq!(wallet.tranches.len(), 1);
+        assert_eq!(wallet.tranches[0].source, TrancheSource::Earnings);
+        assert_eq!(wallet.tranches[0].amount, dec!(20));
+        assert_eq!(wallet.expires_at, wallet.tranches[0].expires_at);
+
+        // Locked HC keeps its tranche and comes back with it
+        wallet.lock(dec!(15)).unwrap();
+        assert_eq!(wallet.locked_tranches[0].source, TrancheSource::Earnings);
+        wallet.release(dec!(15)).unwrap();
+        assert_eq!(wallet.tranches[0].amount, dec!(20));
+        assert!(wallet.locked_tranches.is_empty());
+    }
+
+    #[test]
+    fn test_expired_tranches_are_not_spendable() {
+        let mut wallet = HcWallet::new("did:key:test".to_string());
+        wallet.credit_from(dec!(40), TrancheSource::Grant).unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        wallet.tranches[0].expires_at = now - 1;
+        wallet.credit_from(dec!(10), TrancheSource::Loan).unwrap();
+
+        assert_eq!(wallet.spendable(now), dec!(10));
+        assert_eq!(wallet.debit(dec!(20)), Err(WalletError::Expired));
+        assert_eq!(wallet.upcoming_expirations(now).count(), 1);
+
+        assert_eq!(wallet.sweep_expired(now), dec!(40));
+        assert_eq!(wallet.available, dec!(10));
+        assert_eq!(wallet.tranches[0].source, TrancheSource::Loan);
+        assert_eq!(wallet.expires_at, wallet.tranches[0].expires_at);
+    }
+
+    #[test]
+    fn test_untracked_balance_becomes_tranche() {
+        let mut wallet: HcWallet = serde_json::from_value(serde_json::json!({
+            "owner_did": "did:key:legacy",
+            "available": "100",
+            "locked": "0",
+            "expires_at": chrono::Utc::now().timestamp_millis() + 3 * DAY_MS / 2,
+            "version": 3,
+            "updated_at": 0,
+        }))
+        .unwrap();
+        assert!(wallet.tranches.is_empty());
+
+        wallet.debit(dec!(40)).unwrap();
+        assert_eq!(wallet.tranches.len(), 1);
+        assert_eq!(wallet.tranches[0].amount, dec!(60));
+        assert_eq!(wallet.days_until_expiry(), 1);
+    }
 }
@@ -1,7 +1,11 @@
 //! Core data types for Actoris Economic OS
 
+pub mod eigentrust;
+pub mod hc_journal;
 pub mod hc_wallet;
 pub mod outcome_record;
 pub mod pricing;
+pub mod principal;
+pub mod saga;
 pub mod trust_score;
 pub mod unified_id;
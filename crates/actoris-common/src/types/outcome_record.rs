@@ -6,6 +6,7 @@
 //! - Verification result from oracle consensus
 //! - FROST threshold signature from oracles
 //! - Merkle proof for audit trail
+//! - Verification mode (optimistic, sampled or full quorum)
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -124,6 +125,10 @@ pub struct OracleVote {
 
     /// Vote timestamp
     pub timestamp: i64,
+
+    /// VRF proof of committee membership (80 bytes, per-request committees only)
+    #[serde(default)]
+    pub vrf_proof: Option<Vec<u8>>,
 }
 
 impl VerificationResult {
@@ -163,6 +168,79 @@ impl VerificationResult {
     }
 }
 
+/// Verification depth applied to an action
+///
+/// Low-risk actions are accepted optimistically or sampled instead of paying
+/// for a full oracle quorum. Pricing and insurance read this to adjust for the
+/// weaker guarantee.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum VerificationMode {
+    /// Accepted immediately; can be challenged until the deadline (Unix ms)
+    Optimistic { challenge_deadline: i64 },
+    /// Sent to the quorum with probability `rate`, otherwise accepted
+    Sampled { rate: f64, sampled: bool },
+    /// Verified by a full oracle quorum
+    #[default]
+    FullQuorum,
+}
+
+impl VerificationMode {
+    /// Whether an oracle quorum actually checked the action
+    pub fn quorum_checked(&self) -> bool {
+        matches!(
+            self,
+            VerificationMode::FullQuorum | VerificationMode::Sampled { sampled: true, .. }
+        )
+    }
+
+    /// Whether the action can still be challenged at `now` (Unix ms)
+    pub fn challengeable_at(&self, now: i64) -> bool {
+        match self {
+            VerificationMode::Optimistic { challenge_deadline } => now <= *challenge_deadline,
+            _ => false,
+        }
+    }
+
+    /// Short name for logs and wire formats
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationMode::Optimistic { .. } => "optimistic",
+            VerificationMode::Sampled { .. } => "sampled",
+            VerificationMode::FullQuorum => "full_quorum",
+        }
+    }
+}
+
+/// Domain separator for verdict messages
+const VERDICT_DOMAIN: &[u8] = b"actoris.verdict.v1";
+
+/// Message oracles threshold-sign for an action's verdict
+///
+/// Binds the action's identity to the outcome so a signature over one
+/// verdict can't be reattached to another action or the opposite outcome.
+pub fn verdict_message(
+    actor_did: &str,
+    client_did: &str,
+    action_type: &str,
+    input_hash: &[u8; 32],
+    output_hash: &[u8; 32],
+    compute_hc: &Decimal,
+    passed: bool,
+) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(VERDICT_DOMAIN);
+    for field in [actor_did, client_did, action_type] {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.update(input_hash);
+    hasher.update(output_hash);
+    hasher.update(compute_hc.to_string().as_bytes());
+    hasher.update(&[passed as u8]);
+    *hasher.finalize().as_bytes()
+}
+
 /// Verified action record with full audit trail
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OutcomeRecord {
@@ -193,6 +271,14 @@ pub struct OutcomeRecord {
     /// FROST threshold signature from oracles
     pub signature: FrostSignature,
 
+    /// Verification depth applied to this action
+    #[serde(default)]
+    pub verification_mode: VerificationMode,
+
+    /// Whether an optimistic acceptance was challenged before this outcome
+    #[serde(default)]
+    pub challenged: bool,
+
     /// Merkle proof path for audit trail
     pub merkle_proof: Vec<[u8; 32]>,
 
@@ -236,6 +322,8 @@ impl OutcomeRecord {
             compute_hc,
             verification,
             signature,
+            verification_mode: VerificationMode::FullQuorum,
+            challenged: false,
             merkle_proof: Vec::new(),
             merkle_root: [0u8; 32],
             merkle_index: 0,
@@ -259,6 +347,46 @@ impl OutcomeRecord {
         *hasher.finalize().as_bytes()
     }
 
+    /// Message the oracle quorum signed for this record's verdict
+    pub fn verdict_message(&self) -> [u8; 32] {
+        verdict_message(
+            &self.actor_did,
+            &self.client_did,
+            &self.action_type,
+            &self.input_hash,
+            &self.output_hash,
+            &self.compute_hc,
+            self.verification.passed,
+        )
+    }
+
+    /// Check the aggregated FROST signature against the record's group key
+    pub fn verify_signature(&self) -> bool {
+        crate::crypto::frost::verify_signature_bytes(
+            &self.verdict_message(),
+            &self.signature.signature,
+            &self.signature.group_key,
+        )
+        .unwrap_or(false)
+    }
+
+    /// Set the verification mode applied to this record
+    pub fn with_verification_mode(mut self, mode: VerificationMode) -> Self {
+        self.verification_mode = mode;
+        self
+    }
+
+    /// Mark the record as the outcome of a challenge
+    pub fn with_challenged(mut self, challenged: bool) -> Self {
+        self.challenged = challenged;
+        self
+    }
+
+    /// Whether a challenge against the action was upheld
+    pub fn dispute_upheld(&self) -> bool {
+        self.challenged && !self.verification.passed
+    }
+
     /// Set Merkle proof after tree inclusion
     pub fn set_merkle_proof(&mut self, proof: Vec<[u8; 32]>, root: [u8; 32], index: u64) {
         self.merkle_proof = proof;
@@ -267,11 +395,10 @@ impl OutcomeRecord {
     }
 
     /// Verify the Merkle proof is valid
+    ///
+    /// An empty proof only holds for the sole record of a tree, whose root is
+    /// the record's own hash.
     pub fn verify_merkle_proof(&self) -> bool {
-        if self.merkle_proof.is_empty() {
-            return false;
-        }
-
         let leaf_hash = self.canonical_hash();
         let mut current = leaf_hash;
         let mut index = self.merkle_index;
@@ -297,7 +424,13 @@ impl OutcomeRecord {
     }
 
     /// Check if this record passed verification
+    ///
+    /// Records accepted without a quorum (optimistic or unsampled) carry no
+    /// oracle signature and only need to have passed.
     pub fn is_verified(&self) -> bool {
+        if !self.verification_mode.quorum_checked() {
+            return self.verification.passed;
+        }
         self.verification.passed && self.signature.quorum_reached()
     }
 
@@ -405,6 +538,7 @@ mod tests {
                 approved: true,
                 reason: None,
                 timestamp: chrono::Utc::now().timestamp_millis(),
+                vrf_proof: None,
             }],
         );
 
@@ -461,6 +595,29 @@ mod tests {
         assert!(!sig_no_quorum.quorum_reached());
     }
 
+    #[test]
+    fn test_verification_mode() {
+        let record = create_test_record();
+        assert_eq!(record.verification_mode, VerificationMode::FullQuorum);
+
+        let optimistic = record.with_verification_mode(VerificationMode::Optimistic {
+            challenge_deadline: 1_000,
+        });
+        assert!(!optimistic.verification_mode.quorum_checked());
+        assert!(optimistic.verification_mode.challengeable_at(1_000));
+        assert!(!optimistic.verification_mode.challengeable_at(1_001));
+
+        // Optimistic records carry no quorum signature
+        assert!(optimistic.is_verified());
+
+        let sampled = VerificationMode::Sampled {
+            rate: 0.1,
+            sampled: true,
+        };
+        assert!(sampled.quorum_checked());
+        assert_eq!(sampled.as_str(), "sampled");
+    }
+
     #[test]
     fn test_verification_result_sla() {
         let fast = VerificationResult::success(3, 1500, vec![]);
@@ -10,8 +10,10 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use super::trust_score::TrustScore;
+
 /// Task complexity levels affecting risk premium
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskComplexity {
     /// Simple, well-defined tasks (+0% risk)
@@ -43,7 +45,7 @@ impl Default for TaskComplexity {
 }
 
 /// Data sensitivity levels affecting risk premium
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DataSensitivity {
     /// Public data, no privacy concerns (+0% risk)
@@ -160,6 +162,12 @@ impl PricingRequest {
         self.custom_factors.push(factor);
         self
     }
+
+    /// Use the actor's trust in this request's action domain
+    pub fn with_domain_trust(mut self, trust: &TrustScore) -> Self {
+        self.trust_score = trust.domain_score(&self.action_type);
+        self
+    }
 }
 
 /// Detailed pricing breakdown
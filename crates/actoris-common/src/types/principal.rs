@@ -0,0 +1,399 @@
+//! Principal hierarchy - who acts, who pays, who underwrites
+//!
+//! Links the entities behind every action:
+//! - Organizations have human members, each with a [`MemberRole`]
+//! - Humans own agents, optionally on behalf of an organization
+//! - Spawned agents answer to their parent's owner
+//! - Organizations (and humans) hold [`BillingAccount`]s
+//!
+//! [`PrincipalGraph::accountable_for`] walks from an acting entity up to the
+//! principal that is financially accountable for it.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::unified_id::{EntityType, UnifiedID};
+
+/// Principal graph errors
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum PrincipalError {
+    #[error("Unknown principal: {0}")]
+    UnknownPrincipal(String),
+
+    #[error("{did} is a {actual}, expected {expected}")]
+    WrongEntityType {
+        did: String,
+        expected: EntityType,
+        actual: EntityType,
+    },
+
+    #[error("{human} is not a member of {org}")]
+    NotMember { org: String, human: String },
+
+    #[error("{human} is a {role} of {org} and cannot do this")]
+    InsufficientRole {
+        org: String,
+        human: String,
+        role: MemberRole,
+    },
+}
+
+/// Role of a human within an organization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberRole {
+    /// Full control, including membership and billing
+    Owner,
+    /// Manages members and agents
+    Admin,
+    /// Manages the organization's billing accounts only
+    Billing,
+    /// Deploys and operates agents
+    Member,
+}
+
+impl MemberRole {
+    /// Whether the member may run agents on the organization's behalf
+    pub fn can_operate_agents(&self) -> bool {
+        !matches!(self, MemberRole::Billing)
+    }
+
+    /// Whether the member may manage billing accounts
+    pub fn can_manage_billing(&self) -> bool {
+        matches!(self, MemberRole::Owner | MemberRole::Billing)
+    }
+}
+
+impl std::fmt::Display for MemberRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberRole::Owner => write!(f, "owner"),
+            MemberRole::Admin => write!(f, "admin"),
+            MemberRole::Billing => write!(f, "billing"),
+            MemberRole::Member => write!(f, "member"),
+        }
+    }
+}
+
+/// A human's ownership of an agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ownership {
+    /// Owning human
+    pub owner_did: String,
+    /// Organization the agent operates for, if any
+    pub org_did: Option<String>,
+}
+
+/// Account that costs are billed to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BillingAccount {
+    /// Account identifier
+    pub id: String,
+    /// Organization or human holding the account
+    pub principal_did: String,
+    /// Wallet charged for the account's costs
+    pub wallet_did: String,
+}
+
+impl BillingAccount {
+    /// Account charged to the principal's own wallet
+    pub fn new(id: impl Into<String>, principal_did: impl Into<String>) -> Self {
+        let principal_did = principal_did.into();
+        Self {
+            id: id.into(),
+            wallet_did: principal_did.clone(),
+            principal_did,
+        }
+    }
+
+    /// Charge a different wallet (e.g. an org treasury)
+    pub fn with_wallet(mut self, wallet_did: impl Into<String>) -> Self {
+        self.wallet_did = wallet_did.into();
+        self
+    }
+}
+
+/// Who answers for an entity's actions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Accountability {
+    /// Entity that acted
+    pub actor_did: String,
+    /// Human owning the actor (directly or through its spawn lineage)
+    pub owner_did: Option<String>,
+    /// Organization the actor operates for
+    pub org_did: Option<String>,
+    /// Principal that pays: the organization, else the owner, else the actor
+    pub payer_did: String,
+    /// The payer's billing account, if one is set up
+    pub billing_account: Option<BillingAccount>,
+}
+
+/// Graph of organizations, humans and agents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrincipalGraph {
+    entities: HashMap<String, EntityType>,
+    /// org → human → role
+    members: HashMap<String, HashMap<String, MemberRole>>,
+    /// agent → direct owner
+    owners: HashMap<String, Ownership>,
+    /// spawned agent → parent
+    parents: HashMap<String, String>,
+    /// principal → billing account
+    accounts: HashMap<String, BillingAccount>,
+}
+
+impl PrincipalGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entity, recording spawn lineage for agents
+    pub fn register(&mut self, id: &UnifiedID) {
+        self.entities.insert(id.did.clone(), id.entity_type);
+        if let (EntityType::Agent, Some(parent)) = (id.entity_type, &id.parent_did) {
+            self.parents.insert(id.did.clone(), parent.clone());
+        }
+    }
+
+    pub fn entity_type(&self, did: &str) -> Option<EntityType> {
+        self.entities.get(did).copied()
+    }
+
+    /// Add (or change the role of) an organization member
+    pub fn add_member(
+        &mut self,
+        org_did: &str,
+        human_did: &str,
+        role: MemberRole,
+    ) -> Result<(), PrincipalError> {
+        self.expect_type(org_did, EntityType::Organization)?;
+        self.expect_type(human_did, EntityType::Human)?;
+        self.members
+            .entry(org_did.to_string())
+            .or_default()
+            .insert(human_did.to_string(), role);
+        Ok(())
+    }
+
+    /// Remove a member; agents they ran for the org fall back to them personally
+    pub fn remove_member(&mut self, org_did: &str, human_did: &str) -> Result<(), PrincipalError> {
+        self.members
+            .get_mut(org_did)
+            .and_then(|members| members.remove(human_did))
+            .ok_or_else(|| PrincipalError::NotMember {
+                org: org_did.to_string(),
+                human: human_did.to_string(),
+            })?;
+        for ownership in self.owners.values_mut() {
+            if ownership.owner_did == human_did && ownership.org_did.as_deref() == Some(org_did) {
+                ownership.org_did = None;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn role(&self, org_did: &str, human_did: &str) -> Option<MemberRole> {
+        self.members.get(org_did)?.get(human_did).copied()
+    }
+
+    /// Members of an organization
+    pub fn members(&self, org_did: &str) -> impl Iterator<Item = (&String, &MemberRole)> {
+        self.members.get(org_did).into_iter().flatten()
+    }
+
+    /// Make `human_did` the owner of `agent_did`, running it for `org_did`
+    ///
+    /// Running an agent for an organization requires a member role that
+    /// may operate agents.
+    pub fn assign_owner(
+        &mut self,
+        agent_did: &str,
+        human_did: &str,
+        org_did: Option<&str>,
+    ) -> Result<(), PrincipalError> {
+        self.expect_type(agent_did, EntityType::Agent)?;
+        self.expect_type(human_did, EntityType::Human)?;
+        if let Some(org) = org_did {
+            let role = self
+                .role(org, human_did)
+                .ok_or_else(|| PrincipalError::NotMember {
+                    org: org.to_string(),
+                    human: human_did.to_string(),
+                })?;
+            if !role.can_operate_agents() {
+                return Err(PrincipalError::InsufficientRole {
+                    org: org.to_string(),
+                    human: human_did.to_string(),
+                    role,
+                });
+            }
+        }
+        self.owners.insert(
+            agent_did.to_string(),
+            Ownership {
+                owner_did: human_did.to_string(),
+                org_did: org_did.map(str::to_string),
+            },
+        );
+        Ok(())
+    }
+
+    /// Owner of an agent, inherited from the nearest owned ancestor
+    pub fn owner_of(&self, agent_did: &str) -> Option<&Ownership> {
+        let mut current = agent_did;
+        let mut seen = HashSet::new();
+        while seen.insert(current) {
+            if let Some(ownership) = self.owners.get(current) {
+                return Some(ownership);
+            }
+            current = self.parents.get(current)?;
+        }
+        None
+    }
+
+    /// Set a principal's billing account
+    pub fn set_billing_account(&mut self, account: BillingAccount) -> Result<(), PrincipalError> {
+        match self.entity_type(&account.principal_did) {
+            Some(EntityType::Organization | EntityType::Human) => {}
+            Some(actual) => {
+                return Err(PrincipalError::WrongEntityType {
+                    did: account.principal_did,
+                    expected: EntityType::Organization,
+                    actual,
+                })
+            }
+            None => return Err(PrincipalError::UnknownPrincipal(account.principal_did)),
+        }
+        self.accounts.insert(account.principal_did.clone(), account);
+        Ok(())
+    }
+
+    pub fn billing_account(&self, principal_did: &str) -> Option<&BillingAccount> {
+        self.accounts.get(principal_did)
+    }
+
+    /// Who is financially accountable for `did`'s actions
+    pub fn accountable_for(&self, did: &str) -> Result<Accountability, PrincipalError> {
+        let entity_type = self
+            .entity_type(did)
+            .ok_or_else(|| PrincipalError::UnknownPrincipal(did.to_string()))?;
+
+        let (owner_did, org_did) = match entity_type {
+            EntityType::Agent => match self.owner_of(did) {
+                Some(ownership) => (Some(ownership.owner_did.clone()), ownership.org_did.clone()),
+                None => (None, None),
+            },
+            EntityType::Human => (None, None),
+            EntityType::Organization => (None, Some(did.to_string())),
+        };
+        let payer_did = org_did
+            .clone()
+            .or_else(|| owner_did.clone())
+            .unwrap_or_else(|| did.to_string());
+
+        Ok(Accountability {
+            actor_did: did.to_string(),
+            billing_account: self.accounts.get(&payer_did).cloned(),
+            owner_did,
+            org_did,
+            payer_did,
+        })
+    }
+
+    /// Payer for `did`, treating unknown entities as paying for themselves
+    pub fn payer_of(&self, did: &str) -> String {
+        self.accountable_for(did)
+            .map(|a| a.payer_did)
+            .unwrap_or_else(|_| did.to_string())
+    }
+
+    fn expect_type(&self, did: &str, expected: EntityType) -> Result<(), PrincipalError> {
+        match self.entity_type(did) {
+            Some(actual) if actual == expected => Ok(()),
+            Some(actual) => Err(PrincipalError::WrongEntityType {
+                did: did.to_string(),
+                expected,
+                actual,
+            }),
+            None => Err(PrincipalError::UnknownPrincipal(did.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principals() -> (PrincipalGraph, UnifiedID, UnifiedID, UnifiedID) {
+        let org = UnifiedID::new_organization("acme.example", [1u8; 32]);
+        let alice = UnifiedID::new_human_web("acme.example:users:alice", [2u8; 32]);
+        let (agent, _) = UnifiedID::new_agent(None);
+
+        let mut graph = PrincipalGraph::new();
+        graph.register(&org);
+        graph.register(&alice);
+        graph.register(&agent);
+        (graph, org, alice, agent)
+    }
+
+    #[test]
+    fn test_org_accountable_for_spawned_agents() {
+        let (mut graph, org, alice, agent) = principals();
+        let (child, _) = UnifiedID::new_agent(Some(&agent));
+        graph.register(&child);
+
+        // Alice must belong to the org before running agents for it
+        assert!(matches!(
+            graph.assign_owner(&agent.did, &alice.did, Some(&org.did)),
+            Err(PrincipalError::NotMember { .. })
+        ));
+        graph
+            .add_member(&org.did, &alice.did, MemberRole::Member)
+            .unwrap();
+        graph
+            .assign_owner(&agent.did, &alice.did, Some(&org.did))
+            .unwrap();
+        graph
+            .set_billing_account(BillingAccount::new("acct-acme", &org.did))
+            .unwrap();
+
+        let accountable = graph.accountable_for(&child.did).unwrap();
+        assert_eq!(accountable.owner_did.as_deref(), Some(alice.did.as_str()));
+        assert_eq!(accountable.org_did.as_deref(), Some(org.did.as_str()));
+        assert_eq!(accountable.payer_did, org.did);
+        assert_eq!(accountable.billing_account.unwrap().id, "acct-acme");
+
+        // Leaving the org makes Alice personally accountable
+        graph.remove_member(&org.did, &alice.did).unwrap();
+        assert_eq!(graph.payer_of(&child.did), alice.did);
+    }
+
+    #[test]
+    fn test_roles_and_entity_types() {
+        let (mut graph, org, alice, agent) = principals();
+
+        graph
+            .add_member(&org.did, &alice.did, MemberRole::Billing)
+            .unwrap();
+        assert!(matches!(
+            graph.assign_owner(&agent.did, &alice.did, Some(&org.did)),
+            Err(PrincipalError::InsufficientRole {
+                role: MemberRole::Billing,
+                ..
+            })
+        ));
+        assert!(matches!(
+            graph.add_member(&alice.did, &org.did, MemberRole::Owner),
+            Err(PrincipalError::WrongEntityType { .. })
+        ));
+        assert!(graph
+            .set_billing_account(BillingAccount::new("acct-agent", &agent.did))
+            .is_err());
+
+        // Unowned agents pay for themselves
+        assert_eq!(graph.payer_of(&agent.did), agent.did);
+        assert_eq!(graph.payer_of("did:key:unknown"), "did:key:unknown");
+    }
+}
@@ -0,0 +1,220 @@
+//! Sagas - Multi-step flows with compensating actions
+//!
+//! Flows that span services (escrow → settlement → fee split) cannot share
+//! one transaction. A [`Saga`] runs its steps in order; when a step fails,
+//! the steps that already completed are compensated in reverse order, so
+//! the flow either finishes or is undone. A compensation that itself fails
+//! is reported in [`SagaError::Uncompensated`] for manual repair.
+
+use std::fmt;
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+/// Saga errors
+#[derive(Debug, Error)]
+pub enum SagaError<E: fmt::Debug + fmt::Display> {
+    #[error("Saga {saga} aborted at step {step}: {error}")]
+    Aborted {
+        saga: String,
+        step: String,
+        error: E,
+    },
+
+    #[error(
+        "Saga {saga} aborted at step {step}: {error}; {} step(s) could not be compensated",
+        .uncompensated.len()
+    )]
+    Uncompensated {
+        saga: String,
+        step: String,
+        error: E,
+        /// Completed steps whose compensation failed, with the failure
+        uncompensated: Vec<(String, E)>,
+    },
+}
+
+/// One step of a saga
+///
+/// `compensate` is only called after `execute` succeeded and must undo it.
+#[async_trait]
+pub trait SagaStep<C: Send, E>: Send + Sync {
+    /// Step name used in errors and logs
+    fn name(&self) -> &str;
+
+    async fn execute(&self, ctx: &mut C) -> Result<(), E>;
+
+    async fn compensate(&self, ctx: &mut C) -> Result<(), E>;
+}
+
+/// Ordered steps run all-or-compensated
+pub struct Saga<C, E> {
+    name: String,
+    steps: Vec<Box<dyn SagaStep<C, E>>>,
+}
+
+impl<C: Send, E: fmt::Debug + fmt::Display + Send> Saga<C, E> {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Append a step
+    pub fn with_step(mut self, step: impl SagaStep<C, E> + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Step names in execution order
+    pub fn steps(&self) -> impl Iterator<Item = &str> {
+        self.steps.iter().map(|step| step.name())
+    }
+
+    /// Run every step, compensating completed steps if one fails
+    pub async fn run(&self, ctx: &mut C) -> Result<(), SagaError<E>> {
+        for (index, step) in self.steps.iter().enumerate() {
+            debug!(saga = %self.name, step = step.name(), "Executing saga step");
+            let Err(error) = step.execute(ctx).await else {
+                continue;
+            };
+
+            warn!(
+                saga = %self.name,
+                step = step.name(),
+                error = %error,
+                "Saga step failed, compensating"
+            );
+            let mut uncompensated = Vec::new();
+            for done in self.steps[..index].iter().rev() {
+                if let Err(e) = done.compensate(ctx).await {
+                    error!(
+                        saga = %self.name,
+                        step = done.name(),
+                        error = %e,
+                        "Saga compensation failed"
+                    );
+                    uncompensated.push((done.name().to_string(), e));
+                }
+            }
+
+            let saga = self.name.clone();
+            let step = step.name().to_string();
+            return Err(if uncompensated.is_empty() {
+                SagaError::Aborted { saga, step, error }
+            } else {
+                SagaError::Uncompensated {
+                    saga,
+                    step,
+                    error,
+                    uncompensated,
+                }
+            });
+        }
+
+        info!(saga = %self.name, steps = self.steps.len(), "Saga completed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records calls in the context; fails where configured
+    struct Step {
+        name: &'static str,
+        fail_execute: bool,
+        fail_compensate: bool,
+    }
+
+    impl Step {
+        fn ok(name: &'static str) -> Self {
+            Self {
+                name,
+                fail_execute: false,
+                fail_compensate: false,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SagaStep<Vec<String>, String> for Step {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn execute(&self, log: &mut Vec<String>) -> Result<(), String> {
+            if self.fail_execute {
+                return Err(format!("{} failed", self.name));
+            }
+            log.push(format!("+{}", self.name));
+            Ok(())
+        }
+
+        async fn compensate(&self, log: &mut Vec<String>) -> Result<(), String> {
+            if self.fail_compensate {
+                return Err(format!("{} stuck", self.name));
+            }
+            log.push(format!("-{}", self.name));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_compensates_in_reverse() {
+        let saga = Saga::new("settlement")
+            .with_step(Step::ok("escrow"))
+            .with_step(Step::ok("settle"))
+            .with_step(Step {
+                fail_execute: true,
+                ..Step::ok("fees")
+            });
+
+        let mut log = Vec::new();
+        let err = saga.run(&mut log).await.unwrap_err();
+        assert!(matches!(err, SagaError::Aborted { ref step, .. } if step == "fees"));
+        assert_eq!(log, ["+escrow", "+settle", "-settle", "-escrow"]);
+
+        let mut log = Vec::new();
+        Saga::new("ok")
+            .with_step(Step::ok("a"))
+            .run(&mut log)
+            .await
+            .unwrap();
+        assert_eq!(log, ["+a"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_compensation_is_reported() {
+        let saga = Saga::new("settlement")
+            .with_step(Step::ok("escrow"))
+            .with_step(Step {
+                fail_compensate: true,
+                ..Step::ok("settle")
+            })
+            .with_step(Step {
+                fail_execute: true,
+                ..Step::ok("fees")
+            });
+
+        let mut log = Vec::new();
+        match saga.run(&mut log).await {
+            Err(SagaError::Uncompensated { uncompensated, .. }) => {
+                assert_eq!(
+                    uncompensated,
+                    [("settle".to_string(), "settle stuck".to_string())]
+                );
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        // Remaining steps are still compensated
+        assert_eq!(log, ["+escrow", "+settle", "-escrow"]);
+    }
+}
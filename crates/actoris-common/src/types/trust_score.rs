@@ -5,6 +5,20 @@
 //! - Resource allocation (Darwinian selection)
 //! - Protocol access (certain primitives require minimum trust)
 //! - Credit limits (Lend primitive)
+//!
+//! Verification and dispute components are derived from time-decayed
+//! evidence ([`TrustEvidence`]) under a [`TrustModel`]: outcomes update a
+//! Beta posterior whose mean sets the verification score, so a handful of
+//! outcomes barely moves a new entity away from the prior, and both
+//! outcomes and dispute penalties fade with configurable half-lives. The
+//! network component comes from [`super::eigentrust::EigenTrust`].
+//!
+//! Evidence is also kept per action-type domain: an outcome for
+//! `code.review.rust` counts towards `code.review.rust`, `code.review` and
+//! `code`. [`TrustScore::domain_score`] uses the most specific domain with
+//! enough evidence and falls back to the global score otherwise.
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
@@ -20,8 +34,132 @@ pub const DEFAULT_SCORE: u16 = 500;
 /// Maximum trust discount rate (20%)
 pub const MAX_DISCOUNT_RATE: f64 = 0.20;
 
+/// Maximum verification component
+const MAX_VERIFICATION_SCORE: f64 = 400.0;
+
+/// Maximum dispute penalty
+const MAX_DISPUTE_PENALTY: f64 = 200.0;
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Parameters of the trust model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustModel {
+    /// Days for verification evidence to lose half its weight
+    pub evidence_half_life_days: f64,
+    /// Days for a dispute penalty to halve
+    pub dispute_half_life_days: f64,
+    /// Beta prior pseudo-count of successes
+    pub prior_successes: f64,
+    /// Beta prior pseudo-count of failures
+    pub prior_failures: f64,
+    /// Penalty points per (undecayed) lost dispute
+    pub dispute_penalty_points: f64,
+    /// Decayed outcomes a domain needs before its own score is used
+    pub min_domain_evidence: f64,
+}
+
+impl Default for TrustModel {
+    fn default() -> Self {
+        Self {
+            evidence_half_life_days: 90.0,
+            dispute_half_life_days: 30.0,
+            // Prior mean 0.5 matches the default verification score
+            prior_successes: 2.0,
+            prior_failures: 2.0,
+            dispute_penalty_points: 20.0,
+            min_domain_evidence: 10.0,
+        }
+    }
+}
+
+impl TrustModel {
+    /// Weight left after `elapsed_ms` with the given half-life
+    pub fn decay_factor(half_life_days: f64, elapsed_ms: i64) -> f64 {
+        if elapsed_ms <= 0 || half_life_days <= 0.0 {
+            return 1.0;
+        }
+        0.5f64.powf(elapsed_ms as f64 / MS_PER_DAY / half_life_days)
+    }
+}
+
+/// Time-decayed evidence behind the verification and dispute components
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrustEvidence {
+    /// Decayed count of passed verifications
+    pub successes: f64,
+    /// Decayed count of failed verifications
+    pub failures: f64,
+    /// Decayed count of lost disputes
+    pub disputes: f64,
+    /// When the counts were last decayed (Unix milliseconds, 0 = never)
+    pub decayed_at: i64,
+}
+
+impl TrustEvidence {
+    /// Decay the counts from `decayed_at` to `now`
+    pub fn decay_to(&mut self, now: i64, model: &TrustModel) {
+        if self.decayed_at > 0 && now > self.decayed_at {
+            let elapsed = now - self.decayed_at;
+            let evidence = TrustModel::decay_factor(model.evidence_half_life_days, elapsed);
+            let dispute = TrustModel::decay_factor(model.dispute_half_life_days, elapsed);
+            self.successes *= evidence;
+            self.failures *= evidence;
+            self.disputes *= dispute;
+        }
+        self.decayed_at = self.decayed_at.max(now);
+    }
+
+    /// Posterior mean verification success rate
+    pub fn success_rate(&self, model: &TrustModel) -> f64 {
+        let alpha = model.prior_successes + self.successes;
+        let beta = model.prior_failures + self.failures;
+        alpha / (alpha + beta)
+    }
+
+    /// Decayed number of verified outcomes
+    pub fn outcomes(&self) -> f64 {
+        self.successes + self.failures
+    }
+
+    /// Verification component implied by the evidence (0-400)
+    pub fn verification_score(&self, model: &TrustModel) -> u16 {
+        (self.success_rate(model) * MAX_VERIFICATION_SCORE).round() as u16
+    }
+
+    /// Dispute penalty implied by the evidence (0-200)
+    pub fn dispute_penalty(&self, model: &TrustModel) -> u16 {
+        (self.disputes * model.dispute_penalty_points)
+            .min(MAX_DISPUTE_PENALTY)
+            .round() as u16
+    }
+
+    /// Share of the posterior that comes from evidence rather than the prior
+    pub fn confidence(&self, model: &TrustModel) -> f64 {
+        let evidence = self.outcomes();
+        let prior = model.prior_successes + model.prior_failures;
+        if evidence + prior <= 0.0 {
+            return 0.0;
+        }
+        evidence / (evidence + prior)
+    }
+}
+
+/// Why a trust score is what it is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustExplanation {
+    pub score: u16,
+    pub components: TrustComponents,
+    /// Evidence as of the explanation time
+    pub evidence: TrustEvidence,
+    /// Posterior mean success rate behind `verification_score`
+    pub success_rate: f64,
+    /// Weight of evidence versus prior (0 = prior only)
+    pub confidence: f64,
+}
+
 /// Trust score breakdown by component
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TrustComponents {
     /// Outcome verification success rate (0-400 points)
     /// Based on percentage of actions that pass oracle verification
@@ -78,6 +216,14 @@ pub struct TrustScore {
 
     /// Score version for optimistic concurrency
     pub version: u64,
+
+    /// Decayed evidence behind the verification and dispute components
+    #[serde(default)]
+    pub evidence: TrustEvidence,
+
+    /// Evidence per action-type domain (e.g. `code`, `code.review`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub domains: BTreeMap<String, TrustEvidence>,
 }
 
 impl Default for TrustScore {
@@ -97,6 +243,8 @@ impl TrustScore {
             verified_outcomes: 0,
             dispute_rate: 0.0,
             version: 0,
+            evidence: TrustEvidence::default(),
+            domains: BTreeMap::new(),
         }
     }
 
@@ -127,33 +275,131 @@ impl TrustScore {
 
     /// Update verification component based on outcome
     pub fn record_verification(&mut self, success: bool) {
-        self.verified_outcomes += 1;
+        let now = chrono::Utc::now().timestamp_millis();
+        self.record_verification_at(success, now, &TrustModel::default());
+    }
 
-        // Calculate new success rate
-        let success_rate = if success {
-            (self.components.verification_score as f64 / 400.0 * (self.verified_outcomes - 1) as f64
-                + 1.0)
-                / self.verified_outcomes as f64
+    /// Record a verification outcome observed at `now` (Unix milliseconds)
+    pub fn record_verification_at(&mut self, success: bool, now: i64, model: &TrustModel) {
+        self.evidence.decay_to(now, model);
+        self.verified_outcomes += 1;
+        if success {
+            self.evidence.successes += 1.0;
         } else {
-            (self.components.verification_score as f64 / 400.0 * (self.verified_outcomes - 1) as f64)
-                / self.verified_outcomes as f64
-        };
-
-        // Update verification score (max 400)
-        self.components.verification_score = (success_rate * 400.0) as u16;
-        self.recalculate();
+            self.evidence.failures += 1.0;
+        }
+        self.refresh(now, model);
     }
 
     /// Record a dispute (reduces trust)
     pub fn record_dispute(&mut self) {
-        // Each dispute adds penalty (decays over time in separate process)
-        self.components.dispute_penalty = (self.components.dispute_penalty + 20).min(200);
+        let now = chrono::Utc::now().timestamp_millis();
+        self.record_dispute_at(now, &TrustModel::default());
+    }
 
-        // Update dispute rate
-        let total_interactions = self.verified_outcomes.max(1);
-        self.dispute_rate = self.components.dispute_penalty as f64 / 200.0;
+    /// Record a lost dispute at `now`; its penalty decays from then on
+    pub fn record_dispute_at(&mut self, now: i64, model: &TrustModel) {
+        self.evidence.decay_to(now, model);
+        self.evidence.disputes += 1.0;
+        self.refresh(now, model);
+    }
 
-        self.recalculate();
+    /// Record a verification outcome for an action type
+    ///
+    /// Counts towards the global score and every domain of `action_type`.
+    pub fn record_outcome_at(
+        &mut self,
+        action_type: &str,
+        success: bool,
+        now: i64,
+        model: &TrustModel,
+    ) {
+        for domain in action_domains(action_type) {
+            let evidence = self.domains.entry(domain.to_string()).or_default();
+            evidence.decay_to(now, model);
+            if success {
+                evidence.successes += 1.0;
+            } else {
+                evidence.failures += 1.0;
+            }
+        }
+        self.record_verification_at(success, now, model);
+    }
+
+    /// Record a lost dispute over an action type
+    pub fn record_domain_dispute_at(&mut self, action_type: &str, now: i64, model: &TrustModel) {
+        for domain in action_domains(action_type) {
+            let evidence = self.domains.entry(domain.to_string()).or_default();
+            evidence.decay_to(now, model);
+            evidence.disputes += 1.0;
+        }
+        self.record_dispute_at(now, model);
+    }
+
+    /// Most specific domain of `action_type` with enough evidence of its own
+    pub fn resolve_domain(&self, action_type: &str, model: &TrustModel) -> Option<&str> {
+        action_domains(action_type)
+            .into_iter()
+            .filter_map(|domain| self.domains.get_key_value(domain))
+            .find(|(_, evidence)| evidence.outcomes() >= model.min_domain_evidence)
+            .map(|(domain, _)| domain.as_str())
+    }
+
+    /// Trust score for an action type (0-1000)
+    pub fn domain_score(&self, action_type: &str) -> u16 {
+        self.domain_score_with(action_type, &TrustModel::default())
+    }
+
+    /// Trust score for an action type under `model`
+    ///
+    /// SLA and network components are always global; verification and
+    /// dispute components come from the resolved domain, if any.
+    pub fn domain_score_with(&self, action_type: &str, model: &TrustModel) -> u16 {
+        let Some(evidence) = self
+            .resolve_domain(action_type, model)
+            .and_then(|domain| self.domains.get(domain))
+        else {
+            return self.score;
+        };
+        TrustComponents {
+            verification_score: evidence.verification_score(model),
+            dispute_penalty: evidence.dispute_penalty(model),
+            ..self.components
+        }
+        .total()
+    }
+
+    /// Normalized tau for an action type (0.0 - 1.0)
+    pub fn domain_tau(&self, action_type: &str) -> f64 {
+        self.domain_score(action_type) as f64 / MAX_SCORE as f64
+    }
+
+    /// Let evidence and dispute penalties decay up to `now`
+    pub fn decay_to(&mut self, now: i64, model: &TrustModel) {
+        self.evidence.decay_to(now, model);
+        self.refresh(now, model);
+    }
+
+    /// Break the score down as of `now` without modifying it
+    pub fn explain(&self, now: i64, model: &TrustModel) -> TrustExplanation {
+        let mut current = self.clone();
+        current.decay_to(now, model);
+        TrustExplanation {
+            score: current.score,
+            success_rate: current.evidence.success_rate(model),
+            confidence: current.evidence.confidence(model),
+            components: current.components,
+            evidence: current.evidence,
+        }
+    }
+
+    /// Derive the verification and dispute components from the evidence
+    fn refresh(&mut self, now: i64, model: &TrustModel) {
+        let evidence = &self.evidence;
+        self.components.verification_score = evidence.verification_score(model);
+        self.components.dispute_penalty = evidence.dispute_penalty(model);
+        self.dispute_rate = (evidence.disputes / evidence.outcomes().max(1.0)).min(1.0);
+        self.recalculate_at(now);
     }
 
     /// Update SLA compliance score
@@ -170,8 +416,12 @@ impl TrustScore {
 
     /// Recalculate composite score from components
     fn recalculate(&mut self) {
+        self.recalculate_at(chrono::Utc::now().timestamp_millis());
+    }
+
+    fn recalculate_at(&mut self, now: i64) {
         self.score = self.components.total();
-        self.updated_at = chrono::Utc::now().timestamp_millis();
+        self.updated_at = now;
         self.version += 1;
     }
 
@@ -200,6 +450,23 @@ impl std::fmt::Display for TrustScore {
     }
 }
 
+/// Domains of an action type, most specific first
+///
+/// `code.review.rust` belongs to `code.review.rust`, `code.review` and `code`.
+pub fn action_domains(action_type: &str) -> Vec<&str> {
+    if action_type.is_empty() {
+        return Vec::new();
+    }
+    std::iter::once(action_type)
+        .chain(
+            action_type
+                .rmatch_indices('.')
+                .map(|(i, _)| &action_type[..i]),
+        )
+        .filter(|domain| !domain.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,4 +523,82 @@ mod tests {
         assert!(score.score < initial);
         assert!(score.components.dispute_penalty > 0);
     }
+
+    #[test]
+    fn test_bayesian_evidence_decays() {
+        let model = TrustModel::default();
+        let day = 86_400_000;
+        let start = 1_700_000_000_000;
+
+        // One success barely moves a new entity; many move it a lot
+        let mut newcomer = TrustScore::new();
+        newcomer.record_verification_at(true, start, &model);
+        assert_eq!(newcomer.components.verification_score, 240);
+        let mut veteran = TrustScore::new();
+        for i in 0..100 {
+            veteran.record_verification_at(true, start + i, &model);
+        }
+        assert!(veteran.components.verification_score > 390);
+        assert!(veteran.explain(start + 100, &model).confidence > 0.95);
+
+        // Old failures fade: after two half-lives they count a quarter
+        let mut recovering = TrustScore::new();
+        for _ in 0..8 {
+            recovering.record_verification_at(false, start, &model);
+        }
+        let fresh = recovering.components.verification_score;
+        let later = recovering.explain(start + 180 * day, &model);
+        assert!((later.evidence.failures - 2.0).abs() < 1e-9);
+        assert!(later.components.verification_score > fresh);
+
+        // Dispute penalties halve every 30 days
+        let mut disputed = TrustScore::new();
+        disputed.record_dispute_at(start, &model);
+        disputed.record_dispute_at(start, &model);
+        assert_eq!(disputed.components.dispute_penalty, 40);
+        disputed.decay_to(start + 30 * day, &model);
+        assert_eq!(disputed.components.dispute_penalty, 20);
+        assert_eq!(disputed.updated_at, start + 30 * day);
+    }
+
+    #[test]
+    fn test_domain_scores_fall_back_to_global() {
+        let model = TrustModel::default();
+        let now = 1_700_000_000_000;
+        assert_eq!(
+            action_domains("code.review.rust"),
+            vec!["code.review.rust", "code.review", "code"]
+        );
+
+        let mut score = TrustScore::new();
+        for i in 0..20 {
+            score.record_outcome_at("code.review.rust", true, now + i, &model);
+        }
+        for i in 0..20 {
+            score.record_outcome_at("finance.reconcile", i % 2 == 0, now + 20 + i, &model);
+        }
+        score.record_outcome_at("finance.audit", false, now + 40, &model);
+
+        let code = score.domain_score("code.review.python");
+        let finance = score.domain_score("finance.reconcile");
+        assert_eq!(
+            score.resolve_domain("code.review.python", &model),
+            Some("code.review")
+        );
+        assert!(code > score.score);
+        assert!(finance < code);
+
+        // Too little evidence: the audit failure falls back to "finance",
+        // an unseen domain to the global score
+        assert_eq!(
+            score.resolve_domain("finance.audit", &model),
+            Some("finance")
+        );
+        assert_eq!(score.domain_score("legal.draft"), score.score);
+        assert_eq!(score.domain_tau("legal.draft"), score.tau());
+
+        score.record_domain_dispute_at("code.review.rust", now + 50, &model);
+        assert_eq!(score.domain_score("code.review.python"), code - 20);
+        assert_eq!(score.domain_score("finance.reconcile"), finance);
+    }
 }
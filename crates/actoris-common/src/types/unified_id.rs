@@ -9,6 +9,8 @@ use ed25519_dalek::{SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 
+use crate::crypto::did::DidDocument;
+
 /// Entity type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -187,6 +189,41 @@ impl UnifiedID {
         VerifyingKey::from_bytes(&self.public_key).map_err(|_| UnifiedIdError::InvalidPublicKey)
     }
 
+    /// Initial DID Document for this identity
+    ///
+    /// A did:key document is fixed by the key; other methods start with the
+    /// registered key as `#key-1` and may rotate from there.
+    pub fn did_document(&self) -> DidDocument {
+        if self.did.starts_with("did:key:") {
+            return DidDocument::for_did_key(&self.public_key);
+        }
+        let mut doc = DidDocument::new(self.did.clone());
+        doc.add_key(&self.public_key);
+        doc
+    }
+
+    /// Adopt the current key from a resolved (possibly rotated) DID Document
+    ///
+    /// The DID is unchanged. did:key identities cannot rotate.
+    pub fn rotate_key(&mut self, document: &DidDocument) -> Result<(), UnifiedIdError> {
+        if document.id != self.did {
+            return Err(UnifiedIdError::DidMismatch(document.id.clone()));
+        }
+        if self.did.starts_with("did:key:") {
+            return Err(UnifiedIdError::RotationUnsupported);
+        }
+
+        let method = document
+            .authentication_methods()
+            .into_iter()
+            .next_back()
+            .ok_or(UnifiedIdError::InvalidPublicKey)?;
+        self.public_key = method
+            .public_key()
+            .map_err(|_| UnifiedIdError::InvalidPublicKey)?;
+        Ok(())
+    }
+
     /// Check if this entity is a spawned agent (has a parent)
     pub fn is_spawned(&self) -> bool {
         self.parent_did.is_some()
@@ -232,6 +269,12 @@ pub enum UnifiedIdError {
 
     #[error("Invalid public key")]
     InvalidPublicKey,
+
+    #[error("DID Document is for another DID: {0}")]
+    DidMismatch(String),
+
+    #[error("did:key identities cannot rotate keys")]
+    RotationUnsupported,
 }
 
 #[cfg(test)]
@@ -270,4 +313,37 @@ mod tests {
         assert_eq!(agent.initial_trust_cap(1000), 300); // 30% of max
         assert_eq!(agent.initial_trust_cap(500), 150);
     }
+
+    #[test]
+    fn test_rotate_key() {
+        use crate::crypto::did::KeyRotation;
+
+        let old_key = SigningKey::generate(&mut OsRng);
+        let new_key = SigningKey::generate(&mut OsRng);
+        let mut org =
+            UnifiedID::new_organization("example.com", old_key.verifying_key().to_bytes());
+
+        let mut doc = org.did_document();
+        let old_id = format!("{}#key-1", org.did);
+        let rotation = KeyRotation::new(
+            org.did.clone(),
+            &old_id,
+            &new_key.verifying_key().to_bytes(),
+            &old_id,
+            &old_key,
+        );
+        let signer = doc.clone();
+        doc.apply_rotation(&rotation, &signer).unwrap();
+
+        org.rotate_key(&doc).unwrap();
+        assert_eq!(org.did, "did:web:example.com");
+        assert_eq!(org.public_key, new_key.verifying_key().to_bytes());
+
+        let (mut agent, _) = UnifiedID::new_agent(None);
+        let agent_doc = agent.did_document();
+        assert!(matches!(
+            agent.rotate_key(&agent_doc),
+            Err(UnifiedIdError::RotationUnsupported)
+        ));
+    }
 }
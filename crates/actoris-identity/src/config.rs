@@ -0,0 +1,54 @@
+//! Identity service configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Identity service configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityConfig {
+    /// Service host
+    pub host: String,
+    /// gRPC port
+    pub port: u16,
+    /// DIDs allowed to credit wallets and update trust scores
+    pub operators: Vec<String>,
+}
+
+impl Default for IdentityConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 50055,
+            operators: Vec::new(),
+        }
+    }
+}
+
+impl IdentityConfig {
+    /// Load configuration from the environment
+    ///
+    /// `IDENTITY_HOST` and `IDENTITY_PORT` override the defaults; a bare
+    /// `PORT` (as set by most PaaS hosts) takes priority over both.
+    /// `IDENTITY_OPERATORS` is a comma-separated list of operator DIDs.
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(host) = std::env::var("IDENTITY_HOST") {
+            cfg.host = host;
+        }
+        for var in ["IDENTITY_PORT", "PORT"] {
+            if let Some(port) = std::env::var(var).ok().and_then(|p| p.parse().ok()) {
+                cfg.port = port;
+            }
+        }
+        if let Ok(operators) = std::env::var("IDENTITY_OPERATORS") {
+            cfg.operators = operators
+                .split(',')
+                .map(str::trim)
+                .filter(|did| !did.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        cfg
+    }
+}
@@ -0,0 +1,156 @@
+//! Wallet expiry sweeps
+//!
+//! Each HC tranche expires 30 days after it was credited. [`ExpirySweeper`]
+//! periodically moves expired available tranches into the journal's expiry
+//! account, so stale credit stops counting as spendable without anyone
+//! having to touch the wallet. Locked HC is left alone until its escrow
+//! settles.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use actoris_common::Posting;
+use rust_decimal::Decimal;
+use tracing::{debug, info, warn};
+
+use crate::repository::{IdentityRepository, RepositoryError};
+
+/// Expiry sweeper configuration
+#[derive(Debug, Clone)]
+pub struct ExpirySweeperConfig {
+    /// Interval between sweeps in milliseconds
+    pub interval_ms: u64,
+}
+
+impl Default for ExpirySweeperConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 60 * 60 * 1000, // hourly
+        }
+    }
+}
+
+/// Outcome of one sweep
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpirySweepReport {
+    /// Wallets whose balance was swept
+    pub wallets: usize,
+    /// Total HC swept
+    pub swept: Decimal,
+    /// Wallets skipped because they changed mid-sweep; retried next pass
+    pub conflicts: usize,
+}
+
+/// Sweeps expired wallet balances in a repository
+pub struct ExpirySweeper<R: IdentityRepository> {
+    repo: Arc<R>,
+    config: ExpirySweeperConfig,
+}
+
+impl<R: IdentityRepository> ExpirySweeper<R> {
+    pub fn new(repo: Arc<R>, config: ExpirySweeperConfig) -> Self {
+        Self { repo, config }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &ExpirySweeperConfig {
+        &self.config
+    }
+
+    /// Sweep every wallet holding tranches that expired before `now`
+    pub async fn run_once(&self, now: i64) -> Result<ExpirySweepReport, RepositoryError> {
+        let mut report = ExpirySweepReport::default();
+        for mut wallet in self.repo.expired_wallets(now).await? {
+            let version = wallet.version;
+            let amount = wallet.sweep_expired(now);
+            if amount.is_zero() {
+                continue;
+            }
+
+            let did = wallet.owner_did.clone();
+            let posting = Posting::expire(&did, amount).with_posted_at(now);
+            match self.repo.save_wallet(wallet, version, posting).await {
+                Ok(()) => {
+                    debug!(did = %did, amount = %amount, "Expired HC swept");
+                    report.wallets += 1;
+                    report.swept += amount;
+                }
+                Err(RepositoryError::VersionConflict { .. }) => report.conflicts += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        if report.wallets > 0 {
+            info!(
+                wallets = report.wallets,
+                swept = %report.swept,
+                conflicts = report.conflicts,
+                "Expiry sweep complete"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Spawn the sweep loop on the current runtime
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.interval_ms));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once(chrono::Utc::now().timestamp_millis()).await {
+                    warn!(error = %e, "Expiry sweep failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{IdentityRecord, InMemoryIdentityRepository};
+    use actoris_common::{EntityType, HcJournal, HcWallet, TrustScore, UnifiedID};
+    use rust_decimal_macros::dec;
+
+    fn record(did: &str, balance: Decimal) -> IdentityRecord {
+        IdentityRecord {
+            identity: UnifiedID {
+                did: did.to_string(),
+                entity_type: EntityType::Agent,
+                parent_did: None,
+                created_at: 0,
+                public_key: [0u8; 32],
+            },
+            trust_score: TrustScore::new(),
+            wallet: HcWallet::with_balance(did.to_string(), balance),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expires_stale_balances() {
+        let repo = Arc::new(InMemoryIdentityRepository::new());
+        repo.create(record("did:key:idle", dec!(25))).await.unwrap();
+        repo.create(record("did:key:empty", dec!(0))).await.unwrap();
+        let expires_at = repo.get_wallet("did:key:idle").await.unwrap().expires_at;
+
+        let sweeper = ExpirySweeper::new(repo.clone(), ExpirySweeperConfig::default());
+        assert_eq!(
+            sweeper.run_once(expires_at).await.unwrap(),
+            ExpirySweepReport::default()
+        );
+
+        let report = sweeper.run_once(expires_at + 1).await.unwrap();
+        assert_eq!((report.wallets, report.swept), (1, dec!(25)));
+        let wallet = repo.get_wallet("did:key:idle").await.unwrap();
+        assert_eq!(wallet.available, Decimal::ZERO);
+
+        // The sweep is journaled like any other movement
+        let postings = repo.postings("did:key:idle").await.unwrap();
+        HcJournal::replay(postings)
+            .unwrap()
+            .verify(&wallet)
+            .unwrap();
+        assert_eq!(sweeper.run_once(expires_at + 2).await.unwrap().wallets, 0);
+    }
+}
@@ -0,0 +1,556 @@
+//! Generated protobuf types for IdentityService
+//!
+//! These types are designed to match the proto definitions in proto/actoris/
+
+pub mod common {
+    pub mod v1 {
+        // Common protobuf types matching proto/actoris/common.proto
+
+        use prost::{Enumeration, Message};
+        use serde::{Deserialize, Serialize};
+
+        /// EntityType classification for UnifiedID
+        #[derive(
+            Clone,
+            Copy,
+            Debug,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            Enumeration,
+            Serialize,
+            Deserialize,
+        )]
+        #[repr(i32)]
+        pub enum EntityType {
+            Unspecified = 0,
+            Human = 1,
+            Agent = 2,
+            Organization = 3,
+        }
+
+        /// W3C DID-based unified identity
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct UnifiedId {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(enumeration = "EntityType", tag = "2")]
+            pub entity_type: i32,
+            #[prost(string, optional, tag = "3")]
+            pub parent_did: Option<String>,
+            #[prost(int64, tag = "4")]
+            pub created_at: i64,
+            #[prost(bytes = "vec", tag = "5")]
+            pub public_key: Vec<u8>,
+        }
+
+        /// Trust score breakdown by component
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct TrustComponents {
+            #[prost(uint32, tag = "1")]
+            pub verification_score: u32,
+            #[prost(uint32, tag = "2")]
+            pub dispute_penalty: u32,
+            #[prost(uint32, tag = "3")]
+            pub sla_score: u32,
+            #[prost(uint32, tag = "4")]
+            pub network_score: u32,
+        }
+
+        /// Entity trust score with full breakdown
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct TrustScore {
+            #[prost(uint32, tag = "1")]
+            pub score: u32,
+            #[prost(message, optional, tag = "2")]
+            pub components: Option<TrustComponents>,
+            #[prost(int64, tag = "3")]
+            pub updated_at: i64,
+            #[prost(uint64, tag = "4")]
+            pub verified_outcomes: u64,
+            #[prost(double, tag = "5")]
+            pub dispute_rate: f64,
+            #[prost(uint64, tag = "6")]
+            pub version: u64,
+        }
+
+        /// HC Wallet for compute credit management
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct HcWallet {
+            #[prost(string, tag = "1")]
+            pub owner_did: String,
+            #[prost(string, tag = "2")]
+            pub available: String,
+            #[prost(string, tag = "3")]
+            pub locked: String,
+            #[prost(int64, tag = "4")]
+            pub expires_at: i64,
+            #[prost(uint64, tag = "5")]
+            pub version: u64,
+            #[prost(int64, tag = "6")]
+            pub updated_at: i64,
+        }
+    }
+}
+
+pub mod identity {
+    pub mod v1 {
+        use super::super::common::v1 as common;
+        use prost::Message;
+        use serde::{Deserialize, Serialize};
+
+        /// CreateUnifiedID request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct CreateUnifiedIdRequest {
+            #[prost(enumeration = "common::EntityType", tag = "1")]
+            pub entity_type: i32,
+            #[prost(string, optional, tag = "2")]
+            pub parent_did: Option<String>,
+            #[prost(bytes = "vec", tag = "3")]
+            pub public_key: Vec<u8>,
+            #[prost(string, optional, tag = "4")]
+            pub domain: Option<String>,
+            #[prost(bytes = "vec", optional, tag = "5")]
+            pub parent_signature: Option<Vec<u8>>,
+        }
+
+        /// CreateUnifiedID response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct CreateUnifiedIdResponse {
+            #[prost(message, optional, tag = "1")]
+            pub unified_id: Option<common::UnifiedId>,
+            #[prost(message, optional, tag = "2")]
+            pub trust_score: Option<common::TrustScore>,
+            #[prost(message, optional, tag = "3")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// GetUnifiedID request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetUnifiedIdRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+        }
+
+        /// GetUnifiedID response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetUnifiedIdResponse {
+            #[prost(message, optional, tag = "1")]
+            pub unified_id: Option<common::UnifiedId>,
+        }
+
+        /// ResolveLineage request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ResolveLineageRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(uint32, tag = "2")]
+            pub max_depth: u32,
+        }
+
+        /// ResolveLineage response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ResolveLineageResponse {
+            #[prost(message, repeated, tag = "1")]
+            pub lineage: Vec<common::UnifiedId>,
+            #[prost(double, repeated, tag = "2")]
+            pub trust_inheritance: Vec<f64>,
+        }
+
+        /// GetTrustScore request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetTrustScoreRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+        }
+
+        /// GetTrustScore response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetTrustScoreResponse {
+            #[prost(message, optional, tag = "1")]
+            pub trust_score: Option<common::TrustScore>,
+        }
+
+        /// UpdateTrustScore request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct UpdateTrustScoreRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(message, optional, tag = "2")]
+            pub verification_update: Option<VerificationUpdate>,
+            #[prost(message, optional, tag = "3")]
+            pub dispute_update: Option<DisputeUpdate>,
+            #[prost(double, optional, tag = "4")]
+            pub sla_compliance: Option<f64>,
+            #[prost(double, optional, tag = "5")]
+            pub network_reputation: Option<f64>,
+            #[prost(uint64, tag = "6")]
+            pub expected_version: u64,
+        }
+
+        /// Verification outcome update
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct VerificationUpdate {
+            #[prost(bool, tag = "1")]
+            pub success: bool,
+        }
+
+        /// Dispute update
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct DisputeUpdate {
+            #[prost(bool, tag = "1")]
+            pub dispute_occurred: bool,
+            #[prost(bool, tag = "2")]
+            pub dispute_won: bool,
+        }
+
+        /// UpdateTrustScore response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct UpdateTrustScoreResponse {
+            #[prost(message, optional, tag = "1")]
+            pub trust_score: Option<common::TrustScore>,
+        }
+
+        /// GetWallet request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetWalletRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+        }
+
+        /// GetWallet response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct GetWalletResponse {
+            #[prost(message, optional, tag = "1")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// CreditWallet request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct CreditWalletRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(string, tag = "2")]
+            pub amount: String,
+            #[prost(string, tag = "3")]
+            pub reason: String,
+            #[prost(string, optional, tag = "4")]
+            pub reference_id: Option<String>,
+        }
+
+        /// CreditWallet response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct CreditWalletResponse {
+            #[prost(message, optional, tag = "1")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// DebitWallet request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct DebitWalletRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(string, tag = "2")]
+            pub amount: String,
+            #[prost(string, tag = "3")]
+            pub reason: String,
+            #[prost(string, optional, tag = "4")]
+            pub reference_id: Option<String>,
+            #[prost(uint64, tag = "5")]
+            pub expected_version: u64,
+        }
+
+        /// DebitWallet response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct DebitWalletResponse {
+            #[prost(message, optional, tag = "1")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// LockWallet request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct LockWalletRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(string, tag = "2")]
+            pub amount: String,
+            #[prost(string, tag = "3")]
+            pub escrow_id: String,
+            #[prost(uint64, tag = "4")]
+            pub expected_version: u64,
+        }
+
+        /// LockWallet response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct LockWalletResponse {
+            #[prost(message, optional, tag = "1")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// ReleaseWallet request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ReleaseWalletRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(string, tag = "2")]
+            pub amount: String,
+            #[prost(string, tag = "3")]
+            pub escrow_id: String,
+            #[prost(string, optional, tag = "4")]
+            pub target_did: Option<String>,
+            #[prost(uint64, tag = "5")]
+            pub expected_version: u64,
+        }
+
+        /// ReleaseWallet response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ReleaseWalletResponse {
+            #[prost(message, optional, tag = "1")]
+            pub wallet: Option<common::HcWallet>,
+        }
+
+        /// VerifySignature request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct VerifySignatureRequest {
+            #[prost(string, tag = "1")]
+            pub did: String,
+            #[prost(bytes = "vec", tag = "2")]
+            pub message: Vec<u8>,
+            #[prost(bytes = "vec", tag = "3")]
+            pub signature: Vec<u8>,
+        }
+
+        /// VerifySignature response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct VerifySignatureResponse {
+            #[prost(bool, tag = "1")]
+            pub valid: bool,
+        }
+
+        /// Server side of IdentityService (would be auto-generated by tonic-build)
+        pub mod identity_service_server {
+            use tonic::codegen::*;
+
+            /// IdentityService trait matching proto/actoris/identity.proto
+            #[async_trait]
+            pub trait IdentityService: Send + Sync + 'static {
+                async fn create_unified_id(
+                    &self,
+                    request: tonic::Request<super::CreateUnifiedIdRequest>,
+                ) -> Result<tonic::Response<super::CreateUnifiedIdResponse>, tonic::Status>;
+
+                async fn get_unified_id(
+                    &self,
+                    request: tonic::Request<super::GetUnifiedIdRequest>,
+                ) -> Result<tonic::Response<super::GetUnifiedIdResponse>, tonic::Status>;
+
+                async fn resolve_lineage(
+                    &self,
+                    request: tonic::Request<super::ResolveLineageRequest>,
+                ) -> Result<tonic::Response<super::ResolveLineageResponse>, tonic::Status>;
+
+                async fn get_trust_score(
+                    &self,
+                    request: tonic::Request<super::GetTrustScoreRequest>,
+                ) -> Result<tonic::Response<super::GetTrustScoreResponse>, tonic::Status>;
+
+                async fn update_trust_score(
+                    &self,
+                    request: tonic::Request<super::UpdateTrustScoreRequest>,
+                ) -> Result<tonic::Response<super::UpdateTrustScoreResponse>, tonic::Status>;
+
+                async fn get_wallet(
+                    &self,
+                    request: tonic::Request<super::GetWalletRequest>,
+                ) -> Result<tonic::Response<super::GetWalletResponse>, tonic::Status>;
+
+                async fn credit_wallet(
+                    &self,
+                    request: tonic::Request<super::CreditWalletRequest>,
+                ) -> Result<tonic::Response<super::CreditWalletResponse>, tonic::Status>;
+
+                async fn debit_wallet(
+                    &self,
+                    request: tonic::Request<super::DebitWalletRequest>,
+                ) -> Result<tonic::Response<super::DebitWalletResponse>, tonic::Status>;
+
+                async fn lock_wallet(
+                    &self,
+                    request: tonic::Request<super::LockWalletRequest>,
+                ) -> Result<tonic::Response<super::LockWalletResponse>, tonic::Status>;
+
+                async fn release_wallet(
+                    &self,
+                    request: tonic::Request<super::ReleaseWalletRequest>,
+                ) -> Result<tonic::Response<super::ReleaseWalletResponse>, tonic::Status>;
+
+                async fn verify_signature(
+                    &self,
+                    request: tonic::Request<super::VerifySignatureRequest>,
+                ) -> Result<tonic::Response<super::VerifySignatureResponse>, tonic::Status>;
+            }
+
+            /// Route a unary call to a method of the service
+            macro_rules! unary {
+                ($inner:expr, $req:expr, $method:ident, $request:ty, $response:ty) => {{
+                    struct Svc<T>(Arc<T>);
+
+                    impl<T: IdentityService> tonic::server::UnaryService<$request> for Svc<T> {
+                        type Response = $response;
+                        type Future = BoxFuture<tonic::Response<$response>, tonic::Status>;
+
+                        fn call(&mut self, request: tonic::Request<$request>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            Box::pin(async move { inner.$method(request).await })
+                        }
+                    }
+
+                    let inner = Arc::clone($inner);
+                    let req = $req;
+                    Box::pin(async move {
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec);
+                        Ok(grpc.unary(Svc(inner), req).await)
+                    })
+                }};
+            }
+
+            /// tower `Service` serving an [`IdentityService`] implementation
+            #[derive(Debug)]
+            pub struct IdentityServiceServer<T: IdentityService> {
+                inner: Arc<T>,
+            }
+
+            impl<T: IdentityService> IdentityServiceServer<T> {
+                pub fn new(inner: T) -> Self {
+                    Self::from_arc(Arc::new(inner))
+                }
+
+                pub fn from_arc(inner: Arc<T>) -> Self {
+                    Self { inner }
+                }
+            }
+
+            impl<T: IdentityService> Clone for IdentityServiceServer<T> {
+                fn clone(&self) -> Self {
+                    Self {
+                        inner: Arc::clone(&self.inner),
+                    }
+                }
+            }
+
+            impl<T, B> Service<http::Request<B>> for IdentityServiceServer<T>
+            where
+                T: IdentityService,
+                B: Body + Send + 'static,
+                B::Error: Into<StdError> + Send + 'static,
+            {
+                type Response = http::Response<tonic::body::BoxBody>;
+                type Error = std::convert::Infallible;
+                type Future = BoxFuture<Self::Response, Self::Error>;
+
+                fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    Poll::Ready(Ok(()))
+                }
+
+                fn call(&mut self, req: http::Request<B>) -> Self::Future {
+                    let inner = &self.inner;
+                    match req.uri().path() {
+                        "/actoris.identity.v1.IdentityService/CreateUnifiedID" => unary!(
+                            inner,
+                            req,
+                            create_unified_id,
+                            super::CreateUnifiedIdRequest,
+                            super::CreateUnifiedIdResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/GetUnifiedID" => unary!(
+                            inner,
+                            req,
+                            get_unified_id,
+                            super::GetUnifiedIdRequest,
+                            super::GetUnifiedIdResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/ResolveLineage" => unary!(
+                            inner,
+                            req,
+                            resolve_lineage,
+                            super::ResolveLineageRequest,
+                            super::ResolveLineageResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/GetTrustScore" => unary!(
+                            inner,
+                            req,
+                            get_trust_score,
+                            super::GetTrustScoreRequest,
+                            super::GetTrustScoreResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/UpdateTrustScore" => unary!(
+                            inner,
+                            req,
+                            update_trust_score,
+                            super::UpdateTrustScoreRequest,
+                            super::UpdateTrustScoreResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/GetWallet" => unary!(
+                            inner,
+                            req,
+                            get_wallet,
+                            super::GetWalletRequest,
+                            super::GetWalletResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/CreditWallet" => unary!(
+                            inner,
+                            req,
+                            credit_wallet,
+                            super::CreditWalletRequest,
+                            super::CreditWalletResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/DebitWallet" => unary!(
+                            inner,
+                            req,
+                            debit_wallet,
+                            super::DebitWalletRequest,
+                            super::DebitWalletResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/LockWallet" => unary!(
+                            inner,
+                            req,
+                            lock_wallet,
+                            super::LockWalletRequest,
+                            super::LockWalletResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/ReleaseWallet" => unary!(
+                            inner,
+                            req,
+                            release_wallet,
+                            super::ReleaseWalletRequest,
+                            super::ReleaseWalletResponse
+                        ),
+                        "/actoris.identity.v1.IdentityService/VerifySignature" => unary!(
+                            inner,
+                            req,
+                            verify_signature,
+                            super::VerifySignatureRequest,
+                            super::VerifySignatureResponse
+                        ),
+                        _ => Box::pin(async move {
+                            // UNIMPLEMENTED
+                            Ok(http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap())
+                        }),
+                    }
+                }
+            }
+
+            impl<T: IdentityService> tonic::server::NamedService for IdentityServiceServer<T> {
+                const NAME: &'static str = "actoris.identity.v1.IdentityService";
+            }
+        }
+    }
+}
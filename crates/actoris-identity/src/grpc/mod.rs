@@ -0,0 +1,11 @@
+//! gRPC service implementation for identity
+//!
+//! Provides the IdentityService: UnifiedID registration and lineage, trust
+//! scores, HC wallets and DID signature checks.
+
+pub mod service;
+
+pub use crate::generated::identity::v1::identity_service_server::{
+    IdentityService, IdentityServiceServer,
+};
+pub use service::IdentityGrpcService;
@@ -0,0 +1,1377 @@
+//! Identity gRPC service implementation
+//!
+//! Implements the IdentityService from proto/actoris/identity.proto
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use actoris_common::crypto::did::verify_with_did;
+use actoris_common::crypto::DidResolver;
+use actoris_common::security::RequestAuthenticator;
+use actoris_common::{
+    EntityType, HcWallet, JournalError, Posting, Reference, Statement, TrustScore, UnifiedID,
+    WalletError,
+};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rust_decimal::Decimal;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info, instrument, warn};
+
+use crate::generated::common::v1 as proto_common;
+use crate::generated::identity::v1 as proto;
+use crate::generated::identity::v1::identity_service_server::IdentityService;
+use crate::repository::{IdentityRecord, IdentityRepository, RepositoryError};
+use crate::{spawn_authorization, DEFAULT_LINEAGE_DEPTH, INHERITED_TRUST};
+
+/// Attempts at a credit before giving up on concurrent writers
+const MAX_CREDIT_ATTEMPTS: usize = 3;
+
+/// Identity gRPC service handler
+pub struct IdentityGrpcService<R: IdentityRepository> {
+    repo: Arc<R>,
+    /// Resolves did:web documents so rotated keys verify
+    resolver: Option<Arc<dyn DidResolver>>,
+    /// Checks that registrations and wallet mutations are DID-signed
+    request_auth: Arc<RequestAuthenticator>,
+    /// Services allowed to credit wallets and update trust scores
+    operators: HashSet<String>,
+}
+
+// Helpers fail with the same `Status` the handlers return
+#[allow(clippy::result_large_err)]
+impl<R: IdentityRepository> IdentityGrpcService<R> {
+    /// Create a new identity service over a repository
+    ///
+    /// did:key registrations must be signed by the key itself, debits, locks
+    /// and releases by the wallet owner, and credits and trust updates by an
+    /// operator. Callers sign with
+    /// [`actoris_common::security::RequestSigner::sign_grpc`].
+    pub fn new(repo: R, request_auth: Arc<RequestAuthenticator>) -> Self {
+        Self::from_shared(Arc::new(repo), request_auth)
+    }
+
+    /// Create a service around a repository shared with other tasks
+    pub fn from_shared(repo: Arc<R>, request_auth: Arc<RequestAuthenticator>) -> Self {
+        Self {
+            repo,
+            resolver: None,
+            request_auth,
+            operators: HashSet::new(),
+        }
+    }
+
+    /// Verify did:web signatures against resolved DID Documents
+    ///
+    /// Without a resolver they are checked against the key registered at
+    /// creation, which goes stale once the identity rotates its key.
+    pub fn with_resolver(mut self, resolver: Arc<dyn DidResolver>) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Allow `dids` to credit wallets and update trust scores
+    ///
+    /// No caller is an operator by default.
+    pub fn with_operators<I, S>(mut self, dids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.operators = dids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check the caller signed as an operator
+    fn authorize_operator<T: prost::Message>(
+        &self,
+        request: &Request<T>,
+        method: &str,
+    ) -> Result<(), Status> {
+        let path = format!("/actoris.identity.v1.IdentityService/{}", method);
+        let caller = self.request_auth.authenticate_grpc(request, &path)?;
+        if !self.operators.contains(&caller) {
+            warn!(caller = %caller, method = %method, "Caller is not an operator");
+            return Err(Status::permission_denied(format!(
+                "{} may not call {}",
+                caller, method
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the caller signed as `did`
+    fn authorize<T: prost::Message>(
+        &self,
+        request: &Request<T>,
+        method: &str,
+        did: &str,
+    ) -> Result<(), Status> {
+        let path = format!("/actoris.identity.v1.IdentityService/{}", method);
+        let caller = self.request_auth.authenticate_grpc(request, &path)?;
+        if caller != did {
+            warn!(caller = %caller, did = %did, method = %method, "Caller does not hold the DID");
+            return Err(Status::permission_denied(format!(
+                "{} cannot act as {}",
+                caller, did
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check a signature against an identity's current key
+    ///
+    /// did:key carries its own key; did:web follows its current document
+    /// when a resolver is configured, else the registered key.
+    async fn verify_as(
+        &self,
+        identity: &UnifiedID,
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Status> {
+        if identity.did.starts_with("did:key:") {
+            verify_with_did(&identity.did, message, signature)
+                .map_err(|e| Status::internal(format!("DID verification failed: {}", e)))
+        } else if let Some(resolver) = &self.resolver {
+            let document = resolver.resolve(&identity.did).await.map_err(|e| {
+                warn!(did = %identity.did, error = %e, "DID resolution failed");
+                Status::unavailable(format!("DID resolution failed: {}", e))
+            })?;
+            Ok(document.verify(message, signature))
+        } else {
+            let key = identity
+                .verifying_key()
+                .map_err(|e| Status::internal(format!("Invalid registered key: {}", e)))?;
+            Ok(key
+                .verify(message, &Signature::from_bytes(signature))
+                .is_ok())
+        }
+    }
+
+    /// Check the domain of a did:web identity serves its key
+    ///
+    /// Registering a did:web proves control of the domain: its resolved
+    /// document must list the key being registered.
+    async fn check_domain(&self, identity: &UnifiedID) -> Result<(), Status> {
+        let resolver = self.resolver.as_ref().ok_or_else(|| {
+            Status::failed_precondition("did:web registration needs a DID resolver")
+        })?;
+        let document = resolver.resolve(&identity.did).await.map_err(|e| {
+            warn!(did = %identity.did, error = %e, "DID resolution failed");
+            Status::failed_precondition(format!("DID resolution failed: {}", e))
+        })?;
+        let listed = document
+            .authentication_methods()
+            .iter()
+            .any(|method| method.public_key().ok() == Some(identity.public_key));
+        if !listed {
+            return Err(Status::permission_denied(format!(
+                "{} does not list the registered key",
+                identity.did
+            )));
+        }
+        Ok(())
+    }
+
+    /// Convert a repository error to a gRPC status
+    fn repo_status(err: RepositoryError) -> Status {
+        match err {
+            RepositoryError::NotFound(_) => Status::not_found(err.to_string()),
+            RepositoryError::AlreadyExists(_) => Status::already_exists(err.to_string()),
+            RepositoryError::VersionConflict { .. } => Status::aborted(err.to_string()),
+            RepositoryError::Journal(JournalError::Wallet(_)) => {
+                Status::failed_precondition(err.to_string())
+            }
+            RepositoryError::Journal(_) | RepositoryError::Storage(_) => {
+                Status::internal(err.to_string())
+            }
+        }
+    }
+
+    /// Convert a wallet error to a gRPC status
+    fn wallet_status(err: WalletError) -> Status {
+        match err {
+            WalletError::InvalidAmount => Status::invalid_argument(err.to_string()),
+            WalletError::VersionConflict { .. } => Status::aborted(err.to_string()),
+            WalletError::InsufficientBalance { .. }
+            | WalletError::InsufficientLocked { .. }
+            | WalletError::Expired => Status::failed_precondition(err.to_string()),
+        }
+    }
+
+    fn require_did(did: &str) -> Result<(), Status> {
+        if did.is_empty() {
+            return Err(Status::invalid_argument("did is required"));
+        }
+        Ok(())
+    }
+
+    /// Parse a positive HC amount
+    fn parse_amount(amount: &str) -> Result<Decimal, Status> {
+        let amount = Decimal::from_str(amount)
+            .map_err(|e| Status::invalid_argument(format!("Invalid amount: {}", e)))?;
+        if amount <= Decimal::ZERO {
+            return Err(Status::invalid_argument("amount must be positive"));
+        }
+        Ok(amount)
+    }
+
+    /// Stamp a posting with the wallet update time and an optional reference
+    fn posting(posting: Posting, wallet: &HcWallet, reference: Option<Reference>) -> Posting {
+        let posting = posting.with_posted_at(wallet.updated_at);
+        match reference {
+            Some(reference) => posting.with_reference(reference),
+            None => posting,
+        }
+    }
+
+    /// Statement of a wallet's journal postings for `[from, to)`
+    ///
+    /// Export with [`Statement::to_csv`] or [`Statement::to_json`].
+    pub async fn wallet_statement(
+        &self,
+        did: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<Statement, Status> {
+        Self::require_did(did)?;
+        let postings = self.repo.postings(did).await.map_err(Self::repo_status)?;
+        Ok(Statement::from_postings(did, &postings, from, to))
+    }
+
+    /// Current versions of the wallets of `dids`
+    pub async fn wallet_versions(&self, dids: &[&str]) -> Result<HashMap<String, u64>, Status> {
+        let mut versions = HashMap::new();
+        for &did in dids {
+            let wallet = self.repo.get_wallet(did).await.map_err(Self::repo_status)?;
+            versions.insert(did.to_string(), wallet.version);
+        }
+        Ok(versions)
+    }
+
+    /// Apply postings across several wallets atomically
+    ///
+    /// Every wallet a posting touches must be listed in `expected_versions`
+    /// at its current version. The postings are applied in order and either
+    /// all wallets and postings are saved or, on any version conflict or
+    /// wallet error, none are.
+    pub async fn apply_postings(
+        &self,
+        postings: Vec<Posting>,
+        expected_versions: &HashMap<String, u64>,
+    ) -> Result<Vec<HcWallet>, Status> {
+        if postings.is_empty() {
+            return Err(Status::invalid_argument("no postings to apply"));
+        }
+        if let Some(posting) = postings.iter().find(|p| !p.is_balanced()) {
+            return Err(Status::invalid_argument(format!(
+                "posting {} is not balanced",
+                posting.id
+            )));
+        }
+
+        // Wallets in first-touched order, each loaded at its expected version
+        let mut dids: Vec<String> = Vec::new();
+        for leg in postings.iter().flat_map(|p| &p.legs) {
+            if let Some(did) = leg.account.owner() {
+                if !dids.iter().any(|d| d == did) {
+                    dids.push(did.to_string());
+                }
+            }
+        }
+        let mut wallets: Vec<(HcWallet, u64)> = Vec::with_capacity(dids.len());
+        for did in &dids {
+            let version = *expected_versions.get(did).ok_or_else(|| {
+                Status::invalid_argument(format!("no expected version for {}", did))
+            })?;
+            wallets.push((self.load_wallet(did, version).await?, version));
+        }
+
+        for posting in &postings {
+            for (wallet, _) in wallets
+                .iter_mut()
+                .filter(|(w, _)| posting.touches(&w.owner_did))
+            {
+                posting.apply_to(wallet).map_err(Self::wallet_status)?;
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let postings: Vec<Posting> = postings
+            .into_iter()
+            .map(|p| p.with_posted_at(now))
+            .collect();
+        let count = postings.len();
+        self.repo
+            .save_wallets(wallets.clone(), postings)
+            .await
+            .map_err(Self::repo_status)?;
+
+        info!(
+            postings = count,
+            wallets = wallets.len(),
+            "Postings applied"
+        );
+        Ok(wallets.into_iter().map(|(wallet, _)| wallet).collect())
+    }
+
+    /// Load a wallet and check the caller saw its current version
+    async fn load_wallet(&self, did: &str, expected_version: u64) -> Result<HcWallet, Status> {
+        let wallet = self.repo.get_wallet(did).await.map_err(Self::repo_status)?;
+        wallet
+            .check_version(expected_version)
+            .map_err(Self::wallet_status)?;
+        Ok(wallet)
+    }
+
+    fn entity_type_from_proto(entity_type: i32) -> Result<EntityType, Status> {
+        match proto_common::EntityType::try_from(entity_type) {
+            Ok(proto_common::EntityType::Human) => Ok(EntityType::Human),
+            Ok(proto_common::EntityType::Agent) => Ok(EntityType::Agent),
+            Ok(proto_common::EntityType::Organization) => Ok(EntityType::Organization),
+            _ => Err(Status::invalid_argument("entity_type is required")),
+        }
+    }
+
+    fn entity_type_to_proto(entity_type: EntityType) -> i32 {
+        match entity_type {
+            EntityType::Human => proto_common::EntityType::Human as i32,
+            EntityType::Agent => proto_common::EntityType::Agent as i32,
+            EntityType::Organization => proto_common::EntityType::Organization as i32,
+        }
+    }
+
+    /// Convert internal UnifiedID to proto
+    fn identity_to_proto(identity: &UnifiedID) -> proto_common::UnifiedId {
+        proto_common::UnifiedId {
+            did: identity.did.clone(),
+            entity_type: Self::entity_type_to_proto(identity.entity_type),
+            parent_did: identity.parent_did.clone(),
+            created_at: identity.created_at,
+            public_key: identity.public_key.to_vec(),
+        }
+    }
+
+    /// Convert internal TrustScore to proto
+    fn trust_to_proto(trust: &TrustScore) -> proto_common::TrustScore {
+        proto_common::TrustScore {
+            score: trust.score as u32,
+            components: Some(proto_common::TrustComponents {
+                verification_score: trust.components.verification_score as u32,
+                dispute_penalty: trust.components.dispute_penalty as u32,
+                sla_score: trust.components.sla_score as u32,
+                network_score: trust.components.network_score as u32,
+            }),
+            updated_at: trust.updated_at,
+            verified_outcomes: trust.verified_outcomes,
+            dispute_rate: trust.dispute_rate,
+            version: trust.version,
+        }
+    }
+
+    /// Convert internal HcWallet to proto
+    fn wallet_to_proto(wallet: &HcWallet) -> proto_common::HcWallet {
+        proto_common::HcWallet {
+            owner_did: wallet.owner_did.clone(),
+            available: wallet.available.to_string(),
+            locked: wallet.locked.to_string(),
+            expires_at: wallet.expires_at,
+            version: wallet.version,
+            updated_at: wallet.updated_at,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<R: IdentityRepository> IdentityService for IdentityGrpcService<R> {
+    /// Register a UnifiedID with a fresh trust score and empty wallet
+    #[instrument(skip(self, request))]
+    async fn create_unified_id(
+        &self,
+        request: Request<proto::CreateUnifiedIdRequest>,
+    ) -> Result<Response<proto::CreateUnifiedIdResponse>, Status> {
+        let entity_type = Self::entity_type_from_proto(request.get_ref().entity_type)?;
+
+        let public_key: [u8; 32] = request
+            .get_ref()
+            .public_key
+            .as_slice()
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Invalid public key length"))?;
+        VerifyingKey::from_bytes(&public_key)
+            .map_err(|_| Status::invalid_argument("Invalid public key"))?;
+
+        // Agents always use did:key; humans and organizations may use did:web
+        let identity = match (entity_type, request.get_ref().domain.as_deref()) {
+            (EntityType::Human, Some(domain)) => UnifiedID::new_human_web(domain, public_key),
+            (EntityType::Organization, Some(domain)) => {
+                UnifiedID::new_organization(domain, public_key)
+            }
+            _ => UnifiedID::from_public_key(public_key, entity_type, None),
+        };
+        if identity.did.starts_with("did:web:") {
+            self.check_domain(&identity).await?;
+        } else {
+            // Proof of possession: only the key's holder registers its did:key
+            self.authorize(&request, "CreateUnifiedId", &identity.did)?;
+        }
+        let req = request.into_inner();
+
+        // Spawned agents start capped at a share of the parent's trust
+        let trust_score = match req.parent_did {
+            Some(_) if entity_type != EntityType::Agent => {
+                return Err(Status::invalid_argument(format!(
+                    "Only agents can be spawned, not {}",
+                    entity_type
+                )))
+            }
+            Some(ref parent_did) => {
+                let parent = self
+                    .repo
+                    .get_trust_score(parent_did)
+                    .await
+                    .map_err(|e| match e {
+                        RepositoryError::NotFound(_) => Status::failed_precondition(format!(
+                            "Parent not registered: {}",
+                            parent_did
+                        )),
+                        other => Self::repo_status(other),
+                    })?;
+
+                // The parent must have authorized this key
+                let signature: [u8; 64] = req
+                    .parent_signature
+                    .as_deref()
+                    .ok_or_else(|| Status::unauthenticated("parent_signature is required"))?
+                    .try_into()
+                    .map_err(|_| Status::invalid_argument("Invalid parent signature length"))?;
+                let parent_id = self
+                    .repo
+                    .get_identity(parent_did)
+                    .await
+                    .map_err(Self::repo_status)?;
+                if !self
+                    .verify_as(&parent_id, &spawn_authorization(&public_key), &signature)
+                    .await?
+                {
+                    warn!(parent = %parent_did, "Spawn not authorized by parent");
+                    return Err(Status::permission_denied(format!(
+                        "Spawn not authorized by {}",
+                        parent_did
+                    )));
+                }
+                TrustScore::new_spawned(parent.score)
+            }
+            None => TrustScore::new(),
+        };
+        let identity = UnifiedID {
+            parent_did: req.parent_did,
+            ..identity
+        };
+
+        let record = IdentityRecord {
+            wallet: HcWallet::new(identity.did.clone()),
+            trust_score,
+            identity,
+        };
+        self.repo
+            .create(record.clone())
+            .await
+            .map_err(Self::repo_status)?;
+
+        info!(
+            did = %record.identity.did,
+            entity_type = %record.identity.entity_type,
+            parent = ?record.identity.parent_did,
+            trust = record.trust_score.score,
+            "UnifiedID created"
+        );
+
+        Ok(Response::new(proto::CreateUnifiedIdResponse {
+            unified_id: Some(Self::identity_to_proto(&record.identity)),
+            trust_score: Some(Self::trust_to_proto(&record.trust_score)),
+            wallet: Some(Self::wallet_to_proto(&record.wallet)),
+        }))
+    }
+
+    /// Get a UnifiedID by DID
+    #[instrument(skip(self, request))]
+    async fn get_unified_id(
+        &self,
+        request: Request<proto::GetUnifiedIdRequest>,
+    ) -> Result<Response<proto::GetUnifiedIdResponse>, Status> {
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+
+        let identity = self
+            .repo
+            .get_identity(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+
+        Ok(Response::new(proto::GetUnifiedIdResponse {
+            unified_id: Some(Self::identity_to_proto(&identity)),
+        }))
+    }
+
+    /// Walk the parent chain from a DID toward its root
+    #[instrument(skip(self, request))]
+    async fn resolve_lineage(
+        &self,
+        request: Request<proto::ResolveLineageRequest>,
+    ) -> Result<Response<proto::ResolveLineageResponse>, Status> {
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        let max_depth = match req.max_depth {
+            0 => DEFAULT_LINEAGE_DEPTH,
+            depth => depth,
+        };
+
+        let mut current = self
+            .repo
+            .get_identity(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+        let mut lineage = vec![Self::identity_to_proto(&current)];
+        let mut trust_inheritance = vec![1.0];
+
+        while let Some(parent_did) = current.parent_did.take() {
+            if lineage.len() > max_depth as usize {
+                break;
+            }
+            current = match self.repo.get_identity(&parent_did).await {
+                Ok(parent) => parent,
+                Err(RepositoryError::NotFound(_)) => {
+                    warn!(did = %req.did, ancestor = %parent_did, "Lineage ancestor missing");
+                    break;
+                }
+                Err(e) => return Err(Self::repo_status(e)),
+            };
+            lineage.push(Self::identity_to_proto(&current));
+            trust_inheritance
+                .push(trust_inheritance[trust_inheritance.len() - 1] * INHERITED_TRUST);
+        }
+
+        debug!(did = %req.did, depth = lineage.len() - 1, "Lineage resolved");
+
+        Ok(Response::new(proto::ResolveLineageResponse {
+            lineage,
+            trust_inheritance,
+        }))
+    }
+
+    /// Get the trust score of a DID
+    #[instrument(skip(self, request))]
+    async fn get_trust_score(
+        &self,
+        request: Request<proto::GetTrustScoreRequest>,
+    ) -> Result<Response<proto::GetTrustScoreResponse>, Status> {
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+
+        let trust = self
+            .repo
+            .get_trust_score(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+
+        Ok(Response::new(proto::GetTrustScoreResponse {
+            trust_score: Some(Self::trust_to_proto(&trust)),
+        }))
+    }
+
+    /// Apply verification, dispute, SLA and network updates to a trust score
+    ///
+    /// Only operators may update trust.
+    #[instrument(skip(self, request))]
+    async fn update_trust_score(
+        &self,
+        request: Request<proto::UpdateTrustScoreRequest>,
+    ) -> Result<Response<proto::UpdateTrustScoreResponse>, Status> {
+        self.authorize_operator(&request, "UpdateTrustScore")?;
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+
+        let mut trust = self
+            .repo
+            .get_trust_score(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+        if trust.version != req.expected_version {
+            return Err(Status::aborted(format!(
+                "Version conflict: expected {}, found {}",
+                req.expected_version, trust.version
+            )));
+        }
+
+        let mut updated = false;
+        if let Some(update) = req.verification_update {
+            trust.record_verification(update.success);
+            updated = true;
+        }
+        if let Some(update) = req.dispute_update {
+            if update.dispute_occurred && !update.dispute_won {
+                trust.record_dispute();
+            }
+            updated = true;
+        }
+        if let Some(compliance) = req.sla_compliance {
+            if !(0.0..=1.0).contains(&compliance) {
+                return Err(Status::invalid_argument(
+                    "sla_compliance must be within 0-1",
+                ));
+            }
+            trust.update_sla_score(compliance);
+            updated = true;
+        }
+        if let Some(reputation) = req.network_reputation {
+            if !(0.0..=1.0).contains(&reputation) {
+                return Err(Status::invalid_argument(
+                    "network_reputation must be within 0-1",
+                ));
+            }
+            trust.update_network_score(reputation);
+            updated = true;
+        }
+        if !updated {
+            return Err(Status::invalid_argument("No trust update given"));
+        }
+
+        self.repo
+            .save_trust_score(&req.did, trust.clone(), req.expected_version)
+            .await
+            .map_err(Self::repo_status)?;
+
+        debug!(did = %req.did, score = trust.score, version = trust.version, "Trust score updated");
+
+        Ok(Response::new(proto::UpdateTrustScoreResponse {
+            trust_score: Some(Self::trust_to_proto(&trust)),
+        }))
+    }
+
+    /// Get the HC wallet of a DID
+    #[instrument(skip(self, request))]
+    async fn get_wallet(
+        &self,
+        request: Request<proto::GetWalletRequest>,
+    ) -> Result<Response<proto::GetWalletResponse>, Status> {
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+
+        let wallet = self
+            .repo
+            .get_wallet(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+
+        Ok(Response::new(proto::GetWalletResponse {
+            wallet: Some(Self::wallet_to_proto(&wallet)),
+        }))
+    }
+
+    /// Credit HC to a wallet
+    ///
+    /// Only operators may credit. Credits carry no expected version, so a
+    /// save that loses a race is retried against the fresh wallet.
+    #[instrument(skip(self, request))]
+    async fn credit_wallet(
+        &self,
+        request: Request<proto::CreditWalletRequest>,
+    ) -> Result<Response<proto::CreditWalletResponse>, Status> {
+        self.authorize_operator(&request, "CreditWallet")?;
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        let amount = Self::parse_amount(&req.amount)?;
+
+        let mut attempt = 0;
+        let wallet = loop {
+            attempt += 1;
+            let mut wallet = self
+                .repo
+                .get_wallet(&req.did)
+                .await
+                .map_err(Self::repo_status)?;
+            let version = wallet.version;
+            let reference = req.reference_id.as_deref().map(Reference::parse);
+            wallet
+                .credit_from(
+                    amount,
+                    reference
+                        .as_ref()
+                        .map(Reference::tranche_source)
+                        .unwrap_or_default(),
+                )
+                .map_err(Self::wallet_status)?;
+            let posting = Self::posting(
+                Posting::credit(&req.did, amount, req.reason.clone()),
+                &wallet,
+                reference,
+            );
+
+            match self
+                .repo
+                .save_wallet(wallet.clone(), version, posting)
+                .await
+            {
+                Ok(()) => break wallet,
+                Err(RepositoryError::VersionConflict { .. }) if attempt < MAX_CREDIT_ATTEMPTS => {
+                    debug!(did = %req.did, attempt, "Credit raced another write, retrying");
+                }
+                Err(e) => return Err(Self::repo_status(e)),
+            }
+        };
+
+        info!(
+            did = %req.did,
+            amount = %amount,
+            reason = %req.reason,
+            reference = ?req.reference_id,
+            "Wallet credited"
+        );
+
+        Ok(Response::new(proto::CreditWalletResponse {
+            wallet: Some(Self::wallet_to_proto(&wallet)),
+        }))
+    }
+
+    /// Debit HC from a wallet's available balance
+    #[instrument(skip(self, request))]
+    async fn debit_wallet(
+        &self,
+        request: Request<proto::DebitWalletRequest>,
+    ) -> Result<Response<proto::DebitWalletResponse>, Status> {
+        self.authorize(&request, "DebitWallet", &request.get_ref().did)?;
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        let amount = Self::parse_amount(&req.amount)?;
+
+        let mut wallet = self.load_wallet(&req.did, req.expected_version).await?;
+        wallet.debit(amount).map_err(Self::wallet_status)?;
+        let posting = Self::posting(
+            Posting::debit(&req.did, amount, req.reason.clone()),
+            &wallet,
+            req.reference_id.as_deref().map(Reference::parse),
+        );
+        self.repo
+            .save_wallet(wallet.clone(), req.expected_version, posting)
+            .await
+            .map_err(Self::repo_status)?;
+
+        info!(
+            did = %req.did,
+            amount = %amount,
+            reason = %req.reason,
+            reference = ?req.reference_id,
+            "Wallet debited"
+        );
+
+        Ok(Response::new(proto::DebitWalletResponse {
+            wallet: Some(Self::wallet_to_proto(&wallet)),
+        }))
+    }
+
+    /// Lock HC in escrow
+    #[instrument(skip(self, request))]
+    async fn lock_wallet(
+        &self,
+        request: Request<proto::LockWalletRequest>,
+    ) -> Result<Response<proto::LockWalletResponse>, Status> {
+        self.authorize(&request, "LockWallet", &request.get_ref().did)?;
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        let amount = Self::parse_amount(&req.amount)?;
+
+        let mut wallet = self.load_wallet(&req.did, req.expected_version).await?;
+        wallet.lock(amount).map_err(Self::wallet_status)?;
+        let posting = Self::posting(
+            Posting::lock(&req.did, amount, "escrow locked"),
+            &wallet,
+            Some(Reference::Escrow(req.escrow_id.clone())),
+        );
+        self.repo
+            .save_wallet(wallet.clone(), req.expected_version, posting)
+            .await
+            .map_err(Self::repo_status)?;
+
+        info!(did = %req.did, amount = %amount, escrow = %req.escrow_id, "HC locked");
+
+        Ok(Response::new(proto::LockWalletResponse {
+            wallet: Some(Self::wallet_to_proto(&wallet)),
+        }))
+    }
+
+    /// Release HC from escrow, back to the owner or to a target DID
+    #[instrument(skip(self, request))]
+    async fn release_wallet(
+        &self,
+        request: Request<proto::ReleaseWalletRequest>,
+    ) -> Result<Response<proto::ReleaseWalletResponse>, Status> {
+        self.authorize(&request, "ReleaseWallet", &request.get_ref().did)?;
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        let amount = Self::parse_amount(&req.amount)?;
+
+        let mut wallet = self.load_wallet(&req.did, req.expected_version).await?;
+        let target_did = req
+            .target_did
+            .filter(|target| !target.is_empty() && *target != req.did);
+
+        match target_did {
+            Some(ref target_did) => {
+                let mut target = self
+                    .repo
+                    .get_wallet(target_did)
+                    .await
+                    .map_err(Self::repo_status)?;
+                let target_version = target.version;
+                wallet
+                    .transfer_locked(amount, &mut target)
+                    .map_err(Self::wallet_status)?;
+                let posting = Self::posting(
+                    Posting::transfer_locked(&req.did, target_did, amount, "escrow settled"),
+                    &wallet,
+                    Some(Reference::Escrow(req.escrow_id.clone())),
+                );
+                self.repo
+                    .save_wallets(
+                        vec![
+                            (wallet.clone(), req.expected_version),
+                            (target, target_version),
+                        ],
+                        vec![posting],
+                    )
+                    .await
+                    .map_err(Self::repo_status)?;
+            }
+            None => {
+                wallet.release(amount).map_err(Self::wallet_status)?;
+                let posting = Self::posting(
+                    Posting::release(&req.did, amount, "escrow released"),
+                    &wallet,
+                    Some(Reference::Escrow(req.escrow_id.clone())),
+                );
+                self.repo
+                    .save_wallet(wallet.clone(), req.expected_version, posting)
+                    .await
+                    .map_err(Self::repo_status)?;
+            }
+        }
+
+        info!(
+            did = %req.did,
+            amount = %amount,
+            escrow = %req.escrow_id,
+            target = ?target_did,
+            "HC released"
+        );
+
+        Ok(Response::new(proto::ReleaseWalletResponse {
+            wallet: Some(Self::wallet_to_proto(&wallet)),
+        }))
+    }
+
+    /// Verify a signature made with a registered identity's key
+    #[instrument(skip(self, request))]
+    async fn verify_signature(
+        &self,
+        request: Request<proto::VerifySignatureRequest>,
+    ) -> Result<Response<proto::VerifySignatureResponse>, Status> {
+        let req = request.into_inner();
+        Self::require_did(&req.did)?;
+        if req.message.is_empty() {
+            return Err(Status::invalid_argument("message is required"));
+        }
+        let signature: [u8; 64] = req
+            .signature
+            .as_slice()
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Invalid signature length"))?;
+
+        let identity = self
+            .repo
+            .get_identity(&req.did)
+            .await
+            .map_err(Self::repo_status)?;
+
+        let valid = self.verify_as(&identity, &req.message, &signature).await?;
+
+        Ok(Response::new(proto::VerifySignatureResponse { valid }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::InMemoryIdentityRepository;
+    use actoris_common::crypto::did::sign_with_key;
+    use actoris_common::security::RequestSigner;
+    use ed25519_dalek::SigningKey;
+
+    fn operator_key() -> SigningKey {
+        SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    fn service() -> IdentityGrpcService<InMemoryIdentityRepository> {
+        IdentityGrpcService::new(
+            InMemoryIdentityRepository::new(),
+            Arc::new(RequestAuthenticator::default()),
+        )
+        .with_operators([RequestSigner::new(operator_key()).did().to_string()])
+    }
+
+    /// Wrap `message` in a request signed by `key` for `method`
+    fn signed<T: prost::Message>(key: &SigningKey, method: &str, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        RequestSigner::new(key.clone()).sign_grpc(
+            &mut request,
+            &format!("/actoris.identity.v1.IdentityService/{}", method),
+        );
+        request
+    }
+
+    async fn create_agent(
+        service: &IdentityGrpcService<InMemoryIdentityRepository>,
+        parent: Option<(&str, &SigningKey)>,
+    ) -> (proto::CreateUnifiedIdResponse, SigningKey) {
+        let (id, key) = UnifiedID::new_agent(None);
+        let response = service
+            .create_unified_id(signed(
+                &key,
+                "CreateUnifiedId",
+                proto::CreateUnifiedIdRequest {
+                    entity_type: proto_common::EntityType::Agent as i32,
+                    parent_did: parent.map(|(did, _)| did.to_string()),
+                    public_key: id.public_key.to_vec(),
+                    domain: None,
+                    parent_signature: parent.map(|(_, key)| {
+                        sign_with_key(key, &spawn_authorization(&id.public_key)).to_vec()
+                    }),
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        (response, key)
+    }
+
+    #[tokio::test]
+    async fn test_wallet_operations_check_versions() {
+        let service = service();
+        let (alice, alice_key) = create_agent(&service, None).await;
+        let (bob, _) = create_agent(&service, None).await;
+        let alice_did = alice.unified_id.unwrap().did;
+        let bob_did = bob.unified_id.unwrap().did;
+
+        let wallet = service
+            .credit_wallet(signed(
+                &operator_key(),
+                "CreditWallet",
+                proto::CreditWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "100".to_string(),
+                    reason: "top-up".to_string(),
+                    reference_id: None,
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner()
+            .wallet
+            .unwrap();
+        assert_eq!(wallet.available, "100");
+        assert_eq!(wallet.version, 1);
+
+        // A stale version is rejected without touching the wallet
+        let stale = service
+            .debit_wallet(signed(
+                &alice_key,
+                "DebitWallet",
+                proto::DebitWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "10".to_string(),
+                    reason: "usage".to_string(),
+                    reference_id: None,
+                    expected_version: 0,
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(stale.code(), tonic::Code::Aborted);
+
+        let wallet = service
+            .lock_wallet(signed(
+                &alice_key,
+                "LockWallet",
+                proto::LockWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "40".to_string(),
+                    escrow_id: "escrow-1".to_string(),
+                    expected_version: 1,
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner()
+            .wallet
+            .unwrap();
+        assert_eq!(
+            (wallet.available.as_str(), wallet.locked.as_str()),
+            ("60", "40")
+        );
+
+        let overdraw = service
+            .debit_wallet(signed(
+                &alice_key,
+                "DebitWallet",
+                proto::DebitWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "61".to_string(),
+                    reason: "usage".to_string(),
+                    reference_id: None,
+                    expected_version: 2,
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(overdraw.code(), tonic::Code::FailedPrecondition);
+
+        // Escrow settles to Bob
+        service
+            .release_wallet(signed(
+                &alice_key,
+                "ReleaseWallet",
+                proto::ReleaseWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "40".to_string(),
+                    escrow_id: "escrow-1".to_string(),
+                    target_did: Some(bob_did.clone()),
+                    expected_version: 2,
+                },
+            ))
+            .await
+            .unwrap();
+        let bob_wallet = service
+            .get_wallet(Request::new(proto::GetWalletRequest { did: bob_did }))
+            .await
+            .unwrap()
+            .into_inner()
+            .wallet
+            .unwrap();
+        assert_eq!(bob_wallet.available, "40");
+    }
+
+    #[tokio::test]
+    async fn test_wallet_statement_follows_journal() {
+        let service = service();
+        let (alice, alice_key) = create_agent(&service, None).await;
+        let did = alice.unified_id.unwrap().did;
+
+        service
+            .credit_wallet(signed(
+                &operator_key(),
+                "CreditWallet",
+                proto::CreditWalletRequest {
+                    did: did.clone(),
+                    amount: "100".to_string(),
+                    reason: "top-up".to_string(),
+                    reference_id: Some("invoice:inv-1".to_string()),
+                },
+            ))
+            .await
+            .unwrap();
+        let wallet = service
+            .lock_wallet(signed(
+                &alice_key,
+                "LockWallet",
+                proto::LockWalletRequest {
+                    did: did.clone(),
+                    amount: "30".to_string(),
+                    escrow_id: "escrow-9".to_string(),
+                    expected_version: 1,
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner()
+            .wallet
+            .unwrap();
+
+        let statement = service.wallet_statement(&did, 0, i64::MAX).await.unwrap();
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(
+            statement.lines[0].reference,
+            Some(Reference::Invoice("inv-1".to_string()))
+        );
+        assert_eq!(statement.closing_available.to_string(), wallet.available);
+        assert_eq!(statement.closing_locked.to_string(), wallet.locked);
+        assert!(statement
+            .to_csv()
+            .contains(",lock,escrow locked,escrow:escrow-9,"));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_operations_require_signatures() {
+        let service = service();
+        let (alice, alice_key) = create_agent(&service, None).await;
+        let (_, mallory_key) = create_agent(&service, None).await;
+        let alice_did = alice.unified_id.unwrap().did;
+
+        // Only an operator mints HC or moves trust
+        let credit = |key: &SigningKey| {
+            service.credit_wallet(signed(
+                key,
+                "CreditWallet",
+                proto::CreditWalletRequest {
+                    did: alice_did.clone(),
+                    amount: "100".to_string(),
+                    reason: "top-up".to_string(),
+                    reference_id: None,
+                },
+            ))
+        };
+        let self_credit = credit(&alice_key).await.unwrap_err();
+        assert_eq!(self_credit.code(), tonic::Code::PermissionDenied);
+        credit(&operator_key()).await.unwrap();
+        let trust = service
+            .update_trust_score(Request::new(proto::UpdateTrustScoreRequest {
+                did: alice_did.clone(),
+                expected_version: 0,
+                sla_compliance: Some(1.0),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(trust.code(), tonic::Code::Unauthenticated);
+
+        let debit = |key: Option<&SigningKey>| {
+            let message = proto::DebitWalletRequest {
+                did: alice_did.clone(),
+                amount: "10".to_string(),
+                reason: "usage".to_string(),
+                reference_id: None,
+                expected_version: 1,
+            };
+            service.debit_wallet(match key {
+                Some(key) => signed(key, "DebitWallet", message),
+                None => Request::new(message),
+            })
+        };
+
+        let unsigned = debit(None).await.unwrap_err();
+        assert_eq!(unsigned.code(), tonic::Code::Unauthenticated);
+        let impostor = debit(Some(&mallory_key)).await.unwrap_err();
+        assert_eq!(impostor.code(), tonic::Code::PermissionDenied);
+
+        let wallet = debit(Some(&alice_key))
+            .await
+            .unwrap()
+            .into_inner()
+            .wallet
+            .unwrap();
+        assert_eq!(wallet.available, "90");
+    }
+
+    #[tokio::test]
+    async fn test_did_key_registration_proves_possession() {
+        let service = service();
+        let (id, key) = UnifiedID::new_agent(None);
+        let (_, mallory_key) = UnifiedID::new_agent(None);
+        let register = |key: Option<&SigningKey>| {
+            let message = proto::CreateUnifiedIdRequest {
+                entity_type: proto_common::EntityType::Agent as i32,
+                parent_did: None,
+                public_key: id.public_key.to_vec(),
+                domain: None,
+                parent_signature: None,
+            };
+            service.create_unified_id(match key {
+                Some(key) => signed(key, "CreateUnifiedId", message),
+                None => Request::new(message),
+            })
+        };
+
+        // Registering someone else's key is refused
+        let unsigned = register(None).await.unwrap_err();
+        assert_eq!(unsigned.code(), tonic::Code::Unauthenticated);
+        let squatter = register(Some(&mallory_key)).await.unwrap_err();
+        assert_eq!(squatter.code(), tonic::Code::PermissionDenied);
+
+        let created = register(Some(&key)).await.unwrap().into_inner();
+        assert_eq!(created.unified_id.unwrap().did, id.did);
+    }
+
+    #[tokio::test]
+    async fn test_spawned_lineage_and_signatures() {
+        let service = service();
+        let (root, root_key) = create_agent(&service, None).await;
+        let root_did = root.unified_id.unwrap().did;
+        let (child, child_key) = create_agent(&service, Some((&root_did, &root_key))).await;
+        let child_did = child.unified_id.unwrap().did;
+        let (grandchild, _) = create_agent(&service, Some((&child_did, &child_key))).await;
+        let grandchild_did = grandchild.unified_id.unwrap().did;
+
+        let root_score = root.trust_score.unwrap().score;
+        assert_eq!(
+            child.trust_score.unwrap().score,
+            TrustScore::new_spawned(root_score as u16).score as u32
+        );
+
+        let lineage = service
+            .resolve_lineage(Request::new(proto::ResolveLineageRequest {
+                did: grandchild_did.clone(),
+                max_depth: 0,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let dids: Vec<_> = lineage.lineage.iter().map(|id| id.did.as_str()).collect();
+        assert_eq!(dids, vec![grandchild_did.as_str(), &child_did, &root_did]);
+        assert_eq!(lineage.trust_inheritance.len(), 3);
+        assert!((lineage.trust_inheritance[2] - INHERITED_TRUST * INHERITED_TRUST).abs() < 1e-12);
+
+        let truncated = service
+            .resolve_lineage(Request::new(proto::ResolveLineageRequest {
+                did: grandchild_did,
+                max_depth: 1,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(truncated.lineage.len(), 2);
+
+        // Unknown parents are refused, as are spawns the parent did not sign
+        let spawn = |parent_did: &str, parent_signature: Option<Vec<u8>>| {
+            let (id, key) = UnifiedID::new_agent(None);
+            service.create_unified_id(signed(
+                &key,
+                "CreateUnifiedId",
+                proto::CreateUnifiedIdRequest {
+                    entity_type: proto_common::EntityType::Agent as i32,
+                    parent_did: Some(parent_did.to_string()),
+                    public_key: id.public_key.to_vec(),
+                    domain: None,
+                    parent_signature,
+                },
+            ))
+        };
+        let orphan = spawn("did:key:z6Mkmissing", None).await.unwrap_err();
+        assert_eq!(orphan.code(), tonic::Code::FailedPrecondition);
+        let unsigned = spawn(&root_did, None).await.unwrap_err();
+        assert_eq!(unsigned.code(), tonic::Code::Unauthenticated);
+        let (other, _) = UnifiedID::new_agent(None);
+        let forged = sign_with_key(&root_key, &spawn_authorization(&other.public_key)).to_vec();
+        let forged = spawn(&root_did, Some(forged)).await.unwrap_err();
+        assert_eq!(forged.code(), tonic::Code::PermissionDenied);
+
+        let message = b"action payload".to_vec();
+        let signature = sign_with_key(&root_key, &message).to_vec();
+        let verify = |did: String, signature: Vec<u8>| {
+            service.verify_signature(Request::new(proto::VerifySignatureRequest {
+                did,
+                message: message.clone(),
+                signature,
+            }))
+        };
+        assert!(
+            verify(root_did.clone(), signature.clone())
+                .await
+                .unwrap()
+                .into_inner()
+                .valid
+        );
+        assert!(
+            !verify(child_did, signature)
+                .await
+                .unwrap()
+                .into_inner()
+                .valid
+        );
+    }
+
+    struct StaticResolver(actoris_common::crypto::DidDocument);
+
+    #[async_trait::async_trait]
+    impl DidResolver for StaticResolver {
+        async fn resolve(
+            &self,
+            did: &str,
+        ) -> Result<actoris_common::crypto::DidDocument, actoris_common::crypto::did::DidError>
+        {
+            assert_eq!(did, self.0.id);
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_did_web_signatures_follow_rotation() {
+        use actoris_common::crypto::KeyRotation;
+
+        let (_, old_key) = UnifiedID::new_agent(None);
+        let (_, new_key) = UnifiedID::new_agent(None);
+        let org = UnifiedID::new_organization("example.com", old_key.verifying_key().to_bytes());
+
+        let mut document = org.did_document();
+        let registration = document.clone();
+        let old_id = format!("{}#key-1", org.did);
+        let rotation = KeyRotation::new(
+            org.did.clone(),
+            &old_id,
+            &new_key.verifying_key().to_bytes(),
+            &old_id,
+            &old_key,
+        );
+        let signer = document.clone();
+        document.apply_rotation(&rotation, &signer).unwrap();
+
+        let repo = Arc::new(InMemoryIdentityRepository::new());
+        let auth = Arc::new(RequestAuthenticator::default());
+        let registered = IdentityGrpcService::from_shared(repo.clone(), auth.clone());
+        let resolving = IdentityGrpcService::from_shared(repo.clone(), auth.clone())
+            .with_resolver(Arc::new(StaticResolver(document)));
+        let register = || {
+            Request::new(proto::CreateUnifiedIdRequest {
+                entity_type: proto_common::EntityType::Organization as i32,
+                parent_did: None,
+                public_key: org.public_key.to_vec(),
+                domain: Some("example.com".to_string()),
+                parent_signature: None,
+            })
+        };
+
+        // Registering needs the domain to serve the key: unresolved or
+        // already rotated away, it is refused
+        let unresolved = registered.create_unified_id(register()).await.unwrap_err();
+        assert_eq!(unresolved.code(), tonic::Code::FailedPrecondition);
+        let rotated = resolving.create_unified_id(register()).await.unwrap_err();
+        assert_eq!(rotated.code(), tonic::Code::PermissionDenied);
+        IdentityGrpcService::from_shared(repo, auth)
+            .with_resolver(Arc::new(StaticResolver(registration)))
+            .create_unified_id(register())
+            .await
+            .unwrap();
+
+        let message = b"rotated".to_vec();
+        let request = |key: &SigningKey| {
+            Request::new(proto::VerifySignatureRequest {
+                did: org.did.clone(),
+                message: message.clone(),
+                signature: sign_with_key(key, &message).to_vec(),
+            })
+        };
+
+        // The registered key is stale; the resolved document is current
+        let valid = |r: Response<proto::VerifySignatureResponse>| r.into_inner().valid;
+        assert!(valid(
+            registered
+                .verify_signature(request(&old_key))
+                .await
+                .unwrap()
+        ));
+        assert!(valid(
+            resolving.verify_signature(request(&new_key)).await.unwrap()
+        ));
+        assert!(!valid(
+            resolving.verify_signature(request(&old_key)).await.unwrap()
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+//! # Actoris Identity
+//!
+//! Rust implementation of the IdentityService gRPC API
+//! (`proto/actoris/identity.proto`) over the shared `actoris-common` types:
+//!
+//! - [`UnifiedID`](actoris_common::UnifiedID) registration and lineage
+//! - [`TrustScore`](actoris_common::TrustScore) reads and updates
+//! - [`HcWallet`](actoris_common::HcWallet) credit, debit, lock and release,
+//!   with optimistic concurrency on the wallet version
+//! - A double-entry [`HcJournal`](actoris_common::HcJournal) of every wallet
+//!   movement, statements, and [`ExpirySweeper`] sweeps of expired balances
+//! - Atomic multi-wallet postings and [`EscrowSettlement`] sagas
+//! - Signature checks against registered DIDs, parent-signed spawns and
+//!   resolved did:web registrations
+//!
+//! Storage is pluggable through [`IdentityRepository`]; an in-memory backend
+//! is included.
+
+pub mod config;
+pub mod expiry;
+pub mod generated;
+pub mod grpc;
+pub mod repository;
+pub mod resolver;
+pub mod settlement;
+
+pub use config::IdentityConfig;
+pub use expiry::{ExpirySweepReport, ExpirySweeper, ExpirySweeperConfig};
+pub use grpc::{IdentityGrpcService, IdentityService, IdentityServiceServer};
+pub use repository::{
+    IdentityRecord, IdentityRepository, InMemoryIdentityRepository, RepositoryError,
+};
+pub use resolver::HttpsClient;
+pub use settlement::{EscrowSettlement, FeeSplit, PostingStep, WalletVersions};
+
+// Re-export generated proto types
+pub use generated::identity::v1 as proto;
+
+/// Share of a parent's trust a spawned agent inherits
+///
+/// Matches the cap applied by `TrustScore::new_spawned`.
+pub const INHERITED_TRUST: f64 = 0.30;
+
+/// Ancestors walked by ResolveLineage when no depth is given
+pub const DEFAULT_LINEAGE_DEPTH: u32 = 10;
+
+/// Message a parent signs to authorize spawning an agent with `public_key`
+///
+/// Sent as `parent_signature` in CreateUnifiedID alongside `parent_did`.
+pub fn spawn_authorization(public_key: &[u8; 32]) -> Vec<u8> {
+    [b"actoris:spawn:".as_slice(), public_key].concat()
+}
@@ -0,0 +1,59 @@
+//! Identity Service Binary
+//!
+//! Serves the IdentityService gRPC API backed by the in-memory repository.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use actoris_common::security::RequestAuthenticator;
+use anyhow::Result;
+use tonic::transport::Server;
+use tracing::info;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+use actoris_identity::{
+    ExpirySweeper, ExpirySweeperConfig, IdentityConfig, IdentityGrpcService, IdentityServiceServer,
+    InMemoryIdentityRepository,
+};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize tracing
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    info!(
+        "Starting Actoris Identity Service v{}",
+        actoris_common::VERSION
+    );
+
+    let config = IdentityConfig::from_env();
+    let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+
+    let repo = Arc::new(InMemoryIdentityRepository::new());
+    let service =
+        IdentityGrpcService::from_shared(repo.clone(), Arc::new(RequestAuthenticator::default()))
+            .with_resolver(Arc::new(actoris_identity::resolver::resolver()))
+            .with_operators(config.operators);
+
+    // Sweep expired wallet balances into the journal's expiry account
+    Arc::new(ExpirySweeper::new(repo, ExpirySweeperConfig::default())).spawn();
+
+    let shutdown = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install CTRL+C signal handler");
+        info!("Received shutdown signal");
+    };
+
+    info!("Identity gRPC server listening on {}", addr);
+    Server::builder()
+        .add_service(IdentityServiceServer::new(service))
+        .serve_with_shutdown(addr, shutdown)
+        .await?;
+
+    info!("Shutting down Identity service");
+    Ok(())
+}
@@ -0,0 +1,359 @@
+//! Identity storage
+//!
+//! The gRPC service reads and writes identities, trust scores and wallets
+//! through [`IdentityRepository`], so the backend can be swapped without
+//! touching the service. Writes are compare-and-swap on the record version:
+//! a caller saves what it loaded, and the save fails if anyone else saved in
+//! between.
+//!
+//! Wallets are saved together with the [`Posting`]s that moved them, so the
+//! HC journal always accounts for the stored balances.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+
+use actoris_common::{
+    HcJournal, HcWallet, JournalError, Posting, TrustScore, UnifiedID, WalletError,
+};
+
+/// Errors from repository operations
+#[derive(Debug, thiserror::Error)]
+pub enum RepositoryError {
+    #[error("Identity not found: {0}")]
+    NotFound(String),
+
+    #[error("Identity already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Version conflict for {did}: expected {expected}, found {found}")]
+    VersionConflict {
+        did: String,
+        expected: u64,
+        found: u64,
+    },
+
+    #[error("Journal error: {0}")]
+    Journal(#[from] JournalError),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+}
+
+/// Identity with its trust score and wallet, as created together
+#[derive(Debug, Clone)]
+pub struct IdentityRecord {
+    pub identity: UnifiedID,
+    pub trust_score: TrustScore,
+    pub wallet: HcWallet,
+}
+
+/// Storage backend for the identity service
+#[async_trait]
+pub trait IdentityRepository: Send + Sync + 'static {
+    /// Store a new identity, failing if the DID is taken
+    async fn create(&self, record: IdentityRecord) -> Result<(), RepositoryError>;
+
+    /// Get an identity by DID
+    async fn get_identity(&self, did: &str) -> Result<UnifiedID, RepositoryError>;
+
+    /// Get the trust score of a DID
+    async fn get_trust_score(&self, did: &str) -> Result<TrustScore, RepositoryError>;
+
+    /// Replace a trust score if the stored one is still at `expected_version`
+    async fn save_trust_score(
+        &self,
+        did: &str,
+        score: TrustScore,
+        expected_version: u64,
+    ) -> Result<(), RepositoryError>;
+
+    /// Get the wallet of a DID
+    async fn get_wallet(&self, did: &str) -> Result<HcWallet, RepositoryError>;
+
+    /// Replace wallets, each paired with the version it was loaded at, and
+    /// append the postings that moved them to the journal
+    ///
+    /// All wallets and postings are written or none are. The write fails if
+    /// the postings do not account for the new balances.
+    async fn save_wallets(
+        &self,
+        wallets: Vec<(HcWallet, u64)>,
+        postings: Vec<Posting>,
+    ) -> Result<(), RepositoryError>;
+
+    /// Replace a single wallet loaded at `expected_version`
+    async fn save_wallet(
+        &self,
+        wallet: HcWallet,
+        expected_version: u64,
+        posting: Posting,
+    ) -> Result<(), RepositoryError> {
+        self.save_wallets(vec![(wallet, expected_version)], vec![posting])
+            .await
+    }
+
+    /// Journal postings touching the wallet of a DID, oldest first
+    async fn postings(&self, did: &str) -> Result<Vec<Posting>, RepositoryError>;
+
+    /// Wallets that expired before `now` and still hold available HC
+    async fn expired_wallets(&self, now: i64) -> Result<Vec<HcWallet>, RepositoryError>;
+}
+
+/// In-memory repository (for testing and single-process deployments)
+#[derive(Default)]
+pub struct InMemoryIdentityRepository {
+    identities: RwLock<HashMap<String, UnifiedID>>,
+    trust_scores: RwLock<HashMap<String, TrustScore>>,
+    wallets: RwLock<HashMap<String, HcWallet>>,
+    journal: RwLock<HcJournal>,
+}
+
+impl InMemoryIdentityRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored identities
+    pub fn len(&self) -> usize {
+        self.identities.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.identities.read().is_empty()
+    }
+}
+
+#[async_trait]
+impl IdentityRepository for InMemoryIdentityRepository {
+    async fn create(&self, record: IdentityRecord) -> Result<(), RepositoryError> {
+        let did = record.identity.did.clone();
+        let mut identities = self.identities.write();
+        if identities.contains_key(&did) {
+            return Err(RepositoryError::AlreadyExists(did));
+        }
+
+        // Journal any balance the wallet starts with
+        let wallet = &record.wallet;
+        let mut opening = Vec::new();
+        if wallet.total() > Decimal::ZERO {
+            opening.push(Posting::credit(&did, wallet.total(), "opening balance"));
+        }
+        if wallet.locked > Decimal::ZERO {
+            opening.push(Posting::lock(&did, wallet.locked, "opening balance"));
+        }
+        // Wallets are always locked before the journal
+        let mut wallets = self.wallets.write();
+        self.journal
+            .write()
+            .post_with_wallets(opening, std::slice::from_ref(wallet))?;
+
+        self.trust_scores
+            .write()
+            .insert(did.clone(), record.trust_score);
+        wallets.insert(did.clone(), record.wallet);
+        identities.insert(did, record.identity);
+        Ok(())
+    }
+
+    async fn get_identity(&self, did: &str) -> Result<UnifiedID, RepositoryError> {
+        self.identities
+            .read()
+            .get(did)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(did.to_string()))
+    }
+
+    async fn get_trust_score(&self, did: &str) -> Result<TrustScore, RepositoryError> {
+        self.trust_scores
+            .read()
+            .get(did)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(did.to_string()))
+    }
+
+    async fn save_trust_score(
+        &self,
+        did: &str,
+        score: TrustScore,
+        expected_version: u64,
+    ) -> Result<(), RepositoryError> {
+        let mut scores = self.trust_scores.write();
+        let stored = scores
+            .get_mut(did)
+            .ok_or_else(|| RepositoryError::NotFound(did.to_string()))?;
+        if stored.version != expected_version {
+            return Err(RepositoryError::VersionConflict {
+                did: did.to_string(),
+                expected: expected_version,
+                found: stored.version,
+            });
+        }
+
+        *stored = score;
+        Ok(())
+    }
+
+    async fn get_wallet(&self, did: &str) -> Result<HcWallet, RepositoryError> {
+        self.wallets
+            .read()
+            .get(did)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(did.to_string()))
+    }
+
+    async fn save_wallets(
+        &self,
+        wallets: Vec<(HcWallet, u64)>,
+        postings: Vec<Posting>,
+    ) -> Result<(), RepositoryError> {
+        let mut stored = self.wallets.write();
+        for (wallet, expected_version) in &wallets {
+            let current = stored
+                .get(&wallet.owner_did)
+                .ok_or_else(|| RepositoryError::NotFound(wallet.owner_did.clone()))?;
+            current
+                .check_version(*expected_version)
+                .map_err(|e| match e {
+                    WalletError::VersionConflict { expected, found } => {
+                        RepositoryError::VersionConflict {
+                            did: wallet.owner_did.clone(),
+                            expected,
+                            found,
+                        }
+                    }
+                    other => RepositoryError::Storage(other.to_string()),
+                })?;
+        }
+
+        let updated: Vec<HcWallet> = wallets.into_iter().map(|(wallet, _)| wallet).collect();
+        self.journal.write().post_with_wallets(postings, &updated)?;
+        for wallet in updated {
+            stored.insert(wallet.owner_did.clone(), wallet);
+        }
+        Ok(())
+    }
+
+    async fn postings(&self, did: &str) -> Result<Vec<Posting>, RepositoryError> {
+        if !self.wallets.read().contains_key(did) {
+            return Err(RepositoryError::NotFound(did.to_string()));
+        }
+        Ok(self.journal.read().postings_for(did).cloned().collect())
+    }
+
+    async fn expired_wallets(&self, now: i64) -> Result<Vec<HcWallet>, RepositoryError> {
+        Ok(self
+            .wallets
+            .read()
+            .values()
+            .filter(|wallet| wallet.expires_at < now && wallet.available > Decimal::ZERO)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actoris_common::EntityType;
+    use rust_decimal_macros::dec;
+
+    fn record(did: &str) -> IdentityRecord {
+        IdentityRecord {
+            identity: UnifiedID {
+                did: did.to_string(),
+                entity_type: EntityType::Agent,
+                parent_did: None,
+                created_at: 0,
+                public_key: [0u8; 32],
+            },
+            trust_score: TrustScore::new(),
+            wallet: HcWallet::new(did.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallet_saves_are_compare_and_swap() {
+        let repo = InMemoryIdentityRepository::new();
+        repo.create(record("did:key:a")).await.unwrap();
+        repo.create(record("did:key:b")).await.unwrap();
+        assert!(matches!(
+            repo.create(record("did:key:a")).await,
+            Err(RepositoryError::AlreadyExists(_))
+        ));
+
+        // Two writers load the same version; only the first save lands
+        let mut first = repo.get_wallet("did:key:a").await.unwrap();
+        let mut second = first.clone();
+        first.credit(dec!(10)).unwrap();
+        second.credit(dec!(20)).unwrap();
+        repo.save_wallet(first, 0, Posting::credit("did:key:a", dec!(10), "top-up"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            repo.save_wallet(second, 0, Posting::credit("did:key:a", dec!(20), "top-up"))
+                .await,
+            Err(RepositoryError::VersionConflict { found: 1, .. })
+        ));
+
+        // A conflict on one wallet leaves the other untouched
+        let mut a = repo.get_wallet("did:key:a").await.unwrap();
+        let mut b = repo.get_wallet("did:key:b").await.unwrap();
+        a.debit(dec!(5)).unwrap();
+        b.credit(dec!(5)).unwrap();
+        let postings = vec![
+            Posting::credit("did:key:b", dec!(5), "top-up"),
+            Posting::debit("did:key:a", dec!(5), "usage"),
+        ];
+        assert!(repo
+            .save_wallets(vec![(b, 0), (a, 0)], postings)
+            .await
+            .is_err());
+        assert_eq!(
+            repo.get_wallet("did:key:b").await.unwrap().available,
+            dec!(0)
+        );
+        assert_eq!(
+            repo.get_wallet("did:key:a").await.unwrap().available,
+            dec!(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wallet_saves_must_match_journal() {
+        let repo = InMemoryIdentityRepository::new();
+        let mut funded = record("did:key:funded");
+        funded.wallet = HcWallet::with_balance("did:key:funded".to_string(), dec!(50));
+        repo.create(funded).await.unwrap();
+
+        // The opening balance is journaled
+        let postings = repo.postings("did:key:funded").await.unwrap();
+        assert_eq!(postings.len(), 1);
+        assert_eq!(postings[0].change("did:key:funded"), (dec!(50), dec!(0)));
+
+        // A balance change without a matching posting is refused
+        let mut wallet = repo.get_wallet("did:key:funded").await.unwrap();
+        wallet.debit(dec!(20)).unwrap();
+        assert!(matches!(
+            repo.save_wallets(vec![(wallet.clone(), 0)], Vec::new())
+                .await,
+            Err(RepositoryError::Journal(
+                JournalError::BalanceMismatch { .. }
+            ))
+        ));
+        repo.save_wallet(
+            wallet,
+            0,
+            Posting::debit("did:key:funded", dec!(20), "usage"),
+        )
+        .await
+        .unwrap();
+
+        let journal = HcJournal::replay(repo.postings("did:key:funded").await.unwrap()).unwrap();
+        journal
+            .verify(&repo.get_wallet("did:key:funded").await.unwrap())
+            .unwrap();
+    }
+}
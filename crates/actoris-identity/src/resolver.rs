@@ -0,0 +1,40 @@
+//! DID resolution over HTTPS
+//!
+//! did:web documents are fetched with [`HttpsClient`]; [`resolver`] builds
+//! the resolver the service binary verifies did:web identities against.
+
+use actoris_common::crypto::did::{DidError, DidMethod};
+use actoris_common::crypto::{HttpClient, UniversalResolver, WebResolver};
+use async_trait::async_trait;
+
+/// [`HttpClient`] over reqwest with rustls
+#[derive(Debug, Clone, Default)]
+pub struct HttpsClient {
+    client: reqwest::Client,
+}
+
+impl HttpsClient {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HttpClient for HttpsClient {
+    async fn get(&self, url: &str) -> Result<Vec<u8>, DidError> {
+        let failed = |e: reqwest::Error| DidError::ResolutionFailed(format!("{}: {}", url, e));
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(failed)?;
+        Ok(response.bytes().await.map_err(failed)?.to_vec())
+    }
+}
+
+/// Resolver for did:key and, over HTTPS, did:web
+pub fn resolver() -> UniversalResolver {
+    UniversalResolver::new().with_resolver(DidMethod::Web, WebResolver::new(HttpsClient::default()))
+}
@@ -0,0 +1,323 @@
+//! Escrow settlement sagas
+//!
+//! Settling an escrow locked with LockWallet pays the agent and then splits
+//! fees off to their recipients. Each stage is one atomic
+//! [`IdentityGrpcService::apply_postings`] batch; [`EscrowSettlement::saga`]
+//! chains them as a [`Saga`] so a fee split that fails reverses the
+//! settlement instead of leaving the agent paid in full.
+//!
+//! The saga runs against the wallet versions read when it was built, so a
+//! wallet written by anything else in the meantime aborts it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actoris_common::{Posting, Reference, Saga, SagaStep};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tonic::Status;
+
+use crate::grpc::IdentityGrpcService;
+use crate::repository::IdentityRepository;
+
+/// Expected version of each wallet a saga writes, keyed by DID
+pub type WalletVersions = HashMap<String, u64>;
+
+/// Saga step applying a batch of postings, reversed on compensation
+///
+/// The step saves only wallets still at the versions in the saga context and
+/// then records their new versions there for the steps that follow, so a
+/// concurrent write fails the step rather than being overwritten.
+pub struct PostingStep<R: IdentityRepository> {
+    name: String,
+    service: Arc<IdentityGrpcService<R>>,
+    postings: Vec<Posting>,
+}
+
+impl<R: IdentityRepository> PostingStep<R> {
+    pub fn new(
+        name: impl Into<String>,
+        service: Arc<IdentityGrpcService<R>>,
+        postings: Vec<Posting>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            service,
+            postings,
+        }
+    }
+
+    async fn apply(
+        &self,
+        postings: Vec<Posting>,
+        versions: &mut WalletVersions,
+    ) -> Result<(), Box<Status>> {
+        let saved = self.service.apply_postings(postings, versions).await?;
+        versions.extend(
+            saved
+                .into_iter()
+                .map(|wallet| (wallet.owner_did, wallet.version)),
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<R: IdentityRepository> SagaStep<WalletVersions, Box<Status>> for PostingStep<R> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, versions: &mut WalletVersions) -> Result<(), Box<Status>> {
+        self.apply(self.postings.clone(), versions).await
+    }
+
+    async fn compensate(&self, versions: &mut WalletVersions) -> Result<(), Box<Status>> {
+        let reversals = self.postings.iter().rev().map(Posting::reversal).collect();
+        self.apply(reversals, versions).await
+    }
+}
+
+/// Fee paid out of a settled escrow
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSplit {
+    pub recipient_did: String,
+    pub amount: Decimal,
+}
+
+/// Settlement of locked client HC to an agent, less fees
+#[derive(Debug, Clone)]
+pub struct EscrowSettlement {
+    pub escrow_id: String,
+    pub client_did: String,
+    pub agent_did: String,
+    /// Locked HC settled to the agent
+    pub amount: Decimal,
+    /// Fees the agent pays out of the settlement
+    pub fees: Vec<FeeSplit>,
+}
+
+impl EscrowSettlement {
+    pub fn new(
+        escrow_id: impl Into<String>,
+        client_did: impl Into<String>,
+        agent_did: impl Into<String>,
+        amount: Decimal,
+    ) -> Self {
+        Self {
+            escrow_id: escrow_id.into(),
+            client_did: client_did.into(),
+            agent_did: agent_did.into(),
+            amount,
+            fees: Vec::new(),
+        }
+    }
+
+    /// Split a fee off the settlement
+    pub fn with_fee(mut self, recipient_did: impl Into<String>, amount: Decimal) -> Self {
+        self.fees.push(FeeSplit {
+            recipient_did: recipient_did.into(),
+            amount,
+        });
+        self
+    }
+
+    /// Saga settling the escrow, then splitting fees, with the current
+    /// versions of every wallet it touches to run it against
+    pub async fn saga<R: IdentityRepository>(
+        &self,
+        service: Arc<IdentityGrpcService<R>>,
+    ) -> Result<(Saga<WalletVersions, Box<Status>>, WalletVersions), Box<Status>> {
+        let mut dids = vec![self.client_did.as_str(), self.agent_did.as_str()];
+        dids.extend(self.fees.iter().map(|fee| fee.recipient_did.as_str()));
+        let versions = service.wallet_versions(&dids).await?;
+
+        let reference = Reference::Escrow(self.escrow_id.clone());
+        let settle = Posting::transfer_locked(
+            &self.client_did,
+            &self.agent_did,
+            self.amount,
+            "escrow settled",
+        )
+        .with_reference(reference.clone());
+        let fees = self
+            .fees
+            .iter()
+            .map(|fee| {
+                Posting::transfer(&self.agent_did, &fee.recipient_did, fee.amount, "fee")
+                    .with_reference(reference.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let saga = Saga::new(format!("settle {}", self.escrow_id)).with_step(PostingStep::new(
+            "settle",
+            service.clone(),
+            vec![settle],
+        ));
+        if fees.is_empty() {
+            return Ok((saga, versions));
+        }
+        Ok((
+            saga.with_step(PostingStep::new("fee split", service, fees)),
+            versions,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{IdentityRecord, InMemoryIdentityRepository};
+    use actoris_common::security::RequestAuthenticator;
+    use actoris_common::{EntityType, HcWallet, SagaError, Statement, TrustScore, UnifiedID};
+    use rust_decimal_macros::dec;
+
+    const CLIENT: &str = "did:key:client";
+    const AGENT: &str = "did:key:agent";
+    const PLATFORM: &str = "did:key:platform";
+
+    async fn setup() -> (
+        Arc<InMemoryIdentityRepository>,
+        Arc<IdentityGrpcService<InMemoryIdentityRepository>>,
+    ) {
+        let repo = Arc::new(InMemoryIdentityRepository::new());
+        for (did, balance) in [(CLIENT, dec!(100)), (AGENT, dec!(0)), (PLATFORM, dec!(0))] {
+            repo.create(IdentityRecord {
+                identity: UnifiedID {
+                    did: did.to_string(),
+                    entity_type: EntityType::Agent,
+                    parent_did: None,
+                    created_at: 0,
+                    public_key: [0u8; 32],
+                },
+                trust_score: TrustScore::new(),
+                wallet: HcWallet::with_balance(did.to_string(), balance),
+            })
+            .await
+            .unwrap();
+        }
+        let service = Arc::new(IdentityGrpcService::from_shared(
+            repo.clone(),
+            Arc::new(RequestAuthenticator::default()),
+        ));
+        let lock = Posting::lock(CLIENT, dec!(50), "escrow locked");
+        service
+            .apply_postings(vec![lock], &[(CLIENT.to_string(), 0)].into())
+            .await
+            .unwrap();
+        (repo, service)
+    }
+
+    async fn balances(repo: &InMemoryIdentityRepository, did: &str) -> (Decimal, Decimal) {
+        let wallet = repo.get_wallet(did).await.unwrap();
+        // Other wallets' legs make a single wallet's postings unreplayable
+        // on their own, so check against the statement instead
+        let postings = repo.postings(did).await.unwrap();
+        let statement = Statement::from_postings(did, &postings, 0, i64::MAX);
+        assert_eq!(
+            (statement.closing_available, statement.closing_locked),
+            (wallet.available, wallet.locked)
+        );
+        (wallet.available, wallet.locked)
+    }
+
+    #[tokio::test]
+    async fn test_apply_postings_is_all_or_nothing() {
+        let (repo, service) = setup().await;
+        let versions = service.wallet_versions(&[CLIENT, AGENT]).await.unwrap();
+
+        // The second posting overdraws the agent, so neither is saved
+        let err = service
+            .apply_postings(
+                vec![
+                    Posting::transfer_locked(CLIENT, AGENT, dec!(20), "settle"),
+                    Posting::transfer(AGENT, CLIENT, dec!(30), "refund"),
+                ],
+                &versions,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(balances(&repo, CLIENT).await, (dec!(50), dec!(50)));
+        assert_eq!(balances(&repo, AGENT).await, (dec!(0), dec!(0)));
+
+        // Stale versions are rejected
+        service
+            .apply_postings(
+                vec![Posting::transfer_locked(CLIENT, AGENT, dec!(20), "settle")],
+                &versions,
+            )
+            .await
+            .unwrap();
+        let err = service
+            .apply_postings(
+                vec![Posting::transfer_locked(CLIENT, AGENT, dec!(20), "settle")],
+                &versions,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Aborted);
+        assert_eq!(balances(&repo, AGENT).await, (dec!(20), dec!(0)));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_saga_splits_fees() {
+        let (repo, service) = setup().await;
+        let (saga, mut versions) = EscrowSettlement::new("escrow-1", CLIENT, AGENT, dec!(50))
+            .with_fee(PLATFORM, dec!(5))
+            .saga(service)
+            .await
+            .unwrap();
+        saga.run(&mut versions).await.unwrap();
+
+        assert_eq!(balances(&repo, CLIENT).await, (dec!(50), dec!(0)));
+        assert_eq!(balances(&repo, AGENT).await, (dec!(45), dec!(0)));
+        assert_eq!(balances(&repo, PLATFORM).await, (dec!(5), dec!(0)));
+    }
+
+    #[tokio::test]
+    async fn test_failed_fee_split_reverses_settlement() {
+        let (repo, service) = setup().await;
+        // Fees exceed what the agent is paid
+        let (saga, mut versions) = EscrowSettlement::new("escrow-1", CLIENT, AGENT, dec!(50))
+            .with_fee(PLATFORM, dec!(60))
+            .saga(service)
+            .await
+            .unwrap();
+        let err = saga.run(&mut versions).await.unwrap_err();
+
+        assert!(matches!(err, SagaError::Aborted { ref step, .. } if step == "fee split"));
+        assert_eq!(balances(&repo, CLIENT).await, (dec!(50), dec!(50)));
+        assert_eq!(balances(&repo, AGENT).await, (dec!(0), dec!(0)));
+        assert_eq!(balances(&repo, PLATFORM).await, (dec!(0), dec!(0)));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_aborts_on_writes_since_built() {
+        let (repo, service) = setup().await;
+        let (saga, mut versions) = EscrowSettlement::new("escrow-1", CLIENT, AGENT, dec!(50))
+            .with_fee(PLATFORM, dec!(5))
+            .saga(service.clone())
+            .await
+            .unwrap();
+
+        // The client's escrow is refunded before the saga runs
+        let current = service.wallet_versions(&[CLIENT]).await.unwrap();
+        service
+            .apply_postings(
+                vec![Posting::release(CLIENT, dec!(50), "escrow refunded")],
+                &current,
+            )
+            .await
+            .unwrap();
+
+        let err = saga.run(&mut versions).await.unwrap_err();
+        assert!(matches!(
+            err,
+            SagaError::Aborted { ref step, ref error, .. }
+                if step == "settle" && error.code() == tonic::Code::Aborted
+        ));
+        assert_eq!(balances(&repo, CLIENT).await, (dec!(100), dec!(0)));
+        assert_eq!(balances(&repo, AGENT).await, (dec!(0), dec!(0)));
+    }
+}
@@ -1,8 +1,10 @@
 //! Usage aggregation with DashMap
 //!
-//! Aggregates usage events by actor, client, and time period for billing.
+//! Aggregates usage events by actor, client, and time period for billing,
+//! and rolls actor usage up to the principals that pay for it.
 
 use super::collector::{UsageBatch, UsageEvent};
+use actoris_common::types::principal::PrincipalGraph;
 use actoris_common::Result;
 use dashmap::DashMap;
 use rust_decimal::Decimal;
@@ -114,6 +116,25 @@ pub struct ActorUsageSummary {
     pub action_types: Vec<String>,
 }
 
+/// Usage rolled up to the principal that pays for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountUsageSummary {
+    /// Paying principal (organization, owner, or the actor itself)
+    pub payer_did: String,
+    /// Billing account charged, if the payer has one
+    pub billing_account_id: Option<String>,
+    /// Period start (Unix millis)
+    pub period_start: i64,
+    /// Period end (Unix millis)
+    pub period_end: i64,
+    /// Total compute consumed
+    pub total_compute: Decimal,
+    /// Total events
+    pub total_events: u64,
+    /// Actors whose usage is included
+    pub actors: Vec<String>,
+}
+
 /// Metering aggregator
 pub struct MeteringAggregator {
     /// Aggregation period in milliseconds (default: 1 hour)
@@ -231,6 +252,53 @@ impl MeteringAggregator {
         }
     }
 
+    /// Roll usage in a time range up to the accountable payers
+    ///
+    /// Each actor's usage is billed to its organization, else its owner,
+    /// else the actor itself (see [`PrincipalGraph::accountable_for`]).
+    pub fn get_account_summaries(
+        &self,
+        principals: &PrincipalGraph,
+        start: i64,
+        end: i64,
+    ) -> Vec<AccountUsageSummary> {
+        let mut summaries: std::collections::HashMap<String, AccountUsageSummary> =
+            std::collections::HashMap::new();
+        let mut add = |agg: &AggregatedUsage| {
+            if agg.key.period_start < start || agg.key.period_start >= end {
+                return;
+            }
+            let payer = principals.payer_of(&agg.key.actor_did);
+            let summary = summaries
+                .entry(payer.clone())
+                .or_insert_with(|| AccountUsageSummary {
+                    billing_account_id: principals.billing_account(&payer).map(|a| a.id.clone()),
+                    payer_did: payer,
+                    period_start: start,
+                    period_end: end,
+                    total_compute: Decimal::ZERO,
+                    total_events: 0,
+                    actors: Vec::new(),
+                });
+            summary.total_compute += agg.total_compute;
+            summary.total_events += agg.event_count;
+            if !summary.actors.contains(&agg.key.actor_did) {
+                summary.actors.push(agg.key.actor_did.clone());
+            }
+        };
+
+        for entry in self.aggregations.iter() {
+            add(entry.value());
+        }
+        for entry in self.completed.iter() {
+            entry.value().iter().for_each(&mut add);
+        }
+
+        let mut summaries: Vec<_> = summaries.into_values().collect();
+        summaries.sort_by(|a, b| a.payer_did.cmp(&b.payer_did));
+        summaries
+    }
+
     /// Get all current aggregations for an actor
     pub fn get_actor_aggregations(&self, actor_did: &str) -> Vec<AggregatedUsage> {
         self.aggregations
@@ -343,4 +411,98 @@ mod tests {
         assert_eq!(summary.total_events, 5);
         assert_eq!(summary.unique_clients, 5);
     }
+
+    #[test]
+    fn test_account_summaries_roll_up_to_org() {
+        use actoris_common::types::principal::{BillingAccount, MemberRole};
+        use actoris_common::UnifiedID;
+
+        let org = UnifiedID::new_organization("acme.example", [1u8; 32]);
+        let alice = UnifiedID::new_human_web("acme.example:users:alice", [2u8; 32]);
+        let (agent, _) = UnifiedID::new_agent(None);
+        let (child, _) = UnifiedID::new_agent(Some(&agent));
+        let mut principals = PrincipalGraph::new();
+        for id in [&org, &alice, &agent, &child] {
+            principals.register(id);
+        }
+        principals
+            .add_member(&org.did, &alice.did, MemberRole::Member)
+            .unwrap();
+        principals
+            .assign_owner(&agent.did, &alice.did, Some(&org.did))
+            .unwrap();
+        principals
+            .set_billing_account(BillingAccount::new("acct-acme", &org.did))
+            .unwrap();
+
+        let aggregator = MeteringAggregator::hourly();
+        for (actor, compute) in [
+            (agent.did.as_str(), dec!(10)),
+            (child.did.as_str(), dec!(5)),
+            ("did:key:solo", dec!(7)),
+        ] {
+            aggregator.process_event(&UsageEvent::new(
+                actor.into(),
+                "did:key:client".into(),
+                "test.action".into(),
+                compute,
+            ));
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let summaries = aggregator.get_account_summaries(&principals, now - 3600000, now + 3600000);
+        assert_eq!(summaries.len(), 2);
+
+        let acme = summaries.iter().find(|s| s.payer_did == org.did).unwrap();
+        assert_eq!(acme.total_compute, dec!(15));
+        assert_eq!(acme.billing_account_id.as_deref(), Some("acct-acme"));
+        assert_eq!(acme.actors.len(), 2);
+
+        let solo = summaries
+            .iter()
+            .find(|s| s.payer_did == "did:key:solo")
+            .unwrap();
+        assert_eq!(solo.total_compute, dec!(7));
+        assert!(solo.billing_account_id.is_none());
+    }
+
+    #[test]
+    fn test_account_summaries_span_closed_periods_within_range() {
+        let hour = 3600000;
+        let start = 1_700_000_000_000 / hour * hour;
+        let principals = PrincipalGraph::new();
+        let aggregator = MeteringAggregator::hourly();
+        let usage = |actor: &str, compute, timestamp| {
+            let mut event = UsageEvent::new(
+                actor.into(),
+                "did:key:client".into(),
+                "test.action".into(),
+                compute,
+            );
+            event.timestamp = timestamp;
+            aggregator.process_event(&event);
+        };
+
+        usage("did:key:agent", dec!(10), start + 10);
+        usage("did:key:agent", dec!(4), start + 20);
+        aggregator.close_period(start + hour);
+        usage("did:key:agent", dec!(6), start + hour + 10);
+        usage("did:key:other", dec!(3), start + hour + 20);
+        // Outside the billed range
+        usage("did:key:agent", dec!(100), start + 2 * hour + 10);
+        usage("did:key:agent", dec!(100), start - 10);
+
+        let summaries = aggregator.get_account_summaries(&principals, start, start + 2 * hour);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].payer_did, "did:key:agent");
+        assert_eq!(summaries[0].total_compute, dec!(20));
+        assert_eq!(summaries[0].total_events, 3);
+        assert_eq!(summaries[0].actors, vec!["did:key:agent".to_string()]);
+        assert_eq!(summaries[1].payer_did, "did:key:other");
+        assert_eq!(summaries[1].total_compute, dec!(3));
+
+        assert!(aggregator
+            .get_account_summaries(&principals, start + 3 * hour, start + 4 * hour)
+            .is_empty());
+    }
 }
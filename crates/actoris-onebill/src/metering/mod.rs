@@ -2,10 +2,13 @@
 //!
 //! Provides usage event collection and aggregation:
 //! - UsageCollector: Collects and batches usage events
-//! - MeteringAggregator: Aggregates usage by actor/client/period
+//! - MeteringAggregator: Aggregates usage by actor/client/period and rolls
+//!   it up to the accountable payer
 
 pub mod aggregator;
 pub mod collector;
 
-pub use aggregator::{AggregatedUsage, AggregationKey, ActorUsageSummary, MeteringAggregator};
+pub use aggregator::{
+    AccountUsageSummary, ActorUsageSummary, AggregatedUsage, AggregationKey, MeteringAggregator,
+};
 pub use collector::{CollectorConfig, CollectorMetrics, UsageBatch, UsageCollector, UsageEvent};
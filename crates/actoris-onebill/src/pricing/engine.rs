@@ -7,7 +7,7 @@
 
 use actoris_common::{
     ActorisError, DataSensitivity, PricingBreakdown, PricingRequest, PricingResponse, Result,
-    RiskFactor, TaskComplexity,
+    RiskFactor, TaskComplexity, TrustScore,
 };
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -93,6 +93,19 @@ impl PricingEngine {
         })
     }
 
+    /// Calculate price using the actor's trust in the request's action domain
+    ///
+    /// An actor trusted for `code.review` but not `finance` gets the discount
+    /// its `code.review.*` history earns, not its global average.
+    pub async fn calculate_with_trust(
+        &self,
+        request: &PricingRequest,
+        trust: &TrustScore,
+    ) -> Result<PricingResponse> {
+        let request = request.clone().with_domain_trust(trust);
+        self.calculate(&request).await
+    }
+
     /// Calculate price for a request using Zen-Engine rules
     #[instrument(skip(self))]
     pub async fn calculate(&self, request: &PricingRequest) -> Result<PricingResponse> {
@@ -462,8 +475,18 @@ impl Default for PricingRulesBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use actoris_common::TrustModel;
     use rust_decimal_macros::dec;
 
+    /// Engine without a decision graph, priced by the default calculation only
+    fn rules_free_engine() -> PricingEngine {
+        PricingEngine {
+            engine: DecisionEngine::default(),
+            base_rate: dec!(1.0),
+            decision_graph: None,
+        }
+    }
+
     #[tokio::test]
     async fn test_default_pricing() {
         let engine = PricingEngine::new(dec!(1.0));
@@ -523,4 +546,63 @@ mod tests {
         assert!(rules.get("complexity_rules").is_some());
         assert!(rules.get("trust_rules").is_some());
     }
+
+    #[tokio::test]
+    async fn test_default_calculation_formula() {
+        let engine = rules_free_engine();
+
+        let request = PricingRequest::new("did:key:test", "test.action", dec!(100), 500)
+            .with_task_complexity(TaskComplexity::High)
+            .with_data_sensitivity(DataSensitivity::Internal);
+
+        let response = engine.calculate(&request).await.unwrap();
+
+        // P = C + R - T with a 1.25 risk multiplier and a 10% discount
+        assert_eq!(response.breakdown.compute_cost, dec!(100));
+        assert_eq!(response.breakdown.risk_premium, dec!(25));
+        assert_eq!(response.breakdown.trust_discount, dec!(12.5));
+        assert_eq!(response.final_price, dec!(112.5));
+    }
+
+    #[tokio::test]
+    async fn test_calculate_with_domain_trust() {
+        let engine = rules_free_engine();
+        let model = TrustModel::default();
+        let now = 1_700_000_000_000;
+
+        let mut trust = TrustScore::new();
+        for i in 0..20 {
+            trust.record_outcome_at("code.review.rust", true, now + i, &model);
+            trust.record_outcome_at("finance.reconcile", i % 4 == 0, now + i, &model);
+        }
+
+        let price = |action_type: &str| {
+            let request = PricingRequest::new("did:key:test", action_type, dec!(100), 0);
+            let engine = &engine;
+            let trust = &trust;
+            async move { engine.calculate_with_trust(&request, trust).await.unwrap() }
+        };
+
+        // The discount follows the trust earned in the request's domain
+        let code = price("code.review.python").await;
+        let finance = price("finance.reconcile").await;
+        assert!(code.final_price < finance.final_price);
+        let tau = Decimal::from(trust.domain_score("code.review.python")) / dec!(1000);
+        // Medium complexity on internal data: 100 HC plus a 10% risk premium
+        assert_eq!(code.breakdown.trust_discount, dec!(110) * tau * dec!(0.20));
+
+        // An unseen domain is priced at the global score, not the request's
+        let legal = price("legal.draft").await;
+        let global = engine
+            .calculate(&PricingRequest::new(
+                "did:key:test",
+                "legal.draft",
+                dec!(100),
+                trust.score,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(legal.final_price, global.final_price);
+        assert!(legal.breakdown.trust_discount > Decimal::ZERO);
+    }
 }
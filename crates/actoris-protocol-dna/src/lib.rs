@@ -7,15 +7,34 @@
 //! - **Spawn**: Create child agents (30% trust cap from parent)
 //! - **Lend**: Risk-priced credit extension
 //! - **Insure**: Outcome guarantees with premium pricing
-//! - **Delegate**: Escrow-based task delegation with verification
+//! - **Delegate**: Escrow-based task delegation with verification, released
+//!   per milestone against outcome records
+//!
+//! Every transition is an event on the ledgered [`machine::DnaMachine`],
+//! priced by the canonical formulas in [`pricing`] and applied to `HcWallet`s
+//! so that HC is conserved. Organizations cap what their agents commit with
+//! [`policy::SpendingPolicy`].
+//!
+//! Custom primitives can be deployed as sandboxed WASM contracts through
+//! [`wasm::WasmRuntime`].
 
+pub mod machine;
+pub mod policy;
+pub mod pricing;
 pub mod primitives;
 pub mod wasm;
 
+pub use machine::{
+    Commitment, DnaEvent, DnaMachine, HcDelta, MachineEscrow, Position, TransitionError,
+};
+pub use policy::{SpendingPolicies, SpendingPolicy};
+pub use pricing::DnaPricing;
+
 // Re-export primitives
 pub use primitives::{
-    delegate::DelegatePrimitive,
+    delegate::{DelegatePrimitive, DelegationContract, EscrowLedger, OutcomeAnchor},
     insure::InsurePrimitive,
     lend::LendPrimitive,
     spawn::SpawnPrimitive,
 };
+pub use wasm::{ContractCall, DnaLedger, WasmRuntime};
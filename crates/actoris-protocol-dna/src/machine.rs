@@ -0,0 +1,1499 @@
+//! Ledgered Protocol DNA state machine
+//!
+//! Every Spawn, Lend, Insure and Delegate transition is a [`DnaEvent`]
+//! applied to the participants' [`HcWallet`]s and appended to the machine's
+//! log. HC only enters through `Minted` and leaves through `Burned`, so the
+//! HC held across all wallets always equals minted minus burned, and
+//! replaying the log rebuilds the same wallets.
+//!
+//! The command methods (`spawn`, `lend`, `insure`, `delegate`, ...) price the
+//! transition with [`DnaPricing`] and apply the resulting event. Priced
+//! amounts are rounded to `HC_SCALE` decimals so sums stay exact. A rejected
+//! event leaves every wallet untouched.
+//!
+//! Ledgers with richer positions than the four primitives (loan servicing,
+//! credit lines, reinsurance) move HC with [`DnaEvent::Adjusted`]: a set of
+//! per-wallet deltas that must net to zero, applied all-or-nothing like any
+//! other transition.
+//!
+//! Every transition also posts its balanced [`Posting`]s to the machine's
+//! [`HcJournal`] in the same step that commits the wallets, and is rejected
+//! if the journal would derive different balances, so wallet statements
+//! cover every HC movement.
+//!
+//! Commands are also checked against the [`SpendingPolicies`] of the
+//! organizations paying for them. Replay does not re-check: logged events
+//! were admitted under the policies in force at the time.
+
+use std::collections::HashMap;
+
+use actoris_common::error::PricingError;
+use actoris_common::types::hc_journal::{
+    Account, HcJournal, JournalError, Leg, Posting, Reference, Statement,
+};
+use actoris_common::types::hc_wallet::{HcWallet, TrancheSource, WalletError};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::policy::SpendingPolicies;
+use crate::pricing::{decimal_rate, DnaPricing};
+use crate::primitives::delegate::{EscrowError, EscrowLedger};
+use crate::wasm::HC_SCALE;
+
+/// State transition errors
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum TransitionError {
+    #[error("Wallet not found: {0}")]
+    WalletNotFound(String),
+
+    #[error("Position not found: {0}")]
+    PositionNotFound(String),
+
+    #[error("Position already exists: {0}")]
+    DuplicatePosition(String),
+
+    #[error("Position {id} is not a {expected}")]
+    WrongPosition { id: String, expected: &'static str },
+
+    #[error("Both parties are {0}")]
+    SameParty(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(Decimal),
+
+    #[error("{amount} HC exceeds {org}'s limit of {limit} per transition")]
+    OverTransitionLimit {
+        org: String,
+        amount: Decimal,
+        limit: Decimal,
+    },
+
+    #[error("{amount} HC exceeds {org}'s remaining budget of {remaining}")]
+    OverBudget {
+        org: String,
+        amount: Decimal,
+        remaining: Decimal,
+    },
+
+    #[error("Adjustment does not balance: nets to {0} HC")]
+    Unbalanced(Decimal),
+
+    #[error(transparent)]
+    Pricing(#[from] PricingError),
+
+    #[error(transparent)]
+    Wallet(#[from] WalletError),
+
+    #[error(transparent)]
+    Journal(#[from] JournalError),
+}
+
+/// One wallet's side of an [`DnaEvent::Adjusted`] transition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HcDelta {
+    pub did: String,
+    /// Change to the available balance
+    pub available: Decimal,
+    /// Change to the locked balance
+    pub locked: Decimal,
+}
+
+impl HcDelta {
+    pub fn new(did: impl Into<String>, available: Decimal, locked: Decimal) -> Self {
+        Self {
+            did: did.into(),
+            available,
+            locked,
+        }
+    }
+
+    /// HC the wallet gains overall
+    pub fn net(&self) -> Decimal {
+        self.available + self.locked
+    }
+}
+
+/// HC a party commits to a new position in an [`DnaEvent::Adjusted`]
+/// transition, counted against its organization's spending policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Commitment {
+    pub did: String,
+    pub amount: Decimal,
+}
+
+impl Commitment {
+    pub fn new(did: impl Into<String>, amount: Decimal) -> Self {
+        Self {
+            did: did.into(),
+            amount,
+        }
+    }
+}
+
+/// A Protocol DNA state transition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DnaEvent {
+    /// HC entered the economy (purchase, grant or metered earnings)
+    Minted {
+        did: String,
+        amount: Decimal,
+        #[serde(default)]
+        source: TrancheSource,
+    },
+    /// Available HC left the economy (expiry or withdrawal)
+    Burned { did: String, amount: Decimal },
+    /// Locked HC left the economy (slashing)
+    Forfeited { did: String, amount: Decimal },
+    /// Balanced moves between wallets, applied in order
+    Adjusted {
+        memo: String,
+        /// Source of the tranches credited to wallets that gain HC
+        source: TrancheSource,
+        deltas: Vec<HcDelta>,
+        /// Positions opened by the adjustment
+        #[serde(default)]
+        commitments: Vec<Commitment>,
+    },
+    /// Parent locked a stake for a new child and endowed it
+    Spawned {
+        spawn_id: String,
+        parent_did: String,
+        child_did: String,
+        inherited_tau: f64,
+        stake: Decimal,
+        endowment: Decimal,
+    },
+    /// Spawn stake unlocked back to the parent
+    StakeReleased { spawn_id: String },
+    /// Principal paid to the borrower against locked collateral
+    LoanIssued {
+        loan_id: String,
+        lender_did: String,
+        borrower_did: String,
+        principal: Decimal,
+        collateral: Decimal,
+        interest_rate: f64,
+        term_days: u32,
+    },
+    /// Borrower paid `amount` to the lender and got the collateral back
+    LoanRepaid { loan_id: String, amount: Decimal },
+    /// Collateral seized by the lender
+    LoanDefaulted { loan_id: String },
+    /// Premium paid to the insurer, which locked the coverage
+    PolicyIssued {
+        policy_id: String,
+        insurer_did: String,
+        insured_did: String,
+        coverage: Decimal,
+        premium: Decimal,
+    },
+    /// Claim paid from the locked coverage; the rest is released
+    ClaimPaid { policy_id: String, amount: Decimal },
+    /// Policy lapsed without a claim; coverage released
+    PolicyExpired { policy_id: String },
+    /// Client locked escrow for a delegated task
+    EscrowLocked {
+        contract_id: String,
+        client_did: String,
+        agent_did: String,
+        amount: Decimal,
+    },
+    /// Escrow paid to the agent
+    EscrowReleased {
+        contract_id: String,
+        amount: Decimal,
+    },
+    /// Escrow returned to the client
+    EscrowRefunded {
+        contract_id: String,
+        amount: Decimal,
+    },
+    /// Escrow burned by a dispute ruling
+    EscrowForfeited {
+        contract_id: String,
+        amount: Decimal,
+    },
+}
+
+impl DnaEvent {
+    /// Id of the position the event opens, if it opens one
+    pub fn opened_position(&self) -> Option<&str> {
+        match self {
+            DnaEvent::Spawned { spawn_id: id, .. }
+            | DnaEvent::LoanIssued { loan_id: id, .. }
+            | DnaEvent::PolicyIssued { policy_id: id, .. }
+            | DnaEvent::EscrowLocked {
+                contract_id: id, ..
+            } => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// Open obligation backed by locked HC
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Position {
+    Spawn {
+        parent_did: String,
+        child_did: String,
+        stake: Decimal,
+    },
+    Loan {
+        lender_did: String,
+        borrower_did: String,
+        principal: Decimal,
+        collateral: Decimal,
+        interest_rate: f64,
+        term_days: u32,
+    },
+    Policy {
+        insurer_did: String,
+        insured_did: String,
+        coverage: Decimal,
+    },
+    Escrow {
+        client_did: String,
+        agent_did: String,
+        locked: Decimal,
+    },
+}
+
+impl Position {
+    /// HC locked against the position
+    pub fn locked(&self) -> Decimal {
+        match self {
+            Position::Spawn { stake, .. } => *stake,
+            Position::Loan { collateral, .. } => *collateral,
+            Position::Policy { coverage, .. } => *coverage,
+            Position::Escrow { locked, .. } => *locked,
+        }
+    }
+}
+
+/// Protocol DNA state: wallets, open positions and the event log
+#[derive(Debug, Clone, Default)]
+pub struct DnaMachine {
+    pricing: DnaPricing,
+    policies: SpendingPolicies,
+    wallets: HashMap<String, HcWallet>,
+    positions: HashMap<String, Position>,
+    log: Vec<DnaEvent>,
+    journal: HcJournal,
+    minted: Decimal,
+    burned: Decimal,
+}
+
+impl DnaMachine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pricing(mut self, pricing: DnaPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    pub fn with_spending_policies(mut self, policies: SpendingPolicies) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Rebuild a machine by applying a log from the start
+    pub fn replay(
+        pricing: DnaPricing,
+        events: impl IntoIterator<Item = DnaEvent>,
+    ) -> Result<Self, TransitionError> {
+        let mut machine = Self::new().with_pricing(pricing);
+        for event in events {
+            machine.apply(event)?;
+        }
+        Ok(machine)
+    }
+
+    pub fn pricing(&self) -> &DnaPricing {
+        &self.pricing
+    }
+
+    pub fn spending_policies(&self) -> &SpendingPolicies {
+        &self.policies
+    }
+
+    pub fn spending_policies_mut(&mut self) -> &mut SpendingPolicies {
+        &mut self.policies
+    }
+
+    pub fn wallet(&self, did: &str) -> Option<&HcWallet> {
+        self.wallets.get(did)
+    }
+
+    pub fn wallets(&self) -> impl Iterator<Item = &HcWallet> {
+        self.wallets.values()
+    }
+
+    pub fn position(&self, id: &str) -> Option<&Position> {
+        self.positions.get(id)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (&String, &Position)> {
+        self.positions.iter()
+    }
+
+    /// Applied events, oldest first
+    pub fn events(&self) -> &[DnaEvent] {
+        &self.log
+    }
+
+    /// Postings of every applied event
+    pub fn journal(&self) -> &HcJournal {
+        &self.journal
+    }
+
+    /// Statement of the wallet of `did` for `[from, to)`
+    pub fn statement(&self, did: &str, from: i64, to: i64) -> Statement {
+        self.journal.statement(did, from, to)
+    }
+
+    /// HC held across all wallets (available and locked)
+    pub fn total_hc(&self) -> Decimal {
+        self.wallets.values().map(HcWallet::total).sum()
+    }
+
+    /// HC minted minus HC burned
+    pub fn supply(&self) -> Decimal {
+        self.minted - self.burned
+    }
+
+    /// Whether wallet holdings match the supply
+    pub fn is_conserved(&self) -> bool {
+        self.total_hc() == self.supply()
+    }
+
+    // ============ Commands ============
+
+    pub fn mint(&mut self, did: &str, amount: Decimal) -> Result<DnaEvent, TransitionError> {
+        self.mint_from(did, amount, TrancheSource::Purchase)
+    }
+
+    pub fn mint_from(
+        &mut self,
+        did: &str,
+        amount: Decimal,
+        source: TrancheSource,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::Minted {
+            did: did.to_string(),
+            amount,
+            source,
+        })
+    }
+
+    pub fn burn(&mut self, did: &str, amount: Decimal) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::Burned {
+            did: did.to_string(),
+            amount,
+        })
+    }
+
+    /// Burn HC locked in `did`'s wallet
+    pub fn forfeit(&mut self, did: &str, amount: Decimal) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::Forfeited {
+            did: did.to_string(),
+            amount,
+        })
+    }
+
+    /// Apply balanced per-wallet deltas in one transition
+    ///
+    /// Wallets that gain HC receive it as tranches from `source`.
+    pub fn adjust(
+        &mut self,
+        memo: &str,
+        source: TrancheSource,
+        deltas: Vec<HcDelta>,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.adjust_committing(memo, source, deltas, Vec::new())
+    }
+
+    /// Apply balanced deltas that open positions worth `commitments`
+    ///
+    /// The commitments are checked against spending policies like those of
+    /// the typed commands; they may accompany no deltas at all when a
+    /// position reserves HC without moving it.
+    pub fn adjust_committing(
+        &mut self,
+        memo: &str,
+        source: TrancheSource,
+        deltas: Vec<HcDelta>,
+        commitments: Vec<Commitment>,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::Adjusted {
+            memo: memo.to_string(),
+            source,
+            deltas,
+            commitments,
+        })
+    }
+
+    /// Spawn `child_did`, locking `stake` and endowing the child from the
+    /// parent's available HC
+    pub fn spawn(
+        &mut self,
+        parent_did: &str,
+        parent_tau: f64,
+        child_did: &str,
+        stake: Decimal,
+        endowment: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::Spawned {
+            spawn_id: new_id(),
+            parent_did: parent_did.to_string(),
+            child_did: child_did.to_string(),
+            inherited_tau: self.pricing.inherited_tau(parent_tau),
+            stake,
+            endowment,
+        })
+    }
+
+    pub fn release_stake(&mut self, spawn_id: &str) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::StakeReleased {
+            spawn_id: spawn_id.to_string(),
+        })
+    }
+
+    /// Lend `principal` at the borrower's trust-priced rate
+    pub fn lend(
+        &mut self,
+        lender_did: &str,
+        borrower_did: &str,
+        borrower_tau: f64,
+        principal: Decimal,
+        collateral_pct: f64,
+        term_days: u32,
+    ) -> Result<DnaEvent, TransitionError> {
+        let collateral = self.pricing.collateral(principal, collateral_pct)?;
+        self.execute(DnaEvent::LoanIssued {
+            loan_id: new_id(),
+            lender_did: lender_did.to_string(),
+            borrower_did: borrower_did.to_string(),
+            principal,
+            collateral,
+            interest_rate: self.pricing.interest_rate(borrower_tau),
+            term_days,
+        })
+    }
+
+    /// Principal plus interest over the full term
+    pub fn amount_due(&self, loan_id: &str) -> Result<Decimal, TransitionError> {
+        match self.positions.get(loan_id) {
+            Some(Position::Loan {
+                principal,
+                interest_rate,
+                term_days,
+                ..
+            }) => Ok(*principal
+                + self
+                    .pricing
+                    .interest(*principal, *interest_rate, *term_days)?),
+            _ => Err(self.missing(loan_id, "loan")),
+        }
+    }
+
+    pub fn repay(&mut self, loan_id: &str) -> Result<DnaEvent, TransitionError> {
+        let amount = self.amount_due(loan_id)?;
+        self.execute(DnaEvent::LoanRepaid {
+            loan_id: loan_id.to_string(),
+            amount,
+        })
+    }
+
+    pub fn default_loan(&mut self, loan_id: &str) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::LoanDefaulted {
+            loan_id: loan_id.to_string(),
+        })
+    }
+
+    /// Insure `coverage` at the insured's trust-priced premium
+    pub fn insure(
+        &mut self,
+        insurer_did: &str,
+        insured_did: &str,
+        insured_tau: f64,
+        coverage: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::PolicyIssued {
+            policy_id: new_id(),
+            insurer_did: insurer_did.to_string(),
+            insured_did: insured_did.to_string(),
+            coverage,
+            premium: self.pricing.premium(coverage, insured_tau)?,
+        })
+    }
+
+    pub fn claim(&mut self, policy_id: &str, amount: Decimal) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::ClaimPaid {
+            policy_id: policy_id.to_string(),
+            amount,
+        })
+    }
+
+    pub fn expire_policy(&mut self, policy_id: &str) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::PolicyExpired {
+            policy_id: policy_id.to_string(),
+        })
+    }
+
+    pub fn delegate(
+        &mut self,
+        client_did: &str,
+        agent_did: &str,
+        amount: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::EscrowLocked {
+            contract_id: new_id(),
+            client_did: client_did.to_string(),
+            agent_did: agent_did.to_string(),
+            amount,
+        })
+    }
+
+    pub fn release_escrow(
+        &mut self,
+        contract_id: &str,
+        amount: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::EscrowReleased {
+            contract_id: contract_id.to_string(),
+            amount,
+        })
+    }
+
+    pub fn refund_escrow(
+        &mut self,
+        contract_id: &str,
+        amount: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::EscrowRefunded {
+            contract_id: contract_id.to_string(),
+            amount,
+        })
+    }
+
+    /// Burn part of an escrow under a dispute ruling
+    pub fn forfeit_escrow(
+        &mut self,
+        contract_id: &str,
+        amount: Decimal,
+    ) -> Result<DnaEvent, TransitionError> {
+        self.execute(DnaEvent::EscrowForfeited {
+            contract_id: contract_id.to_string(),
+            amount,
+        })
+    }
+
+    /// Escrow position `contract_id`, for settling a
+    /// [`crate::primitives::delegate::DelegationContract`] through the log
+    pub fn escrow<'a>(&'a mut self, contract_id: &'a str) -> MachineEscrow<'a> {
+        MachineEscrow {
+            machine: self,
+            contract_id,
+        }
+    }
+
+    fn execute(&mut self, event: DnaEvent) -> Result<DnaEvent, TransitionError> {
+        self.policies.check(&event)?;
+        self.apply(event.clone())?;
+        self.policies.record(&event);
+        Ok(event)
+    }
+
+    // ============ Transitions ============
+
+    /// Apply an event and append it to the log
+    ///
+    /// Wallet changes are made on copies and only committed once every step
+    /// of the transition has succeeded.
+    pub fn apply(&mut self, event: DnaEvent) -> Result<(), TransitionError> {
+        match &event {
+            DnaEvent::Minted {
+                did,
+                amount,
+                source,
+            } => {
+                let mut wallet = self.wallet_or_new(did);
+                wallet.credit_from(*amount, *source)?;
+                self.commit([wallet], [Posting::credit(did, *amount, "minted")])?;
+                self.minted += *amount;
+            }
+            DnaEvent::Burned { did, amount } => {
+                let mut wallet = self.load(did)?;
+                wallet.debit(*amount)?;
+                self.commit([wallet], [Posting::debit(did, *amount, "burned")])?;
+                self.burned += *amount;
+            }
+            DnaEvent::Forfeited { did, amount } => {
+                let mut wallet = self.load(did)?;
+                wallet.forfeit_locked(*amount)?;
+                self.commit([wallet], [Posting::forfeit(did, *amount, "slashed")])?;
+                self.burned += *amount;
+            }
+            DnaEvent::Adjusted {
+                memo,
+                source,
+                deltas,
+                ..
+            } => {
+                let net: Decimal = deltas.iter().map(HcDelta::net).sum();
+                if !net.is_zero() {
+                    return Err(TransitionError::Unbalanced(net));
+                }
+                let mut wallets: HashMap<&str, HcWallet> = HashMap::new();
+                for delta in deltas {
+                    let wallet = wallets
+                        .entry(&delta.did)
+                        .or_insert_with(|| self.wallet_or_new(&delta.did));
+                    adjust(wallet, delta, *source)?;
+                }
+                let legs = deltas
+                    .iter()
+                    .flat_map(|delta| {
+                        [
+                            Leg {
+                                account: Account::Available(delta.did.clone()),
+                                amount: delta.available,
+                            },
+                            Leg {
+                                account: Account::Locked(delta.did.clone()),
+                                amount: delta.locked,
+                            },
+                        ]
+                    })
+                    .filter(|leg| !leg.amount.is_zero())
+                    .collect();
+                self.commit(wallets.into_values(), [Posting::adjustment(legs, memo)])?;
+            }
+            DnaEvent::Spawned {
+                spawn_id,
+                parent_did,
+                child_did,
+                stake,
+                endowment,
+                ..
+            } => {
+                self.ensure_new(spawn_id)?;
+                let mut parent = self.load(parent_did)?;
+                let mut child = self.counterparty(parent_did, child_did)?;
+                lock(&mut parent, *stake)?;
+                pay(&mut parent, &mut child, *endowment, TrancheSource::Grant)?;
+                let reference = Reference::Other(spawn_id.clone());
+                self.commit(
+                    [parent, child],
+                    [
+                        Posting::lock(parent_did, *stake, "spawn stake"),
+                        Posting::transfer(parent_did, child_did, *endowment, "endowment"),
+                    ]
+                    .map(|posting| posting.with_reference(reference.clone())),
+                )?;
+                self.positions.insert(
+                    spawn_id.clone(),
+                    Position::Spawn {
+                        parent_did: parent_did.clone(),
+                        child_did: child_did.clone(),
+                        stake: *stake,
+                    },
+                );
+            }
+            DnaEvent::StakeReleased { spawn_id } => {
+                let Some(Position::Spawn {
+                    parent_did, stake, ..
+                }) = self.positions.get(spawn_id).cloned()
+                else {
+                    return Err(self.missing(spawn_id, "spawn"));
+                };
+                let mut parent = self.load(&parent_did)?;
+                release(&mut parent, stake)?;
+                self.commit(
+                    [parent],
+                    [Posting::release(&parent_did, stake, "stake released")
+                        .with_reference(Reference::Other(spawn_id.clone()))],
+                )?;
+                self.positions.remove(spawn_id);
+            }
+            DnaEvent::LoanIssued {
+                loan_id,
+                lender_did,
+                borrower_did,
+                principal,
+                collateral,
+                interest_rate,
+                term_days,
+            } => {
+                self.ensure_new(loan_id)?;
+                positive(*principal)?;
+                let mut lender = self.load(lender_did)?;
+                let mut borrower = self.counterparty(lender_did, borrower_did)?;
+                lock(&mut borrower, *collateral)?;
+                pay(&mut lender, &mut borrower, *principal, TrancheSource::Loan)?;
+                let reference = Reference::Loan(loan_id.clone());
+                self.commit(
+                    [lender, borrower],
+                    [
+                        Posting::lock(borrower_did, *collateral, "collateral"),
+                        Posting::transfer(lender_did, borrower_did, *principal, "principal"),
+                    ]
+                    .map(|posting| posting.with_reference(reference.clone())),
+                )?;
+                self.positions.insert(
+                    loan_id.clone(),
+                    Position::Loan {
+                        lender_did: lender_did.clone(),
+                        borrower_did: borrower_did.clone(),
+                        principal: *principal,
+                        collateral: *collateral,
+                        interest_rate: *interest_rate,
+                        term_days: *term_days,
+                    },
+                );
+            }
+            DnaEvent::LoanRepaid { loan_id, amount } => {
+                let Some(Position::Loan {
+                    lender_did,
+                    borrower_did,
+                    principal,
+                    collateral,
+                    ..
+                }) = self.positions.get(loan_id).cloned()
+                else {
+                    return Err(self.missing(loan_id, "loan"));
+                };
+                if *amount < principal {
+                    return Err(TransitionError::InvalidAmount(*amount));
+                }
+                let mut lender = self.load(&lender_did)?;
+                let mut borrower = self.load(&borrower_did)?;
+                release(&mut borrower, collateral)?;
+                pay(&mut borrower, &mut lender, *amount, TrancheSource::Earnings)?;
+                let reference = Reference::Loan(loan_id.clone());
+                self.commit(
+                    [lender, borrower],
+                    [
+                        Posting::release(&borrower_did, collateral, "collateral returned"),
+                        Posting::transfer(&borrower_did, &lender_did, *amount, "repayment"),
+                    ]
+                    .map(|posting| posting.with_reference(reference.clone())),
+                )?;
+                self.positions.remove(loan_id);
+            }
+            DnaEvent::LoanDefaulted { loan_id } => {
+                let Some(Position::Loan {
+                    lender_did,
+                    borrower_did,
+                    collateral,
+                    ..
+                }) = self.positions.get(loan_id).cloned()
+                else {
+                    return Err(self.missing(loan_id, "loan"));
+                };
+                let mut lender = self.load(&lender_did)?;
+                let mut borrower = self.load(&borrower_did)?;
+                seize(&mut borrower, &mut lender, collateral)?;
+                self.commit(
+                    [lender, borrower],
+                    [Posting::transfer_locked(
+                        &borrower_did,
+                        &lender_did,
+                        collateral,
+                        "collateral seized",
+                    )
+                    .with_reference(Reference::Loan(loan_id.clone()))],
+                )?;
+                self.positions.remove(loan_id);
+            }
+            DnaEvent::PolicyIssued {
+                policy_id,
+                insurer_did,
+                insured_did,
+                coverage,
+                premium,
+            } => {
+                self.ensure_new(policy_id)?;
+                positive(*coverage)?;
+                let mut insurer = self.load(insurer_did)?;
+                let mut insured = self.counterparty(insurer_did, insured_did)?;
+                pay(
+                    &mut insured,
+                    &mut insurer,
+                    *premium,
+                    TrancheSource::Earnings,
+                )?;
+                lock(&mut insurer, *coverage)?;
+                let reference = Reference::Policy(policy_id.clone());
+                self.commit(
+                    [insurer, insured],
+                    [
+                        Posting::transfer(insured_did, insurer_did, *premium, "premium"),
+                        Posting::lock(insurer_did, *coverage, "coverage"),
+                    ]
+                    .map(|posting| posting.with_reference(reference.clone())),
+                )?;
+                self.positions.insert(
+                    policy_id.clone(),
+                    Position::Policy {
+                        insurer_did: insurer_did.clone(),
+                        insured_did: insured_did.clone(),
+                        coverage: *coverage,
+                    },
+                );
+            }
+            DnaEvent::ClaimPaid { policy_id, amount } => {
+                let Some(Position::Policy {
+                    insurer_did,
+                    insured_did,
+                    coverage,
+                }) = self.positions.get(policy_id).cloned()
+                else {
+                    return Err(self.missing(policy_id, "policy"));
+                };
+                if *amount <= Decimal::ZERO || *amount > coverage {
+                    return Err(TransitionError::InvalidAmount(*amount));
+                }
+                let mut insurer = self.load(&insurer_did)?;
+                let mut insured = self.load(&insured_did)?;
+                seize(&mut insurer, &mut insured, *amount)?;
+                release(&mut insurer, coverage - *amount)?;
+                let reference = Reference::Policy(policy_id.clone());
+                self.commit(
+                    [insurer, insured],
+                    [
+                        Posting::transfer_locked(&insurer_did, &insured_did, *amount, "claim"),
+                        Posting::release(&insurer_did, coverage - *amount, "coverage released"),
+                    ]
+                    .map(|posting| posting.with_reference(reference.clone())),
+                )?;
+                self.positions.remove(policy_id);
+            }
+            DnaEvent::PolicyExpired { policy_id } => {
+                let Some(Position::Policy {
+                    insurer_did,
+                    coverage,
+                    ..
+                }) = self.positions.get(policy_id).cloned()
+                else {
+                    return Err(self.missing(policy_id, "policy"));
+                };
+                let mut insurer = self.load(&insurer_did)?;
+                release(&mut insurer, coverage)?;
+                self.commit(
+                    [insurer],
+                    [Posting::release(&insurer_did, coverage, "policy expired")
+                        .with_reference(Reference::Policy(policy_id.clone()))],
+                )?;
+                self.positions.remove(policy_id);
+            }
+            DnaEvent::EscrowLocked {
+                contract_id,
+                client_did,
+                agent_did,
+                amount,
+            } => {
+                self.ensure_new(contract_id)?;
+                let mut client = self.load(client_did)?;
+                let agent = self.counterparty(client_did, agent_did)?;
+                client.lock(*amount)?;
+                self.commit(
+                    [client, agent],
+                    [Posting::lock(client_did, *amount, "escrow")
+                        .with_reference(Reference::Escrow(contract_id.clone()))],
+                )?;
+                self.positions.insert(
+                    contract_id.clone(),
+                    Position::Escrow {
+                        client_did: client_did.clone(),
+                        agent_did: agent_did.clone(),
+                        locked: *amount,
+                    },
+                );
+            }
+            DnaEvent::EscrowReleased {
+                contract_id,
+                amount,
+            }
+            | DnaEvent::EscrowRefunded {
+                contract_id,
+                amount,
+            }
+            | DnaEvent::EscrowForfeited {
+                contract_id,
+                amount,
+            } => {
+                let Some(Position::Escrow {
+                    client_did,
+                    agent_did,
+                    locked,
+                }) = self.positions.get(contract_id).cloned()
+                else {
+                    return Err(self.missing(contract_id, "escrow"));
+                };
+                if *amount <= Decimal::ZERO || *amount > locked {
+                    return Err(TransitionError::InvalidAmount(*amount));
+                }
+                let mut client = self.load(&client_did)?;
+                let reference = Reference::Escrow(contract_id.clone());
+                match event {
+                    DnaEvent::EscrowReleased { .. } => {
+                        let mut agent = self.load(&agent_did)?;
+                        client.transfer_locked(*amount, &mut agent)?;
+                        let posting = Posting::transfer_locked(
+                            &client_did,
+                            &agent_did,
+                            *amount,
+                            "escrow released",
+                        );
+                        self.commit([client, agent], [posting.with_reference(reference)])?;
+                    }
+                    DnaEvent::EscrowRefunded { .. } => {
+                        client.release(*amount)?;
+                        let posting = Posting::release(&client_did, *amount, "escrow refunded");
+                        self.commit([client], [posting.with_reference(reference)])?;
+                    }
+                    _ => {
+                        client.forfeit_locked(*amount)?;
+                        let posting = Posting::forfeit(&client_did, *amount, "escrow forfeited");
+                        self.commit([client], [posting.with_reference(reference)])?;
+                        self.burned += *amount;
+                    }
+                }
+                let remaining = locked - *amount;
+                if remaining.is_zero() {
+                    self.positions.remove(contract_id);
+                } else if let Some(Position::Escrow { locked, .. }) =
+                    self.positions.get_mut(contract_id)
+                {
+                    *locked = remaining;
+                }
+            }
+        }
+
+        self.log.push(event);
+        Ok(())
+    }
+
+    fn load(&self, did: &str) -> Result<HcWallet, TransitionError> {
+        self.wallets
+            .get(did)
+            .cloned()
+            .ok_or_else(|| TransitionError::WalletNotFound(did.to_string()))
+    }
+
+    fn wallet_or_new(&self, did: &str) -> HcWallet {
+        self.wallets
+            .get(did)
+            .cloned()
+            .unwrap_or_else(|| HcWallet::new(did.to_string()))
+    }
+
+    /// Wallet of the second party, opened if needed
+    fn counterparty(&self, first: &str, did: &str) -> Result<HcWallet, TransitionError> {
+        if first == did {
+            return Err(TransitionError::SameParty(did.to_string()));
+        }
+        Ok(self.wallet_or_new(did))
+    }
+
+    fn ensure_new(&self, id: &str) -> Result<(), TransitionError> {
+        if self.positions.contains_key(id) {
+            return Err(TransitionError::DuplicatePosition(id.to_string()));
+        }
+        Ok(())
+    }
+
+    fn missing(&self, id: &str, expected: &'static str) -> TransitionError {
+        if self.positions.contains_key(id) {
+            TransitionError::WrongPosition {
+                id: id.to_string(),
+                expected,
+            }
+        } else {
+            TransitionError::PositionNotFound(id.to_string())
+        }
+    }
+
+    /// Post the transition's postings and store its wallets, or neither
+    ///
+    /// Zero-amount postings (a spawn without endowment, a loan without
+    /// collateral) move nothing and are left out of the journal.
+    fn commit<const N: usize>(
+        &mut self,
+        wallets: impl IntoIterator<Item = HcWallet>,
+        postings: [Posting; N],
+    ) -> Result<(), TransitionError> {
+        let wallets: Vec<HcWallet> = wallets.into_iter().collect();
+        let postings = postings
+            .into_iter()
+            .filter(|posting| posting.legs.iter().any(|leg| !leg.amount.is_zero()))
+            .collect();
+        self.journal.post_with_wallets(postings, &wallets)?;
+        for wallet in wallets {
+            self.wallets.insert(wallet.owner_did.clone(), wallet);
+        }
+        Ok(())
+    }
+}
+
+fn new_id() -> String {
+    uuid::Uuid::now_v7().to_string()
+}
+
+fn positive(amount: Decimal) -> Result<(), TransitionError> {
+    if amount <= Decimal::ZERO {
+        return Err(TransitionError::InvalidAmount(amount));
+    }
+    Ok(())
+}
+
+// Zero amounts are no-ops; negative ones are rejected by the wallet.
+
+fn pay(
+    from: &mut HcWallet,
+    to: &mut HcWallet,
+    amount: Decimal,
+    source: TrancheSource,
+) -> Result<(), WalletError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    from.debit(amount)?;
+    to.credit_from(amount, source)
+}
+
+fn lock(wallet: &mut HcWallet, amount: Decimal) -> Result<(), WalletError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    wallet.lock(amount)
+}
+
+fn release(wallet: &mut HcWallet, amount: Decimal) -> Result<(), WalletError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    wallet.release(amount)
+}
+
+fn seize(from: &mut HcWallet, to: &mut HcWallet, amount: Decimal) -> Result<(), WalletError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    from.transfer_locked(amount, to)
+}
+
+/// Escrow held by an `EscrowLocked` position of a [`DnaMachine`]
+pub struct MachineEscrow<'a> {
+    machine: &'a mut DnaMachine,
+    contract_id: &'a str,
+}
+
+impl EscrowLedger for MachineEscrow<'_> {
+    fn release(&mut self, amount: Decimal) -> Result<(), EscrowError> {
+        self.machine.release_escrow(self.contract_id, amount)?;
+        Ok(())
+    }
+
+    fn refund(&mut self, amount: Decimal) -> Result<(), EscrowError> {
+        self.machine.refund_escrow(self.contract_id, amount)?;
+        Ok(())
+    }
+
+    fn forfeit(&mut self, amount: Decimal) -> Result<Decimal, EscrowError> {
+        self.machine.forfeit_escrow(self.contract_id, amount)?;
+        Ok(amount)
+    }
+}
+
+/// Apply one wallet's side of an adjustment
+///
+/// A wallet that gains HC and locks some is credited first so the lock can
+/// draw on the credit; otherwise the lock or release comes first, so a
+/// debit can spend what was just released.
+fn adjust(
+    wallet: &mut HcWallet,
+    delta: &HcDelta,
+    source: TrancheSource,
+) -> Result<(), WalletError> {
+    let net = delta.net();
+    if delta.locked > Decimal::ZERO && net > Decimal::ZERO {
+        wallet.credit_from(net, source)?;
+        return wallet.lock(delta.locked);
+    }
+    if delta.locked > Decimal::ZERO {
+        wallet.lock(delta.locked)?;
+    } else {
+        release(wallet, -delta.locked)?;
+    }
+    if net > Decimal::ZERO {
+        wallet.credit_from(net, source)
+    } else if net < Decimal::ZERO {
+        wallet.debit(-net)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rust_decimal_macros::dec;
+
+    fn funded() -> DnaMachine {
+        let mut machine = DnaMachine::new();
+        machine.mint("did:key:a", dec!(1000)).unwrap();
+        machine.mint("did:key:b", dec!(1000)).unwrap();
+        machine
+    }
+
+    fn balances(machine: &DnaMachine) -> Vec<(String, Decimal, Decimal)> {
+        let mut balances: Vec<_> = machine
+            .wallets()
+            .map(|w| (w.owner_did.clone(), w.available, w.locked))
+            .collect();
+        balances.sort_by(|a, b| a.0.cmp(&b.0));
+        balances
+    }
+
+    fn position_id(event: &DnaEvent) -> String {
+        event
+            .opened_position()
+            .expect("event opens no position")
+            .to_string()
+    }
+
+    #[test]
+    fn test_lifecycle_moves_hc_between_wallets() {
+        let mut machine = funded();
+
+        let spawn = machine
+            .spawn("did:key:a", 0.8, "did:key:child", dec!(100), dec!(50))
+            .unwrap();
+        assert!(
+            matches!(spawn, DnaEvent::Spawned { inherited_tau, .. } if (inherited_tau - 0.24).abs() < 1e-12)
+        );
+        assert_eq!(machine.wallet("did:key:a").unwrap().locked, dec!(100));
+        assert_eq!(machine.wallet("did:key:child").unwrap().available, dec!(50));
+
+        let loan = machine
+            .lend("did:key:a", "did:key:b", 1.0, dec!(365), 0.2, 30)
+            .unwrap();
+        let loan_id = position_id(&loan);
+        // 5% at full trust for 30 days
+        assert_eq!(machine.amount_due(&loan_id).unwrap(), dec!(366.5));
+        assert_eq!(machine.wallet("did:key:b").unwrap().locked, dec!(73));
+        machine.repay(&loan_id).unwrap();
+        assert_eq!(machine.wallet("did:key:b").unwrap().total(), dec!(998.5));
+
+        let policy = machine
+            .insure("did:key:b", "did:key:child", 0.0, dec!(200))
+            .unwrap();
+        // 10% at zero trust
+        assert!(matches!(policy, DnaEvent::PolicyIssued { premium, .. } if premium == dec!(20)));
+        machine.claim(&position_id(&policy), dec!(150)).unwrap();
+        assert_eq!(
+            machine.wallet("did:key:child").unwrap().available,
+            dec!(180)
+        );
+        assert_eq!(machine.wallet("did:key:b").unwrap().locked, Decimal::ZERO);
+
+        let escrow = machine
+            .delegate("did:key:a", "did:key:b", dec!(60))
+            .unwrap();
+        let contract_id = position_id(&escrow);
+        machine.release_escrow(&contract_id, dec!(40)).unwrap();
+        machine.refund_escrow(&contract_id, dec!(20)).unwrap();
+        assert!(machine.position(&contract_id).is_none());
+        assert!(matches!(
+            machine.refund_escrow(&contract_id, dec!(1)),
+            Err(TransitionError::PositionNotFound(_))
+        ));
+
+        assert!(machine.is_conserved());
+        assert_eq!(machine.supply(), dec!(2000));
+
+        // The journal accounts for every wallet, and statements show each move
+        for wallet in machine.wallets() {
+            machine.journal().verify(wallet).unwrap();
+        }
+        let statement = machine.statement("did:key:child", 0, i64::MAX);
+        let reasons: Vec<&str> = statement.lines.iter().map(|l| l.reason.as_str()).collect();
+        assert_eq!(reasons, ["endowment", "premium", "claim"]);
+        assert_eq!(statement.closing_available, dec!(180));
+
+        let replayed =
+            DnaMachine::replay(DnaPricing::default(), machine.events().to_vec()).unwrap();
+        assert_eq!(balances(&replayed), balances(&machine));
+        assert_eq!(
+            replayed.journal().postings().len(),
+            machine.journal().postings().len()
+        );
+    }
+
+    #[test]
+    fn test_org_spending_policy() {
+        use crate::policy::{SpendingPolicies, SpendingPolicy};
+        use actoris_common::types::principal::{MemberRole, PrincipalGraph};
+        use actoris_common::UnifiedID;
+
+        let org = UnifiedID::new_organization("acme.example", [1u8; 32]);
+        let alice = UnifiedID::new_human_web("acme.example:users:alice", [2u8; 32]);
+        let (agent, _) = UnifiedID::new_agent(None);
+        let mut principals = PrincipalGraph::new();
+        for id in [&org, &alice, &agent] {
+            principals.register(id);
+        }
+        principals
+            .add_member(&org.did, &alice.did, MemberRole::Member)
+            .unwrap();
+        principals
+            .assign_owner(&agent.did, &alice.did, Some(&org.did))
+            .unwrap();
+
+        let policies = SpendingPolicies::new(principals).with_policy(
+            org.did.clone(),
+            SpendingPolicy::default()
+                .with_max_per_transition(dec!(100))
+                .with_budget(dec!(150)),
+        );
+        let mut machine = DnaMachine::new().with_spending_policies(policies);
+        machine.mint(&agent.did, dec!(1000)).unwrap();
+
+        assert!(matches!(
+            machine.delegate(&agent.did, "did:key:b", dec!(120)),
+            Err(TransitionError::OverTransitionLimit { .. })
+        ));
+        let escrow = machine
+            .delegate(&agent.did, "did:key:b", dec!(100))
+            .unwrap();
+        assert_eq!(machine.spending_policies().spent(&org.did), dec!(100));
+        assert!(matches!(
+            machine.delegate(&agent.did, "did:key:b", dec!(60)),
+            Err(TransitionError::OverBudget { remaining, .. }) if remaining == dec!(50)
+        ));
+
+        // Settling is never blocked, and a new period restores the budget
+        machine
+            .refund_escrow(&position_id(&escrow), dec!(100))
+            .unwrap();
+        machine.spending_policies_mut().reset_budget(&org.did);
+        machine.delegate(&agent.did, "did:key:b", dec!(60)).unwrap();
+
+        // Adjustments are held to the commitments they declare
+        let reserve = |amount| vec![Commitment::new(&agent.did, amount)];
+        assert!(matches!(
+            machine.adjust_committing(
+                "reserve",
+                TrancheSource::Earnings,
+                vec![],
+                reserve(dec!(101))
+            ),
+            Err(TransitionError::OverTransitionLimit { .. })
+        ));
+        machine
+            .adjust_committing(
+                "reserve",
+                TrancheSource::Earnings,
+                vec![],
+                reserve(dec!(40)),
+            )
+            .unwrap();
+        assert_eq!(machine.spending_policies().spent(&org.did), dec!(100));
+
+        // Unaffiliated wallets are unconstrained
+        machine.mint("did:key:b", dec!(500)).unwrap();
+        machine
+            .delegate("did:key:b", &agent.did, dec!(500))
+            .unwrap();
+        assert!(machine.is_conserved());
+    }
+
+    #[test]
+    fn test_adjustments_balance_and_apply_all_or_nothing() {
+        let mut machine = funded();
+        let a = "did:key:a";
+        let b = "did:key:b";
+
+        // Lock 100 on a, pay 50 of a's HC to b, credited as earnings
+        machine
+            .adjust(
+                "settle",
+                TrancheSource::Earnings,
+                vec![
+                    HcDelta::new(a, dec!(-150), dec!(100)),
+                    HcDelta::new(b, dec!(50), Decimal::ZERO),
+                ],
+            )
+            .unwrap();
+        assert_eq!(machine.wallet(a).unwrap().available, dec!(850));
+        assert_eq!(machine.wallet(a).unwrap().locked, dec!(100));
+        assert!(machine
+            .wallet(b)
+            .unwrap()
+            .tranches
+            .iter()
+            .any(|t| t.source == TrancheSource::Earnings && t.amount == dec!(50)));
+
+        assert!(matches!(
+            machine.adjust(
+                "mint",
+                TrancheSource::Grant,
+                vec![HcDelta::new(b, dec!(10), Decimal::ZERO)]
+            ),
+            Err(TransitionError::Unbalanced(net)) if net == dec!(10)
+        ));
+
+        // The second delta overdraws, so the first is not applied either
+        let before = balances(&machine);
+        assert!(machine
+            .adjust(
+                "overdraw",
+                TrancheSource::Earnings,
+                vec![
+                    HcDelta::new(a, dec!(100), dec!(-100)),
+                    HcDelta::new(b, dec!(-5000), Decimal::ZERO),
+                    HcDelta::new(a, dec!(4900), Decimal::ZERO),
+                ],
+            )
+            .is_err());
+        assert_eq!(balances(&machine), before);
+
+        // Forfeiting burns locked HC
+        machine.forfeit(a, dec!(40)).unwrap();
+        assert_eq!(machine.wallet(a).unwrap().locked, dec!(60));
+        assert_eq!(machine.supply(), dec!(1960));
+        assert!(machine.is_conserved());
+
+        // Adjustments are journaled as one posting across both wallets
+        let settle = machine.statement(b, 0, i64::MAX).lines.pop().unwrap();
+        assert_eq!(settle.reason, "settle");
+        assert_eq!(settle.available_change, dec!(50));
+        assert_eq!(machine.journal().wallet_balances(a), (dec!(850), dec!(60)));
+
+        let replayed =
+            DnaMachine::replay(DnaPricing::default(), machine.events().to_vec()).unwrap();
+        assert_eq!(balances(&replayed), balances(&machine));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Mint(usize, u32),
+        Burn(usize, u32),
+        Spawn(usize, usize, u32, u32),
+        ReleaseStake(usize),
+        Lend(usize, usize, u32, u8),
+        Repay(usize),
+        Default(usize),
+        Insure(usize, usize, u32, u8),
+        Claim(usize, u32),
+        Expire(usize),
+        Delegate(usize, usize, u32),
+        Release(usize, u32),
+        Refund(usize, u32),
+    }
+
+    const DIDS: [&str; 4] = ["did:key:a", "did:key:b", "did:key:c", "did:key:d"];
+
+    fn op() -> impl Strategy<Value = Op> {
+        let did = 0..DIDS.len();
+        let amount = 0u32..600;
+        let pick = 0usize..8;
+        prop_oneof![
+            (did.clone(), amount.clone())
+                .prop_map(|(d, a)| Op::Mint(d, a))
+                .boxed(),
+            (did.clone(), amount.clone())
+                .prop_map(|(d, a)| Op::Burn(d, a))
+                .boxed(),
+            (did.clone(), did.clone(), amount.clone(), amount.clone())
+                .prop_map(|(p, c, s, e)| Op::Spawn(p, c, s, e))
+                .boxed(),
+            pick.clone().prop_map(Op::ReleaseStake).boxed(),
+            (did.clone(), did.clone(), amount.clone(), 0u8..=100)
+                .prop_map(|(l, b, a, t)| Op::Lend(l, b, a, t))
+                .boxed(),
+            pick.clone().prop_map(Op::Repay).boxed(),
+            pick.clone().prop_map(Op::Default).boxed(),
+            (did.clone(), did.clone(), amount.clone(), 0u8..=100)
+                .prop_map(|(i, d, a, t)| Op::Insure(i, d, a, t))
+                .boxed(),
+            (pick.clone(), amount.clone())
+                .prop_map(|(p, a)| Op::Claim(p, a))
+                .boxed(),
+            pick.clone().prop_map(Op::Expire).boxed(),
+            (did.clone(), did, amount.clone())
+                .prop_map(|(c, a, m)| Op::Delegate(c, a, m))
+                .boxed(),
+            (pick.clone(), amount.clone())
+                .prop_map(|(p, a)| Op::Release(p, a))
+                .boxed(),
+            (pick, amount).prop_map(|(p, a)| Op::Refund(p, a)).boxed(),
+        ]
+    }
+
+    fn run(machine: &mut DnaMachine, opened: &mut Vec<String>, op: Op) {
+        let hc = |amount: u32| Decimal::from(amount);
+        let tau = |t: u8| t as f64 / 100.0;
+        let id = |opened: &Vec<String>, i: usize| {
+            opened
+                .get(i % opened.len().max(1))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let result = match op {
+            Op::Mint(d, a) => machine.mint(DIDS[d], hc(a)),
+            Op::Burn(d, a) => machine.burn(DIDS[d], hc(a)),
+            Op::Spawn(p, c, s, e) => machine.spawn(DIDS[p], 0.5, DIDS[c], hc(s), hc(e)),
+            Op::ReleaseStake(i) => machine.release_stake(&id(opened, i)),
+            Op::Lend(l, b, a, t) => machine.lend(DIDS[l], DIDS[b], tau(t), hc(a), 0.25, 30),
+            Op::Repay(i) => machine.repay(&id(opened, i)),
+            Op::Default(i) => machine.default_loan(&id(opened, i)),
+            Op::Insure(i, d, a, t) => machine.insure(DIDS[i], DIDS[d], tau(t), hc(a)),
+            Op::Claim(i, a) => machine.claim(&id(opened, i), hc(a)),
+            Op::Expire(i) => machine.expire_policy(&id(opened, i)),
+            Op::Delegate(c, a, m) => machine.delegate(DIDS[c], DIDS[a], hc(m)),
+            Op::Release(i, a) => machine.release_escrow(&id(opened, i), hc(a)),
+            Op::Refund(i, a) => machine.refund_escrow(&id(opened, i), hc(a)),
+        };
+        if let Some(id) = result.as_ref().ok().and_then(DnaEvent::opened_position) {
+            opened.push(id.to_string());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_hc_is_conserved(ops in prop::collection::vec(op(), 1..80)) {
+            let mut machine = funded();
+            let mut opened = Vec::new();
+
+            for op in ops {
+                let before = balances(&machine);
+                let logged = machine.events().len();
+                run(&mut machine, &mut opened, op);
+
+                prop_assert!(machine.is_conserved());
+                prop_assert!(machine.wallets().all(|w| w.available >= Decimal::ZERO && w.locked >= Decimal::ZERO));
+                prop_assert!(machine.wallets().all(|w| machine.journal().verify(w).is_ok()));
+                if machine.events().len() == logged {
+                    // Rejected transitions leave no trace
+                    prop_assert_eq!(balances(&machine), before);
+                }
+            }
+
+            // Locked HC is exactly what open positions hold
+            let locked: Decimal = machine.wallets().map(|w| w.locked).sum();
+            let held: Decimal = machine.positions().map(|(_, p)| p.locked()).sum();
+            prop_assert_eq!(locked, held);
+
+            let replayed = DnaMachine::replay(DnaPricing::default(), machine.events().to_vec()).unwrap();
+            prop_assert_eq!(balances(&replayed), balances(&machine));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Organization spending policies
+//!
+//! Agents spend on behalf of whoever is accountable for them (see
+//! [`PrincipalGraph`]). An organization can cap what its agents and members
+//! commit through Protocol DNA: per transition and in total until the budget
+//! is reset.
+//!
+//! Only transitions that open a position commit HC: the spawn stake and
+//! endowment, loan principal, insurance premium and coverage, escrow, and
+//! the commitments declared by an adjustment.
+//! Repayments, releases and refunds settle existing obligations and are
+//! never blocked.
+
+use std::collections::HashMap;
+
+use actoris_common::types::principal::PrincipalGraph;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::machine::{DnaEvent, TransitionError};
+
+/// Limits on HC committed by an organization's agents and members
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpendingPolicy {
+    /// Largest commitment in a single transition
+    pub max_per_transition: Option<Decimal>,
+    /// Total commitments allowed until the budget is reset
+    pub budget: Option<Decimal>,
+}
+
+impl SpendingPolicy {
+    pub fn with_max_per_transition(mut self, max: Decimal) -> Self {
+        self.max_per_transition = Some(max);
+        self
+    }
+
+    pub fn with_budget(mut self, budget: Decimal) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+}
+
+/// Spending policies enforced on [`crate::DnaMachine`] commands
+#[derive(Debug, Clone, Default)]
+pub struct SpendingPolicies {
+    principals: PrincipalGraph,
+    policies: HashMap<String, SpendingPolicy>,
+    spent: HashMap<String, Decimal>,
+}
+
+impl SpendingPolicies {
+    pub fn new(principals: PrincipalGraph) -> Self {
+        Self {
+            principals,
+            ..Default::default()
+        }
+    }
+
+    /// Set an organization's policy
+    pub fn with_policy(mut self, org_did: impl Into<String>, policy: SpendingPolicy) -> Self {
+        self.policies.insert(org_did.into(), policy);
+        self
+    }
+
+    pub fn principals(&self) -> &PrincipalGraph {
+        &self.principals
+    }
+
+    pub fn principals_mut(&mut self) -> &mut PrincipalGraph {
+        &mut self.principals
+    }
+
+    /// HC committed against an organization's budget
+    pub fn spent(&self, org_did: &str) -> Decimal {
+        self.spent.get(org_did).copied().unwrap_or_default()
+    }
+
+    /// Start a new budget period
+    pub fn reset_budget(&mut self, org_did: &str) {
+        self.spent.remove(org_did);
+    }
+
+    /// Check an event against the policies of the organizations paying for it
+    pub fn check(&self, event: &DnaEvent) -> Result<(), TransitionError> {
+        let mut per_org: HashMap<String, Decimal> = HashMap::new();
+        for (did, amount) in commitments(event) {
+            let Some((org, policy)) = self.policy_for(did) else {
+                continue;
+            };
+            if let Some(max) = policy.max_per_transition {
+                if amount > max {
+                    return Err(TransitionError::OverTransitionLimit {
+                        org: org.to_string(),
+                        amount,
+                        limit: max,
+                    });
+                }
+            }
+            *per_org.entry(org.to_string()).or_default() += amount;
+        }
+
+        for (org, amount) in per_org {
+            let Some(budget) = self.policies.get(&org).and_then(|p| p.budget) else {
+                continue;
+            };
+            let remaining = budget - self.spent(&org);
+            if amount > remaining {
+                return Err(TransitionError::OverBudget {
+                    org,
+                    amount,
+                    remaining,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Count an applied event against budgets
+    pub fn record(&mut self, event: &DnaEvent) {
+        for (did, amount) in commitments(event) {
+            if let Some((org, _)) = self.policy_for(did) {
+                let org = org.to_string();
+                *self.spent.entry(org).or_default() += amount;
+            }
+        }
+    }
+
+    /// Organization accountable for `did` and its policy, if it has one
+    fn policy_for(&self, did: &str) -> Option<(&str, &SpendingPolicy)> {
+        let org = self.principals.accountable_for(did).ok()?.org_did?;
+        self.policies
+            .get_key_value(&org)
+            .map(|(org, policy)| (org.as_str(), policy))
+    }
+}
+
+/// HC each party commits in a position-opening event
+fn commitments(event: &DnaEvent) -> Vec<(&str, Decimal)> {
+    match event {
+        DnaEvent::Spawned {
+            parent_did,
+            stake,
+            endowment,
+            ..
+        } => vec![(parent_did, *stake + *endowment)],
+        DnaEvent::LoanIssued {
+            lender_did,
+            principal,
+            ..
+        } => vec![(lender_did, *principal)],
+        DnaEvent::PolicyIssued {
+            insurer_did,
+            insured_did,
+            coverage,
+            premium,
+            ..
+        } => vec![(insurer_did, *coverage), (insured_did, *premium)],
+        DnaEvent::EscrowLocked {
+            client_did, amount, ..
+        } => vec![(client_did, *amount)],
+        DnaEvent::Adjusted { commitments, .. } => commitments
+            .iter()
+            .map(|c| (c.did.as_str(), c.amount))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
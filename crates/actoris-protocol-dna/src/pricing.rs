@@ -0,0 +1,182 @@
+//! Canonical Protocol DNA pricing
+//!
+//! One set of formulas for every place that prices a primitive: the
+//! [`DnaMachine`](crate::machine::DnaMachine), the trust ledger's
+//! `ProtocolDna` and the API gateway. Loan rates and insurance premiums both
+//! scale a base rate by the [`risk_factor`], which is 1× at full trust and
+//! 2× at zero trust.
+
+use actoris_common::error::PricingError;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::spawn::SpawnPrimitive;
+use crate::wasm::HC_SCALE;
+
+/// Annual interest rate charged at full trust
+pub const DEFAULT_BASE_INTEREST_RATE: f64 = 0.05;
+
+/// Failure probability priced into premiums at full trust
+pub const DEFAULT_BASE_FAILURE_PROBABILITY: f64 = 0.05;
+
+/// Price multiplier for a trust level: 1.0 at τ = 1, 2.0 at τ = 0
+#[inline]
+pub fn risk_factor(tau: f64) -> f64 {
+    2.0 - tau.clamp(0.0, 1.0)
+}
+
+/// Pricing parameters for the Protocol DNA primitives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DnaPricing {
+    /// Annual loan rate at full trust
+    pub base_interest_rate: f64,
+    /// Failure probability charged on coverage at full trust
+    pub base_failure_probability: f64,
+    /// Share of the parent's trust a spawned child inherits
+    pub inherited_trust_pct: f64,
+}
+
+impl Default for DnaPricing {
+    fn default() -> Self {
+        Self {
+            base_interest_rate: DEFAULT_BASE_INTEREST_RATE,
+            base_failure_probability: DEFAULT_BASE_FAILURE_PROBABILITY,
+            inherited_trust_pct: SpawnPrimitive::TRUST_CAP,
+        }
+    }
+}
+
+impl DnaPricing {
+    /// Annual interest rate for a borrower
+    pub fn interest_rate(&self, borrower_tau: f64) -> f64 {
+        self.base_interest_rate * risk_factor(borrower_tau)
+    }
+
+    /// Interest accrued on `principal` over `term_days`, rounded to HC
+    /// precision
+    pub fn interest(
+        &self,
+        principal: Decimal,
+        rate: f64,
+        term_days: u32,
+    ) -> Result<Decimal, PricingError> {
+        let rate = decimal_rate(rate)?;
+        let interest = principal
+            .checked_mul(rate)
+            .and_then(|i| i.checked_mul(Decimal::from(term_days)))
+            .ok_or(PricingError::Overflow)?;
+        Ok((interest / Decimal::from(365)).round_dp(HC_SCALE))
+    }
+
+    /// Collateral a borrower posts against `principal`, rounded to HC
+    /// precision
+    pub fn collateral(
+        &self,
+        principal: Decimal,
+        collateral_pct: f64,
+    ) -> Result<Decimal, PricingError> {
+        let collateral = principal
+            .checked_mul(decimal_rate(collateral_pct)?)
+            .ok_or(PricingError::Overflow)?;
+        Ok(collateral.round_dp(HC_SCALE))
+    }
+
+    /// Premium rate on coverage for an insured agent
+    pub fn premium_rate(&self, insured_tau: f64) -> f64 {
+        self.base_failure_probability * risk_factor(insured_tau)
+    }
+
+    /// Premium for `coverage`, rounded to HC precision
+    pub fn premium(&self, coverage: Decimal, insured_tau: f64) -> Result<Decimal, PricingError> {
+        self.premium_at(coverage, self.premium_rate(insured_tau))
+    }
+
+    /// Premium for `coverage` at an explicit rate, which must lie in `[0, 1]`
+    pub fn premium_at(&self, coverage: Decimal, rate: f64) -> Result<Decimal, PricingError> {
+        if rate > 1.0 {
+            return Err(PricingError::InvalidRate(rate));
+        }
+        let premium = coverage
+            .checked_mul(decimal_rate(rate)?)
+            .ok_or(PricingError::Overflow)?;
+        Ok(premium.round_dp(HC_SCALE))
+    }
+
+    /// Trust a child inherits from its parent at spawn
+    pub fn inherited_tau(&self, parent_tau: f64) -> f64 {
+        parent_tau.clamp(0.0, 1.0) * self.inherited_trust_pct
+    }
+}
+
+/// A configured rate as a decimal; negative and non-finite rates are
+/// rejected rather than priced as zero
+pub(crate) fn decimal_rate(rate: f64) -> Result<Decimal, PricingError> {
+    if rate.is_nan() || rate < 0.0 {
+        return Err(PricingError::InvalidRate(rate));
+    }
+    Decimal::try_from(rate).map_err(|_| PricingError::InvalidRate(rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InsurePrimitive, LendPrimitive};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_primitives_agree_with_pricing() {
+        let pricing = DnaPricing::default();
+
+        assert_eq!(pricing.interest_rate(1.0), 0.05);
+        assert_eq!(pricing.interest_rate(0.0), 0.10);
+        assert_eq!(
+            pricing.interest_rate(0.7),
+            LendPrimitive::calculate_interest_rate(pricing.base_interest_rate, 700)
+        );
+        assert_eq!(
+            pricing.premium(dec!(1000), 0.4).unwrap(),
+            InsurePrimitive::calculate_premium(dec!(1000), 400, pricing.base_failure_probability)
+        );
+        assert_eq!(pricing.interest(dec!(365), 0.1, 30).unwrap(), dec!(3));
+        assert!((pricing.inherited_tau(0.8) - 0.24).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unpriceable_rates_are_errors() {
+        let pricing = DnaPricing {
+            base_failure_probability: f64::NAN,
+            ..Default::default()
+        };
+        assert!(matches!(
+            pricing.premium(dec!(1000), 0.5),
+            Err(PricingError::InvalidRate(_))
+        ));
+        assert_eq!(
+            pricing.interest(dec!(100), -0.05, 30),
+            Err(PricingError::InvalidRate(-0.05))
+        );
+        assert_eq!(
+            pricing.interest(Decimal::MAX, 0.5, 365),
+            Err(PricingError::Overflow)
+        );
+        assert!(matches!(
+            DnaPricing::default().collateral(dec!(100), f64::NAN),
+            Err(PricingError::InvalidRate(_))
+        ));
+        assert_eq!(
+            DnaPricing::default().collateral(dec!(100), 0.2),
+            Ok(dec!(20))
+        );
+        for rate in [f64::INFINITY, f64::NEG_INFINITY, 1.5] {
+            assert_eq!(
+                DnaPricing::default().premium_at(dec!(100), rate),
+                Err(PricingError::InvalidRate(rate))
+            );
+        }
+        assert_eq!(
+            DnaPricing::default().premium_at(dec!(100), 0.1),
+            Ok(dec!(10))
+        );
+    }
+}
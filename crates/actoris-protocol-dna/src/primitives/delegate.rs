@@ -1,7 +1,23 @@
 //! Delegate primitive - Escrow + verification
+//!
+//! Contracts can be split into ordered milestones, each with its own escrow
+//! portion, deadline and acceptance criteria. A milestone is released to the
+//! agent against a verified [`OutcomeRecord`] that carries the oracle
+//! committee's signature and proves its inclusion in the outcome ledger (see
+//! [`OutcomeAnchor`]); cancelling refunds whatever has not been released, and
+//! a dispute freezes the remaining escrow until a ruling settles it.
+//!
+//! Escrow is held by an [`EscrowLedger`]: the parties' wallets directly, or
+//! the [`crate::DnaMachine`] position opened by `delegate` (see
+//! [`crate::machine::MachineEscrow`]).
 
+use actoris_common::types::hc_wallet::{HcWallet, WalletError};
+use actoris_common::types::outcome_record::OutcomeRecord;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::machine::TransitionError;
 
 /// Task delegation with escrow
 pub struct DelegatePrimitive;
@@ -16,6 +32,9 @@ pub struct DelegationContract {
     pub escrow_amount: Decimal,
     pub deadline: i64,
     pub status: DelegationStatus,
+    /// Ordered milestones (empty = single all-or-nothing escrow)
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -27,6 +46,196 @@ pub enum DelegationStatus {
     Cancelled,
 }
 
+/// Milestone as requested by the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MilestoneSpec {
+    pub description: String,
+    /// Escrow released when the milestone is accepted
+    pub escrow_amount: Decimal,
+    /// Latest submission time of the delivering action (Unix ms)
+    pub deadline: i64,
+    #[serde(default)]
+    pub acceptance: AcceptanceCriteria,
+}
+
+/// Conditions the delivering outcome must meet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AcceptanceCriteria {
+    /// Required action type
+    pub action_type: Option<String>,
+    /// Required output hash (pre-agreed deliverable)
+    pub output_hash: Option<[u8; 32]>,
+    /// Verification must have met the latency SLA
+    pub require_latency_sla: bool,
+}
+
+impl AcceptanceCriteria {
+    /// Check an outcome against the criteria
+    pub fn check(&self, record: &OutcomeRecord) -> Result<(), String> {
+        if let Some(action_type) = &self.action_type {
+            if &record.action_type != action_type {
+                return Err(format!("expected action type {}", action_type));
+            }
+        }
+        if let Some(hash) = self.output_hash {
+            if record.output_hash != hash {
+                return Err("output hash does not match the deliverable".to_string());
+            }
+        }
+        if self.require_latency_sla && !record.verification.met_latency_sla() {
+            return Err("verification missed the latency SLA".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Oracle committee and outcome ledger an outcome must chain to before it
+/// releases escrow
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeAnchor {
+    /// FROST group key of the oracle committee
+    pub group_key: [u8; 32],
+    /// Committed roots of the outcome Merkle tree
+    pub roots: Vec<[u8; 32]>,
+}
+
+impl OutcomeAnchor {
+    pub fn new(group_key: [u8; 32]) -> Self {
+        Self {
+            group_key,
+            roots: Vec::new(),
+        }
+    }
+
+    pub fn with_root(mut self, root: [u8; 32]) -> Self {
+        self.roots.push(root);
+        self
+    }
+
+    /// Check the record is in the ledger and, if a quorum checked it, that
+    /// the committee signed its verdict
+    ///
+    /// Optimistic and unsampled records carry no quorum signature; ledger
+    /// inclusion is what vouches for them.
+    pub fn check(&self, record: &OutcomeRecord) -> Result<(), String> {
+        if !self.roots.contains(&record.merkle_root) || !record.verify_merkle_proof() {
+            return Err("outcome is not included in the outcome ledger".to_string());
+        }
+        if record.verification_mode.quorum_checked()
+            && (record.signature.group_key != self.group_key || !record.verify_signature())
+        {
+            return Err("oracle signature does not verify".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Where a contract's escrow is held
+pub trait EscrowLedger {
+    /// Pay locked escrow to the agent
+    fn release(&mut self, amount: Decimal) -> Result<(), EscrowError>;
+
+    /// Return locked escrow to the client
+    fn refund(&mut self, amount: Decimal) -> Result<(), EscrowError>;
+
+    /// Burn locked escrow, returning the amount burned
+    fn forfeit(&mut self, amount: Decimal) -> Result<Decimal, EscrowError>;
+}
+
+/// Escrow locked in the client's wallet
+pub struct WalletEscrow<'a> {
+    client: &'a mut HcWallet,
+    agent: Option<&'a mut HcWallet>,
+}
+
+impl EscrowLedger for WalletEscrow<'_> {
+    fn release(&mut self, amount: Decimal) -> Result<(), EscrowError> {
+        let agent = self.agent.as_deref_mut().ok_or(EscrowError::MissingAgent)?;
+        Ok(self.client.transfer_locked(amount, agent)?)
+    }
+
+    fn refund(&mut self, amount: Decimal) -> Result<(), EscrowError> {
+        Ok(self.client.release(amount)?)
+    }
+
+    fn forfeit(&mut self, amount: Decimal) -> Result<Decimal, EscrowError> {
+        Ok(self.client.forfeit_locked(amount)?)
+    }
+}
+
+/// Escrowed milestone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub index: usize,
+    pub description: String,
+    pub escrow_amount: Decimal,
+    pub deadline: i64,
+    pub acceptance: AcceptanceCriteria,
+    pub status: MilestoneStatus,
+    /// Outcome record the milestone was released against
+    pub outcome_id: Option<String>,
+    pub settled_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    /// Escrow locked, awaiting delivery
+    Pending,
+    /// Escrow paid to the agent
+    Released,
+    /// Escrow returned to the client
+    Refunded,
+    /// Escrow frozen by a dispute
+    Frozen,
+    /// Escrow distributed by a dispute ruling
+    Settled,
+}
+
+/// How a dispute over the frozen escrow is settled
+///
+/// `to_agent` is paid to the agent and `forfeited` is removed from escrow for
+/// the caller to redistribute; the rest is refunded to the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisputeRuling {
+    pub to_agent: Decimal,
+    pub forfeited: Decimal,
+}
+
+/// Escrow errors
+#[derive(Debug, Error)]
+pub enum EscrowError {
+    #[error("Invalid milestones: {0}")]
+    InvalidMilestones(String),
+
+    #[error("Contract is {0:?}")]
+    NotActive(DelegationStatus),
+
+    #[error("Wallet of {0} does not belong to a contract party")]
+    WalletMismatch(String),
+
+    #[error("Releasing escrow needs the agent's wallet")]
+    MissingAgent,
+
+    #[error("No pending milestone")]
+    NoPendingMilestone,
+
+    #[error("Milestone {index} deadline passed")]
+    DeadlineMissed { index: usize },
+
+    #[error("Outcome rejected: {0}")]
+    OutcomeRejected(String),
+
+    #[error("Ruling distributes {requested} HC but only {frozen} HC is frozen")]
+    InvalidRuling { requested: Decimal, frozen: Decimal },
+
+    #[error(transparent)]
+    Wallet(#[from] WalletError),
+
+    #[error(transparent)]
+    Ledger(#[from] TransitionError),
+}
+
 impl DelegatePrimitive {
     /// Create a new delegation (locks escrow)
     pub fn create_delegation(
@@ -44,6 +253,693 @@ impl DelegatePrimitive {
             escrow_amount,
             deadline,
             status: DelegationStatus::Pending,
+            milestones: Vec::new(),
+        }
+    }
+
+    /// Create a milestone contract, locking the full escrow in the client's
+    /// wallet
+    ///
+    /// Milestone deadlines must not decrease; the contract deadline is the
+    /// last one.
+    pub fn create_milestone_contract(
+        client_wallet: &mut HcWallet,
+        agent_did: String,
+        task_description: String,
+        milestones: Vec<MilestoneSpec>,
+    ) -> Result<DelegationContract, EscrowError> {
+        let contract = Self::open_milestone_contract(
+            uuid::Uuid::now_v7().to_string(),
+            client_wallet.owner_did.clone(),
+            agent_did,
+            task_description,
+            milestones,
+        )?;
+        client_wallet.lock(contract.escrow_amount)?;
+        Ok(contract)
+    }
+
+    /// Create a milestone contract over escrow already locked elsewhere,
+    /// such as the `EscrowLocked` position `id` of a [`crate::DnaMachine`]
+    pub fn open_milestone_contract(
+        id: String,
+        client_did: String,
+        agent_did: String,
+        task_description: String,
+        milestones: Vec<MilestoneSpec>,
+    ) -> Result<DelegationContract, EscrowError> {
+        if milestones.is_empty() {
+            return Err(EscrowError::InvalidMilestones("no milestones".into()));
         }
+        if milestones.iter().any(|m| m.escrow_amount <= Decimal::ZERO) {
+            return Err(EscrowError::InvalidMilestones(
+                "escrow portions must be positive".into(),
+            ));
+        }
+        if milestones.windows(2).any(|w| w[1].deadline < w[0].deadline) {
+            return Err(EscrowError::InvalidMilestones(
+                "deadlines must follow milestone order".into(),
+            ));
+        }
+
+        let escrow_amount: Decimal = milestones.iter().map(|m| m.escrow_amount).sum();
+
+        let deadline = milestones.last().map(|m| m.deadline).unwrap_or_default();
+        let milestones = milestones
+            .into_iter()
+            .enumerate()
+            .map(|(index, spec)| Milestone {
+                index,
+                description: spec.description,
+                escrow_amount: spec.escrow_amount,
+                deadline: spec.deadline,
+                acceptance: spec.acceptance,
+                status: MilestoneStatus::Pending,
+                outcome_id: None,
+                settled_at: None,
+            })
+            .collect();
+
+        Ok(DelegationContract {
+            id,
+            client_did,
+            agent_did,
+            task_description,
+            escrow_amount,
+            deadline,
+            status: DelegationStatus::Active,
+            milestones,
+        })
+    }
+}
+
+impl DelegationContract {
+    /// First milestone still awaiting delivery
+    pub fn next_milestone(&self) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .find(|m| m.status == MilestoneStatus::Pending)
+    }
+
+    /// Escrow paid to the agent so far
+    pub fn released_amount(&self) -> Decimal {
+        self.total_in(MilestoneStatus::Released)
+    }
+
+    /// Escrow still locked in the client's wallet
+    pub fn locked_amount(&self) -> Decimal {
+        self.total_in(MilestoneStatus::Pending) + self.total_in(MilestoneStatus::Frozen)
+    }
+
+    fn total_in(&self, status: MilestoneStatus) -> Decimal {
+        self.milestones
+            .iter()
+            .filter(|m| m.status == status)
+            .map(|m| m.escrow_amount)
+            .sum()
+    }
+
+    /// Release the next milestone against the outcome that delivers it
+    ///
+    /// The outcome must chain to `anchor`, be verified and past any challenge
+    /// window, performed by the agent for the client, submitted by the
+    /// milestone deadline and meet its acceptance criteria. Returns the
+    /// amount released.
+    pub fn release_milestone(
+        &mut self,
+        record: &OutcomeRecord,
+        anchor: &OutcomeAnchor,
+        now: i64,
+        client: &mut HcWallet,
+        agent: &mut HcWallet,
+    ) -> Result<Decimal, EscrowError> {
+        let mut escrow = self.wallet_escrow(client, Some(agent))?;
+        self.release_milestone_in(record, anchor, now, &mut escrow)
+    }
+
+    /// [`Self::release_milestone`] against escrow held by `escrow`
+    pub fn release_milestone_in(
+        &mut self,
+        record: &OutcomeRecord,
+        anchor: &OutcomeAnchor,
+        now: i64,
+        escrow: &mut impl EscrowLedger,
+    ) -> Result<Decimal, EscrowError> {
+        self.ensure_active()?;
+
+        let outcome_id = record.id.to_string();
+        if self
+            .milestones
+            .iter()
+            .any(|m| m.outcome_id.as_deref() == Some(outcome_id.as_str()))
+        {
+            return Err(EscrowError::OutcomeRejected(
+                "outcome already released a milestone".into(),
+            ));
+        }
+        if record.actor_did != self.agent_did || record.client_did != self.client_did {
+            return Err(EscrowError::OutcomeRejected(
+                "outcome is not between the contract parties".into(),
+            ));
+        }
+        anchor.check(record).map_err(EscrowError::OutcomeRejected)?;
+        if !record.is_verified() || record.verification_mode.challengeable_at(now) {
+            return Err(EscrowError::OutcomeRejected(
+                "outcome is not finally verified".into(),
+            ));
+        }
+
+        let milestone = self
+            .milestones
+            .iter_mut()
+            .find(|m| m.status == MilestoneStatus::Pending)
+            .ok_or(EscrowError::NoPendingMilestone)?;
+        if record.submitted_at > milestone.deadline {
+            return Err(EscrowError::DeadlineMissed {
+                index: milestone.index,
+            });
+        }
+        milestone
+            .acceptance
+            .check(record)
+            .map_err(EscrowError::OutcomeRejected)?;
+
+        escrow.release(milestone.escrow_amount)?;
+        milestone.status = MilestoneStatus::Released;
+        milestone.outcome_id = Some(outcome_id);
+        milestone.settled_at = Some(now);
+        let released = milestone.escrow_amount;
+
+        if self.next_milestone().is_none() {
+            self.status = DelegationStatus::Completed;
+        }
+        Ok(released)
+    }
+
+    /// Release every pending milestone on the client's acceptance of the
+    /// work, without an outcome record; returns the amount released
+    pub fn accept(
+        &mut self,
+        now: i64,
+        client: &mut HcWallet,
+        agent: &mut HcWallet,
+    ) -> Result<Decimal, EscrowError> {
+        let mut escrow = self.wallet_escrow(client, Some(agent))?;
+        self.accept_in(now, &mut escrow)
+    }
+
+    /// [`Self::accept`] against escrow held by `escrow`
+    pub fn accept_in(
+        &mut self,
+        now: i64,
+        escrow: &mut impl EscrowLedger,
+    ) -> Result<Decimal, EscrowError> {
+        self.ensure_active()?;
+
+        let released = self.total_in(MilestoneStatus::Pending);
+        if released > Decimal::ZERO {
+            escrow.release(released)?;
+        }
+        for milestone in self
+            .milestones
+            .iter_mut()
+            .filter(|m| m.status == MilestoneStatus::Pending)
+        {
+            milestone.status = MilestoneStatus::Released;
+            milestone.settled_at = Some(now);
+        }
+        self.status = DelegationStatus::Completed;
+        Ok(released)
+    }
+
+    /// Cancel the contract, refunding every unreleased milestone
+    ///
+    /// Milestones already released stay with the agent. Returns the amount
+    /// refunded.
+    pub fn cancel(&mut self, now: i64, client: &mut HcWallet) -> Result<Decimal, EscrowError> {
+        let mut escrow = self.wallet_escrow(client, None)?;
+        self.cancel_in(now, &mut escrow)
+    }
+
+    /// [`Self::cancel`] against escrow held by `escrow`
+    pub fn cancel_in(
+        &mut self,
+        now: i64,
+        escrow: &mut impl EscrowLedger,
+    ) -> Result<Decimal, EscrowError> {
+        self.ensure_active()?;
+
+        let refund = self.total_in(MilestoneStatus::Pending);
+        if refund > Decimal::ZERO {
+            escrow.refund(refund)?;
+        }
+        for milestone in self
+            .milestones
+            .iter_mut()
+            .filter(|m| m.status == MilestoneStatus::Pending)
+        {
+            milestone.status = MilestoneStatus::Refunded;
+            milestone.settled_at = Some(now);
+        }
+        self.status = DelegationStatus::Cancelled;
+        Ok(refund)
+    }
+
+    /// Dispute the contract, freezing the remaining escrow
+    ///
+    /// Frozen escrow stays locked until [`Self::resolve_dispute`]. Returns the
+    /// amount frozen.
+    pub fn dispute(&mut self) -> Result<Decimal, EscrowError> {
+        self.ensure_active()?;
+        for milestone in self
+            .milestones
+            .iter_mut()
+            .filter(|m| m.status == MilestoneStatus::Pending)
+        {
+            milestone.status = MilestoneStatus::Frozen;
+        }
+        self.status = DelegationStatus::Disputed;
+        Ok(self.total_in(MilestoneStatus::Frozen))
+    }
+
+    /// Settle the frozen escrow according to a ruling
+    ///
+    /// Returns the forfeited amount for redistribution.
+    pub fn resolve_dispute(
+        &mut self,
+        ruling: DisputeRuling,
+        now: i64,
+        client: &mut HcWallet,
+        agent: &mut HcWallet,
+    ) -> Result<Decimal, EscrowError> {
+        let mut escrow = self.wallet_escrow(client, Some(agent))?;
+        self.resolve_dispute_in(ruling, now, &mut escrow)
+    }
+
+    /// [`Self::resolve_dispute`] against escrow held by `escrow`
+    pub fn resolve_dispute_in(
+        &mut self,
+        ruling: DisputeRuling,
+        now: i64,
+        escrow: &mut impl EscrowLedger,
+    ) -> Result<Decimal, EscrowError> {
+        if self.status != DelegationStatus::Disputed {
+            return Err(EscrowError::NotActive(self.status));
+        }
+
+        let frozen = self.total_in(MilestoneStatus::Frozen);
+        let requested = ruling.to_agent + ruling.forfeited;
+        if ruling.to_agent < Decimal::ZERO || ruling.forfeited < Decimal::ZERO || requested > frozen
+        {
+            return Err(EscrowError::InvalidRuling { requested, frozen });
+        }
+
+        if ruling.to_agent > Decimal::ZERO {
+            escrow.release(ruling.to_agent)?;
+        }
+        let forfeited = if ruling.forfeited > Decimal::ZERO {
+            escrow.forfeit(ruling.forfeited)?
+        } else {
+            Decimal::ZERO
+        };
+        let refund = frozen - requested;
+        if refund > Decimal::ZERO {
+            escrow.refund(refund)?;
+        }
+
+        for milestone in self
+            .milestones
+            .iter_mut()
+            .filter(|m| m.status == MilestoneStatus::Frozen)
+        {
+            milestone.status = MilestoneStatus::Settled;
+            milestone.settled_at = Some(now);
+        }
+        self.status = DelegationStatus::Completed;
+        Ok(forfeited)
+    }
+
+    fn ensure_active(&self) -> Result<(), EscrowError> {
+        match self.status {
+            DelegationStatus::Active => Ok(()),
+            status => Err(EscrowError::NotActive(status)),
+        }
+    }
+
+    /// Escrow in the parties' wallets, checked against the contract
+    fn wallet_escrow<'a>(
+        &self,
+        client: &'a mut HcWallet,
+        agent: Option<&'a mut HcWallet>,
+    ) -> Result<WalletEscrow<'a>, EscrowError> {
+        if client.owner_did != self.client_did {
+            return Err(EscrowError::WalletMismatch(client.owner_did.clone()));
+        }
+        match agent {
+            Some(agent) if agent.owner_did != self.agent_did => {
+                Err(EscrowError::WalletMismatch(agent.owner_did.clone()))
+            }
+            agent => Ok(WalletEscrow { client, agent }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DnaMachine;
+    use actoris_common::crypto::frost::{
+        generate_key_shares_trusted, FrostCoordinator, FrostKeyShare, FrostSigner,
+    };
+    use actoris_common::crypto::merkle::MerkleTree;
+    use actoris_common::types::outcome_record::{FrostSignature, VerificationResult};
+    use rust_decimal_macros::dec;
+
+    fn milestone(amount: Decimal, deadline: i64) -> MilestoneSpec {
+        MilestoneSpec {
+            description: "part".to_string(),
+            escrow_amount: amount,
+            deadline,
+            acceptance: AcceptanceCriteria {
+                action_type: Some("build".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 3-of-5 oracle committee signing verdicts into an outcome ledger
+    struct Oracles {
+        shares: Vec<FrostKeyShare>,
+        coordinator: FrostCoordinator,
+        ledger: MerkleTree,
+        anchor: OutcomeAnchor,
+    }
+
+    impl Oracles {
+        fn new() -> Self {
+            let keys = generate_key_shares_trusted(3, 5).unwrap();
+            let coordinator = FrostCoordinator::new(keys.public_key_package, 3);
+            let anchor = OutcomeAnchor::new(coordinator.group_public_key());
+            Self {
+                shares: keys.key_shares,
+                coordinator,
+                ledger: MerkleTree::new(),
+                anchor,
+            }
+        }
+
+        /// Outcome of `action_type`, signed by three oracles and appended to
+        /// the ledger
+        async fn outcome(&mut self, action_type: &str, passed: bool) -> OutcomeRecord {
+            let verification = if passed {
+                VerificationResult::success(3, 100, vec![])
+            } else {
+                VerificationResult::failure(3, true, 100, vec![], "rejected".into())
+            };
+            let mut record = OutcomeRecord::new(
+                "did:key:agent".to_string(),
+                "did:key:client".to_string(),
+                action_type.to_string(),
+                [1u8; 32],
+                [2u8; 32],
+                dec!(5),
+                verification,
+                FrostSignature::new([0u8; 64], vec![], [0u8; 32], 3, 5),
+            );
+            record.signature = self.sign(&record.verdict_message()).await;
+
+            let index = self.ledger.append(record.canonical_hash());
+            self.ledger.commit();
+            let proof = self.ledger.generate_proof(index).unwrap();
+            self.anchor.roots.push(proof.root);
+            record.set_merkle_proof(proof.siblings, proof.root, index);
+            record
+        }
+
+        async fn sign(&self, message: &[u8]) -> FrostSignature {
+            let signers: Vec<FrostSigner> = self.shares[..3]
+                .iter()
+                .cloned()
+                .map(FrostSigner::new)
+                .collect();
+            let mut commitments = Vec::new();
+            for signer in &signers {
+                commitments.push(signer.start_signing("verdict", message).await.unwrap());
+            }
+            for (i, signer) in signers.iter().enumerate() {
+                for (j, commitment) in commitments.iter().enumerate() {
+                    if i != j {
+                        signer
+                            .add_commitment("verdict", commitment.clone())
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+            let mut shares = Vec::new();
+            for signer in &signers {
+                shares.push(signer.sign("verdict").await.unwrap());
+            }
+            let signature = self
+                .coordinator
+                .aggregate(message, &commitments, &shares)
+                .unwrap();
+            FrostSignature::new(
+                signature.to_bytes(),
+                vec!["o1".to_string(), "o2".to_string(), "o3".to_string()],
+                self.coordinator.group_public_key(),
+                3,
+                5,
+            )
+        }
+    }
+
+    fn setup() -> (DelegationContract, HcWallet, HcWallet) {
+        let mut client = HcWallet::with_balance("did:key:client".to_string(), dec!(100));
+        let agent = HcWallet::new("did:key:agent".to_string());
+        let far = i64::MAX;
+        let contract = DelegatePrimitive::create_milestone_contract(
+            &mut client,
+            "did:key:agent".to_string(),
+            "three parts".to_string(),
+            vec![
+                milestone(dec!(20), far),
+                milestone(dec!(30), far),
+                milestone(dec!(10), far),
+            ],
+        )
+        .unwrap();
+        (contract, client, agent)
+    }
+
+    #[tokio::test]
+    async fn test_milestones_release_in_order_then_cancel_refunds_rest() {
+        let mut oracles = Oracles::new();
+        let (mut contract, mut client, mut agent) = setup();
+        assert_eq!(contract.escrow_amount, dec!(60));
+        assert_eq!(client.locked, dec!(60));
+        assert_eq!(client.available, dec!(40));
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let first = oracles.outcome("build", true).await;
+        let anchor = &oracles.anchor.clone();
+        assert_eq!(
+            contract
+                .release_milestone(&first, anchor, now, &mut client, &mut agent)
+                .unwrap(),
+            dec!(20)
+        );
+        assert_eq!(agent.available, dec!(20));
+        assert_eq!(contract.next_milestone().unwrap().index, 1);
+
+        // The same outcome can't pay twice; failed or off-spec outcomes pay nothing
+        assert!(contract
+            .release_milestone(&first, anchor, now, &mut client, &mut agent)
+            .is_err());
+        let failed = oracles.outcome("build", false).await;
+        let off_spec = oracles.outcome("deploy", true).await;
+        let anchor = &oracles.anchor;
+        assert!(matches!(
+            contract.release_milestone(&failed, anchor, now, &mut client, &mut agent),
+            Err(EscrowError::OutcomeRejected(_))
+        ));
+        assert!(matches!(
+            contract.release_milestone(&off_spec, anchor, now, &mut client, &mut agent),
+            Err(EscrowError::OutcomeRejected(_))
+        ));
+
+        // Cancelling refunds the two unreleased milestones only
+        assert_eq!(contract.cancel(now, &mut client).unwrap(), dec!(40));
+        assert_eq!(contract.status, DelegationStatus::Cancelled);
+        assert_eq!(client.locked, Decimal::ZERO);
+        assert_eq!(client.available, dec!(80));
+        assert_eq!(contract.released_amount(), dec!(20));
+    }
+
+    #[tokio::test]
+    async fn test_outcome_must_be_signed_and_in_the_ledger() {
+        let mut oracles = Oracles::new();
+        let mut impostors = Oracles::new();
+        let (mut contract, mut client, mut agent) = setup();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Signed by another committee
+        let forged = impostors.outcome("build", true).await;
+        let mut anchor = oracles.anchor.clone();
+        anchor.roots.push(forged.merkle_root);
+        assert!(matches!(
+            contract.release_milestone(&forged, &anchor, now, &mut client, &mut agent),
+            Err(EscrowError::OutcomeRejected(reason)) if reason.contains("signature")
+        ));
+
+        // Signature copied onto a different deliverable
+        let mut tampered = oracles.outcome("build", true).await;
+        tampered.output_hash = [9u8; 32];
+        assert!(contract
+            .release_milestone(&tampered, &oracles.anchor, now, &mut client, &mut agent)
+            .is_err());
+
+        // Properly signed, but not under a committed ledger root
+        let record = oracles.outcome("build", true).await;
+        let stale = OutcomeAnchor::new(oracles.anchor.group_key);
+        assert!(matches!(
+            contract.release_milestone(&record, &stale, now, &mut client, &mut agent),
+            Err(EscrowError::OutcomeRejected(reason)) if reason.contains("ledger")
+        ));
+        assert_eq!(client.locked, dec!(60));
+
+        contract
+            .release_milestone(&record, &oracles.anchor, now, &mut client, &mut agent)
+            .unwrap();
+        assert_eq!(agent.available, dec!(20));
+    }
+
+    #[tokio::test]
+    async fn test_dispute_freezes_remaining_escrow() {
+        let mut oracles = Oracles::new();
+        let (mut contract, mut client, mut agent) = setup();
+        let now = chrono::Utc::now().timestamp_millis();
+        let first = oracles.outcome("build", true).await;
+        let second = oracles.outcome("build", true).await;
+        let anchor = &oracles.anchor;
+        contract
+            .release_milestone(&first, anchor, now, &mut client, &mut agent)
+            .unwrap();
+
+        assert_eq!(contract.dispute().unwrap(), dec!(40));
+        assert_eq!(client.locked, dec!(40));
+
+        // Frozen escrow can be neither released nor refunded
+        assert!(matches!(
+            contract.release_milestone(&second, anchor, now, &mut client, &mut agent),
+            Err(EscrowError::NotActive(DelegationStatus::Disputed))
+        ));
+        assert!(contract.cancel(now, &mut client).is_err());
+
+        // A ruling can't distribute more than is frozen
+        let greedy = DisputeRuling {
+            to_agent: dec!(30),
+            forfeited: dec!(20),
+        };
+        assert!(matches!(
+            contract.resolve_dispute(greedy, now, &mut client, &mut agent),
+            Err(EscrowError::InvalidRuling { .. })
+        ));
+
+        let ruling = DisputeRuling {
+            to_agent: dec!(15),
+            forfeited: dec!(5),
+        };
+        let forfeited = contract
+            .resolve_dispute(ruling, now, &mut client, &mut agent)
+            .unwrap();
+        assert_eq!(forfeited, dec!(5));
+        assert_eq!(agent.available, dec!(35));
+        assert_eq!(client.locked, Decimal::ZERO);
+        assert_eq!(client.available, dec!(60));
+        assert!(contract
+            .milestones
+            .iter()
+            .skip(1)
+            .all(|m| m.status == MilestoneStatus::Settled));
+    }
+
+    #[tokio::test]
+    async fn test_missed_deadline_and_bad_milestones() {
+        let mut oracles = Oracles::new();
+        let mut client = HcWallet::with_balance("did:key:client".to_string(), dec!(100));
+        let mut agent = HcWallet::new("did:key:agent".to_string());
+
+        assert!(matches!(
+            DelegatePrimitive::create_milestone_contract(
+                &mut client,
+                "did:key:agent".to_string(),
+                "bad order".to_string(),
+                vec![milestone(dec!(10), 2_000), milestone(dec!(10), 1_000)],
+            ),
+            Err(EscrowError::InvalidMilestones(_))
+        ));
+        assert!(matches!(
+            DelegatePrimitive::create_milestone_contract(
+                &mut client,
+                "did:key:agent".to_string(),
+                "too big".to_string(),
+                vec![milestone(dec!(500), 1_000)],
+            ),
+            Err(EscrowError::Wallet(WalletError::InsufficientBalance { .. }))
+        ));
+
+        let mut contract = DelegatePrimitive::create_milestone_contract(
+            &mut client,
+            "did:key:agent".to_string(),
+            "late".to_string(),
+            vec![milestone(dec!(10), 1_000)],
+        )
+        .unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        let late = oracles.outcome("build", true).await;
+        assert!(matches!(
+            contract.release_milestone(&late, &oracles.anchor, now, &mut client, &mut agent),
+            Err(EscrowError::DeadlineMissed { index: 0 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_contract_settles_through_machine_escrow() {
+        let mut oracles = Oracles::new();
+        let mut machine = DnaMachine::new();
+        machine.mint("did:key:client", dec!(100)).unwrap();
+        let locked = machine
+            .delegate("did:key:client", "did:key:agent", dec!(30))
+            .unwrap();
+        let id = locked.opened_position().unwrap().to_string();
+
+        let far = i64::MAX;
+        let mut contract = DelegatePrimitive::open_milestone_contract(
+            id.clone(),
+            "did:key:client".to_string(),
+            "did:key:agent".to_string(),
+            "two parts".to_string(),
+            vec![milestone(dec!(20), far), milestone(dec!(10), far)],
+        )
+        .unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        let record = oracles.outcome("build", true).await;
+        contract
+            .release_milestone_in(&record, &oracles.anchor, now, &mut machine.escrow(&id))
+            .unwrap();
+        assert_eq!(
+            contract.accept_in(now, &mut machine.escrow(&id)).unwrap(),
+            dec!(10)
+        );
+        assert_eq!(contract.status, DelegationStatus::Completed);
+
+        let agent = machine.wallet("did:key:agent").unwrap();
+        assert_eq!(agent.available, dec!(30));
+        assert_eq!(
+            machine.wallet("did:key:client").unwrap().locked,
+            Decimal::ZERO
+        );
+        assert!(machine.is_conserved());
     }
 }
@@ -1,7 +1,10 @@
 //! Insure primitive - Outcome guarantees
 
+use actoris_common::TrustScore;
 use rust_decimal::Decimal;
 
+use crate::pricing::risk_factor;
+
 /// Insure outcomes with premium pricing
 pub struct InsurePrimitive;
 
@@ -14,8 +17,40 @@ impl InsurePrimitive {
     ) -> Decimal {
         let tau = trust_score as f64 / 1000.0;
         // Lower trust = higher premium
-        let risk_factor = 1.0 + (1.0 - tau);
-        let base_premium = failure_probability * risk_factor;
+        let base_premium = failure_probability * risk_factor(tau);
         coverage * Decimal::try_from(base_premium).unwrap_or(Decimal::ONE)
     }
+
+    /// Calculate insurance premium from the insured's trust in the action's domain
+    pub fn calculate_domain_premium(
+        coverage: Decimal,
+        trust: &TrustScore,
+        action_type: &str,
+        failure_probability: f64,
+    ) -> Decimal {
+        Self::calculate_premium(
+            coverage,
+            trust.domain_score(action_type),
+            failure_probability,
+        )
+    }
+
+    /// Bayesian failure rate from observed history
+    ///
+    /// `failures` out of `trials` are combined with `prior_weight`
+    /// pseudo-observations at `prior_rate` (a Beta prior), so sparse
+    /// histories stay close to the prior.
+    pub fn smoothed_failure_rate(
+        failures: u64,
+        trials: u64,
+        prior_rate: f64,
+        prior_weight: f64,
+    ) -> f64 {
+        let prior_rate = prior_rate.clamp(0.0, 1.0);
+        let weight = prior_weight.max(0.0);
+        if trials == 0 && weight == 0.0 {
+            return prior_rate;
+        }
+        ((failures as f64 + prior_rate * weight) / (trials as f64 + weight)).clamp(0.0, 1.0)
+    }
 }
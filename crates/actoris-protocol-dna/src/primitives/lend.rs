@@ -1,11 +1,88 @@
 //! Lend primitive - Risk-priced credit extension
+//!
+//! Besides one-off loan pricing, this scores borrowers for revolving credit:
+//! the [`CreditScore`] blends trust, repayment history, the stability of
+//! metered earnings and the current debt burden.
 
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::pricing::risk_factor;
+use crate::primitives::insure::InsurePrimitive;
 
 /// Lend HC credits with risk-adjusted pricing
 pub struct LendPrimitive;
 
+/// Relative weights of the credit score components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreditWeights {
+    pub trust: f64,
+    pub repayment: f64,
+    pub stability: f64,
+    pub debt: f64,
+}
+
+impl Default for CreditWeights {
+    fn default() -> Self {
+        Self {
+            trust: 0.4,
+            repayment: 0.3,
+            stability: 0.15,
+            debt: 0.15,
+        }
+    }
+}
+
+/// What a credit score is computed from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreditInputs {
+    /// Borrower trust (τ)
+    pub tau: f64,
+    /// Loans closed by repayment
+    pub loans_repaid: u64,
+    /// Loans closed by default or liquidation
+    pub loans_defaulted: u64,
+    /// Metered earnings per period, oldest first
+    pub earnings: Vec<Decimal>,
+    /// Debt currently owed (loans and credit lines)
+    pub outstanding_debt: Decimal,
+}
+
+/// Credit score with its components, all in 0.0 - 1.0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreditScore {
+    pub score: f64,
+    pub trust: f64,
+    /// Smoothed share of loans repaid
+    pub repayment: f64,
+    /// 1 / (1 + coefficient of variation of earnings)
+    pub stability: f64,
+    /// 1 - debt / (debt + earnings)
+    pub debt: f64,
+}
+
+impl CreditScore {
+    /// Score on the 0 - 1000 trust scale
+    pub fn as_trust_score(&self) -> u16 {
+        (self.score.clamp(0.0, 1.0) * 1000.0).round() as u16
+    }
+}
+
 impl LendPrimitive {
+    /// Default rate assumed before any loan has closed
+    pub const PRIOR_DEFAULT_RATE: f64 = 0.2;
+
+    /// Pseudo-loans the prior default rate counts for
+    pub const PRIOR_WEIGHT: f64 = 2.0;
+
+    /// Earnings periods needed before volatility is measured
+    pub const MIN_EARNINGS_PERIODS: usize = 3;
+
+    /// Stability assumed while earnings history is too short
+    const NEUTRAL_STABILITY: f64 = 0.5;
+
     /// Calculate credit limit based on trust score
     pub fn calculate_credit_limit(base_limit: Decimal, trust_score: u16) -> Decimal {
         let multiplier = Self::credit_multiplier(trust_score);
@@ -21,6 +98,114 @@ impl LendPrimitive {
     /// Calculate interest rate based on trust (inverse relationship)
     pub fn calculate_interest_rate(base_rate: f64, trust_score: u16) -> f64 {
         let tau = trust_score as f64 / 1000.0;
-        base_rate * (2.0 - tau) // High trust = lower rate
+        base_rate * risk_factor(tau) // High trust = lower rate
+    }
+
+    /// Score a borrower for credit
+    pub fn credit_score(inputs: &CreditInputs, weights: &CreditWeights) -> CreditScore {
+        let trust = inputs.tau.clamp(0.0, 1.0);
+
+        let closed = inputs.loans_repaid + inputs.loans_defaulted;
+        let repayment = 1.0
+            - InsurePrimitive::smoothed_failure_rate(
+                inputs.loans_defaulted,
+                closed,
+                Self::PRIOR_DEFAULT_RATE,
+                Self::PRIOR_WEIGHT,
+            );
+
+        let stability = Self::earnings_volatility(&inputs.earnings)
+            .map(|cv| 1.0 / (1.0 + cv))
+            .unwrap_or(Self::NEUTRAL_STABILITY);
+
+        let debt = inputs.outstanding_debt.max(Decimal::ZERO);
+        let earned: Decimal = inputs.earnings.iter().copied().sum();
+        let debt = if debt.is_zero() {
+            1.0
+        } else {
+            let burden = debt / (debt + earned.max(Decimal::ZERO));
+            1.0 - burden.to_f64().unwrap_or(1.0)
+        };
+
+        let total = weights.trust + weights.repayment + weights.stability + weights.debt;
+        let score = if total > 0.0 {
+            (weights.trust * trust
+                + weights.repayment * repayment
+                + weights.stability * stability
+                + weights.debt * debt)
+                / total
+        } else {
+            trust
+        };
+
+        CreditScore {
+            score: score.clamp(0.0, 1.0),
+            trust,
+            repayment,
+            stability,
+            debt,
+        }
+    }
+
+    /// Coefficient of variation of per-period earnings
+    ///
+    /// `None` while there are fewer than [`Self::MIN_EARNINGS_PERIODS`]
+    /// periods; infinite when nothing was earned.
+    pub fn earnings_volatility(earnings: &[Decimal]) -> Option<f64> {
+        if earnings.len() < Self::MIN_EARNINGS_PERIODS {
+            return None;
+        }
+        let values: Vec<f64> = earnings.iter().map(|e| e.to_f64().unwrap_or(0.0)).collect();
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        if mean <= 0.0 {
+            return Some(f64::INFINITY);
+        }
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Some(variance.sqrt() / mean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_credit_score_components() {
+        let weights = CreditWeights::default();
+        let steady = CreditInputs {
+            tau: 0.8,
+            loans_repaid: 8,
+            loans_defaulted: 0,
+            earnings: vec![dec!(100); 10],
+            outstanding_debt: Decimal::ZERO,
+        };
+        let score = LendPrimitive::credit_score(&steady, &weights);
+        assert_eq!(score.stability, 1.0);
+        assert_eq!(score.debt, 1.0);
+        assert!((score.repayment - 0.96).abs() < 1e-12);
+
+        // Erratic earnings, a default and heavy debt all pull the score down
+        let erratic = CreditInputs {
+            earnings: vec![dec!(0), dec!(300), dec!(0), dec!(100)],
+            ..steady.clone()
+        };
+        let defaulted = CreditInputs {
+            loans_defaulted: 3,
+            ..steady.clone()
+        };
+        let indebted = CreditInputs {
+            outstanding_debt: dec!(3000),
+            ..steady.clone()
+        };
+        for worse in [&erratic, &defaulted, &indebted] {
+            assert!(LendPrimitive::credit_score(worse, &weights).score < score.score);
+        }
+        assert!((LendPrimitive::credit_score(&indebted, &weights).debt - 0.25).abs() < 1e-12);
+
+        // Too little history is neutral
+        assert_eq!(LendPrimitive::earnings_volatility(&[dec!(5)]), None);
+        assert_eq!(score.as_trust_score(), 908);
     }
 }
@@ -0,0 +1,279 @@
+//! Host API exposed to sandboxed contracts
+//!
+//! Contracts import a small set of functions from the `actoris` namespace.
+//! Every call is checked against a working copy of the balances touched so
+//! far, and effects are buffered until the contract returns successfully.
+//! Nothing reaches the [`DnaLedger`] from a failed or trapped execution, and
+//! a successful one commits its effects as a single all-or-nothing batch.
+//!
+//! HC a contract locks is escrowed under that contract; it may only unlock
+//! what it escrowed itself.
+//!
+//! HC amounts cross the boundary as `i64` micro-HC (`HC_SCALE` decimals).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Decimal places of HC amounts passed as integers
+pub const HC_SCALE: u32 = 6;
+
+/// Host namespace contracts import from
+pub const HOST_MODULE: &str = "actoris";
+
+/// Host functions a contract may import
+pub const HOST_FUNCTIONS: &[&str] = &[
+    "input_len",
+    "read_input",
+    "trust",
+    "balance",
+    "locked",
+    "lock",
+    "unlock",
+    "transfer",
+    "emit",
+];
+
+/// Status codes returned by host functions
+pub mod status {
+    pub const OK: i32 = 0;
+    pub const INSUFFICIENT_FUNDS: i32 = 1;
+    pub const UNAUTHORIZED: i32 = 2;
+    pub const INVALID_ARGUMENT: i32 = 3;
+    pub const LIMIT_EXCEEDED: i32 = 4;
+}
+
+/// Ledger errors surfaced while committing contract effects
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    #[error("Insufficient funds for {did}: need {required}, have {available}")]
+    InsufficientFunds {
+        did: String,
+        required: Decimal,
+        available: Decimal,
+    },
+
+    #[error("Ledger rejected operation: {0}")]
+    Rejected(String),
+}
+
+/// Balance and trust access for contracts
+///
+/// Implemented by the embedding executor so contracts move HC through the
+/// same paths as the built-in primitives.
+pub trait DnaLedger: Send + Sync {
+    /// Trust score (0-1000)
+    fn trust(&self, did: &str) -> u16;
+
+    /// Available HC
+    fn balance(&self, did: &str) -> Decimal;
+
+    /// HC locked against the DID
+    fn locked(&self, did: &str) -> Decimal;
+
+    /// Part of the DID's locked HC escrowed by a contract
+    fn escrowed(&self, contract: &str, did: &str) -> Decimal;
+
+    /// Apply a contract's effects all-or-nothing
+    ///
+    /// Locks escrow HC under `contract` and unlocks draw on that escrow only.
+    /// If any effect fails, no balance or escrow changes.
+    fn apply_effects(&self, contract: &str, effects: &[LedgerEffect]) -> Result<(), LedgerError>;
+}
+
+/// Ledger effect requested by a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum LedgerEffect {
+    Lock {
+        did: String,
+        amount: Decimal,
+    },
+    Unlock {
+        did: String,
+        amount: Decimal,
+    },
+    Transfer {
+        from: String,
+        to: String,
+        amount: Decimal,
+    },
+}
+
+/// Event emitted by a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractEvent {
+    pub topic: String,
+    pub data: Vec<u8>,
+}
+
+/// Per-execution state behind the host functions
+pub(crate) struct HostState {
+    ledger: Arc<dyn DnaLedger>,
+    /// Contract whose escrow this call locks into and unlocks from
+    contract: String,
+    /// DIDs that authorized this call; only they can be debited or locked
+    authorized: BTreeSet<String>,
+    pub(crate) input: Vec<u8>,
+    /// Working copy of available balances touched so far
+    available: BTreeMap<String, Decimal>,
+    /// Working copy of locked balances touched so far
+    locked: BTreeMap<String, Decimal>,
+    /// Working copy of this contract's escrow touched so far
+    escrowed: BTreeMap<String, Decimal>,
+    pub(crate) effects: Vec<LedgerEffect>,
+    pub(crate) events: Vec<ContractEvent>,
+    max_events: usize,
+    max_event_bytes: usize,
+    pub(crate) limits: wasmtime::StoreLimits,
+}
+
+impl HostState {
+    pub(crate) fn new(
+        ledger: Arc<dyn DnaLedger>,
+        contract: String,
+        authorized: BTreeSet<String>,
+        input: Vec<u8>,
+        max_events: usize,
+        max_event_bytes: usize,
+        limits: wasmtime::StoreLimits,
+    ) -> Self {
+        Self {
+            ledger,
+            contract,
+            authorized,
+            input,
+            available: BTreeMap::new(),
+            locked: BTreeMap::new(),
+            escrowed: BTreeMap::new(),
+            effects: Vec::new(),
+            events: Vec::new(),
+            max_events,
+            max_event_bytes,
+            limits,
+        }
+    }
+
+    pub(crate) fn trust(&self, did: &str) -> u16 {
+        self.ledger.trust(did)
+    }
+
+    pub(crate) fn balance(&mut self, did: &str) -> Decimal {
+        let ledger = &self.ledger;
+        *self
+            .available
+            .entry(did.to_string())
+            .or_insert_with(|| ledger.balance(did))
+    }
+
+    pub(crate) fn locked(&mut self, did: &str) -> Decimal {
+        let ledger = &self.ledger;
+        *self
+            .locked
+            .entry(did.to_string())
+            .or_insert_with(|| ledger.locked(did))
+    }
+
+    fn escrowed(&mut self, did: &str) -> Decimal {
+        let (ledger, contract) = (&self.ledger, &self.contract);
+        *self
+            .escrowed
+            .entry(did.to_string())
+            .or_insert_with(|| ledger.escrowed(contract, did))
+    }
+
+    fn debit(&mut self, did: &str, amount: Decimal) -> i32 {
+        if !self.authorized.contains(did) {
+            return status::UNAUTHORIZED;
+        }
+        let available = self.balance(did);
+        if available < amount {
+            return status::INSUFFICIENT_FUNDS;
+        }
+        self.available.insert(did.to_string(), available - amount);
+        status::OK
+    }
+
+    pub(crate) fn lock(&mut self, did: &str, amount: Decimal) -> i32 {
+        let code = self.debit(did, amount);
+        if code != status::OK {
+            return code;
+        }
+        let locked = self.locked(did);
+        self.locked.insert(did.to_string(), locked + amount);
+        let escrowed = self.escrowed(did);
+        self.escrowed.insert(did.to_string(), escrowed + amount);
+        self.effects.push(LedgerEffect::Lock {
+            did: did.to_string(),
+            amount,
+        });
+        status::OK
+    }
+
+    pub(crate) fn unlock(&mut self, did: &str, amount: Decimal) -> i32 {
+        if !self.authorized.contains(did) {
+            return status::UNAUTHORIZED;
+        }
+        let locked = self.locked(did);
+        if locked < amount {
+            return status::INSUFFICIENT_FUNDS;
+        }
+        // HC locked by the built-in primitives or other contracts stays put
+        let escrowed = self.escrowed(did);
+        if escrowed < amount {
+            return status::UNAUTHORIZED;
+        }
+        self.locked.insert(did.to_string(), locked - amount);
+        self.escrowed.insert(did.to_string(), escrowed - amount);
+        let available = self.balance(did);
+        self.available.insert(did.to_string(), available + amount);
+        self.effects.push(LedgerEffect::Unlock {
+            did: did.to_string(),
+            amount,
+        });
+        status::OK
+    }
+
+    pub(crate) fn transfer(&mut self, from: &str, to: &str, amount: Decimal) -> i32 {
+        if from == to {
+            return status::INVALID_ARGUMENT;
+        }
+        let code = self.debit(from, amount);
+        if code != status::OK {
+            return code;
+        }
+        let available = self.balance(to);
+        self.available.insert(to.to_string(), available + amount);
+        self.effects.push(LedgerEffect::Transfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+        });
+        status::OK
+    }
+
+    pub(crate) fn emit(&mut self, topic: String, data: Vec<u8>) -> i32 {
+        if self.events.len() >= self.max_events || data.len() > self.max_event_bytes {
+            return status::LIMIT_EXCEEDED;
+        }
+        self.events.push(ContractEvent { topic, data });
+        status::OK
+    }
+}
+
+/// Convert a micro-HC integer to a positive HC amount
+pub(crate) fn hc_from_units(units: i64) -> Option<Decimal> {
+    (units > 0).then(|| Decimal::new(units, HC_SCALE))
+}
+
+/// Convert an HC amount to micro-HC, saturating at `i64::MAX`
+pub(crate) fn hc_to_units(amount: Decimal) -> i64 {
+    (amount * Decimal::new(10i64.pow(HC_SCALE), 0))
+        .trunc()
+        .to_i64()
+        .unwrap_or(i64::MAX)
+}
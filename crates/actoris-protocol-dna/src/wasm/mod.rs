@@ -1,2 +1,6 @@
-//! WASM sandbox module (Wasmtime placeholder)
+//! WASM sandbox for user-defined primitives
+pub mod host;
 pub mod runtime;
+
+pub use host::{ContractEvent, DnaLedger, LedgerEffect, LedgerError, HC_SCALE};
+pub use runtime::{ContractCall, ExecutionOutcome, WasmError, WasmLimits, WasmRuntime};
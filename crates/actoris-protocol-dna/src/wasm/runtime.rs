@@ -1,2 +1,888 @@
 //! Wasmtime runtime for safe programmable primitives
-pub struct WasmRuntime;
+//!
+//! Loads user-defined contracts (revenue splits, escrows, custom insurance
+//! triggers) and runs them in a sandbox:
+//! - Fuel metering bounds CPU; memory, table and instance limits bound space
+//! - Only the `actoris` host functions may be imported (no WASI, no clock,
+//!   no randomness)
+//! - Threads and relaxed SIMD are disabled and NaNs are canonicalized, so
+//!   every oracle executing the same call gets the same result
+//!
+//! Modules are content-addressed by their BLAKE3 hash.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wasmtime::{Caller, Config, Engine, Linker, Memory, Module, Store, StoreLimitsBuilder, Trap};
+
+use super::host::{
+    hc_from_units, hc_to_units, status, ContractEvent, DnaLedger, HostState, LedgerEffect,
+    LedgerError, HOST_FUNCTIONS, HOST_MODULE,
+};
+
+/// WASM runtime errors
+#[derive(Debug, Error)]
+pub enum WasmError {
+    #[error("Failed to compile module: {0}")]
+    Compile(String),
+
+    #[error("Invalid module: {reason}")]
+    InvalidModule { reason: String },
+
+    #[error("Module not deployed: {module_id}")]
+    ModuleNotFound { module_id: String },
+
+    #[error("Fuel exhausted after {fuel} units")]
+    FuelExhausted { fuel: u64 },
+
+    #[error("Contract trapped: {0}")]
+    Trap(String),
+
+    #[error("Failed to commit contract effects: {0}")]
+    Commit(#[from] LedgerError),
+}
+
+/// Resource limits for a single execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmLimits {
+    /// Fuel available per call
+    pub fuel: u64,
+    /// Fuel charged per host function call
+    pub host_call_fuel: u64,
+    /// Maximum linear memory in bytes
+    pub max_memory_bytes: usize,
+    /// Maximum table elements
+    pub max_table_elements: u32,
+    /// Maximum length of a DID or topic read from guest memory
+    pub max_string_bytes: usize,
+    /// Maximum events per call
+    pub max_events: usize,
+    /// Maximum payload per event
+    pub max_event_bytes: usize,
+    /// Maximum call input size
+    pub max_input_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 10_000_000,
+            host_call_fuel: 1_000,
+            max_memory_bytes: 16 * 1024 * 1024, // 16 MiB
+            max_table_elements: 1_000,
+            max_string_bytes: 256,
+            max_events: 64,
+            max_event_bytes: 4 * 1024,
+            max_input_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// A call into a deployed contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractCall {
+    /// Content hash of the module
+    pub module_id: String,
+    /// Exported function to invoke, with signature `() -> i32`
+    pub entry: String,
+    /// DIDs that authorized the call (may be debited or locked)
+    pub authorized: BTreeSet<String>,
+    /// Opaque input readable through `read_input`
+    pub input: Vec<u8>,
+}
+
+impl ContractCall {
+    pub fn new(module_id: impl Into<String>, entry: impl Into<String>) -> Self {
+        Self {
+            module_id: module_id.into(),
+            entry: entry.into(),
+            authorized: BTreeSet::new(),
+            input: Vec::new(),
+        }
+    }
+
+    /// Add an authorizing DID
+    pub fn authorized_by(mut self, did: impl Into<String>) -> Self {
+        self.authorized.insert(did.into());
+        self
+    }
+
+    /// Set call input
+    pub fn with_input(mut self, input: Vec<u8>) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+/// Result of a contract execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionOutcome {
+    /// Status returned by the contract (0 = success)
+    pub status: i32,
+    /// Whether effects were committed to the ledger
+    pub committed: bool,
+    /// Fuel consumed
+    pub fuel_consumed: u64,
+    /// Ledger effects (committed only when `status == 0`)
+    pub effects: Vec<LedgerEffect>,
+    /// Events emitted (only kept when `status == 0`)
+    pub events: Vec<ContractEvent>,
+}
+
+/// Sandboxed runtime for user-defined primitives
+pub struct WasmRuntime {
+    engine: Engine,
+    limits: WasmLimits,
+    modules: RwLock<HashMap<String, Module>>,
+}
+
+impl WasmRuntime {
+    /// Create a runtime with deterministic execution settings
+    pub fn new(limits: WasmLimits) -> Result<Self, WasmError> {
+        let mut config = Config::new();
+        config
+            .consume_fuel(true)
+            .wasm_threads(false)
+            .wasm_relaxed_simd(false)
+            .wasm_memory64(false)
+            .cranelift_nan_canonicalization(true);
+
+        let engine = Engine::new(&config).map_err(|e| WasmError::Compile(e.to_string()))?;
+
+        Ok(Self {
+            engine,
+            limits,
+            modules: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Get limits
+    pub fn limits(&self) -> &WasmLimits {
+        &self.limits
+    }
+
+    /// Compile, validate and register a module, returning its content hash
+    pub fn deploy(&self, wasm: &[u8]) -> Result<String, WasmError> {
+        let module =
+            Module::new(&self.engine, wasm).map_err(|e| WasmError::Compile(e.to_string()))?;
+
+        for import in module.imports() {
+            if import.module() != HOST_MODULE || !HOST_FUNCTIONS.contains(&import.name()) {
+                return Err(WasmError::InvalidModule {
+                    reason: format!(
+                        "import {}::{} is not part of the host API",
+                        import.module(),
+                        import.name()
+                    ),
+                });
+            }
+        }
+        if module.get_export("memory").is_none() {
+            return Err(WasmError::InvalidModule {
+                reason: "module must export its memory".to_string(),
+            });
+        }
+
+        let module_id = blake3::hash(wasm).to_hex().to_string();
+        self.modules.write().insert(module_id.clone(), module);
+        Ok(module_id)
+    }
+
+    /// Check if a module is deployed
+    pub fn is_deployed(&self, module_id: &str) -> bool {
+        self.modules.read().contains_key(module_id)
+    }
+
+    /// Execute a contract call, committing its effects if it returns 0
+    ///
+    /// Effects are committed as one batch, so a ledger that rejects any of
+    /// them leaves every balance untouched.
+    pub fn execute(
+        &self,
+        call: &ContractCall,
+        ledger: Arc<dyn DnaLedger>,
+    ) -> Result<ExecutionOutcome, WasmError> {
+        let module = self
+            .modules
+            .read()
+            .get(&call.module_id)
+            .cloned()
+            .ok_or_else(|| WasmError::ModuleNotFound {
+                module_id: call.module_id.clone(),
+            })?;
+
+        if call.input.len() > self.limits.max_input_bytes {
+            return Err(WasmError::InvalidModule {
+                reason: format!(
+                    "input of {} bytes exceeds {} byte limit",
+                    call.input.len(),
+                    self.limits.max_input_bytes
+                ),
+            });
+        }
+
+        let store_limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.max_memory_bytes)
+            .table_elements(self.limits.max_table_elements)
+            .instances(1)
+            .memories(1)
+            .tables(1)
+            .build();
+        let state = HostState::new(
+            ledger.clone(),
+            call.module_id.clone(),
+            call.authorized.clone(),
+            call.input.clone(),
+            self.limits.max_events,
+            self.limits.max_event_bytes,
+            store_limits,
+        );
+
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(self.limits.fuel)
+            .map_err(|e| WasmError::Trap(e.to_string()))?;
+
+        let linker = self.linker()?;
+        let result = linker
+            .instantiate(&mut store, &module)
+            .and_then(|instance| instance.get_typed_func::<(), i32>(&mut store, &call.entry))
+            .and_then(|entry| entry.call(&mut store, ()));
+
+        let fuel_left = store.get_fuel().unwrap_or(0);
+        let fuel_consumed = self.limits.fuel - fuel_left;
+
+        let status = match result {
+            Ok(status) => status,
+            Err(e) if e.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) => {
+                return Err(WasmError::FuelExhausted {
+                    fuel: self.limits.fuel,
+                });
+            }
+            Err(e) => return Err(WasmError::Trap(e.to_string())),
+        };
+
+        let state = store.into_data();
+        if status != status::OK {
+            return Ok(ExecutionOutcome {
+                status,
+                committed: false,
+                fuel_consumed,
+                effects: Vec::new(),
+                events: Vec::new(),
+            });
+        }
+
+        ledger.apply_effects(&call.module_id, &state.effects)?;
+
+        Ok(ExecutionOutcome {
+            status,
+            committed: true,
+            fuel_consumed,
+            effects: state.effects,
+            events: state.events,
+        })
+    }
+
+    /// Build the linker exposing the host API
+    fn linker(&self) -> Result<Linker<HostState>, WasmError> {
+        let mut linker = Linker::new(&self.engine);
+        let host_fuel = self.limits.host_call_fuel;
+        let max_string = self.limits.max_string_bytes;
+        let max_event = self.limits.max_event_bytes;
+
+        let link = |e: anyhow::Error| WasmError::Compile(e.to_string());
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "input_len",
+                move |mut caller: Caller<'_, HostState>| -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    Ok(caller.data().input.len() as i32)
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "read_input",
+                move |mut caller: Caller<'_, HostState>, ptr: i32| -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let input = caller.data().input.clone();
+                    let memory = memory(&mut caller)?;
+                    memory.write(&mut caller, ptr as u32 as usize, &input)?;
+                    Ok(status::OK)
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "trust",
+                move |mut caller: Caller<'_, HostState>,
+                      ptr: i32,
+                      len: i32|
+                      -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let Some(did) = read_string(&mut caller, ptr, len, max_string)? else {
+                        return Ok(status::INVALID_ARGUMENT);
+                    };
+                    Ok(caller.data().trust(&did) as i32)
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "balance",
+                move |mut caller: Caller<'_, HostState>,
+                      ptr: i32,
+                      len: i32|
+                      -> anyhow::Result<i64> {
+                    charge(&mut caller, host_fuel)?;
+                    let Some(did) = read_string(&mut caller, ptr, len, max_string)? else {
+                        return Ok(-(status::INVALID_ARGUMENT as i64));
+                    };
+                    Ok(hc_to_units(caller.data_mut().balance(&did)))
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "locked",
+                move |mut caller: Caller<'_, HostState>,
+                      ptr: i32,
+                      len: i32|
+                      -> anyhow::Result<i64> {
+                    charge(&mut caller, host_fuel)?;
+                    let Some(did) = read_string(&mut caller, ptr, len, max_string)? else {
+                        return Ok(-(status::INVALID_ARGUMENT as i64));
+                    };
+                    Ok(hc_to_units(caller.data_mut().locked(&did)))
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "lock",
+                move |mut caller: Caller<'_, HostState>,
+                      ptr: i32,
+                      len: i32,
+                      units: i64|
+                      -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let (Some(did), Some(amount)) = (
+                        read_string(&mut caller, ptr, len, max_string)?,
+                        hc_from_units(units),
+                    ) else {
+                        return Ok(status::INVALID_ARGUMENT);
+                    };
+                    Ok(caller.data_mut().lock(&did, amount))
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "unlock",
+                move |mut caller: Caller<'_, HostState>,
+                      ptr: i32,
+                      len: i32,
+                      units: i64|
+                      -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let (Some(did), Some(amount)) = (
+                        read_string(&mut caller, ptr, len, max_string)?,
+                        hc_from_units(units),
+                    ) else {
+                        return Ok(status::INVALID_ARGUMENT);
+                    };
+                    Ok(caller.data_mut().unlock(&did, amount))
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "transfer",
+                move |mut caller: Caller<'_, HostState>,
+                      from_ptr: i32,
+                      from_len: i32,
+                      to_ptr: i32,
+                      to_len: i32,
+                      units: i64|
+                      -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let (Some(from), Some(to), Some(amount)) = (
+                        read_string(&mut caller, from_ptr, from_len, max_string)?,
+                        read_string(&mut caller, to_ptr, to_len, max_string)?,
+                        hc_from_units(units),
+                    ) else {
+                        return Ok(status::INVALID_ARGUMENT);
+                    };
+                    Ok(caller.data_mut().transfer(&from, &to, amount))
+                },
+            )
+            .map_err(link)?;
+
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "emit",
+                move |mut caller: Caller<'_, HostState>,
+                      topic_ptr: i32,
+                      topic_len: i32,
+                      data_ptr: i32,
+                      data_len: i32|
+                      -> anyhow::Result<i32> {
+                    charge(&mut caller, host_fuel)?;
+                    let (Some(topic), Some(data)) = (
+                        read_string(&mut caller, topic_ptr, topic_len, max_string)?,
+                        read_bytes(&mut caller, data_ptr, data_len, max_event)?,
+                    ) else {
+                        return Ok(status::INVALID_ARGUMENT);
+                    };
+                    Ok(caller.data_mut().emit(topic, data))
+                },
+            )
+            .map_err(link)?;
+
+        Ok(linker)
+    }
+}
+
+/// Charge fuel for a host call, trapping when it runs out
+fn charge(caller: &mut Caller<'_, HostState>, cost: u64) -> anyhow::Result<()> {
+    let fuel = caller.get_fuel()?;
+    if fuel < cost {
+        caller.set_fuel(0)?;
+        return Err(Trap::OutOfFuel.into());
+    }
+    caller.set_fuel(fuel - cost)?;
+    Ok(())
+}
+
+/// Get the guest's exported memory
+fn memory(caller: &mut Caller<'_, HostState>) -> anyhow::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(|export| export.into_memory())
+        .ok_or_else(|| anyhow::anyhow!("module does not export memory"))
+}
+
+/// Read guest bytes, returning None when the length is out of range
+fn read_bytes(
+    caller: &mut Caller<'_, HostState>,
+    ptr: i32,
+    len: i32,
+    max_len: usize,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    if len < 0 || len as usize > max_len {
+        return Ok(None);
+    }
+    let memory = memory(caller)?;
+    let mut buf = vec![0u8; len as usize];
+    memory.read(&caller, ptr as u32 as usize, &mut buf)?;
+    Ok(Some(buf))
+}
+
+/// Read a UTF-8 string from guest memory
+fn read_string(
+    caller: &mut Caller<'_, HostState>,
+    ptr: i32,
+    len: i32,
+    max_len: usize,
+) -> anyhow::Result<Option<String>> {
+    Ok(read_bytes(caller, ptr, len, max_len)?.and_then(|bytes| String::from_utf8(bytes).ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    /// Ledger backed by plain maps
+    #[derive(Default)]
+    struct TestLedger {
+        available: Mutex<HashMap<String, Decimal>>,
+        locked: Mutex<HashMap<String, Decimal>>,
+        escrow: Mutex<HashMap<(String, String), Decimal>>,
+    }
+
+    impl TestLedger {
+        fn with_balance(did: &str, amount: Decimal) -> Arc<Self> {
+            let ledger = Self::default();
+            ledger
+                .available
+                .lock()
+                .unwrap()
+                .insert(did.to_string(), amount);
+            Arc::new(ledger)
+        }
+    }
+
+    impl DnaLedger for TestLedger {
+        fn trust(&self, did: &str) -> u16 {
+            if did == "did:key:a" {
+                800
+            } else {
+                0
+            }
+        }
+
+        fn balance(&self, did: &str) -> Decimal {
+            self.available
+                .lock()
+                .unwrap()
+                .get(did)
+                .copied()
+                .unwrap_or_default()
+        }
+
+        fn locked(&self, did: &str) -> Decimal {
+            self.locked
+                .lock()
+                .unwrap()
+                .get(did)
+                .copied()
+                .unwrap_or_default()
+        }
+
+        fn escrowed(&self, contract: &str, did: &str) -> Decimal {
+            self.escrow
+                .lock()
+                .unwrap()
+                .get(&(contract.into(), did.into()))
+                .copied()
+                .unwrap_or_default()
+        }
+
+        fn apply_effects(
+            &self,
+            contract: &str,
+            effects: &[LedgerEffect],
+        ) -> Result<(), LedgerError> {
+            let mut available = self.available.lock().unwrap();
+            let mut locked = self.locked.lock().unwrap();
+            let mut escrow = self.escrow.lock().unwrap();
+            let (mut next_available, mut next_locked, mut next_escrow) =
+                (available.clone(), locked.clone(), escrow.clone());
+
+            let debit = |map: &mut HashMap<String, Decimal>, did: &str, amount: Decimal| {
+                let balance = map.entry(did.into()).or_default();
+                if *balance < amount {
+                    return Err(LedgerError::InsufficientFunds {
+                        did: did.into(),
+                        required: amount,
+                        available: *balance,
+                    });
+                }
+                *balance -= amount;
+                Ok(())
+            };
+
+            for effect in effects {
+                match effect {
+                    LedgerEffect::Lock { did, amount } => {
+                        debit(&mut next_available, did, *amount)?;
+                        *next_locked.entry(did.clone()).or_default() += amount;
+                        *next_escrow
+                            .entry((contract.into(), did.clone()))
+                            .or_default() += amount;
+                    }
+                    LedgerEffect::Unlock { did, amount } => {
+                        let held = next_escrow
+                            .entry((contract.into(), did.clone()))
+                            .or_default();
+                        if *held < *amount {
+                            return Err(LedgerError::Rejected("not escrowed".into()));
+                        }
+                        *held -= amount;
+                        debit(&mut next_locked, did, *amount)?;
+                        *next_available.entry(did.clone()).or_default() += amount;
+                    }
+                    LedgerEffect::Transfer { from, to, amount } => {
+                        debit(&mut next_available, from, *amount)?;
+                        *next_available.entry(to.clone()).or_default() += amount;
+                    }
+                }
+            }
+
+            *available = next_available;
+            *locked = next_locked;
+            *escrow = next_escrow;
+            Ok(())
+        }
+    }
+
+    /// Reports more HC than the ledger holds, as a stale read would
+    struct StaleLedger {
+        inner: Arc<TestLedger>,
+        overstated: Decimal,
+    }
+
+    impl DnaLedger for StaleLedger {
+        fn trust(&self, did: &str) -> u16 {
+            self.inner.trust(did)
+        }
+
+        fn balance(&self, did: &str) -> Decimal {
+            self.inner.balance(did) + self.overstated
+        }
+
+        fn locked(&self, did: &str) -> Decimal {
+            self.inner.locked(did)
+        }
+
+        fn escrowed(&self, contract: &str, did: &str) -> Decimal {
+            self.inner.escrowed(contract, did)
+        }
+
+        fn apply_effects(
+            &self,
+            contract: &str,
+            effects: &[LedgerEffect],
+        ) -> Result<(), LedgerError> {
+            self.inner.apply_effects(contract, effects)
+        }
+    }
+
+    /// Splits 1 HC from did:key:a: 0.7 to did:key:b, 0.3 locked, then emits
+    /// an event. Returns the first failing host status.
+    const SPLIT: &str = r#"
+        (module
+          (import "actoris" "transfer" (func $transfer (param i32 i32 i32 i32 i64) (result i32)))
+          (import "actoris" "lock" (func $lock (param i32 i32 i64) (result i32)))
+          (import "actoris" "emit" (func $emit (param i32 i32 i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "did:key:a")
+          (data (i32.const 16) "did:key:b")
+          (data (i32.const 32) "split")
+          (func (export "execute") (result i32)
+            (local $s i32)
+            (local.set $s (call $transfer (i32.const 0) (i32.const 9) (i32.const 16) (i32.const 9) (i64.const 700000)))
+            (if (local.get $s) (then (return (local.get $s))))
+            (local.set $s (call $lock (i32.const 0) (i32.const 9) (i64.const 300000)))
+            (if (local.get $s) (then (return (local.get $s))))
+            (call $emit (i32.const 32) (i32.const 5) (i32.const 0) (i32.const 9))))
+    "#;
+
+    fn runtime() -> WasmRuntime {
+        WasmRuntime::new(WasmLimits::default()).unwrap()
+    }
+
+    #[test]
+    fn test_split_commits_effects() {
+        let runtime = runtime();
+        let module_id = runtime.deploy(SPLIT.as_bytes()).unwrap();
+        let ledger = TestLedger::with_balance("did:key:a", dec!(1));
+
+        let call = ContractCall::new(&module_id, "execute").authorized_by("did:key:a");
+        let outcome = runtime.execute(&call, ledger.clone()).unwrap();
+
+        assert_eq!(outcome.status, status::OK);
+        assert!(outcome.committed);
+        assert_eq!(outcome.effects.len(), 2);
+        assert_eq!(outcome.events[0].topic, "split");
+        assert_eq!(ledger.balance("did:key:a"), dec!(0));
+        assert_eq!(ledger.balance("did:key:b"), dec!(0.7));
+        assert_eq!(ledger.locked("did:key:a"), dec!(0.3));
+    }
+
+    #[test]
+    fn test_failed_call_commits_nothing() {
+        let runtime = runtime();
+        let module_id = runtime.deploy(SPLIT.as_bytes()).unwrap();
+
+        // Not authorized by the payer
+        let ledger = TestLedger::with_balance("did:key:a", dec!(1));
+        let call = ContractCall::new(&module_id, "execute").authorized_by("did:key:b");
+        let outcome = runtime.execute(&call, ledger.clone()).unwrap();
+        assert_eq!(outcome.status, status::UNAUTHORIZED);
+        assert!(!outcome.committed);
+        assert_eq!(ledger.balance("did:key:a"), dec!(1));
+
+        // The transfer fits but the lock does not; the transfer is rolled back
+        let ledger = TestLedger::with_balance("did:key:a", dec!(0.8));
+        let call = ContractCall::new(&module_id, "execute").authorized_by("did:key:a");
+        let outcome = runtime.execute(&call, ledger.clone()).unwrap();
+        assert_eq!(outcome.status, status::INSUFFICIENT_FUNDS);
+        assert!(outcome.effects.is_empty());
+        assert_eq!(ledger.balance("did:key:a"), dec!(0.8));
+        assert_eq!(ledger.balance("did:key:b"), dec!(0));
+    }
+
+    #[test]
+    fn test_commit_is_all_or_nothing() {
+        let runtime = runtime();
+        let module_id = runtime.deploy(SPLIT.as_bytes()).unwrap();
+
+        // The contract sees 1 HC, but only the transfer fits what the ledger holds
+        let inner = TestLedger::with_balance("did:key:a", dec!(0.8));
+        let ledger = Arc::new(StaleLedger {
+            inner: inner.clone(),
+            overstated: dec!(0.2),
+        });
+        let call = ContractCall::new(&module_id, "execute").authorized_by("did:key:a");
+
+        assert!(matches!(
+            runtime.execute(&call, ledger),
+            Err(WasmError::Commit(LedgerError::InsufficientFunds { .. }))
+        ));
+        assert_eq!(inner.balance("did:key:a"), dec!(0.8));
+        assert_eq!(inner.balance("did:key:b"), dec!(0));
+        assert_eq!(inner.locked("did:key:a"), dec!(0));
+    }
+
+    #[test]
+    fn test_unlock_limited_to_own_escrow() {
+        let wat = r#"
+            (module
+              (import "actoris" "lock" (func $lock (param i32 i32 i64) (result i32)))
+              (import "actoris" "unlock" (func $unlock (param i32 i32 i64) (result i32)))
+              (import "actoris" "input_len" (func $input_len (result i32)))
+              (memory (export "memory") 1)
+              (data (i32.const 0) "did:key:a")
+              ;; Locks 1 HC when called with input, otherwise unlocks 1 HC
+              (func (export "execute") (result i32)
+                (if (result i32) (call $input_len)
+                  (then (call $lock (i32.const 0) (i32.const 9) (i64.const 1000000)))
+                  (else (call $unlock (i32.const 0) (i32.const 9) (i64.const 1000000))))))
+        "#;
+        let runtime = runtime();
+        let module_id = runtime.deploy(wat.as_bytes()).unwrap();
+        let ledger = TestLedger::with_balance("did:key:a", dec!(5));
+
+        // HC locked outside this contract cannot be unlocked by it
+        ledger
+            .locked
+            .lock()
+            .unwrap()
+            .insert("did:key:a".to_string(), dec!(2));
+        let unlock = ContractCall::new(&module_id, "execute").authorized_by("did:key:a");
+        let outcome = runtime.execute(&unlock, ledger.clone()).unwrap();
+        assert_eq!(outcome.status, status::UNAUTHORIZED);
+
+        // Its own escrow can
+        let lock = unlock.clone().with_input(vec![1]);
+        assert!(runtime.execute(&lock, ledger.clone()).unwrap().committed);
+        assert_eq!(ledger.escrowed(&module_id, "did:key:a"), dec!(1));
+        assert!(runtime.execute(&unlock, ledger.clone()).unwrap().committed);
+        assert_eq!(ledger.escrowed(&module_id, "did:key:a"), dec!(0));
+        assert_eq!(ledger.locked("did:key:a"), dec!(2));
+        assert_eq!(ledger.balance("did:key:a"), dec!(5));
+    }
+
+    #[test]
+    fn test_execution_is_deterministic() {
+        let runtime = runtime();
+        let module_id = runtime.deploy(SPLIT.as_bytes()).unwrap();
+        let call = ContractCall::new(&module_id, "execute").authorized_by("did:key:a");
+
+        let first = runtime
+            .execute(&call, TestLedger::with_balance("did:key:a", dec!(5)))
+            .unwrap();
+        let second = runtime
+            .execute(&call, TestLedger::with_balance("did:key:a", dec!(5)))
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert!(first.fuel_consumed > 0);
+    }
+
+    #[test]
+    fn test_trust_and_input() {
+        let wat = r#"
+            (module
+              (import "actoris" "trust" (func $trust (param i32 i32) (result i32)))
+              (import "actoris" "input_len" (func $input_len (result i32)))
+              (import "actoris" "read_input" (func $read_input (param i32) (result i32)))
+              (memory (export "memory") 1)
+              (func (export "execute") (result i32)
+                (drop (call $read_input (i32.const 0)))
+                ;; 0 when the input DID has trust 800, 1 otherwise
+                (i32.ne (call $trust (i32.const 0) (call $input_len)) (i32.const 800))))
+        "#;
+        let runtime = runtime();
+        let module_id = runtime.deploy(wat.as_bytes()).unwrap();
+        let ledger = Arc::new(TestLedger::default());
+
+        let call = ContractCall::new(&module_id, "execute").with_input(b"did:key:a".to_vec());
+        assert_eq!(runtime.execute(&call, ledger.clone()).unwrap().status, 0);
+
+        let call = ContractCall::new(&module_id, "execute").with_input(b"did:key:z".to_vec());
+        assert_eq!(runtime.execute(&call, ledger).unwrap().status, 1);
+    }
+
+    #[test]
+    fn test_fuel_and_memory_limits() {
+        let runtime = WasmRuntime::new(WasmLimits {
+            fuel: 100_000,
+            max_memory_bytes: 2 * 65536,
+            ..Default::default()
+        })
+        .unwrap();
+        let ledger = Arc::new(TestLedger::default());
+
+        let spin = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "execute") (result i32)
+                (loop $l (br $l))
+                (i32.const 0)))
+        "#;
+        let module_id = runtime.deploy(spin.as_bytes()).unwrap();
+        let call = ContractCall::new(&module_id, "execute");
+        assert!(matches!(
+            runtime.execute(&call, ledger.clone()),
+            Err(WasmError::FuelExhausted { .. })
+        ));
+
+        // Growing past the limit fails inside the guest
+        let grow = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "execute") (result i32)
+                (i32.eq (memory.grow (i32.const 4)) (i32.const -1))))
+        "#;
+        let module_id = runtime.deploy(grow.as_bytes()).unwrap();
+        let call = ContractCall::new(&module_id, "execute");
+        assert_eq!(runtime.execute(&call, ledger).unwrap().status, 1);
+    }
+
+    #[test]
+    fn test_deploy_rejects_foreign_imports() {
+        let wat = r#"
+            (module
+              (import "wasi_snapshot_preview1" "clock_time_get"
+                (func (param i32 i64 i32) (result i32)))
+              (memory (export "memory") 1))
+        "#;
+        assert!(matches!(
+            runtime().deploy(wat.as_bytes()),
+            Err(WasmError::InvalidModule { .. })
+        ));
+
+        let no_memory = r#"(module (func (export "execute") (result i32) (i32.const 0)))"#;
+        assert!(matches!(
+            runtime().deploy(no_memory.as_bytes()),
+            Err(WasmError::InvalidModule { .. })
+        ));
+    }
+}
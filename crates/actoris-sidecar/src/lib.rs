@@ -0,0 +1,11 @@
+//! # Actoris Sidecar
+//!
+//! Proxy that sits in front of an agent and:
+//!
+//! - Authenticates DID-signed requests and attributes them to their actor
+//! - Meters compute per request for HC billing
+//! - Hashes request/response pairs for outcome verification
+
+pub mod metering;
+pub mod proxy;
+pub mod telemetry;
@@ -1,9 +1,9 @@
 //! Actoris Sidecar - Pingora-based proxy with eBPF metering
 
-mod metering;
-mod proxy;
-mod telemetry;
+use std::sync::Arc;
 
+use actoris_common::security::RequestAuthenticator;
+use actoris_sidecar::proxy::interceptor::TrafficInterceptorBuilder;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -18,14 +18,39 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting Actoris Sidecar...");
 
+    // Requests must be signed by their actor unless explicitly opted out
+    let env = |name: &str| std::env::var(name).ok();
+    let mut builder = TrafficInterceptorBuilder::new().require_signed_requests(
+        env("SIDECAR_REQUIRE_SIGNED_REQUESTS").is_none_or(|v| v != "false"),
+    );
+    if let Some(addr) = env("SIDECAR_LISTEN_ADDR") {
+        builder = builder.listen_addr(&addr);
+    }
+    if let Some(addr) = env("SIDECAR_UPSTREAM_ADDR") {
+        builder = builder.upstream_addr(&addr);
+    }
+    if let Some(did) = env("ACTOR_DID") {
+        builder = builder.actor_did(&did);
+    }
+    let (interceptor, mut verification_rx) = builder.build();
+    let interceptor = interceptor.with_request_auth(Arc::new(RequestAuthenticator::default()));
+
     // TODO: Initialize Pingora proxy
     // TODO: Initialize eBPF metering
     // TODO: Connect to NATS for telemetry
+    tokio::spawn(async move {
+        while let Some(pair) = verification_rx.recv().await {
+            tracing::debug!(request_id = %pair.request.request_id, actor = %pair.request.actor_did, "Request completed");
+        }
+    });
 
     tracing::info!("Actoris Sidecar started successfully");
 
-    // Keep running
-    tokio::signal::ctrl_c().await?;
+    // Serve until shutdown
+    tokio::select! {
+        result = interceptor.start() => result?,
+        signal = tokio::signal::ctrl_c() => signal?,
+    }
     tracing::info!("Shutting down...");
 
     Ok(())
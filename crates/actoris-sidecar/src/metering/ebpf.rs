@@ -175,7 +175,8 @@ impl EbpfMeter {
     /// Check if we have required permissions
     fn check_permissions() -> bool {
         // Check for CAP_BPF or root
-        unsafe { libc::geteuid() == 0 } || Self::has_cap_bpf()
+        let root = unsafe { libc::geteuid() } == 0;
+        root || Self::has_cap_bpf()
     }
 
     fn has_cap_bpf() -> bool {
@@ -4,9 +4,11 @@
 //! - Compute metering (CPU time, memory)
 //! - Request/response hashing for verification
 //! - DID attachment to requests
+//! - DID-signed request authentication
 //! - Metrics collection for billing
 
 use crate::metering::{ComputeMetrics, MeteringCollector};
+use actoris_common::security::{RequestAuthenticator, RequestParts};
 use actoris_common::Result;
 use bytes::Bytes;
 use dashmap::DashMap;
@@ -95,6 +97,13 @@ pub struct ProxyConfig {
     pub enable_metering: bool,
     /// Buffer size for verification queue
     pub verification_buffer_size: usize,
+    /// Reject requests that are not signed by the acting DID
+    #[serde(default = "default_require_signed_requests")]
+    pub require_signed_requests: bool,
+}
+
+fn default_require_signed_requests() -> bool {
+    true
 }
 
 impl Default for ProxyConfig {
@@ -107,6 +116,7 @@ impl Default for ProxyConfig {
             timeout_ms: 30000,
             enable_metering: true,
             verification_buffer_size: 10000,
+            require_signed_requests: true,
         }
     }
 }
@@ -124,6 +134,8 @@ pub struct TrafficInterceptor {
     verification_tx: mpsc::Sender<RequestResponsePair>,
     /// Prometheus metrics
     metrics: Arc<ProxyMetrics>,
+    /// Verifies request signatures and tracks nonces
+    request_auth: Arc<RequestAuthenticator>,
 }
 
 /// Prometheus metrics for the proxy
@@ -218,6 +230,7 @@ impl TrafficInterceptor {
             metering: Arc::new(MeteringCollector::new(Duration::from_millis(100))),
             verification_tx,
             metrics: Arc::new(ProxyMetrics::new()),
+            request_auth: Arc::new(RequestAuthenticator::default()),
         }
     }
 
@@ -226,6 +239,12 @@ impl TrafficInterceptor {
         self.verification_tx.clone()
     }
 
+    /// Use a shared request authenticator
+    pub fn with_request_auth(mut self, auth: Arc<RequestAuthenticator>) -> Self {
+        self.request_auth = auth;
+        self
+    }
+
     /// Get metrics reference
     pub fn metrics(&self) -> Arc<ProxyMetrics> {
         self.metrics.clone()
@@ -304,6 +323,7 @@ impl TrafficInterceptor {
             metering: self.metering.clone(),
             verification_tx: self.verification_tx.clone(),
             metrics: self.metrics.clone(),
+            request_auth: self.request_auth.clone(),
         })
     }
 }
@@ -316,6 +336,7 @@ struct TrafficInterceptorHandler {
     metering: Arc<MeteringCollector>,
     verification_tx: mpsc::Sender<RequestResponsePair>,
     metrics: Arc<ProxyMetrics>,
+    request_auth: Arc<RequestAuthenticator>,
 }
 
 impl Clone for TrafficInterceptorHandler {
@@ -327,6 +348,7 @@ impl Clone for TrafficInterceptorHandler {
             metering: self.metering.clone(),
             verification_tx: self.verification_tx.clone(),
             metrics: self.metrics.clone(),
+            request_auth: self.request_auth.clone(),
         }
     }
 }
@@ -338,6 +360,63 @@ impl TrafficInterceptorHandler {
         format!("req-{}-{}", timestamp, count)
     }
 
+    /// Determine the acting DID for a request
+    ///
+    /// A signed request acts as its signer, and any `x-actor-did` header
+    /// must agree. Unsigned requests are rejected unless signatures are
+    /// optional, in which case they act as the sidecar's own DID and may
+    /// not claim another.
+    fn authenticate_actor(
+        &self,
+        parts: &hyper::http::request::Parts,
+        body: &[u8],
+    ) -> std::result::Result<String, (StatusCode, String)> {
+        let method = parts.method.as_str();
+        let claimed = parts
+            .headers
+            .get("x-actor-did")
+            .and_then(|v| v.to_str().ok());
+
+        let request = parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+            .fold(
+                RequestParts::new(method, parts.uri.path(), body),
+                |request, (name, value)| request.with_header(name, value),
+            );
+
+        if !request.is_signed() {
+            if self.config.require_signed_requests {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    "Request must be signed by the acting DID".to_string(),
+                ));
+            }
+            if let Some(claimed) = claimed.filter(|&c| c != self.config.actor_did) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    format!("Unsigned requests cannot act as {}", claimed),
+                ));
+            }
+            return Ok(self.config.actor_did.clone());
+        }
+
+        let signer = self
+            .request_auth
+            .authenticate(&request)
+            .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))?;
+        if let Some(claimed) = claimed {
+            if claimed != signer {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    format!("{} cannot act as {}", signer, claimed),
+                ));
+            }
+        }
+        Ok(signer)
+    }
+
     async fn handle_request(
         &self,
         req: Request<Incoming>,
@@ -369,14 +448,6 @@ impl TrafficInterceptorHandler {
         let path = req.uri().path().to_string();
         let headers = TrafficInterceptor::extract_headers(req.headers());
 
-        // Get actor DID from header or config
-        let actor_did = req
-            .headers()
-            .get("x-actor-did")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| self.config.actor_did.clone());
-
         let client_did = req
             .headers()
             .get("x-client-did")
@@ -397,6 +468,18 @@ impl TrafficInterceptorHandler {
             }
         };
 
+        let actor_did = match self.authenticate_actor(&parts, &body_bytes) {
+            Ok(did) => did,
+            Err((status, reason)) => {
+                warn!(request_id = %request_id, path = %path, "Rejected request: {}", reason);
+                self.metrics.requests_active.dec();
+                return Ok(Response::builder()
+                    .status(status)
+                    .body(Full::new(Bytes::from(reason)))
+                    .unwrap());
+            }
+        };
+
         let body_hash = TrafficInterceptor::hash_body(&body_bytes);
         let content_length = body_bytes.len();
         self.metrics.request_body_bytes.observe(content_length as f64);
@@ -601,6 +684,11 @@ impl TrafficInterceptorBuilder {
         self
     }
 
+    pub fn require_signed_requests(mut self, require: bool) -> Self {
+        self.config.require_signed_requests = require;
+        self
+    }
+
     pub fn buffer_size(mut self, size: usize) -> Self {
         self.buffer_size = size;
         self
@@ -655,5 +743,68 @@ mod tests {
         assert_eq!(config.listen_addr, "127.0.0.1:8080");
         assert_eq!(config.upstream_addr, "127.0.0.1:8000");
         assert!(config.enable_metering);
+        assert!(config.require_signed_requests);
+    }
+
+    #[test]
+    fn test_authenticate_actor() {
+        use actoris_common::security::RequestSigner;
+        use ed25519_dalek::SigningKey;
+
+        let (interceptor, _rx) = TrafficInterceptorBuilder::new().build();
+        let handler = interceptor.clone_for_request();
+        let signer = RequestSigner::new(SigningKey::from_bytes(&[7u8; 32]));
+        let body = br#"{"task":"summarize"}"#;
+
+        let request = |signed: bool, actor: Option<&str>| {
+            let mut builder = Request::builder().method("POST").uri("/v1/run");
+            if signed {
+                for (name, value) in signer.sign("POST", "/v1/run", body).pairs() {
+                    builder = builder.header(name, value);
+                }
+            }
+            if let Some(actor) = actor {
+                builder = builder.header("x-actor-did", actor);
+            }
+            builder.body(()).unwrap().into_parts().0
+        };
+
+        // The signer becomes the actor
+        let parts = request(true, None);
+        assert_eq!(
+            handler.authenticate_actor(&parts, body).unwrap(),
+            signer.did()
+        );
+
+        // Replays, tampered bodies and unsigned requests are rejected
+        let err = handler.authenticate_actor(&parts, body).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+        let parts = request(true, None);
+        let err = handler.authenticate_actor(&parts, b"{}").unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+        let parts = request(false, Some(signer.did()));
+        let err = handler.authenticate_actor(&parts, body).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+
+        // The claimed actor must be the signer
+        let parts = request(true, Some("did:key:z6MkImpostor"));
+        let err = handler.authenticate_actor(&parts, body).unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
+
+        // With signatures optional, unsigned requests act as the sidecar and
+        // an unsigned header cannot claim anyone else
+        let (interceptor, _rx) = TrafficInterceptorBuilder::new()
+            .actor_did("did:key:z6MkSidecar")
+            .require_signed_requests(false)
+            .build();
+        let handler = interceptor.clone_for_request();
+        let parts = request(false, None);
+        assert_eq!(
+            handler.authenticate_actor(&parts, body).unwrap(),
+            "did:key:z6MkSidecar"
+        );
+        let parts = request(false, Some(signer.did()));
+        let err = handler.authenticate_actor(&parts, body).unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
     }
 }
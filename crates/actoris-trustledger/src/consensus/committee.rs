@@ -0,0 +1,284 @@
+//! Per-request oracle committee selection (VRF sortition)
+//!
+//! Instead of sending every verification to the same global quorum, each
+//! request is assigned a committee sampled from the registered oracle pool:
+//! 1. The verifier derives a seed from the request ID and the latest committed
+//!    block hash
+//! 2. Every oracle evaluates its VRF over the seed and submits the proof as a
+//!    sortition ticket
+//! 3. Once every registered oracle has a ticket in, or the ticket window
+//!    closes, the N oracles with the lowest VRF outputs form the committee
+//! 4. Only committee members' votes count; their tickets stay on the votes so
+//!    anyone can re-check the selection
+//!
+//! Ranking outputs instead of thresholding each one gives a committee of
+//! exactly `min(N, tickets)` members. Actors cannot predict membership before
+//! the block is committed, and oracles cannot grind it because VRF outputs are
+//! unique per key and seed.
+
+use crate::consensus::malachite::{Block, BlockHash, CommitCallback, QuorumCertificate};
+use actoris_common::{
+    crypto::vrf::{self, VrfOutput, VrfProof},
+    error::VerificationError,
+    Result,
+};
+use async_trait::async_trait;
+use ed25519_dalek::SigningKey;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Domain separator for committee seeds
+const SEED_DOMAIN: &[u8] = b"actoris.committee.v1";
+
+/// Default time oracles have to submit sortition tickets
+const DEFAULT_TICKET_WINDOW: Duration = Duration::from_millis(500);
+
+/// Seed material fixed at submission time for one verification request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitteeSeed {
+    /// VRF input (alpha) every oracle evaluates
+    pub seed: [u8; 32],
+    /// Block hash the seed was derived from
+    pub block_hash: BlockHash,
+    /// Registered pool size when the seed was drawn
+    pub pool_size: usize,
+}
+
+/// Samples per-request committees from the registered oracle pool
+pub struct CommitteeSelector {
+    /// Committee size (N)
+    committee_size: usize,
+    /// How long tickets are collected before the committee is fixed
+    ticket_window: Duration,
+    /// Registered oracles (DID -> Ed25519 public key)
+    oracles: RwLock<HashMap<String, [u8; 32]>>,
+    /// Hash of the latest committed block
+    latest_block_hash: RwLock<BlockHash>,
+}
+
+impl CommitteeSelector {
+    /// Create a selector with the given committee size
+    pub fn new(committee_size: usize) -> Self {
+        Self {
+            committee_size,
+            ticket_window: DEFAULT_TICKET_WINDOW,
+            oracles: RwLock::new(HashMap::new()),
+            latest_block_hash: RwLock::new(Block::genesis().hash),
+        }
+    }
+
+    /// Set how long tickets are collected before the committee is fixed
+    pub fn with_ticket_window(mut self, window: Duration) -> Self {
+        self.ticket_window = window;
+        self
+    }
+
+    /// Committee size
+    pub fn committee_size(&self) -> usize {
+        self.committee_size
+    }
+
+    /// Ticket collection window
+    pub fn ticket_window(&self) -> Duration {
+        self.ticket_window
+    }
+
+    /// Register an oracle's VRF public key
+    pub fn register_oracle(&self, oracle_did: &str, public_key: [u8; 32]) {
+        self.oracles
+            .write()
+            .insert(oracle_did.to_string(), public_key);
+        info!(oracle = %oracle_did, "Oracle registered for committee selection");
+    }
+
+    /// Remove an oracle from the pool
+    pub fn deregister_oracle(&self, oracle_did: &str) -> bool {
+        self.oracles.write().remove(oracle_did).is_some()
+    }
+
+    /// Check if an oracle is registered
+    pub fn is_registered(&self, oracle_did: &str) -> bool {
+        self.oracles.read().contains_key(oracle_did)
+    }
+
+    /// Number of registered oracles
+    pub fn pool_size(&self) -> usize {
+        self.oracles.read().len()
+    }
+
+    /// Update the latest committed block hash
+    pub fn set_block_hash(&self, block_hash: BlockHash) {
+        *self.latest_block_hash.write() = block_hash;
+    }
+
+    /// Hash of the latest committed block
+    pub fn latest_block_hash(&self) -> BlockHash {
+        *self.latest_block_hash.read()
+    }
+
+    /// Derive the committee seed for a request from a given block hash
+    pub fn derive_seed(request_id: &str, block_hash: &BlockHash) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(SEED_DOMAIN);
+        hasher.update(block_hash);
+        hasher.update(request_id.as_bytes());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Draw the seed for a new request against the latest block
+    pub fn draw(&self, request_id: &str) -> CommitteeSeed {
+        let block_hash = self.latest_block_hash();
+        CommitteeSeed {
+            seed: Self::derive_seed(request_id, &block_hash),
+            block_hash,
+            pool_size: self.pool_size(),
+        }
+    }
+
+    /// Run sortition locally (oracle side)
+    ///
+    /// Returns the ticket to submit for the request; whether it makes the
+    /// committee depends on the other oracles' tickets.
+    pub fn sortition(&self, signing_key: &SigningKey, seed: &CommitteeSeed) -> VrfProof {
+        vrf::prove(signing_key, &seed.seed)
+    }
+
+    /// Verify an oracle's sortition ticket, returning its VRF output
+    pub fn verify_ticket(
+        &self,
+        oracle_did: &str,
+        seed: &CommitteeSeed,
+        proof: &VrfProof,
+    ) -> Result<VrfOutput> {
+        let public_key = self
+            .oracles
+            .read()
+            .get(oracle_did)
+            .copied()
+            .ok_or_else(|| VerificationError::UnknownOracle {
+                oracle_did: oracle_did.to_string(),
+            })?;
+
+        let output = vrf::verify(&public_key, &seed.seed, proof).map_err(|_| {
+            VerificationError::NotInCommittee {
+                oracle_did: oracle_did.to_string(),
+            }
+        })?;
+
+        debug!(oracle = %oracle_did, "Sortition ticket verified");
+        Ok(output)
+    }
+
+    /// Pick the committee: the N tickets with the lowest VRF outputs
+    pub fn select(&self, tickets: &[(String, VrfOutput)]) -> Vec<String> {
+        let mut ranked: Vec<&(String, VrfOutput)> = tickets.iter().collect();
+        ranked.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .take(self.committee_size)
+            .map(|(did, _)| did.clone())
+            .collect()
+    }
+}
+
+/// Keep the selector's block hash in sync with consensus commits
+#[async_trait]
+impl CommitCallback for CommitteeSelector {
+    async fn on_commit(&self, block: &Block, _qc: &QuorumCertificate) -> Result<()> {
+        self.set_block_hash(block.hash);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn selector_with_oracles(
+        committee_size: usize,
+        count: usize,
+    ) -> (CommitteeSelector, Vec<(String, SigningKey)>) {
+        let selector = CommitteeSelector::new(committee_size);
+        let oracles: Vec<(String, SigningKey)> = (0..count)
+            .map(|i| {
+                let key = SigningKey::generate(&mut OsRng);
+                let did = format!("did:key:oracle{}", i);
+                selector.register_oracle(&did, key.verifying_key().to_bytes());
+                (did, key)
+            })
+            .collect();
+        (selector, oracles)
+    }
+
+    #[test]
+    fn test_seed_depends_on_block_hash() {
+        let selector = CommitteeSelector::new(5);
+        let before = selector.draw("req-1");
+
+        selector.set_block_hash([7u8; 32]);
+        let after = selector.draw("req-1");
+
+        assert_ne!(before.seed, after.seed);
+        assert_eq!(after.block_hash, [7u8; 32]);
+        assert_ne!(selector.draw("req-2").seed, after.seed);
+    }
+
+    #[test]
+    fn test_tickets_verify_against_own_key() {
+        let (selector, oracles) = selector_with_oracles(5, 3);
+        selector.set_block_hash([3u8; 32]);
+        let seed = selector.draw("req-1");
+
+        let ticket = selector.sortition(&oracles[0].1, &seed);
+        let output = selector.verify_ticket(&oracles[0].0, &seed, &ticket).unwrap();
+        assert_eq!(output, ticket.to_output().unwrap());
+
+        // Bound to the oracle, the seed and the registry
+        assert!(selector.verify_ticket(&oracles[1].0, &seed, &ticket).is_err());
+        assert!(selector
+            .verify_ticket(&oracles[0].0, &selector.draw("req-2"), &ticket)
+            .is_err());
+        assert!(selector
+            .verify_ticket("did:key:unknown", &seed, &ticket)
+            .is_err());
+    }
+
+    #[test]
+    fn test_committee_has_exact_size() {
+        // A few rounds over a small pool: each ticket is a full ECVRF proof
+        let (selector, oracles) = selector_with_oracles(5, 12);
+
+        for i in 0..4 {
+            let seed = selector.draw(&format!("req-{}", i));
+            let tickets: Vec<(String, VrfOutput)> = oracles
+                .iter()
+                .map(|(did, key)| {
+                    let ticket = selector.sortition(key, &seed);
+                    (did.clone(), selector.verify_ticket(did, &seed, &ticket).unwrap())
+                })
+                .collect();
+
+            let committee = selector.select(&tickets);
+            assert_eq!(committee.len(), 5);
+
+            // Members are exactly the lowest outputs
+            let mut outputs: Vec<VrfOutput> = tickets.iter().map(|(_, o)| *o).collect();
+            outputs.sort();
+            let cutoff = outputs[4];
+            for (did, output) in &tickets {
+                assert_eq!(committee.contains(did), *output <= cutoff);
+            }
+        }
+
+        // Fewer tickets than N: everyone who showed up serves
+        let seed = selector.draw("req-small");
+        let few: Vec<(String, VrfOutput)> = oracles[..3]
+            .iter()
+            .map(|(did, key)| (did.clone(), selector.sortition(key, &seed).to_output().unwrap()))
+            .collect();
+        assert_eq!(selector.select(&few).len(), 3);
+    }
+}
@@ -505,10 +505,8 @@ impl MalachiteConsensus {
         }
 
         // Take requests for this block
-        let requests: Vec<_> = state
-            .pending_requests
-            .drain(..self.config.max_txs_per_block.min(state.pending_requests.len()))
-            .collect();
+        let take = self.config.max_txs_per_block.min(state.pending_requests.len());
+        let requests: Vec<_> = state.pending_requests.drain(..take).collect();
 
         let parent_hash = state
             .committed_blocks
@@ -4,7 +4,9 @@
 //! - Malachite BFT consensus (HotStuff-2 based)
 //! - Oracle node management
 //! - Quorum management for 3-of-N verification
+//! - Per-request committee selection via VRF sortition
 
+pub mod committee;
 pub mod malachite;
 pub mod oracle;
 pub mod quorum;
@@ -14,5 +16,6 @@ pub use malachite::{
     MalachiteConsensus, QuorumCertificate, VerificationRequest, VerificationResult, Vote,
     VoteType, ViewChange,
 };
+pub use committee::{CommitteeSeed, CommitteeSelector};
 pub use oracle::OracleNode;
-pub use quorum::QuorumManager;
+pub use quorum::{QuorumManager, TallyOutcome};
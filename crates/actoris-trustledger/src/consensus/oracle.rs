@@ -1,6 +1,8 @@
 //! Oracle node implementation for action verification
 
-use actoris_common::crypto::frost::FrostKeyShare;
+use crate::consensus::committee::{CommitteeSeed, CommitteeSelector};
+use actoris_common::crypto::{frost::FrostKeyShare, vrf::VrfProof};
+use ed25519_dalek::SigningKey;
 use std::sync::Arc;
 
 /// Oracle node for participating in verification consensus
@@ -9,6 +11,8 @@ pub struct OracleNode {
     pub did: String,
     /// FROST key share
     _frost_share: Arc<FrostKeyShare>,
+    /// DID signing key used for VRF sortition
+    vrf_key: Option<SigningKey>,
 }
 
 impl OracleNode {
@@ -17,6 +21,29 @@ impl OracleNode {
         Self {
             did,
             _frost_share: Arc::new(frost_share),
+            vrf_key: None,
         }
     }
+
+    /// Set the signing key used for committee sortition
+    pub fn with_vrf_key(mut self, key: SigningKey) -> Self {
+        self.vrf_key = Some(key);
+        self
+    }
+
+    /// VRF public key to register with the committee selector
+    pub fn vrf_public_key(&self) -> Option<[u8; 32]> {
+        self.vrf_key.as_ref().map(|k| k.verifying_key().to_bytes())
+    }
+
+    /// Draw this oracle's sortition ticket for a request
+    ///
+    /// Returns `None` if the node has no VRF key.
+    pub fn committee_ticket(
+        &self,
+        selector: &CommitteeSelector,
+        seed: &CommitteeSeed,
+    ) -> Option<VrfProof> {
+        Some(selector.sortition(self.vrf_key.as_ref()?, seed))
+    }
 }
@@ -1,14 +1,34 @@
 //! Quorum management for 3-of-N verification
 
+use parking_lot::RwLock;
+use std::collections::HashSet;
+
+/// Result of tallying votes against the quorum threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyOutcome {
+    /// Passing is still possible but not yet reached
+    Pending,
+    /// Approvals reached the threshold
+    Passed,
+    /// Remaining voters cannot bring approvals up to the threshold
+    Failed,
+}
+
 /// Manages quorum formation and voting
 pub struct QuorumManager {
     threshold: u8,
     total: u8,
+    /// DIDs of registered oracles allowed to vote
+    members: RwLock<HashSet<String>>,
 }
 
 impl QuorumManager {
     pub fn new(threshold: u8, total: u8) -> Self {
-        Self { threshold, total }
+        Self {
+            threshold,
+            total,
+            members: RwLock::new(HashSet::new()),
+        }
     }
 
     pub fn threshold(&self) -> u8 {
@@ -18,4 +38,82 @@ impl QuorumManager {
     pub fn total(&self) -> u8 {
         self.total
     }
+
+    /// Register an oracle as a voting member
+    pub fn register(&self, oracle_did: &str) -> bool {
+        self.members.write().insert(oracle_did.to_string())
+    }
+
+    /// Remove an oracle from the voting members
+    pub fn deregister(&self, oracle_did: &str) -> bool {
+        self.members.write().remove(oracle_did)
+    }
+
+    /// Check if an oracle is a registered voting member
+    pub fn is_member(&self, oracle_did: &str) -> bool {
+        self.members.read().contains(oracle_did)
+    }
+
+    /// Number of registered voting members
+    pub fn member_count(&self) -> usize {
+        self.members.read().len()
+    }
+
+    /// Tally votes cast so far out of `eligible` possible voters
+    pub fn tally(&self, approvals: usize, rejections: usize, eligible: usize) -> TallyOutcome {
+        Self::tally_at(self.threshold, approvals, rejections, eligible)
+    }
+
+    /// Tally votes against an explicit threshold (e.g. an escalated quorum)
+    pub fn tally_at(
+        threshold: u8,
+        approvals: usize,
+        rejections: usize,
+        eligible: usize,
+    ) -> TallyOutcome {
+        let threshold = threshold as usize;
+        if approvals >= threshold {
+            return TallyOutcome::Passed;
+        }
+
+        let remaining = eligible.saturating_sub(approvals + rejections);
+        if approvals + remaining < threshold {
+            return TallyOutcome::Failed;
+        }
+
+        TallyOutcome::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_membership() {
+        let quorum = QuorumManager::new(3, 5);
+        assert!(quorum.register("did:key:oracle1"));
+        assert!(!quorum.register("did:key:oracle1"));
+        assert!(quorum.is_member("did:key:oracle1"));
+        assert_eq!(quorum.member_count(), 1);
+
+        assert!(quorum.deregister("did:key:oracle1"));
+        assert!(!quorum.is_member("did:key:oracle1"));
+    }
+
+    #[test]
+    fn test_tally() {
+        let quorum = QuorumManager::new(3, 5);
+
+        assert_eq!(quorum.tally(3, 0, 5), TallyOutcome::Passed);
+        assert_eq!(quorum.tally(3, 2, 5), TallyOutcome::Passed);
+        assert_eq!(quorum.tally(2, 2, 5), TallyOutcome::Pending);
+
+        // Three rejections out of five leave at most two approvals
+        assert_eq!(quorum.tally(0, 3, 5), TallyOutcome::Failed);
+        assert_eq!(quorum.tally(2, 3, 5), TallyOutcome::Failed);
+
+        // Too few eligible voters can never pass
+        assert_eq!(quorum.tally(1, 1, 3), TallyOutcome::Failed);
+    }
 }
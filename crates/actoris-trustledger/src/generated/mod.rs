@@ -103,6 +103,16 @@ pub mod common {
             Restricted = 4,
         }
 
+        /// Verification depth applied to an action
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration, Serialize, Deserialize)]
+        #[repr(i32)]
+        pub enum VerificationMode {
+            Unspecified = 0,
+            Optimistic = 1,
+            Sampled = 2,
+            FullQuorum = 3,
+        }
+
         /// FROST threshold signature
         #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
         pub struct FrostSignature {
@@ -129,6 +139,8 @@ pub mod common {
             pub reason: Option<String>,
             #[prost(int64, tag = "4")]
             pub timestamp: i64,
+            #[prost(bytes = "vec", optional, tag = "5")]
+            pub vrf_proof: Option<Vec<u8>>,
         }
 
         /// Verification result
@@ -181,6 +193,10 @@ pub mod common {
             pub verified_at: i64,
             #[prost(uint64, optional, tag = "15")]
             pub stream_position: Option<u64>,
+            #[prost(enumeration = "VerificationMode", tag = "16")]
+            pub verification_mode: i32,
+            #[prost(int64, optional, tag = "17")]
+            pub challenge_deadline: Option<i64>,
         }
 
         /// Error details
@@ -230,6 +246,7 @@ pub mod trustledger {
             Completed = 3,
             Failed = 4,
             Timeout = 5,
+            Escalated = 6,
         }
 
         /// SubmitAction request
@@ -255,6 +272,10 @@ pub mod trustledger {
             pub synchronous: bool,
             #[prost(uint32, tag = "10")]
             pub timeout_ms: u32,
+            #[prost(enumeration = "common::TaskComplexity", optional, tag = "11")]
+            pub task_complexity: Option<i32>,
+            #[prost(enumeration = "common::DataSensitivity", optional, tag = "12")]
+            pub data_sensitivity: Option<i32>,
         }
 
         /// SubmitAction response
@@ -403,6 +424,26 @@ pub mod trustledger {
             pub stream_position: u64,
         }
 
+        /// ChallengeAction request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ChallengeActionRequest {
+            #[prost(string, tag = "1")]
+            pub request_id: String,
+            #[prost(string, tag = "2")]
+            pub challenger_did: String,
+            #[prost(string, tag = "3")]
+            pub reason: String,
+        }
+
+        /// ChallengeAction response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct ChallengeActionResponse {
+            #[prost(bool, tag = "1")]
+            pub accepted: bool,
+            #[prost(enumeration = "VerificationStatus", tag = "2")]
+            pub status: i32,
+        }
+
         /// GetLedgerStats request
         #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
         pub struct GetLedgerStatsRequest {
@@ -451,6 +492,26 @@ pub mod trustledger {
             pub quorum_position: u32,
         }
 
+        /// SubmitSortitionTicket request
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct SubmitSortitionTicketRequest {
+            #[prost(string, tag = "1")]
+            pub request_id: String,
+            #[prost(string, tag = "2")]
+            pub oracle_did: String,
+            #[prost(bytes = "vec", tag = "3")]
+            pub vrf_proof: Vec<u8>,
+        }
+
+        /// SubmitSortitionTicket response
+        #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+        pub struct SubmitSortitionTicketResponse {
+            #[prost(bool, tag = "1")]
+            pub committee_formed: bool,
+            #[prost(string, repeated, tag = "2")]
+            pub members: Vec<String>,
+        }
+
         /// SubmitPartialSignature request
         #[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
         pub struct SubmitPartialSignatureRequest {
@@ -466,6 +527,8 @@ pub mod trustledger {
             pub approved: bool,
             #[prost(string, optional, tag = "6")]
             pub reason: Option<String>,
+            #[prost(bytes = "vec", optional, tag = "7")]
+            pub vrf_proof: Option<Vec<u8>>,
         }
 
         /// SubmitPartialSignature response
@@ -5,10 +5,17 @@
 use crate::generated::common::v1 as proto_common;
 use crate::generated::trustledger::v1 as proto;
 use crate::ledger::eventstore::EventStoreClient;
-use crate::verification::verifier::{ActionVerifier, VerificationStatus, VerifierConfig};
-use actoris_common::crypto::frost::PartialSignature;
+use crate::verification::policy::{RiskProfile, TrustLookup};
+use crate::verification::verifier::{
+    ActionVerifier, OracleShare, VerificationStatus,
+};
+use actoris_common::crypto::frost::{SignatureShare, SigningCommitment};
 use actoris_common::crypto::merkle::MerkleTree;
-use actoris_common::types::outcome_record::OutcomeRecord;
+use actoris_common::crypto::vrf::VrfProof;
+use actoris_common::error::VerificationError;
+use actoris_common::security::RequestAuthenticator;
+use actoris_common::types::outcome_record::{OutcomeRecord, VerificationMode};
+use actoris_common::{ActorisError, DataSensitivity, TaskComplexity};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::pin::Pin;
@@ -28,6 +35,10 @@ pub struct TrustLedgerGrpcService {
     records_cache: Arc<RwLock<HashMap<String, OutcomeRecord>>>,
     /// Statistics tracking
     stats: Arc<RwLock<LedgerStats>>,
+    /// Checks that actions are signed by their actor
+    request_auth: Option<Arc<RequestAuthenticator>>,
+    /// Actor trust for risk tiering (None = every action gets a full quorum)
+    trust: Option<Arc<dyn TrustLookup>>,
 }
 
 /// Ledger statistics
@@ -63,11 +74,18 @@ impl LedgerStats {
 impl TrustLedgerGrpcService {
     /// Create a new TrustLedger gRPC service
     pub fn new(verifier: ActionVerifier) -> Self {
+        Self::from_shared(Arc::new(verifier))
+    }
+
+    /// Create a service around a verifier shared with other tasks
+    pub fn from_shared(verifier: Arc<ActionVerifier>) -> Self {
         Self {
-            verifier: Arc::new(verifier),
+            verifier,
             eventstore: None,
             records_cache: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(LedgerStats::default())),
+            request_auth: None,
+            trust: None,
         }
     }
 
@@ -77,6 +95,18 @@ impl TrustLedgerGrpcService {
         self
     }
 
+    /// Require submitted actions to be signed by `actor_did`
+    pub fn with_request_auth(mut self, auth: Arc<RequestAuthenticator>) -> Self {
+        self.request_auth = Some(auth);
+        self
+    }
+
+    /// Look up actor trust server-side when tiering verification depth
+    pub fn with_trust_lookup(mut self, trust: Arc<dyn TrustLookup>) -> Self {
+        self.trust = Some(trust);
+        self
+    }
+
     /// Convert internal verification status to proto
     fn status_to_proto(status: &VerificationStatus) -> i32 {
         match status {
@@ -85,11 +115,85 @@ impl TrustLedgerGrpcService {
             VerificationStatus::Completed(_) => proto::VerificationStatus::Completed as i32,
             VerificationStatus::Failed(_) => proto::VerificationStatus::Failed as i32,
             VerificationStatus::Timeout => proto::VerificationStatus::Timeout as i32,
+            VerificationStatus::Escalated => proto::VerificationStatus::Escalated as i32,
+        }
+    }
+
+    /// Convert verification mode to proto
+    fn mode_to_proto(mode: &VerificationMode) -> (i32, Option<i64>) {
+        match mode {
+            VerificationMode::Optimistic { challenge_deadline } => (
+                proto_common::VerificationMode::Optimistic as i32,
+                Some(*challenge_deadline),
+            ),
+            VerificationMode::Sampled { .. } => {
+                (proto_common::VerificationMode::Sampled as i32, None)
+            }
+            VerificationMode::FullQuorum => {
+                (proto_common::VerificationMode::FullQuorum as i32, None)
+            }
+        }
+    }
+
+    /// Build a risk profile from request fields and the actor's ledger trust
+    ///
+    /// Returns None (full quorum) when the actor's trust is unknown.
+    fn risk_from_request(
+        &self,
+        req: &proto::SubmitActionRequest,
+        compute_hc: Decimal,
+    ) -> Option<RiskProfile> {
+        let trust_score = self
+            .trust
+            .as_ref()?
+            .trust_score(&req.actor_did, &req.action_type)?;
+        let mut risk = RiskProfile::new(compute_hc, trust_score);
+
+        match req
+            .task_complexity
+            .and_then(|c| proto_common::TaskComplexity::try_from(c).ok())
+        {
+            Some(proto_common::TaskComplexity::Low) => {
+                risk = risk.with_complexity(TaskComplexity::Low)
+            }
+            Some(proto_common::TaskComplexity::Medium) => {
+                risk = risk.with_complexity(TaskComplexity::Medium)
+            }
+            Some(proto_common::TaskComplexity::High) => {
+                risk = risk.with_complexity(TaskComplexity::High)
+            }
+            Some(proto_common::TaskComplexity::Critical) => {
+                risk = risk.with_complexity(TaskComplexity::Critical)
+            }
+            _ => {}
+        }
+
+        match req
+            .data_sensitivity
+            .and_then(|s| proto_common::DataSensitivity::try_from(s).ok())
+        {
+            Some(proto_common::DataSensitivity::Public) => {
+                risk = risk.with_sensitivity(DataSensitivity::Public)
+            }
+            Some(proto_common::DataSensitivity::Internal) => {
+                risk = risk.with_sensitivity(DataSensitivity::Internal)
+            }
+            Some(proto_common::DataSensitivity::Confidential) => {
+                risk = risk.with_sensitivity(DataSensitivity::Confidential)
+            }
+            Some(proto_common::DataSensitivity::Restricted) => {
+                risk = risk.with_sensitivity(DataSensitivity::Restricted)
+            }
+            _ => {}
         }
+
+        Some(risk)
     }
 
     /// Convert internal OutcomeRecord to proto
     fn record_to_proto(record: &OutcomeRecord) -> proto_common::OutcomeRecord {
+        let (verification_mode, challenge_deadline) =
+            Self::mode_to_proto(&record.verification_mode);
         proto_common::OutcomeRecord {
             id: record.id.to_string(),
             actor_did: record.actor_did.clone(),
@@ -112,6 +216,7 @@ impl TrustLedgerGrpcService {
                         approved: v.approved,
                         reason: v.reason.clone(),
                         timestamp: v.timestamp,
+                        vrf_proof: v.vrf_proof.clone(),
                     })
                     .collect(),
                 failure_reason: record.verification.failure_reason.clone(),
@@ -129,6 +234,8 @@ impl TrustLedgerGrpcService {
             submitted_at: record.submitted_at,
             verified_at: record.verified_at,
             stream_position: None,
+            verification_mode,
+            challenge_deadline,
         }
     }
 }
@@ -142,25 +249,56 @@ impl TrustLedgerService for TrustLedgerGrpcService {
         &self,
         request: Request<proto::SubmitActionRequest>,
     ) -> Result<Response<proto::SubmitActionResponse>, Status> {
+        if let Some(auth) = &self.request_auth {
+            let caller = auth.authenticate_grpc(
+                &request,
+                "/actoris.trustledger.v1.TrustLedgerService/SubmitAction",
+            )?;
+            let actor = &request.get_ref().actor_did;
+            if &caller != actor {
+                warn!(caller = %caller, actor = %actor, "Action submitted for another actor");
+                return Err(Status::permission_denied(format!(
+                    "{} cannot submit actions as {}",
+                    caller, actor
+                )));
+            }
+        }
+
         let req = request.into_inner();
 
         // Parse compute amount
         let compute_hc = Decimal::from_str(&req.compute_hc)
             .map_err(|e| Status::invalid_argument(format!("Invalid compute_hc: {}", e)))?;
 
-        // Submit to verifier
-        let request_id = self
-            .verifier
-            .submit_action(
-                &req.actor_did,
-                &req.client_did,
-                &req.action_type,
-                &req.input,
-                &req.output,
-                compute_hc,
-            )
-            .await
-            .map_err(|e| Status::internal(format!("Submission failed: {}", e)))?;
+        // Submit to verifier, tiering verification depth when a risk profile is given
+        let submitted = match self.risk_from_request(&req, compute_hc) {
+            Some(risk) => {
+                self.verifier
+                    .submit_action_with_risk(
+                        &req.actor_did,
+                        &req.client_did,
+                        &req.action_type,
+                        &req.input,
+                        &req.output,
+                        &risk,
+                    )
+                    .await
+            }
+            None => {
+                self.verifier
+                    .submit_action(
+                        &req.actor_did,
+                        &req.client_did,
+                        &req.action_type,
+                        &req.input,
+                        &req.output,
+                        compute_hc,
+                    )
+                    .await
+            }
+        };
+        let request_id =
+            submitted.map_err(|e| Status::internal(format!("Submission failed: {}", e)))?;
 
         info!(request_id = %request_id, actor = %req.actor_did, "Action submitted");
 
@@ -231,6 +369,13 @@ impl TrustLedgerService for TrustLedgerGrpcService {
                                 status: proto::VerificationStatus::Timeout as i32,
                             }));
                         }
+                        VerificationStatus::Escalated => {
+                            return Ok(Response::new(proto::SubmitActionResponse {
+                                request_id,
+                                outcome_record: None,
+                                status: proto::VerificationStatus::Escalated as i32,
+                            }));
+                        }
                         _ => {
                             // Still in progress, wait
                             tokio::time::sleep(std::time::Duration::from_millis(50)).await;
@@ -483,6 +628,8 @@ impl TrustLedgerService for TrustLedgerGrpcService {
                     }
 
                     position += 1;
+                    let (verification_mode, challenge_deadline) =
+                        Self::mode_to_proto(&record_data.verification_mode);
 
                     let event = proto::VerificationEvent {
                         record: Some(proto_common::OutcomeRecord {
@@ -514,6 +661,8 @@ impl TrustLedgerService for TrustLedgerGrpcService {
                             submitted_at: record_data.submitted_at,
                             verified_at: record_data.verified_at,
                             stream_position: Some(position),
+                            verification_mode,
+                            challenge_deadline,
                         }),
                         stream_position: position,
                     };
@@ -546,6 +695,64 @@ impl TrustLedgerService for TrustLedgerGrpcService {
             stream_position: stats.stream_position,
         }))
     }
+
+    /// Challenge an optimistically accepted action
+    ///
+    /// The challenger must sign the request; without request authentication
+    /// configured, challenges are refused.
+    #[instrument(skip(self, request))]
+    async fn challenge_action(
+        &self,
+        request: Request<proto::ChallengeActionRequest>,
+    ) -> Result<Response<proto::ChallengeActionResponse>, Status> {
+        let auth = self
+            .request_auth
+            .as_ref()
+            .ok_or_else(|| Status::unauthenticated("Challenges require request authentication"))?;
+        let caller = auth.authenticate_grpc(
+            &request,
+            "/actoris.trustledger.v1.TrustLedgerService/ChallengeAction",
+        )?;
+        let challenger = &request.get_ref().challenger_did;
+        if &caller != challenger {
+            warn!(caller = %caller, challenger = %challenger, "Challenge filed for another DID");
+            return Err(Status::permission_denied(format!(
+                "{} cannot challenge as {}",
+                caller, challenger
+            )));
+        }
+
+        let req = request.into_inner();
+
+        let accepted = match self.verifier.get_status(&req.request_id).await {
+            Some(VerificationStatus::Completed(record)) => Some(record),
+            Some(_) => None,
+            None => return Err(Status::not_found("Request not found")),
+        };
+
+        self.verifier
+            .challenge(&req.request_id, &req.challenger_did, &req.reason)
+            .await
+            .map_err(|e| Status::failed_precondition(format!("Challenge rejected: {}", e)))?;
+
+        // The optimistic record no longer stands
+        if let Some(record) = accepted {
+            let mut cache = self.records_cache.write().await;
+            cache.remove(&record.id.to_string());
+        }
+
+        let status = self
+            .verifier
+            .get_status(&req.request_id)
+            .await
+            .map(|s| Self::status_to_proto(&s))
+            .unwrap_or(proto::VerificationStatus::Unspecified as i32);
+
+        Ok(Response::new(proto::ChallengeActionResponse {
+            accepted: true,
+            status,
+        }))
+    }
 }
 
 /// Oracle service for internal oracle nodes
@@ -571,6 +778,26 @@ impl OracleGrpcService {
             oracles: Arc::new(RwLock::new(HashMap::new())),
         }
     }
+
+    /// Map vote errors to gRPC status codes
+    fn vote_error_to_status(err: ActorisError) -> Status {
+        match &err {
+            ActorisError::Verification(VerificationError::RequestNotFound { .. }) => {
+                Status::not_found(err.to_string())
+            }
+            ActorisError::Verification(VerificationError::DuplicateVote { .. }) => {
+                Status::already_exists(err.to_string())
+            }
+            ActorisError::Verification(
+                VerificationError::UnknownOracle { .. } | VerificationError::NotInCommittee { .. },
+            ) => Status::permission_denied(err.to_string()),
+            ActorisError::Verification(VerificationError::CommitteePending { .. }) => {
+                Status::unavailable(err.to_string())
+            }
+            ActorisError::Validation(_) => Status::failed_precondition(err.to_string()),
+            _ => Status::internal(format!("Failed to record vote: {}", err)),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -594,6 +821,18 @@ impl OracleService for OracleGrpcService {
             }));
         }
 
+        self.verifier.register_oracle(&req.oracle_did);
+
+        // Oracle keys double as VRF keys for committee sortition
+        if let Some(selector) = self.verifier.committee() {
+            let public_key: [u8; 32] = req
+                .public_key
+                .as_slice()
+                .try_into()
+                .map_err(|_| Status::invalid_argument("Invalid public key length"))?;
+            selector.register_oracle(&req.oracle_did, public_key);
+        }
+
         oracles.insert(
             req.oracle_did.clone(),
             OracleInfo {
@@ -613,6 +852,29 @@ impl OracleService for OracleGrpcService {
         }))
     }
 
+    /// Submit a sortition ticket for a committee request
+    #[instrument(skip(self, request))]
+    async fn submit_sortition_ticket(
+        &self,
+        request: Request<proto::SubmitSortitionTicketRequest>,
+    ) -> Result<Response<proto::SubmitSortitionTicketResponse>, Status> {
+        let req = request.into_inner();
+
+        let proof = VrfProof::from_slice(&req.vrf_proof)
+            .map_err(|_| Status::invalid_argument("Invalid VRF proof length"))?;
+
+        let members = self
+            .verifier
+            .submit_ticket(&req.request_id, &req.oracle_did, &proof)
+            .await
+            .map_err(Self::vote_error_to_status)?;
+
+        Ok(Response::new(proto::SubmitSortitionTicketResponse {
+            committee_formed: members.is_some(),
+            members: members.unwrap_or_default(),
+        }))
+    }
+
     /// Submit partial signature for verification
     #[instrument(skip(self, request))]
     async fn submit_partial_signature(
@@ -621,18 +883,24 @@ impl OracleService for OracleGrpcService {
     ) -> Result<Response<proto::SubmitPartialSignatureResponse>, Status> {
         let req = request.into_inner();
 
-        // Create partial signature
-        let mut identifier = [0u8; 32];
-        let did_bytes = req.oracle_did.as_bytes();
-        let len = did_bytes.len().min(32);
-        identifier[..len].copy_from_slice(&did_bytes[..len]);
-
-        let partial_sig = PartialSignature {
-            identifier,
-            share: req.signature_share,
-            commitment: req.commitment,
+        // The commitment carries the oracle's FROST participant identifier
+        let commitment = SigningCommitment::from_bytes(&req.commitment)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let share = OracleShare {
+            share: SignatureShare {
+                identifier: commitment.identifier.clone(),
+                share: req.signature_share,
+            },
+            commitment,
         };
 
+        let vrf_proof = req
+            .vrf_proof
+            .as_deref()
+            .map(VrfProof::from_slice)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Invalid VRF proof length"))?;
+
         // Record the vote
         let status = self
             .verifier
@@ -641,10 +909,11 @@ impl OracleService for OracleGrpcService {
                 &req.oracle_did,
                 req.approved,
                 req.reason,
-                partial_sig,
+                share,
+                vrf_proof,
             )
             .await
-            .map_err(|e| Status::internal(format!("Failed to record vote: {}", e)))?;
+            .map_err(Self::vote_error_to_status)?;
 
         // Update oracle stats
         {
@@ -756,6 +1025,11 @@ pub trait TrustLedgerService: Send + Sync + 'static {
         &self,
         request: Request<proto::GetLedgerStatsRequest>,
     ) -> Result<Response<proto::GetLedgerStatsResponse>, Status>;
+
+    async fn challenge_action(
+        &self,
+        request: Request<proto::ChallengeActionRequest>,
+    ) -> Result<Response<proto::ChallengeActionResponse>, Status>;
 }
 
 /// Oracle service trait (would be auto-generated by tonic-build)
@@ -766,6 +1040,11 @@ pub trait OracleService: Send + Sync + 'static {
         request: Request<proto::JoinQuorumRequest>,
     ) -> Result<Response<proto::JoinQuorumResponse>, Status>;
 
+    async fn submit_sortition_ticket(
+        &self,
+        request: Request<proto::SubmitSortitionTicketRequest>,
+    ) -> Result<Response<proto::SubmitSortitionTicketResponse>, Status>;
+
     async fn submit_partial_signature(
         &self,
         request: Request<proto::SubmitPartialSignatureRequest>,
@@ -808,6 +1087,8 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             synchronous: false,
             timeout_ms: 2000,
+            task_complexity: None,
+            data_sensitivity: None,
         });
 
         let response = service.submit_action(request).await.unwrap();
@@ -817,6 +1098,170 @@ mod tests {
         assert_eq!(resp.status, proto::VerificationStatus::Pending as i32);
     }
 
+    #[tokio::test]
+    async fn test_ledger_service_requires_signed_submissions() {
+        use crate::{TrustLedger, TrustLedgerConfig};
+        use actoris_common::security::RequestSigner;
+        use ed25519_dalek::SigningKey;
+
+        let service = TrustLedger::new_standalone(TrustLedgerConfig::default()).into_grpc_service();
+        let actor = RequestSigner::new(SigningKey::from_bytes(&[3u8; 32]));
+        let mallory = RequestSigner::new(SigningKey::from_bytes(&[4u8; 32]));
+        let submit = |signer: Option<&RequestSigner>| {
+            let mut request = Request::new(SubmitActionRequest {
+                actor_did: actor.did().to_string(),
+                client_did: "did:key:client456".to_string(),
+                action_type: "test.action".to_string(),
+                input: b"test input".to_vec(),
+                output: b"test output".to_vec(),
+                compute_hc: "10.5".to_string(),
+                actor_signature: vec![0u8; 64],
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                synchronous: false,
+                timeout_ms: 2000,
+                task_complexity: None,
+                data_sensitivity: None,
+            });
+            if let Some(signer) = signer {
+                signer.sign_grpc(
+                    &mut request,
+                    "/actoris.trustledger.v1.TrustLedgerService/SubmitAction",
+                );
+            }
+            service.submit_action(request)
+        };
+
+        let unsigned = submit(None).await.unwrap_err();
+        assert_eq!(unsigned.code(), tonic::Code::Unauthenticated);
+        let impostor = submit(Some(&mallory)).await.unwrap_err();
+        assert_eq!(impostor.code(), tonic::Code::PermissionDenied);
+        submit(Some(&actor)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trust_is_looked_up_not_declared() {
+        use crate::verification::dna::ProtocolDna;
+        use crate::verification::policy::VerificationPolicy;
+
+        let dna = Arc::new(ProtocolDna::default());
+        dna.set_trust("did:key:trusted", 0.95);
+        let policy = VerificationPolicy::default().with_action_floor(
+            "test.action",
+            TaskComplexity::Low,
+            DataSensitivity::Public,
+        );
+        let service =
+            TrustLedgerGrpcService::new(ActionVerifier::default().with_policy(policy))
+                .with_trust_lookup(dna);
+
+        let request = |actor: &str| {
+            Request::new(SubmitActionRequest {
+                actor_did: actor.to_string(),
+                client_did: "did:key:client".to_string(),
+                action_type: "test.action".to_string(),
+                input: b"in".to_vec(),
+                output: b"out".to_vec(),
+                compute_hc: "1".to_string(),
+                actor_signature: vec![],
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                synchronous: false,
+                timeout_ms: 0,
+                task_complexity: Some(proto_common::TaskComplexity::Low as i32),
+                data_sensitivity: Some(proto_common::DataSensitivity::Public as i32),
+            })
+        };
+
+        // Declared trust is ignored for an unknown actor
+        let resp = service
+            .submit_action(request("did:key:stranger"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(
+            service.verifier.get_status(&resp.request_id).await,
+            Some(VerificationStatus::Pending)
+        ));
+
+        // Ledger trust lets a low-risk action through optimistically
+        let resp = service
+            .submit_action(request("did:key:trusted"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(
+            service.verifier.get_status(&resp.request_id).await,
+            Some(VerificationStatus::Completed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_challenge_requires_the_signed_challenger() {
+        use crate::verification::policy::VerificationPolicy;
+        use actoris_common::security::RequestSigner;
+        use ed25519_dalek::SigningKey;
+        use rust_decimal_macros::dec;
+
+        let client = RequestSigner::new(SigningKey::from_bytes(&[5u8; 32]));
+        let mallory = RequestSigner::new(SigningKey::from_bytes(&[6u8; 32]));
+        let policy = VerificationPolicy::default().with_action_floor(
+            "test.action",
+            TaskComplexity::Low,
+            DataSensitivity::Public,
+        );
+        let risk = RiskProfile::new(dec!(1), 900)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+
+        let service = TrustLedgerGrpcService::new(ActionVerifier::default().with_policy(policy))
+            .with_request_auth(Arc::new(RequestAuthenticator::default()));
+        let request_id = service
+            .verifier
+            .submit_action_with_risk(
+                "did:key:actor",
+                client.did(),
+                "test.action",
+                b"in",
+                b"out",
+                &risk,
+            )
+            .await
+            .unwrap();
+
+        let challenge = |signer: Option<&RequestSigner>| {
+            let mut request = Request::new(proto::ChallengeActionRequest {
+                request_id: request_id.clone(),
+                challenger_did: client.did().to_string(),
+                reason: "output is wrong".to_string(),
+            });
+            if let Some(signer) = signer {
+                signer.sign_grpc(
+                    &mut request,
+                    "/actoris.trustledger.v1.TrustLedgerService/ChallengeAction",
+                );
+            }
+            service.challenge_action(request)
+        };
+
+        let unsigned = challenge(None).await.unwrap_err();
+        assert_eq!(unsigned.code(), tonic::Code::Unauthenticated);
+        let impostor = challenge(Some(&mallory)).await.unwrap_err();
+        assert_eq!(impostor.code(), tonic::Code::PermissionDenied);
+        let resp = challenge(Some(&client)).await.unwrap().into_inner();
+        assert!(resp.accepted);
+
+        // Without request authentication configured, challenges are refused
+        let open = TrustLedgerGrpcService::default();
+        let refused = open
+            .challenge_action(Request::new(proto::ChallengeActionRequest {
+                request_id: request_id.clone(),
+                challenger_did: client.did().to_string(),
+                reason: "output is wrong".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::Unauthenticated);
+    }
+
     #[tokio::test]
     async fn test_get_ledger_stats() {
         let service = TrustLedgerGrpcService::default();
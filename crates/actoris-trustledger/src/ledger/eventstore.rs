@@ -5,7 +5,9 @@
 //! - Optimistic concurrency via expected revision
 //! - Subscription support for real-time verification events
 
-use actoris_common::{ActorisError, OutcomeRecord, Result};
+use crate::verification::lineage::LineageGraph;
+use crate::verification::syra::SyraSnapshot;
+use actoris_common::{ActorisError, OutcomeRecord, Result, VerificationMode};
 use eventstore::{
     AppendToStreamOptions, Client, ClientSettings, EventData, ExpectedRevision, ReadStreamOptions,
     ResolvedEvent, StreamPosition, SubscribeToStreamOptions,
@@ -41,6 +43,9 @@ pub enum LedgerEvent {
     VerificationStarted {
         request_id: String,
         oracle_count: u8,
+        /// VRF seed for the request's committee (absent for global quorum)
+        #[serde(default)]
+        committee_seed: Option<Vec<u8>>,
         timestamp: i64,
     },
     /// Oracle vote received
@@ -60,8 +65,41 @@ pub enum LedgerEvent {
         signature: Vec<u8>,
         timestamp: i64,
     },
+    /// Verification timed out before the quorum decided
+    VerificationTimedOut {
+        request_id: String,
+        approvals: u8,
+        rejections: u8,
+        elapsed_ms: u64,
+        timestamp: i64,
+    },
+    /// Optimistically accepted action challenged within its window
+    VerificationChallenged {
+        request_id: String,
+        challenger_did: String,
+        reason: String,
+        timestamp: i64,
+    },
+    /// Timed-out verification re-dispatched to a fresh oracle set
+    VerificationRetried {
+        request_id: String,
+        attempt: u8,
+        committee_seed: Option<Vec<u8>>,
+        timestamp: i64,
+    },
+    /// Verification escalated after exhausting retries
+    VerificationEscalated {
+        request_id: String,
+        tier: String,
+        retries: u8,
+        timestamp: i64,
+    },
     /// Outcome record finalized (full record with Merkle proof)
     OutcomeRecordFinalized { record: OutcomeRecordData },
+    /// SyRA state snapshot
+    SyraSnapshotTaken { snapshot: Box<SyraSnapshot> },
+    /// Spawn lineage graph snapshot
+    LineageSnapshotTaken { graph: Box<LineageGraph> },
 }
 
 /// Serializable outcome record data
@@ -83,6 +121,8 @@ pub struct OutcomeRecordData {
     pub merkle_index: u64,
     pub submitted_at: i64,
     pub verified_at: i64,
+    #[serde(default)]
+    pub verification_mode: VerificationMode,
 }
 
 impl From<&OutcomeRecord> for OutcomeRecordData {
@@ -104,6 +144,7 @@ impl From<&OutcomeRecord> for OutcomeRecordData {
             merkle_index: record.merkle_index,
             submitted_at: record.submitted_at,
             verified_at: record.verified_at,
+            verification_mode: record.verification_mode,
         }
     }
 }
@@ -152,6 +193,16 @@ impl EventStoreClient {
         format!("{}-outcomes", self.stream_prefix)
     }
 
+    /// Get stream name for SyRA snapshots
+    fn syra_stream(&self) -> String {
+        format!("{}-syra", self.stream_prefix)
+    }
+
+    /// Get stream name for lineage snapshots
+    fn lineage_stream(&self) -> String {
+        format!("{}-lineage", self.stream_prefix)
+    }
+
     /// Append an event to a stream
     #[instrument(skip(self, event))]
     pub async fn append_event(
@@ -165,7 +216,13 @@ impl EventStoreClient {
             LedgerEvent::VerificationStarted { .. } => "VerificationStarted",
             LedgerEvent::OracleVoteReceived { .. } => "OracleVoteReceived",
             LedgerEvent::VerificationCompleted { .. } => "VerificationCompleted",
+            LedgerEvent::VerificationTimedOut { .. } => "VerificationTimedOut",
+            LedgerEvent::VerificationChallenged { .. } => "VerificationChallenged",
+            LedgerEvent::VerificationRetried { .. } => "VerificationRetried",
+            LedgerEvent::VerificationEscalated { .. } => "VerificationEscalated",
             LedgerEvent::OutcomeRecordFinalized { .. } => "OutcomeRecordFinalized",
+            LedgerEvent::SyraSnapshotTaken { .. } => "SyraSnapshotTaken",
+            LedgerEvent::LineageSnapshotTaken { .. } => "LineageSnapshotTaken",
         };
 
         let event_data = EventData::json(event_type, &event)
@@ -225,6 +282,25 @@ impl EventStoreClient {
         Ok(verification_pos)
     }
 
+    /// Record verification start (and committee seed, if sampled)
+    #[instrument(skip(self))]
+    pub async fn record_verification_started(
+        &self,
+        request_id: &str,
+        oracle_count: u8,
+        committee_seed: Option<[u8; 32]>,
+    ) -> Result<u64> {
+        let event = LedgerEvent::VerificationStarted {
+            request_id: request_id.to_string(),
+            oracle_count,
+            committee_seed: committee_seed.map(|s| s.to_vec()),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
     /// Record verification completion
     #[instrument(skip(self, signature))]
     pub async fn record_verification_completed(
@@ -248,6 +324,105 @@ impl EventStoreClient {
         self.append_event(&stream, event, None).await
     }
 
+    /// Record an oracle vote
+    #[instrument(skip(self))]
+    pub async fn record_oracle_vote(
+        &self,
+        request_id: &str,
+        oracle_did: &str,
+        approved: bool,
+        reason: Option<&str>,
+    ) -> Result<u64> {
+        let event = LedgerEvent::OracleVoteReceived {
+            request_id: request_id.to_string(),
+            oracle_did: oracle_did.to_string(),
+            approved,
+            reason: reason.map(str::to_string),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Record a verification timeout
+    #[instrument(skip(self))]
+    pub async fn record_verification_timed_out(
+        &self,
+        request_id: &str,
+        approvals: u8,
+        rejections: u8,
+        elapsed_ms: u64,
+    ) -> Result<u64> {
+        let event = LedgerEvent::VerificationTimedOut {
+            request_id: request_id.to_string(),
+            approvals,
+            rejections,
+            elapsed_ms,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Record a challenge against an optimistically accepted action
+    #[instrument(skip(self))]
+    pub async fn record_verification_challenged(
+        &self,
+        request_id: &str,
+        challenger_did: &str,
+        reason: &str,
+    ) -> Result<u64> {
+        let event = LedgerEvent::VerificationChallenged {
+            request_id: request_id.to_string(),
+            challenger_did: challenger_did.to_string(),
+            reason: reason.to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Record a retry of a timed-out verification
+    #[instrument(skip(self))]
+    pub async fn record_verification_retried(
+        &self,
+        request_id: &str,
+        attempt: u8,
+        committee_seed: Option<[u8; 32]>,
+    ) -> Result<u64> {
+        let event = LedgerEvent::VerificationRetried {
+            request_id: request_id.to_string(),
+            attempt,
+            committee_seed: committee_seed.map(|s| s.to_vec()),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Record an escalation of a verification
+    #[instrument(skip(self))]
+    pub async fn record_verification_escalated(
+        &self,
+        request_id: &str,
+        tier: &str,
+        retries: u8,
+    ) -> Result<u64> {
+        let event = LedgerEvent::VerificationEscalated {
+            request_id: request_id.to_string(),
+            tier: tier.to_string(),
+            retries,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let stream = self.verification_stream();
+        self.append_event(&stream, event, None).await
+    }
+
     /// Record finalized outcome
     #[instrument(skip(self, record))]
     pub async fn record_outcome_finalized(&self, record: &OutcomeRecord) -> Result<u64> {
@@ -359,14 +534,13 @@ impl EventStoreClient {
         tokio::spawn(async move {
             loop {
                 match subscription.next().await {
-                    Ok(Some(resolved)) => {
+                    Ok(resolved) => {
                         if let Some(event) = Self::parse_event(&resolved) {
                             if sender.send(event).await.is_err() {
                                 break;
                             }
                         }
                     }
-                    Ok(None) => break,
                     Err(e) => {
                         error!("Subscription error: {}", e);
                         break;
@@ -396,7 +570,7 @@ impl EventStoreClient {
         tokio::spawn(async move {
             loop {
                 match subscription.next().await {
-                    Ok(Some(resolved)) => {
+                    Ok(resolved) => {
                         if let Some(LedgerEvent::OutcomeRecordFinalized { record }) =
                             Self::parse_event(&resolved)
                         {
@@ -405,7 +579,6 @@ impl EventStoreClient {
                             }
                         }
                     }
-                    Ok(None) => break,
                     Err(e) => {
                         error!("Subscription error: {}", e);
                         break;
@@ -425,6 +598,82 @@ impl EventStoreClient {
             .and_then(|event| event.as_json::<LedgerEvent>().ok())
     }
 
+    /// Record a SyRA state snapshot
+    #[instrument(skip(self, snapshot))]
+    pub async fn record_syra_snapshot(&self, snapshot: &SyraSnapshot) -> Result<u64> {
+        let event = LedgerEvent::SyraSnapshotTaken {
+            snapshot: Box::new(snapshot.clone()),
+        };
+
+        let stream = self.syra_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Read the latest SyRA state snapshot
+    #[instrument(skip(self))]
+    pub async fn read_latest_syra_snapshot(&self) -> Result<Option<SyraSnapshot>> {
+        let options = ReadStreamOptions::default()
+            .position(StreamPosition::End)
+            .backwards()
+            .max_count(1);
+
+        let result = self
+            .client
+            .read_stream(self.syra_stream(), &options)
+            .await;
+
+        // A missing stream means nothing was persisted yet
+        if let Ok(mut stream) = result {
+            if let Ok(Some(resolved)) = stream.next().await {
+                if let Some(LedgerEvent::SyraSnapshotTaken { snapshot }) =
+                    Self::parse_event(&resolved)
+                {
+                    return Ok(Some(*snapshot));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Record a lineage graph snapshot
+    #[instrument(skip(self, graph))]
+    pub async fn record_lineage_snapshot(&self, graph: &LineageGraph) -> Result<u64> {
+        let event = LedgerEvent::LineageSnapshotTaken {
+            graph: Box::new(graph.clone()),
+        };
+
+        let stream = self.lineage_stream();
+        self.append_event(&stream, event, None).await
+    }
+
+    /// Read the latest lineage graph snapshot
+    #[instrument(skip(self))]
+    pub async fn read_latest_lineage_snapshot(&self) -> Result<Option<LineageGraph>> {
+        let options = ReadStreamOptions::default()
+            .position(StreamPosition::End)
+            .backwards()
+            .max_count(1);
+
+        let result = self
+            .client
+            .read_stream(self.lineage_stream(), &options)
+            .await;
+
+        // A missing stream means nothing was persisted yet
+        if let Ok(mut stream) = result {
+            if let Ok(Some(resolved)) = stream.next().await {
+                if let Some(LedgerEvent::LineageSnapshotTaken { graph }) =
+                    Self::parse_event(&resolved)
+                {
+                    return Ok(Some(*graph));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Get stream statistics
     #[instrument(skip(self))]
     pub async fn get_stream_info(&self, stream_name: &str) -> Result<StreamInfo> {
@@ -29,11 +29,16 @@ pub mod grpc;
 pub mod ledger;
 pub mod verification;
 
-pub use consensus::{OracleNode, QuorumManager};
+pub use consensus::{CommitteeSelector, OracleNode, QuorumManager};
 pub use grpc::{OracleGrpcService, TrustLedgerGrpcService};
 pub use ledger::eventstore::{EventStoreClient, LedgerEvent, OutcomeRecordData, StreamInfo};
-pub use verification::verifier::{ActionVerifier, VerificationStatus, VerifierConfig};
+pub use verification::supervisor::{SupervisorConfig, VerificationSupervisor};
+pub use verification::verifier::{
+    ActionVerifier, EscalationTier, VerificationStatus, VerifierConfig,
+};
 
+use actoris_common::crypto::frost::FrostCoordinator;
+use actoris_common::security::RequestAuthenticator;
 use actoris_common::{OutcomeRecord, Result};
 use std::sync::Arc;
 
@@ -48,10 +53,20 @@ pub struct TrustLedgerConfig {
     pub quorum_threshold: u8,
     /// Total oracle count
     pub oracle_count: u8,
+    /// Per-request committee size (None = global quorum)
+    pub committee_size: Option<usize>,
     /// Verification timeout in milliseconds
     pub verification_timeout_ms: u64,
     /// gRPC listen address
     pub grpc_addr: String,
+    /// Timeout sweeping, retry and escalation
+    pub supervisor: SupervisorConfig,
+    /// Oracle group key package from DKG (None = quorum outcomes can't be signed)
+    pub frost_public_key_package: Option<frost_ed25519::keys::PublicKeyPackage>,
+    /// Reviewers allowed to resolve escalated requests
+    pub reviewers: Vec<String>,
+    /// Require submitted actions to be DID-signed by their actor
+    pub require_signed_requests: bool,
 }
 
 impl Default for TrustLedgerConfig {
@@ -61,8 +76,13 @@ impl Default for TrustLedgerConfig {
             nats_url: "nats://localhost:4222".to_string(),
             quorum_threshold: 3,
             oracle_count: 5,
+            committee_size: None,
             verification_timeout_ms: 2000,
             grpc_addr: "[::1]:50051".to_string(),
+            supervisor: SupervisorConfig::default(),
+            frost_public_key_package: None,
+            reviewers: Vec::new(),
+            require_signed_requests: true,
         }
     }
 }
@@ -71,7 +91,7 @@ impl Default for TrustLedgerConfig {
 pub struct TrustLedger {
     config: TrustLedgerConfig,
     eventstore: Option<Arc<EventStoreClient>>,
-    verifier: ActionVerifier,
+    verifier: Arc<ActionVerifier>,
 }
 
 impl TrustLedger {
@@ -86,13 +106,13 @@ impl TrustLedger {
         let eventstore = EventStoreClient::new(&config.eventstore_url).await?;
         let eventstore = Arc::new(eventstore);
 
-        let verifier = ActionVerifier::new(verifier_config)
+        let verifier = Self::build_verifier(&config, verifier_config)
             .with_eventstore(eventstore.clone());
 
         Ok(Self {
             config,
             eventstore: Some(eventstore),
-            verifier,
+            verifier: Arc::new(verifier),
         })
     }
 
@@ -104,11 +124,29 @@ impl TrustLedger {
             oracle_count: config.oracle_count,
         };
 
+        let verifier = Self::build_verifier(&config, verifier_config);
+
         Self {
             config,
             eventstore: None,
-            verifier: ActionVerifier::new(verifier_config),
+            verifier: Arc::new(verifier),
+        }
+    }
+
+    /// Attach the reviewers, committee selector and FROST coordinator the config asks for
+    fn build_verifier(config: &TrustLedgerConfig, verifier_config: VerifierConfig) -> ActionVerifier {
+        let mut verifier =
+            ActionVerifier::new(verifier_config).with_reviewers(config.reviewers.clone());
+        if let Some(size) = config.committee_size {
+            verifier = verifier.with_committee(Arc::new(CommitteeSelector::new(size)));
         }
+        if let Some(package) = &config.frost_public_key_package {
+            verifier = verifier.with_frost(Arc::new(FrostCoordinator::new(
+                package.clone(),
+                config.quorum_threshold as u16,
+            )));
+        }
+        verifier
     }
 
     /// Get the verifier
@@ -126,12 +164,26 @@ impl TrustLedger {
         &self.config
     }
 
+    /// Spawn the verification supervisor (timeout sweeps, retries, escalation)
+    pub fn spawn_supervisor(&self) -> tokio::task::JoinHandle<()> {
+        Arc::new(VerificationSupervisor::new(
+            self.verifier.clone(),
+            self.config.supervisor.clone(),
+        ))
+        .spawn()
+    }
+
     /// Create gRPC service from this TrustLedger instance
+    ///
+    /// Submissions must be signed by their actor unless the config opts out.
     pub fn into_grpc_service(self) -> TrustLedgerGrpcService {
-        let mut service = TrustLedgerGrpcService::new(self.verifier);
+        let mut service = TrustLedgerGrpcService::from_shared(self.verifier);
         if let Some(es) = self.eventstore {
             service = service.with_eventstore(es);
         }
+        if self.config.require_signed_requests {
+            service = service.with_request_auth(Arc::new(RequestAuthenticator::default()));
+        }
         service
     }
 }
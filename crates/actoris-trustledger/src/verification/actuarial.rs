@@ -0,0 +1,334 @@
+//! Actuarial premium pricing
+//!
+//! Insured-event rates are tracked from finalized outcome records per agent
+//! and per action type. An agent's rate is shrunk toward the rate of the
+//! action types it is insured for, which is itself shrunk toward a base rate,
+//! so a new agent prices at the population rate and moves to its own history
+//! as samples accumulate.
+
+use std::collections::HashMap;
+
+use actoris_common::types::outcome_record::OutcomeRecord;
+use actoris_protocol_dna::InsurePrimitive;
+use parking_lot::RwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Actuarial model configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActuarialConfig {
+    /// Failure rate assumed before any outcome is observed
+    pub base_failure_rate: f64,
+    /// Pseudo-observations given to the prior at each level
+    pub prior_weight: f64,
+    /// Insurer margin as a share of expected loss
+    pub margin_loading: f64,
+    /// Capital held per unit of coverage
+    pub capital_ratio: f64,
+    /// Annual cost of holding that capital
+    pub cost_of_capital: f64,
+}
+
+impl Default for ActuarialConfig {
+    fn default() -> Self {
+        Self {
+            base_failure_rate: 0.05,
+            prior_weight: 20.0,
+            margin_loading: 0.15,
+            capital_ratio: 0.10,
+            cost_of_capital: 0.08,
+        }
+    }
+}
+
+/// Observed outcomes for one agent/action pair or action type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureStats {
+    pub trials: u64,
+    pub failures: u64,
+}
+
+impl FailureStats {
+    fn record(&mut self, failed: bool) {
+        self.trials += 1;
+        if failed {
+            self.failures += 1;
+        }
+    }
+
+    fn add(&mut self, other: &FailureStats) {
+        self.trials += other.trials;
+        self.failures += other.failures;
+    }
+
+    /// Raw failure rate, if anything was observed
+    pub fn observed_rate(&self) -> Option<f64> {
+        (self.trials > 0).then(|| self.failures as f64 / self.trials as f64)
+    }
+}
+
+/// Explainable premium quote
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PremiumQuote {
+    pub agent_did: String,
+    /// Action types priced (empty = all)
+    pub action_types: Vec<String>,
+    pub coverage: Decimal,
+    /// Smoothed failure rate the premium is based on
+    pub failure_rate: f64,
+    /// Agent's raw failure rate, if it has history
+    pub observed_rate: Option<f64>,
+    /// Population rate the agent's history is shrunk toward
+    pub prior_rate: f64,
+    /// Pseudo-observations given to the prior
+    pub prior_weight: f64,
+    /// Agent outcomes behind the quote
+    pub sample_size: u64,
+    pub failures: u64,
+    pub expected_loss: Decimal,
+    pub margin_loading: Decimal,
+    pub capital_loading: Decimal,
+    pub premium: Decimal,
+}
+
+impl PremiumQuote {
+    /// Premium as a share of coverage
+    pub fn premium_rate(&self) -> f64 {
+        if self.coverage.is_zero() {
+            return 0.0;
+        }
+        (self.premium / self.coverage).to_f64().unwrap_or(0.0)
+    }
+
+    /// Human-readable breakdown of the quote
+    pub fn explain(&self) -> String {
+        let observed = self
+            .observed_rate
+            .map(|r| format!("{:.4}", r))
+            .unwrap_or_else(|| "n/a".to_string());
+        format!(
+            "failure rate {:.4} (observed {} over {} outcomes, prior {:.4} weighted as {} outcomes); \
+             expected loss {} + margin {} + capital {} = premium {}",
+            self.failure_rate,
+            observed,
+            self.sample_size,
+            self.prior_rate,
+            self.prior_weight,
+            self.expected_loss,
+            self.margin_loading,
+            self.capital_loading,
+            self.premium
+        )
+    }
+}
+
+/// Whether an outcome would trigger an insurance payout
+///
+/// Failed verifications (including upheld disputes) and missed SLAs count.
+pub fn is_loss_event(record: &OutcomeRecord) -> bool {
+    !record.verification.passed || !record.verification.met_latency_sla()
+}
+
+/// Failure statistics and premium pricing
+pub struct ActuarialModel {
+    config: ActuarialConfig,
+    /// (agent DID, action type) -> stats
+    agents: RwLock<HashMap<(String, String), FailureStats>>,
+    /// action type -> stats across all agents
+    action_types: RwLock<HashMap<String, FailureStats>>,
+}
+
+impl ActuarialModel {
+    pub fn new(config: ActuarialConfig) -> Self {
+        Self {
+            config,
+            agents: RwLock::new(HashMap::new()),
+            action_types: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &ActuarialConfig {
+        &self.config
+    }
+
+    /// Record a finalized outcome
+    ///
+    /// Optimistic acceptances still open to challenge at `now` are skipped.
+    /// Returns whether the record was counted.
+    pub fn observe(&self, record: &OutcomeRecord, now: i64) -> bool {
+        if record.verification_mode.challengeable_at(now) {
+            return false;
+        }
+
+        let failed = is_loss_event(record);
+        self.agents
+            .write()
+            .entry((record.actor_did.clone(), record.action_type.clone()))
+            .or_default()
+            .record(failed);
+        self.action_types
+            .write()
+            .entry(record.action_type.clone())
+            .or_default()
+            .record(failed);
+        true
+    }
+
+    /// Stats of an agent over the given action types (empty = all)
+    pub fn agent_stats(&self, agent_did: &str, action_types: &[String]) -> FailureStats {
+        let mut stats = FailureStats::default();
+        for ((did, action), s) in self.agents.read().iter() {
+            if did == agent_did && (action_types.is_empty() || action_types.contains(action)) {
+                stats.add(s);
+            }
+        }
+        stats
+    }
+
+    /// Stats across all agents over the given action types (empty = all)
+    pub fn action_stats(&self, action_types: &[String]) -> FailureStats {
+        let mut stats = FailureStats::default();
+        for (action, s) in self.action_types.read().iter() {
+            if action_types.is_empty() || action_types.contains(action) {
+                stats.add(s);
+            }
+        }
+        stats
+    }
+
+    /// Quote a premium for covering an agent's actions
+    pub fn quote(
+        &self,
+        agent_did: &str,
+        action_types: &[String],
+        coverage: Decimal,
+        duration_days: u32,
+    ) -> PremiumQuote {
+        let population = self.action_stats(action_types);
+        let prior_rate = InsurePrimitive::smoothed_failure_rate(
+            population.failures,
+            population.trials,
+            self.config.base_failure_rate,
+            self.config.prior_weight,
+        );
+
+        let agent = self.agent_stats(agent_did, action_types);
+        let failure_rate = InsurePrimitive::smoothed_failure_rate(
+            agent.failures,
+            agent.trials,
+            prior_rate,
+            self.config.prior_weight,
+        );
+
+        let decimal = |v: f64| Decimal::try_from(v).unwrap_or_default();
+        let expected_loss = coverage * decimal(failure_rate);
+        let margin_loading = expected_loss * decimal(self.config.margin_loading);
+        let capital_loading = coverage
+            * decimal(self.config.capital_ratio * self.config.cost_of_capital)
+            * Decimal::from(duration_days)
+            / Decimal::from(365);
+        let premium = (expected_loss + margin_loading + capital_loading).round_dp(6);
+
+        PremiumQuote {
+            agent_did: agent_did.to_string(),
+            action_types: action_types.to_vec(),
+            coverage,
+            failure_rate,
+            observed_rate: agent.observed_rate(),
+            prior_rate,
+            prior_weight: self.config.prior_weight,
+            sample_size: agent.trials,
+            failures: agent.failures,
+            expected_loss: expected_loss.round_dp(6),
+            margin_loading: margin_loading.round_dp(6),
+            capital_loading: capital_loading.round_dp(6),
+            premium,
+        }
+    }
+}
+
+impl Default for ActuarialModel {
+    fn default() -> Self {
+        Self::new(ActuarialConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actoris_common::types::outcome_record::{FrostSignature, VerificationResult};
+    use rust_decimal_macros::dec;
+
+    fn outcome(agent: &str, passed: bool) -> OutcomeRecord {
+        let verification = if passed {
+            VerificationResult::success(3, 100, vec![])
+        } else {
+            VerificationResult::failure(3, true, 100, vec![], "rejected".into())
+        };
+        OutcomeRecord::new(
+            agent.to_string(),
+            "did:key:client".to_string(),
+            "test.action".to_string(),
+            [1u8; 32],
+            [2u8; 32],
+            dec!(10),
+            verification,
+            FrostSignature::new([0u8; 64], vec![], [0u8; 32], 3, 5),
+        )
+    }
+
+    fn model() -> ActuarialModel {
+        ActuarialModel::new(ActuarialConfig {
+            prior_weight: 10.0,
+            capital_ratio: 0.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_new_agent_prices_at_population_rate() {
+        let model = model();
+
+        // No data at all: base rate
+        let quote = model.quote("did:key:new", &[], dec!(1000), 30);
+        assert!((quote.failure_rate - 0.05).abs() < 1e-12);
+        assert_eq!(quote.sample_size, 0);
+        assert_eq!(quote.observed_rate, None);
+
+        // Population: 30 outcomes, 12 failed -> (12 + 0.5) / 40
+        for i in 0..30 {
+            model.observe(&outcome("did:key:other", i >= 12), 0);
+        }
+        let quote = model.quote("did:key:new", &[], dec!(1000), 30);
+        assert!((quote.prior_rate - 0.3125).abs() < 1e-9);
+        assert_eq!(quote.failure_rate, quote.prior_rate);
+        assert!(quote.premium > quote.expected_loss);
+    }
+
+    #[test]
+    fn test_history_moves_agent_off_the_prior() {
+        let model = model();
+        for _ in 0..90 {
+            model.observe(&outcome("did:key:good", true), 0);
+        }
+        for i in 0..10 {
+            model.observe(&outcome("did:key:bad", i % 2 == 0), 0);
+        }
+
+        let good = model.quote("did:key:good", &[], dec!(1000), 30);
+        let bad = model.quote("did:key:bad", &[], dec!(1000), 30);
+        assert_eq!(good.sample_size, 90);
+        assert_eq!(bad.failures, 5);
+        assert!(good.failure_rate < good.prior_rate);
+        assert!(bad.failure_rate > bad.prior_rate);
+        assert!(bad.premium > good.premium);
+        assert!(bad.explain().contains("over 10 outcomes"));
+
+        // Uncovered action types don't count toward the agent's history
+        let other = model.quote("did:key:bad", &["other.action".to_string()], dec!(1000), 30);
+        assert_eq!(other.sample_size, 0);
+    }
+}
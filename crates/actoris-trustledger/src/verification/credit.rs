@@ -0,0 +1,216 @@
+//! Revolving credit lines
+//!
+//! A credit line commits part of a lender's HC to a borrower, sized from the
+//! borrower's [`CreditScore`](actoris_protocol_dna::primitives::lend::CreditScore).
+//! The borrower draws against it as they consume HC and repays from revenue;
+//! repaid principal frees capacity again. The lender's undrawn commitment
+//! stays locked while the line is open.
+//!
+//! Lines are reviewed on a schedule: heavily used lines grow toward the
+//! score-sized limit, idle lines shrink, and a line freezes as soon as the
+//! borrower's score falls below the freeze threshold.
+
+use std::collections::VecDeque;
+
+use actoris_protocol_dna::primitives::lend::CreditWeights;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds per day
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Credit scoring and credit line parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreditConfig {
+    /// Weights of the credit score components
+    pub weights: CreditWeights,
+    /// Limit scaled by the credit multiplier (0.1x - 3x) when sizing a line
+    pub base_line_limit: Decimal,
+    /// Minimum score to open a line
+    pub min_open_score: f64,
+    /// Lines freeze when the borrower's score falls below this
+    pub freeze_below: f64,
+    /// Frozen lines reopen once the score is back at this level
+    pub unfreeze_at: f64,
+    /// Utilization at or above which a review raises the limit
+    pub raise_utilization: f64,
+    /// Utilization at or below which a review lowers the limit
+    pub cut_utilization: f64,
+    /// Largest limit change per review, as a share of the current limit
+    pub review_step: f64,
+    /// Days between limit reviews of a line
+    pub review_interval_days: u32,
+    /// Days of earnings history used for volatility and debt burden
+    pub earnings_window_days: usize,
+}
+
+impl Default for CreditConfig {
+    fn default() -> Self {
+        Self {
+            weights: CreditWeights::default(),
+            base_line_limit: Decimal::new(1000, 0),
+            min_open_score: 0.5,
+            freeze_below: 0.4,
+            unfreeze_at: 0.5,
+            raise_utilization: 0.8,
+            cut_utilization: 0.2,
+            review_step: 0.25,
+            review_interval_days: 7,
+            earnings_window_days: 30,
+        }
+    }
+}
+
+/// Daily earnings of a DID as reported by metering
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EarningsHistory {
+    /// (day number, earned) in ascending day order
+    days: VecDeque<(i64, Decimal)>,
+}
+
+impl EarningsHistory {
+    /// Add earnings at `at` (unix ms), dropping days outside the window
+    pub fn record(&mut self, at: i64, amount: Decimal, window_days: usize) {
+        let day = at.div_euclid(DAY_MS);
+        match self.days.iter_mut().find(|(d, _)| *d == day) {
+            Some((_, earned)) => *earned += amount,
+            None => {
+                let pos = self.days.partition_point(|(d, _)| *d < day);
+                self.days.insert(pos, (day, amount));
+            }
+        }
+        let oldest = day - window_days as i64 + 1;
+        while self.days.front().is_some_and(|(d, _)| *d < oldest) {
+            self.days.pop_front();
+        }
+    }
+
+    /// Earnings per day from the first recorded day in the window up to
+    /// the day of `now`, with zero for days without earnings
+    pub fn periods(&self, now: i64, window_days: usize) -> Vec<Decimal> {
+        let today = now.div_euclid(DAY_MS);
+        let oldest = today - window_days as i64 + 1;
+        let Some(first) = self.days.iter().map(|(d, _)| *d).find(|d| *d >= oldest) else {
+            return Vec::new();
+        };
+        (first..=today)
+            .map(|day| {
+                self.days
+                    .iter()
+                    .find(|(d, _)| *d == day)
+                    .map(|(_, earned)| *earned)
+                    .unwrap_or(Decimal::ZERO)
+            })
+            .collect()
+    }
+}
+
+/// Open a credit line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditLineRequest {
+    pub lender_did: String,
+    pub borrower_did: String,
+    /// Requested limit (capped at the score-sized limit)
+    pub limit: Option<Decimal>,
+    /// Annual rate (priced on the credit score when not given)
+    pub interest_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CreditLineStatus {
+    Open,
+    /// No draws until the borrower's score recovers; repayments still apply
+    Frozen,
+    Closed,
+}
+
+/// Revolving credit line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditLine {
+    pub line_id: String,
+    pub lender_did: String,
+    pub borrower_did: String,
+    pub limit: Decimal,
+    /// Principal drawn and not yet repaid
+    pub drawn: Decimal,
+    /// Interest accrued and not yet repaid
+    pub accrued_interest: Decimal,
+    pub interest_rate: f64,
+    pub status: CreditLineStatus,
+    /// Borrower's credit score at the last check
+    pub score: f64,
+    pub opened_at: i64,
+    pub reviewed_at: i64,
+    pub last_accrued_at: i64,
+}
+
+impl CreditLine {
+    /// Amount that can be drawn now
+    pub fn available(&self) -> Decimal {
+        match self.status {
+            CreditLineStatus::Open => (self.limit - self.drawn).max(Decimal::ZERO),
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Drawn share of the limit
+    pub fn utilization(&self) -> f64 {
+        if self.limit <= Decimal::ZERO {
+            return 0.0;
+        }
+        (self.drawn / self.limit).to_f64().unwrap_or(0.0)
+    }
+
+    /// Principal and interest owed
+    pub fn outstanding(&self) -> Decimal {
+        self.drawn + self.accrued_interest
+    }
+
+    /// Lender HC still committed to the line
+    pub fn undrawn(&self) -> Decimal {
+        (self.limit - self.drawn).max(Decimal::ZERO)
+    }
+}
+
+/// Outcome of reviewing a credit line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimitReview {
+    pub line_id: String,
+    pub score: f64,
+    pub utilization: f64,
+    pub old_limit: Decimal,
+    pub new_limit: Decimal,
+    pub old_status: CreditLineStatus,
+    pub new_status: CreditLineStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_earnings_history_fills_gaps_within_window() {
+        let mut history = EarningsHistory::default();
+        let day = |n: i64| n * DAY_MS + 1000;
+
+        history.record(day(10), dec!(5), 5);
+        history.record(day(12), dec!(7), 5);
+        history.record(day(12), dec!(1), 5);
+        history.record(day(11), dec!(2), 5);
+        assert_eq!(
+            history.periods(day(13), 5),
+            vec![dec!(5), dec!(2), dec!(8), dec!(0)]
+        );
+
+        // Day 10 falls out of the window
+        history.record(day(14), dec!(3), 5);
+        assert_eq!(
+            history.periods(day(15), 5),
+            vec![dec!(2), dec!(8), dec!(0), dec!(3), dec!(0)]
+        );
+        assert!(history.periods(day(40), 5).is_empty());
+    }
+}
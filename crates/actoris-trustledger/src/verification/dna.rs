@@ -22,7 +22,7 @@
 //!    - Revocable at any time
 //!    - Audit trail
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
@@ -33,6 +33,41 @@ use thiserror::Error;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use actoris_common::crypto::capability::{
+    self, AuthorizedCapability, CapabilityError, CapabilityScope, CapabilityToken,
+};
+use actoris_common::crypto::did::encode_did_key;
+use actoris_common::error::PricingError;
+use actoris_common::types::hc_journal::Statement;
+use actoris_common::types::hc_wallet::{TrancheSource, WalletError};
+use actoris_common::types::outcome_record::OutcomeRecord;
+use actoris_protocol_dna::machine::{Commitment, DnaEvent, DnaMachine, HcDelta, TransitionError};
+use actoris_protocol_dna::policy::SpendingPolicies;
+use actoris_protocol_dna::pricing::DnaPricing;
+use actoris_protocol_dna::primitives::lend::{CreditInputs, CreditScore, LendPrimitive};
+use actoris_protocol_dna::wasm::{DnaLedger, LedgerEffect, LedgerError, HC_SCALE};
+use ed25519_dalek::SigningKey;
+
+use super::actuarial::{ActuarialModel, PremiumQuote};
+use super::credit::{
+    CreditConfig, CreditLine, CreditLineRequest, CreditLineStatus, EarningsHistory, LimitReview,
+};
+use super::lineage::{
+    LineageConfig, LineageGraph, LineageNode, LineageStatus, RecallReport, ResolvedLineage,
+    SubtreeAggregate,
+};
+use super::lineage_store::LineageStore;
+use super::parametric::{self, Adjudication, ClaimTrigger, PolicyTerms};
+use super::policy::TrustLookup;
+use super::sybil_graph::FundingKind;
+use super::syra::SyraGuard;
+
+/// Milliseconds per day
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Day count basis for interest
+const DAYS_PER_YEAR: i64 = 365;
+
 /// Protocol DNA errors
 #[derive(Debug, Error)]
 pub enum DnaError {
@@ -71,6 +106,79 @@ pub enum DnaError {
 
     #[error("Permission denied: {action}")]
     PermissionDenied { action: String },
+
+    #[error("Insufficient capital: reserve of {required} HC required, {available} HC available")]
+    CapitalInsufficient {
+        required: Decimal,
+        available: Decimal,
+    },
+
+    #[error("Cession rejected: {reason}")]
+    CessionRejected { reason: String },
+
+    #[error("Capability rejected: {0}")]
+    Capability(#[from] CapabilityError),
+
+    #[error("Agent not in spawn lineage: {did}")]
+    AgentNotFound { did: String },
+
+    #[error("Agent already exists: {did}")]
+    AgentExists { did: String },
+
+    #[error("Agent has been retired: {did}")]
+    AgentRetired { did: String },
+
+    #[error("Invalid amount: {amount} HC")]
+    InvalidAmount { amount: Decimal },
+
+    #[error("Credit score too low: need {required}, have {current}")]
+    CreditScoreTooLow { required: f64, current: f64 },
+
+    #[error("Credit line not found: {line_id}")]
+    CreditLineNotFound { line_id: String },
+
+    #[error("Credit line is frozen: {line_id}")]
+    CreditLineFrozen { line_id: String },
+
+    #[error("Credit limit exceeded: requested {requested} HC, {available} HC available")]
+    CreditLimitExceeded {
+        requested: Decimal,
+        available: Decimal,
+    },
+
+    #[error("Credit line {line_id} still owes {outstanding} HC")]
+    CreditLineOutstanding {
+        line_id: String,
+        outstanding: Decimal,
+    },
+
+    #[error("HC ledger rejected transition: {0}")]
+    Ledger(TransitionError),
+
+    #[error(transparent)]
+    Pricing(#[from] PricingError),
+}
+
+impl From<TransitionError> for DnaError {
+    /// Wallet shortfalls surface as `InsufficientFunds`
+    fn from(error: TransitionError) -> Self {
+        match error {
+            TransitionError::Wallet(WalletError::InsufficientBalance {
+                required,
+                available,
+            }) => DnaError::InsufficientFunds {
+                required,
+                available,
+            },
+            TransitionError::Wallet(WalletError::InsufficientLocked { required, locked }) => {
+                DnaError::InsufficientFunds {
+                    required,
+                    available: locked,
+                }
+            }
+            other => DnaError::Ledger(other),
+        }
+    }
 }
 
 /// DNA Primitive type
@@ -142,16 +250,84 @@ pub struct Loan {
     pub expires_at: i64,
     pub auto_repay: bool,
     pub status: LoanStatus,
+    /// Accrued interest not yet repaid (included in `outstanding`)
+    #[serde(default)]
+    pub accrued_interest: Decimal,
+    /// Last time interest was accrued (unix ms)
+    #[serde(default)]
+    pub last_accrued_at: i64,
+    /// Amortization schedule
+    #[serde(default)]
+    pub schedule: Vec<Installment>,
+}
+
+impl Loan {
+    /// Principal still owed
+    pub fn principal_outstanding(&self) -> Decimal {
+        self.outstanding - self.accrued_interest
+    }
+
+    /// Unpaid scheduled amount due at or before `now`
+    pub fn amount_due(&self, now: i64) -> Decimal {
+        let due: Decimal = self
+            .schedule
+            .iter()
+            .filter(|i| i.due_at <= now)
+            .map(Installment::remaining)
+            .sum();
+        due.min(self.outstanding)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LoanStatus {
     Active,
     Repaid,
+    /// Expired unpaid with no collateral to seize
     Defaulted,
+    /// Expired unpaid; collateral seized by the lender
     Liquidated,
 }
 
+/// Scheduled loan installment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Installment {
+    pub due_at: i64,
+    pub principal: Decimal,
+    pub interest: Decimal,
+    /// Paid toward this installment so far
+    pub paid: Decimal,
+}
+
+impl Installment {
+    /// Scheduled amount
+    pub fn amount(&self) -> Decimal {
+        self.principal + self.interest
+    }
+
+    /// Scheduled amount not yet paid
+    pub fn remaining(&self) -> Decimal {
+        (self.amount() - self.paid).max(Decimal::ZERO)
+    }
+}
+
+/// Result of a loan servicing pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServicingReport {
+    /// Interest accrued across all loans
+    pub interest_accrued: Decimal,
+    /// Loans that received an auto-repayment
+    pub auto_repaid: Vec<String>,
+    /// Loans that defaulted with nothing to seize
+    pub defaulted: Vec<String>,
+    /// Loans that defaulted and had collateral seized
+    pub liquidated: Vec<String>,
+    /// Credit lines frozen because the borrower's score dropped
+    pub frozen_lines: Vec<String>,
+    /// Credit lines whose limit changed on review
+    pub resized_lines: Vec<String>,
+}
+
 /// INSURE request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InsureRequest {
@@ -167,6 +343,9 @@ pub struct InsureRequest {
     pub duration_days: u32,
     /// Covered action types (empty = all)
     pub covered_actions: Vec<String>,
+    /// Parametric payout terms
+    #[serde(default)]
+    pub terms: PolicyTerms,
 }
 
 /// Insurance policy
@@ -184,6 +363,80 @@ pub struct InsurancePolicy {
     pub claims_made: u32,
     pub claims_paid: Decimal,
     pub status: PolicyStatus,
+    #[serde(default)]
+    pub terms: PolicyTerms,
+    /// Actuarial quote the premium was priced from
+    #[serde(default)]
+    pub quote: Option<PremiumQuote>,
+    /// Shares ceded to reinsurers
+    #[serde(default)]
+    pub cessions: Vec<Cession>,
+}
+
+impl InsurancePolicy {
+    /// Coverage not yet paid out
+    pub fn remaining_coverage(&self) -> Decimal {
+        (self.coverage_amount - self.claims_paid).max(Decimal::ZERO)
+    }
+
+    /// Whether the policy still exposes its insurer at `now`
+    pub fn is_in_force(&self, now: i64) -> bool {
+        matches!(
+            self.status,
+            PolicyStatus::Active | PolicyStatus::ClaimsPending
+        ) && now <= self.expires_at
+    }
+
+    /// Share of the policy kept by the insurer
+    pub fn retained_share(&self) -> f64 {
+        (1.0 - self.cessions.iter().map(|c| c.share).sum::<f64>()).max(0.0)
+    }
+}
+
+/// Reinsurance of part of a policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cession {
+    pub reinsurer_did: String,
+    /// Share of coverage and premium ceded (0.0 - 1.0)
+    pub share: f64,
+    pub ceded_premium: Decimal,
+    pub ceded_at: i64,
+}
+
+/// Insurer exposure across policies in force
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InsurerExposure {
+    /// Retained coverage across all policies
+    pub total: Decimal,
+    /// Retained coverage restricted to each action type
+    pub by_action: HashMap<String, Decimal>,
+    /// Retained coverage of policies covering every action type
+    pub all_actions: Decimal,
+    /// Capital reserve currently locked
+    pub reserve_held: Decimal,
+}
+
+impl InsurerExposure {
+    fn add(&mut self, covered_actions: &[String], amount: Decimal) {
+        self.total += amount;
+        if covered_actions.is_empty() {
+            self.all_actions += amount;
+        }
+        for action in covered_actions {
+            *self
+                .by_action
+                .entry(action.clone())
+                .or_insert(Decimal::ZERO) += amount;
+        }
+    }
+
+    /// Largest exposure hit by failures of a single action type
+    pub fn max_correlated(&self) -> Decimal {
+        self.by_action
+            .values()
+            .map(|amount| *amount + self.all_actions)
+            .fold(self.all_actions, Decimal::max)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -206,6 +459,12 @@ pub struct InsuranceClaim {
     pub submitted_at: i64,
     pub status: ClaimStatus,
     pub payout: Option<Decimal>,
+    /// Insured event the claim was paid for
+    #[serde(default)]
+    pub trigger: Option<ClaimTrigger>,
+    /// Why the claim was denied or sent to review
+    #[serde(default)]
+    pub resolution: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -253,41 +512,115 @@ pub struct Delegation {
     pub parent_delegation_id: Option<String>,
 }
 
+impl Delegation {
+    /// Whether `action` is covered by the allowed actions (`compute.*` covers
+    /// `compute.run`)
+    pub fn allows_action(&self, action: &str) -> bool {
+        capability::action_allowed(&self.allowed_actions, action)
+    }
+
+    /// Scope of the capability token carrying this delegation
+    pub fn scope(&self) -> CapabilityScope {
+        CapabilityScope {
+            allowed_actions: self.allowed_actions.clone(),
+            max_hc_per_action: self.max_hc_per_action,
+            max_total_hc: self.max_total_hc,
+            not_before: self.started_at,
+            expires_at: self.expires_at,
+            allow_subdelegation: self.allow_subdelegation,
+        }
+    }
+}
+
 /// Protocol DNA configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DnaConfig {
     /// Maximum spawn depth (generations from root)
     pub max_spawn_depth: u32,
-    /// Inherited trust percentage (e.g., 0.30 = 30%)
-    pub inherited_trust_pct: f64,
     /// Minimum trust for spawning
     pub min_spawn_tau: f64,
     /// Minimum trust for lending
     pub min_lend_tau: f64,
-    /// Base interest rate for loans
-    pub base_interest_rate: f64,
-    /// Interest discount per 0.1 tau
-    pub interest_discount_per_tau: f64,
-    /// Base insurance premium rate
-    pub base_premium_rate: f64,
-    /// Premium discount per 0.1 tau
-    pub premium_discount_per_tau: f64,
+    /// Loan rates, flat premiums and trust inheritance, shared with the
+    /// rest of Protocol DNA
+    #[serde(default)]
+    pub pricing: DnaPricing,
     /// Minimum stake for spawning
     pub min_spawn_stake: Decimal,
+    /// Loan servicing parameters
+    #[serde(default)]
+    pub servicing: LoanServicingConfig,
+    /// Insurer capital requirements
+    #[serde(default)]
+    pub capital: CapitalConfig,
+    /// Trust propagation and slashing along spawn lineage
+    #[serde(default)]
+    pub lineage: LineageConfig,
+    /// Credit scoring and revolving credit lines
+    #[serde(default)]
+    pub credit: CreditConfig,
+}
+
+/// Insurer capital requirements
+///
+/// The reserve an insurer must keep locked is
+/// `reserve_ratio * total + correlation_ratio * max_correlated` over its
+/// retained exposure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapitalConfig {
+    /// Reserve per unit of total retained coverage
+    pub reserve_ratio: f64,
+    /// Extra reserve per unit of the largest single-action-type exposure
+    pub correlation_ratio: f64,
+}
+
+impl Default for CapitalConfig {
+    fn default() -> Self {
+        Self {
+            reserve_ratio: 0.10,
+            correlation_ratio: 0.25,
+        }
+    }
+}
+
+/// Loan servicing parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoanServicingConfig {
+    /// Days between scheduled installments
+    pub installment_period_days: u32,
+    /// Days past expiry before an unpaid loan defaults
+    pub default_grace_days: u32,
+    /// Trust (τ) deducted from a borrower on default
+    pub default_trust_penalty: f64,
+    /// Share of credited earnings swept into auto-repay loans
+    pub auto_repay_share: f64,
+}
+
+impl Default for LoanServicingConfig {
+    fn default() -> Self {
+        Self {
+            installment_period_days: 7,
+            default_grace_days: 1,
+            default_trust_penalty: 0.2,
+            auto_repay_share: 0.5,
+        }
+    }
 }
 
 impl Default for DnaConfig {
     fn default() -> Self {
         Self {
             max_spawn_depth: 5,
-            inherited_trust_pct: 0.30,
             min_spawn_tau: 0.3,
             min_lend_tau: 0.5,
-            base_interest_rate: 0.10, // 10% annual
-            interest_discount_per_tau: 0.01, // 1% discount per 0.1 tau
-            base_premium_rate: 0.05, // 5% of coverage
-            premium_discount_per_tau: 0.005, // 0.5% discount per 0.1 tau
+            pricing: DnaPricing::default(),
             min_spawn_stake: Decimal::new(100, 0), // 100 HC
+            servicing: LoanServicingConfig::default(),
+            capital: CapitalConfig::default(),
+            lineage: LineageConfig::default(),
+            credit: CreditConfig::default(),
         }
     }
 }
@@ -299,6 +632,12 @@ pub struct ProtocolDna {
     /// Active loans
     loans: Arc<RwLock<HashMap<String, Loan>>>,
 
+    /// Revolving credit lines
+    credit_lines: Arc<RwLock<HashMap<String, CreditLine>>>,
+
+    /// Metered earnings per DID, for credit scoring
+    earnings: Arc<RwLock<HashMap<String, EarningsHistory>>>,
+
     /// Insurance policies
     policies: Arc<RwLock<HashMap<String, InsurancePolicy>>>,
 
@@ -308,32 +647,93 @@ pub struct ProtocolDna {
     /// Active delegations
     delegations: Arc<RwLock<HashMap<String, Delegation>>>,
 
-    /// Spawn registry (child -> parent)
-    spawn_registry: Arc<RwLock<HashMap<String, String>>>,
-
-    /// Spawn depths (did -> depth)
-    spawn_depths: Arc<RwLock<HashMap<String, u32>>>,
+    /// Spawn lineage (child -> parent, with stakes)
+    lineage: Arc<RwLock<LineageGraph>>,
 
     /// Trust scores (did -> tau)
     trust_scores: Arc<RwLock<HashMap<String, f64>>>,
 
-    /// HC balances (did -> available)
-    hc_balances: Arc<RwLock<HashMap<String, Decimal>>>,
+    /// HC wallets, changed only by ledgered DNA events
+    machine: Arc<RwLock<DnaMachine>>,
+
+    /// Part of locked HC held as insurer capital reserve (did -> reserve)
+    capital_reserves: Arc<RwLock<HashMap<String, Decimal>>>,
+
+    /// Part of locked HC escrowed by WASM contracts (contract -> did -> escrow)
+    contract_escrow: Arc<RwLock<HashMap<String, HashMap<String, Decimal>>>>,
+
+    /// SyRA guard fed with funding flows for cluster detection
+    syra: Option<Arc<SyraGuard>>,
+
+    /// Failure history used to price premiums
+    actuarial: Option<Arc<ActuarialModel>>,
+
+    /// Durable store for lineage snapshots
+    lineage_store: Option<Arc<dyn LineageStore>>,
 }
 
 impl ProtocolDna {
     /// Create new Protocol DNA executor
     pub fn new(config: DnaConfig) -> Self {
+        let machine = DnaMachine::new().with_pricing(config.pricing.clone());
         Self {
             config,
             loans: Arc::new(RwLock::new(HashMap::new())),
+            credit_lines: Arc::new(RwLock::new(HashMap::new())),
+            earnings: Arc::new(RwLock::new(HashMap::new())),
             policies: Arc::new(RwLock::new(HashMap::new())),
             claims: Arc::new(RwLock::new(HashMap::new())),
             delegations: Arc::new(RwLock::new(HashMap::new())),
-            spawn_registry: Arc::new(RwLock::new(HashMap::new())),
-            spawn_depths: Arc::new(RwLock::new(HashMap::new())),
+            lineage: Arc::new(RwLock::new(LineageGraph::new())),
             trust_scores: Arc::new(RwLock::new(HashMap::new())),
-            hc_balances: Arc::new(RwLock::new(HashMap::new())),
+            machine: Arc::new(RwLock::new(machine)),
+            capital_reserves: Arc::new(RwLock::new(HashMap::new())),
+            contract_escrow: Arc::new(RwLock::new(HashMap::new())),
+            syra: None,
+            actuarial: None,
+            lineage_store: None,
+        }
+    }
+
+    /// Report spawns, loans and delegations to a SyRA guard
+    pub fn with_syra(mut self, syra: Arc<SyraGuard>) -> Self {
+        self.syra = Some(syra);
+        self
+    }
+
+    /// Price premiums from observed failure rates instead of the flat rate
+    pub fn with_actuarial(mut self, model: Arc<ActuarialModel>) -> Self {
+        self.actuarial = Some(model);
+        self
+    }
+
+    /// Hold spawns, loans, policies and delegations to the spending
+    /// policies of the organizations accountable for the committing DIDs
+    pub fn with_spending_policies(self, policies: SpendingPolicies) -> Self {
+        *self.machine.write().spending_policies_mut() = policies;
+        self
+    }
+
+    /// HC committed against an organization's budget this period
+    pub fn budget_spent(&self, org_did: &str) -> Decimal {
+        self.machine.read().spending_policies().spent(org_did)
+    }
+
+    /// Start a new budget period for an organization
+    pub fn reset_budget(&self, org_did: &str) {
+        self.machine.write().spending_policies_mut().reset_budget(org_did);
+    }
+
+    /// Persist lineage snapshots to a durable store
+    pub fn with_lineage_store(mut self, store: Arc<dyn LineageStore>) -> Self {
+        self.lineage_store = Some(store);
+        self
+    }
+
+    /// Record a funding flow with the SyRA guard, if any
+    fn record_funding(&self, from: &str, to: &str, kind: FundingKind) {
+        if let Some(syra) = &self.syra {
+            syra.record_funding(from, to, kind);
         }
     }
 
@@ -347,20 +747,128 @@ impl ProtocolDna {
         self.trust_scores.read().get(did).copied().unwrap_or(0.0)
     }
 
-    /// Set HC balance for a DID
+    /// Set HC balance for a DID, minting or burning the difference
     pub fn set_balance(&self, did: &str, balance: Decimal) {
-        self.hc_balances.write().insert(did.to_string(), balance);
+        let mut machine = self.machine.write();
+        let current = machine.wallet(did).map_or(Decimal::ZERO, |w| w.available);
+        let result = if balance > current {
+            machine.mint(did, balance - current)
+        } else if balance < current {
+            machine.burn(did, current - balance)
+        } else {
+            return;
+        };
+        if let Err(e) = result {
+            warn!(did = %did, error = %e, "HC balance not set");
+        }
+    }
+
+    /// Whether `did` already has a trust score, an HC wallet or a lineage node
+    fn agent_exists(&self, did: &str) -> bool {
+        self.trust_scores.read().contains_key(did)
+            || self.machine.read().wallet(did).is_some()
+            || self.lineage.read().node(did).is_some()
     }
 
     /// Get HC balance for a DID
     pub fn get_balance(&self, did: &str) -> Decimal {
-        self.hc_balances.read().get(did).copied().unwrap_or(Decimal::ZERO)
+        self.machine
+            .read()
+            .wallet(did)
+            .map_or(Decimal::ZERO, |w| w.available)
+    }
+
+    /// Get locked HC for a DID
+    pub fn get_locked(&self, did: &str) -> Decimal {
+        self.machine
+            .read()
+            .wallet(did)
+            .map_or(Decimal::ZERO, |w| w.locked)
+    }
+
+    /// HC transitions applied so far, oldest first
+    pub fn hc_events(&self) -> Vec<DnaEvent> {
+        self.machine.read().events().to_vec()
+    }
+
+    /// Journal statement of the HC wallet of `did` for `[from, to)`
+    pub fn hc_statement(&self, did: &str, from: i64, to: i64) -> Statement {
+        self.machine.read().statement(did, from, to)
+    }
+
+    /// Move available HC into the locked balance
+    pub fn lock_hc(&self, did: &str, amount: Decimal) -> Result<(), DnaError> {
+        self.apply_hc("lock", TrancheSource::Earnings, &[(did, -amount, amount)])
+    }
+
+    /// Move locked HC back into the available balance
+    pub fn unlock_hc(&self, did: &str, amount: Decimal) -> Result<(), DnaError> {
+        self.apply_hc("unlock", TrancheSource::Earnings, &[(did, amount, -amount)])
+    }
+
+    /// Transfer available HC between DIDs
+    pub fn transfer_hc(&self, from: &str, to: &str, amount: Decimal) -> Result<(), DnaError> {
+        self.apply_hc(
+            "transfer",
+            TrancheSource::Earnings,
+            &[(from, -amount, Decimal::ZERO), (to, amount, Decimal::ZERO)],
+        )
+    }
+
+    /// Apply HC changes across DIDs all-or-nothing
+    ///
+    /// Each change is a `(did, available, locked)` delta, applied in order as
+    /// one [`DnaEvent::Adjusted`] transition; the deltas must net to zero.
+    /// HC credited to a wallet is a tranche from `source`. A change that
+    /// would overdraw either balance leaves every wallet untouched.
+    fn apply_hc(
+        &self,
+        memo: &str,
+        source: TrancheSource,
+        changes: &[(&str, Decimal, Decimal)],
+    ) -> Result<(), DnaError> {
+        self.commit_hc(memo, source, changes, &[])
+    }
+
+    /// Apply HC changes that open a position, checking the `(did, amount)`
+    /// commitments against spending policies first
+    fn commit_hc(
+        &self,
+        memo: &str,
+        source: TrancheSource,
+        changes: &[(&str, Decimal, Decimal)],
+        commitments: &[(&str, Decimal)],
+    ) -> Result<(), DnaError> {
+        if commitments.is_empty()
+            && changes.iter().all(|(_, available, locked)| available.is_zero() && locked.is_zero())
+        {
+            return Ok(());
+        }
+        let deltas = changes
+            .iter()
+            .map(|&(did, available, locked)| HcDelta::new(did, available, locked))
+            .collect();
+        let commitments = commitments
+            .iter()
+            .map(|&(did, amount)| Commitment::new(did, amount))
+            .collect();
+        self.machine
+            .write()
+            .adjust_committing(memo, source, deltas, commitments)?;
+        Ok(())
     }
 
     // ============ SPAWN ============
 
     /// Execute SPAWN primitive
     pub fn spawn(&self, request: SpawnRequest) -> Result<SpawnResult, DnaError> {
+        // A spawn must not take over an agent's trust, wallet or lineage
+        if self.agent_exists(&request.child_did) {
+            return Err(DnaError::AgentExists {
+                did: request.child_did.clone(),
+            });
+        }
+
         let parent_tau = self.get_trust(&request.parent_did);
 
         // Check minimum trust
@@ -371,8 +879,17 @@ impl ProtocolDna {
             });
         }
 
+        // Culled or recalled agents can't spawn
+        if let Some(parent) = self.lineage.read().node(&request.parent_did) {
+            if !parent.is_active() {
+                return Err(DnaError::AgentRetired {
+                    did: request.parent_did.clone(),
+                });
+            }
+        }
+
         // Check spawn depth
-        let parent_depth = self.spawn_depths.read().get(&request.parent_did).copied().unwrap_or(0);
+        let parent_depth = self.lineage.read().depth(&request.parent_did);
         if parent_depth >= self.config.max_spawn_depth {
             return Err(DnaError::SpawnDepthExceeded {
                 max_depth: self.config.max_spawn_depth,
@@ -398,31 +915,40 @@ impl ProtocolDna {
         }
 
         // Calculate inherited trust
-        let inherited_tau = parent_tau * self.config.inherited_trust_pct;
-
-        // Deduct from parent
-        {
-            let mut balances = self.hc_balances.write();
-            if let Some(balance) = balances.get_mut(&request.parent_did) {
-                *balance -= required;
-            }
-        }
-
-        // Set child trust and balance
+        let inherited_tau = self.config.pricing.inherited_tau(parent_tau);
+
+        // Endow the child from the parent; the stake stays locked for as
+        // long as the child lives
+        self.commit_hc(
+            "spawn",
+            TrancheSource::Grant,
+            &[
+                (request.parent_did.as_str(), -required, request.stake_amount),
+                (request.child_did.as_str(), request.initial_hc, Decimal::ZERO),
+            ],
+            &[(request.parent_did.as_str(), required)],
+        )?;
+
+        // Set child trust
         self.set_trust(&request.child_did, inherited_tau);
-        self.set_balance(&request.child_did, request.initial_hc);
-
-        // Record lineage
-        self.spawn_registry
-            .write()
-            .insert(request.child_did.clone(), request.parent_did.clone());
-        self.spawn_depths
-            .write()
-            .insert(request.child_did.clone(), parent_depth + 1);
 
         let spawn_id = Uuid::now_v7().to_string();
         let timestamp = chrono::Utc::now().timestamp_millis();
 
+        // Record lineage
+        self.lineage.write().insert(LineageNode {
+            did: request.child_did.clone(),
+            parent_did: request.parent_did.clone(),
+            depth: parent_depth + 1,
+            spawn_id: spawn_id.clone(),
+            spawned_at: timestamp,
+            inherited_tau,
+            stake: request.stake_amount,
+            status: LineageStatus::Active,
+            retired_at: None,
+        });
+        self.record_funding(&request.parent_did, &request.child_did, FundingKind::Spawn);
+
         info!(
             spawn_id = %spawn_id,
             parent = %request.parent_did,
@@ -442,6 +968,317 @@ impl ProtocolDna {
         })
     }
 
+    // ============ LINEAGE ============
+
+    /// Resolve a DID's lineage up to its root (`ResolveLineage`)
+    ///
+    /// A `max_depth` of 0 uses the configured default. Trust inheritance is
+    /// the share of each entry's trust passed down to the requested DID: 1.0
+    /// for the DID itself, compounding the inheritance ratio per generation.
+    pub fn resolve_lineage(&self, did: &str, max_depth: u32) -> ResolvedLineage {
+        let max_depth = match max_depth {
+            0 => self.config.lineage.default_resolve_depth,
+            depth => depth,
+        };
+        let mut lineage = vec![did.to_string()];
+        lineage.extend(self.lineage.read().ancestors(did, max_depth));
+        let trust_inheritance = (0..lineage.len())
+            .map(|level| self.config.pricing.inherited_trust_pct.powi(level as i32))
+            .collect();
+
+        ResolvedLineage {
+            lineage,
+            trust_inheritance,
+        }
+    }
+
+    /// Lineage record of a spawned agent
+    pub fn lineage_node(&self, did: &str) -> Option<LineageNode> {
+        self.lineage.read().node(did).cloned()
+    }
+
+    /// Ancestors of a DID, parent first
+    pub fn ancestors(&self, did: &str) -> Vec<String> {
+        self.lineage.read().ancestors(did, u32::MAX)
+    }
+
+    /// Descendants of a DID, breadth-first
+    pub fn descendants(&self, did: &str) -> Vec<String> {
+        self.lineage.read().descendants(did)
+    }
+
+    /// Totals over everything a DID has spawned, directly or not
+    pub fn subtree_aggregate(&self, did: &str) -> SubtreeAggregate {
+        let lineage = self.lineage.read();
+        let base_depth = lineage.depth(did);
+        let mut aggregate = SubtreeAggregate {
+            root_did: did.to_string(),
+            ..Default::default()
+        };
+        let mut tau_sum = 0.0;
+
+        for descendant in lineage.descendants(did) {
+            let node = match lineage.node(&descendant) {
+                Some(node) => node,
+                None => continue,
+            };
+            aggregate.descendants += 1;
+            match node.status {
+                LineageStatus::Active => {
+                    aggregate.active += 1;
+                    tau_sum += self.get_trust(&descendant);
+                }
+                LineageStatus::Culled => aggregate.culled += 1,
+                LineageStatus::Recalled => aggregate.recalled += 1,
+            }
+            aggregate.depth = aggregate.depth.max(node.depth - base_depth);
+            aggregate.total_stake += node.stake;
+            aggregate.total_hc += self.get_balance(&descendant) + self.get_locked(&descendant);
+        }
+
+        if aggregate.active > 0 {
+            aggregate.mean_tau = tau_sum / aggregate.active as f64;
+        }
+        aggregate
+    }
+
+    /// Cull a spawned agent
+    ///
+    /// The agent's trust is zeroed. Culling for misconduct also takes trust
+    /// from its ancestors: the configured penalty from the parent, decaying
+    /// with each generation above. Returns the penalties applied.
+    pub fn cull_agent(&self, did: &str, misconduct: bool) -> Result<Vec<(String, f64)>, DnaError> {
+        let ancestors = {
+            let mut lineage = self.lineage.write();
+            let node = lineage
+                .node_mut(did)
+                .ok_or_else(|| DnaError::AgentNotFound {
+                    did: did.to_string(),
+                })?;
+            if !node.is_active() {
+                return Err(DnaError::AgentRetired {
+                    did: did.to_string(),
+                });
+            }
+            node.status = LineageStatus::Culled;
+            node.retired_at = Some(chrono::Utc::now().timestamp_millis());
+            lineage.ancestors(did, u32::MAX)
+        };
+        self.set_trust(did, 0.0);
+
+        let mut penalties = Vec::new();
+        if misconduct {
+            let mut penalty = self.config.lineage.misconduct_penalty;
+            for ancestor in ancestors {
+                if penalty <= f64::EPSILON {
+                    break;
+                }
+                let tau = self.get_trust(&ancestor);
+                let applied = penalty.min(tau);
+                self.set_trust(&ancestor, tau - applied);
+                penalties.push((ancestor, applied));
+                penalty *= self.config.lineage.penalty_decay;
+            }
+        }
+
+        warn!(did = %did, misconduct, penalized = penalties.len(), "Agent culled");
+
+        Ok(penalties)
+    }
+
+    /// Slash the parent's stake for a child's fraud
+    ///
+    /// Takes the configured share of the stake still locked for `child_did`
+    /// and pays it to `beneficiary` (e.g. the defrauded party), or burns it
+    /// when there is none. An active child is culled for misconduct. Returns
+    /// the amount slashed.
+    pub fn slash_for_fraud(
+        &self,
+        child_did: &str,
+        beneficiary: Option<&str>,
+    ) -> Result<Decimal, DnaError> {
+        let (parent_did, stake, active) = {
+            let lineage = self.lineage.read();
+            let node = lineage
+                .node(child_did)
+                .ok_or_else(|| DnaError::AgentNotFound {
+                    did: child_did.to_string(),
+                })?;
+            (node.parent_did.clone(), node.stake, node.is_active())
+        };
+
+        let share = Decimal::try_from(self.config.lineage.fraud_slash_pct.clamp(0.0, 1.0))
+            .unwrap_or_default();
+        let slashed = (stake * share)
+            .round_dp(HC_SCALE)
+            .min(self.get_locked(&parent_did));
+        if slashed > Decimal::ZERO {
+            match beneficiary {
+                Some(beneficiary) => self.apply_hc(
+                    "fraud_slash",
+                    TrancheSource::Earnings,
+                    &[
+                        (parent_did.as_str(), Decimal::ZERO, -slashed),
+                        (beneficiary, slashed, Decimal::ZERO),
+                    ],
+                )?,
+                None => {
+                    self.machine.write().forfeit(&parent_did, slashed)?;
+                }
+            }
+        }
+        if let Some(node) = self.lineage.write().node_mut(child_did) {
+            node.stake -= slashed;
+        }
+        if active {
+            self.cull_agent(child_did, true)?;
+        }
+
+        warn!(
+            child = %child_did,
+            parent = %parent_did,
+            slashed = %slashed,
+            "Parent stake slashed for fraud"
+        );
+
+        Ok(slashed)
+    }
+
+    /// Recall a spawned agent together with its whole subtree
+    ///
+    /// `recaller_did` must be an ancestor of `child_did`. Every agent in the
+    /// subtree is retired with its trust zeroed. Their available HC and the
+    /// stakes locked inside the subtree go to the recaller; the stake locked
+    /// for `child_did` itself is released to its parent.
+    pub fn recall(&self, recaller_did: &str, child_did: &str) -> Result<RecallReport, DnaError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut lineage = self.lineage.write();
+
+        let node = lineage
+            .node(child_did)
+            .ok_or_else(|| DnaError::AgentNotFound {
+                did: child_did.to_string(),
+            })?;
+        if node.status == LineageStatus::Recalled {
+            return Err(DnaError::AgentRetired {
+                did: child_did.to_string(),
+            });
+        }
+        if !lineage.is_ancestor(recaller_did, child_did) {
+            return Err(DnaError::PermissionDenied {
+                action: "recall".to_string(),
+            });
+        }
+
+        let mut subtree = vec![child_did.to_string()];
+        subtree.extend(lineage.descendants(child_did));
+
+        let mut report = RecallReport::default();
+        let mut changes: Vec<(String, Decimal, Decimal)> = Vec::new();
+        {
+            let machine = self.machine.read();
+            // Locked HC left per parent as stakes are released
+            let mut held: HashMap<String, Decimal> = HashMap::new();
+            for did in subtree {
+                let node = match lineage.node(&did) {
+                    Some(node) if node.status != LineageStatus::Recalled => node,
+                    _ => continue,
+                };
+
+                if node.stake > Decimal::ZERO {
+                    let held = held.entry(node.parent_did.clone()).or_insert_with(|| {
+                        machine
+                            .wallet(&node.parent_did)
+                            .map_or(Decimal::ZERO, |w| w.locked)
+                    });
+                    let released = node.stake.min(*held);
+                    *held -= released;
+                    let to = if did == child_did {
+                        node.parent_did.clone()
+                    } else {
+                        recaller_did.to_string()
+                    };
+                    changes.push((node.parent_did.clone(), Decimal::ZERO, -released));
+                    changes.push((to, released, Decimal::ZERO));
+                    report.stake_released += released;
+                }
+
+                let available = machine
+                    .wallet(&did)
+                    .map_or(Decimal::ZERO, |w| w.spendable(now));
+                if available > Decimal::ZERO {
+                    changes.push((did.clone(), -available, Decimal::ZERO));
+                    changes.push((recaller_did.to_string(), available, Decimal::ZERO));
+                    report.hc_returned += available;
+                }
+                report.retired.push(did);
+            }
+        }
+
+        let changes: Vec<(&str, Decimal, Decimal)> = changes
+            .iter()
+            .map(|(did, available, locked)| (did.as_str(), *available, *locked))
+            .collect();
+        self.apply_hc("recall", TrancheSource::Grant, &changes)?;
+
+        for did in &report.retired {
+            if let Some(node) = lineage.node_mut(did) {
+                node.status = LineageStatus::Recalled;
+                node.retired_at = Some(now);
+                node.stake = Decimal::ZERO;
+            }
+        }
+        drop(lineage);
+
+        for did in &report.retired {
+            self.set_trust(did, 0.0);
+        }
+
+        info!(
+            recaller = %recaller_did,
+            child = %child_did,
+            retired = report.retired.len(),
+            hc_returned = %report.hc_returned,
+            stake_released = %report.stake_released,
+            "Subtree recalled"
+        );
+
+        Ok(report)
+    }
+
+    /// Write a lineage snapshot to the store (no-op without a store)
+    pub async fn persist_lineage(&self) -> actoris_common::Result<()> {
+        if let Some(store) = &self.lineage_store {
+            let mut graph = self.lineage.read().clone();
+            graph.taken_at = chrono::Utc::now().timestamp_millis();
+            store.save(&graph).await?;
+            debug!(agents = graph.len(), "Lineage persisted");
+        }
+        Ok(())
+    }
+
+    /// Replace the lineage graph with the store's latest snapshot
+    ///
+    /// Returns whether a snapshot was found.
+    pub async fn restore_lineage(&self) -> actoris_common::Result<bool> {
+        let store = match &self.lineage_store {
+            Some(store) => store,
+            None => return Ok(false),
+        };
+        match store.load().await? {
+            Some(graph) => {
+                info!(
+                    taken_at = graph.taken_at,
+                    agents = graph.len(),
+                    "Restored lineage"
+                );
+                *self.lineage.write() = graph;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     // ============ LEND ============
 
     /// Execute LEND primitive
@@ -467,7 +1304,10 @@ impl ProtocolDna {
         }
 
         // Calculate collateral
-        let collateral_amount = request.amount * Decimal::try_from(request.collateral_pct).unwrap();
+        let collateral_amount = self
+            .config
+            .pricing
+            .collateral(request.amount, request.collateral_pct)?;
         let borrower_balance = self.get_balance(&request.borrower_did);
         if borrower_balance < collateral_amount {
             return Err(DnaError::InsufficientFunds {
@@ -476,27 +1316,29 @@ impl ProtocolDna {
             });
         }
 
-        // Calculate interest rate (discount based on borrower trust)
-        let interest_rate = request.interest_rate.unwrap_or_else(|| {
-            let discount = borrower_tau * 10.0 * self.config.interest_discount_per_tau;
-            (self.config.base_interest_rate - discount).max(0.01)
-        });
-
-        // Transfer funds
-        {
-            let mut balances = self.hc_balances.write();
-            if let Some(lender_bal) = balances.get_mut(&request.lender_did) {
-                *lender_bal -= request.amount;
-            }
-            if let Some(borrower_bal) = balances.get_mut(&request.borrower_did) {
-                *borrower_bal -= collateral_amount;
-                *borrower_bal += request.amount;
-            }
-        }
+        // Calculate interest rate (priced on borrower trust)
+        let interest_rate = request
+            .interest_rate
+            .unwrap_or_else(|| self.config.pricing.interest_rate(borrower_tau));
+
+        // Transfer funds; collateral is held against the borrower until
+        // repaid or seized, and must be posted before the principal arrives
+        self.commit_hc(
+            "loan",
+            TrancheSource::Loan,
+            &[
+                (request.lender_did.as_str(), -request.amount, Decimal::ZERO),
+                (request.borrower_did.as_str(), -collateral_amount, collateral_amount),
+                (request.borrower_did.as_str(), request.amount, Decimal::ZERO),
+            ],
+            &[(request.lender_did.as_str(), request.amount)],
+        )?;
 
         let loan_id = Uuid::now_v7().to_string();
         let now = chrono::Utc::now().timestamp_millis();
-        let duration_ms = (request.duration_days as i64) * 24 * 60 * 60 * 1000;
+        let duration_ms = (request.duration_days as i64) * DAY_MS;
+        let schedule =
+            self.amortization_schedule(request.amount, interest_rate, now, request.duration_days);
 
         let loan = Loan {
             loan_id: loan_id.clone(),
@@ -511,9 +1353,17 @@ impl ProtocolDna {
             expires_at: now + duration_ms,
             auto_repay: request.auto_repay,
             status: LoanStatus::Active,
+            accrued_interest: Decimal::ZERO,
+            last_accrued_at: now,
+            schedule,
         };
 
         self.loans.write().insert(loan_id.clone(), loan.clone());
+        self.record_funding(
+            &request.lender_did,
+            &request.borrower_did,
+            FundingKind::Loan,
+        );
 
         info!(
             loan_id = %loan_id,
@@ -528,6 +1378,9 @@ impl ProtocolDna {
     }
 
     /// Repay loan
+    ///
+    /// Interest is accrued up to now first; payments cover accrued interest
+    /// before principal.
     pub fn repay_loan(&self, loan_id: &str, amount: Decimal) -> Result<Loan, DnaError> {
         let mut loans = self.loans.write();
         let loan = loans
@@ -542,37 +1395,8 @@ impl ProtocolDna {
             });
         }
 
-        // Check borrower funds
-        let borrower_balance = self.get_balance(&loan.borrower_did);
-        let repay_amount = amount.min(loan.outstanding);
-        if borrower_balance < repay_amount {
-            return Err(DnaError::InsufficientFunds {
-                required: repay_amount,
-                available: borrower_balance,
-            });
-        }
-
-        // Transfer repayment
-        {
-            let mut balances = self.hc_balances.write();
-            if let Some(borrower_bal) = balances.get_mut(&loan.borrower_did) {
-                *borrower_bal -= repay_amount;
-            }
-            let lender_bal = balances.entry(loan.lender_did.clone()).or_insert(Decimal::ZERO);
-            *lender_bal += repay_amount;
-        }
-
-        loan.repaid += repay_amount;
-        loan.outstanding -= repay_amount;
-
-        if loan.outstanding <= Decimal::ZERO {
-            loan.status = LoanStatus::Repaid;
-            // Return collateral
-            let mut balances = self.hc_balances.write();
-            if let Some(borrower_bal) = balances.get_mut(&loan.borrower_did) {
-                *borrower_bal += loan.collateral_amount;
-            }
-        }
+        Self::accrue(loan, chrono::Utc::now().timestamp_millis());
+        let repay_amount = self.settle_payment(loan, amount)?;
 
         info!(
             loan_id = %loan_id,
@@ -585,27 +1409,762 @@ impl ProtocolDna {
         Ok(loan.clone())
     }
 
+    /// Get the amortization schedule of a loan
+    pub fn payment_schedule(&self, loan_id: &str) -> Result<Vec<Installment>, DnaError> {
+        self.loans
+            .read()
+            .get(loan_id)
+            .map(|loan| loan.schedule.clone())
+            .ok_or_else(|| DnaError::LoanNotFound {
+                loan_id: loan_id.to_string(),
+            })
+    }
+
+    // ============ LOAN SERVICING ============
+
+    /// Credit earnings to a DID, sweeping a share into its auto-repay loans
+    /// and then its credit lines
+    ///
+    /// Loans closest to expiry are paid first, then the oldest credit lines.
+    /// The earnings also count toward the DID's credit score. Returns the
+    /// amount swept.
+    pub fn credit_earnings(&self, did: &str, amount: Decimal) -> Decimal {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.record_earnings(did, amount, now);
+        if amount > Decimal::ZERO {
+            if let Err(e) = self
+                .machine
+                .write()
+                .mint_from(did, amount, TrancheSource::Earnings)
+            {
+                warn!(did = %did, error = %e, "Earnings not credited");
+                return Decimal::ZERO;
+            }
+        }
+
+        let share = Decimal::try_from(self.config.servicing.auto_repay_share).unwrap_or_default();
+        let mut budget = (amount * share).round_dp(HC_SCALE);
+        let mut swept = Decimal::ZERO;
+
+        {
+            let mut loans = self.loans.write();
+            let mut borrowed: Vec<&mut Loan> = loans
+                .values_mut()
+                .filter(|l| l.borrower_did == did && l.auto_repay && l.status == LoanStatus::Active)
+                .collect();
+            borrowed.sort_by_key(|l| l.expires_at);
+
+            for loan in borrowed {
+                if budget <= Decimal::ZERO {
+                    break;
+                }
+                Self::accrue(loan, now);
+                if let Ok(paid) = self.settle_payment(loan, budget) {
+                    budget -= paid;
+                    swept += paid;
+                }
+            }
+        }
+        swept += self.sweep_credit_lines(did, budget, now);
+
+        if swept > Decimal::ZERO {
+            debug!(did = %did, earned = %amount, swept = %swept, "Earnings swept into loans");
+        }
+
+        swept
+    }
+
+    /// Run one servicing pass over all active loans and credit lines
+    ///
+    /// Accrues interest, collects due installments from auto-repay
+    /// borrowers, and defaults loans left unpaid past expiry plus the grace
+    /// period: collateral goes to the lender and the borrower loses trust.
+    /// Credit lines are then reviewed, so a default freezes the borrower's
+    /// lines in the same pass.
+    pub fn service_loans(&self, now: i64) -> ServicingReport {
+        let mut report = ServicingReport::default();
+        let grace_ms = self.config.servicing.default_grace_days as i64 * DAY_MS;
+
+        let mut loans = self.loans.write();
+        for loan in loans
+            .values_mut()
+            .filter(|l| l.status == LoanStatus::Active)
+        {
+            report.interest_accrued += Self::accrue(loan, now);
+
+            if loan.auto_repay {
+                let due = if now >= loan.expires_at {
+                    loan.outstanding
+                } else {
+                    loan.amount_due(now)
+                };
+                let payment = due.min(self.get_balance(&loan.borrower_did));
+                if payment > Decimal::ZERO && self.settle_payment(loan, payment).is_ok() {
+                    report.auto_repaid.push(loan.loan_id.clone());
+                }
+            }
+
+            if loan.status == LoanStatus::Active && now >= loan.expires_at + grace_ms {
+                match self.default_loan(loan) {
+                    LoanStatus::Liquidated => report.liquidated.push(loan.loan_id.clone()),
+                    _ => report.defaulted.push(loan.loan_id.clone()),
+                }
+            }
+        }
+        drop(loans);
+
+        for review in self.review_credit_lines(now) {
+            if review.new_status == CreditLineStatus::Frozen
+                && review.old_status != CreditLineStatus::Frozen
+            {
+                report.frozen_lines.push(review.line_id.clone());
+            }
+            if review.new_limit != review.old_limit {
+                report.resized_lines.push(review.line_id);
+            }
+        }
+
+        if !report.defaulted.is_empty() || !report.liquidated.is_empty() {
+            info!(
+                defaulted = report.defaulted.len(),
+                liquidated = report.liquidated.len(),
+                auto_repaid = report.auto_repaid.len(),
+                "Loan servicing pass completed"
+            );
+        }
+
+        report
+    }
+
+    /// Accrue simple daily interest on outstanding principal up to `now`
+    fn accrue(loan: &mut Loan, now: i64) -> Decimal {
+        let days = (now - loan.last_accrued_at) / DAY_MS;
+        if days <= 0 {
+            return Decimal::ZERO;
+        }
+
+        let rate = Decimal::try_from(loan.interest_rate).unwrap_or_default();
+        let interest = (loan.principal_outstanding() * rate * Decimal::from(days)
+            / Decimal::from(DAYS_PER_YEAR))
+        .round_dp(HC_SCALE);
+
+        loan.accrued_interest += interest;
+        loan.outstanding += interest;
+        loan.last_accrued_at += days * DAY_MS;
+        interest
+    }
+
+    /// Move a payment from borrower to lender and apply it to the loan
+    ///
+    /// Returns the amount actually paid (capped at the outstanding balance).
+    fn settle_payment(&self, loan: &mut Loan, amount: Decimal) -> Result<Decimal, DnaError> {
+        let repay_amount = amount.min(loan.outstanding);
+        self.apply_hc(
+            "loan_repayment",
+            TrancheSource::Earnings,
+            &[
+                (loan.borrower_did.as_str(), -repay_amount, Decimal::ZERO),
+                (loan.lender_did.as_str(), repay_amount, Decimal::ZERO),
+            ],
+        )?;
+
+        loan.accrued_interest -= repay_amount.min(loan.accrued_interest);
+        loan.repaid += repay_amount;
+        loan.outstanding -= repay_amount;
+
+        let mut remaining = repay_amount;
+        for installment in loan.schedule.iter_mut() {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let applied = installment.remaining().min(remaining);
+            installment.paid += applied;
+            remaining -= applied;
+        }
+
+        if loan.outstanding <= Decimal::ZERO {
+            loan.status = LoanStatus::Repaid;
+            // Return collateral
+            if let Err(e) = self.unlock_hc(&loan.borrower_did, loan.collateral_amount) {
+                warn!(loan_id = %loan.loan_id, error = %e, "Failed to release loan collateral");
+            }
+        }
+
+        Ok(repay_amount)
+    }
+
+    /// Default a loan: seize collateral to the lender and penalize the borrower
+    fn default_loan(&self, loan: &mut Loan) -> LoanStatus {
+        let collateral = loan
+            .collateral_amount
+            .min(self.get_locked(&loan.borrower_did));
+        let mut seized = collateral.min(loan.outstanding);
+        // Collateral beyond the debt goes back to the borrower
+        if let Err(e) = self.apply_hc(
+            "loan_default",
+            TrancheSource::Earnings,
+            &[
+                (loan.borrower_did.as_str(), collateral - seized, -collateral),
+                (loan.lender_did.as_str(), seized, Decimal::ZERO),
+            ],
+        ) {
+            warn!(loan_id = %loan.loan_id, error = %e, "Failed to seize loan collateral");
+            seized = Decimal::ZERO;
+        }
+        loan.outstanding -= seized;
+        loan.accrued_interest = loan.accrued_interest.min(loan.outstanding);
+        loan.status = if seized > Decimal::ZERO {
+            LoanStatus::Liquidated
+        } else {
+            LoanStatus::Defaulted
+        };
+
+        let tau = self.get_trust(&loan.borrower_did);
+        self.set_trust(
+            &loan.borrower_did,
+            tau - self.config.servicing.default_trust_penalty,
+        );
+
+        warn!(
+            loan_id = %loan.loan_id,
+            borrower = %loan.borrower_did,
+            seized = %seized,
+            shortfall = %loan.outstanding,
+            status = ?loan.status,
+            "Loan defaulted"
+        );
+
+        loan.status
+    }
+
+    /// Build an equal-payment amortization schedule
+    fn amortization_schedule(
+        &self,
+        principal: Decimal,
+        interest_rate: f64,
+        start: i64,
+        duration_days: u32,
+    ) -> Vec<Installment> {
+        let period_days = self.config.servicing.installment_period_days.max(1);
+        let count = duration_days.div_ceil(period_days).max(1);
+        let end = start + duration_days as i64 * DAY_MS;
+
+        let rate = Decimal::try_from(interest_rate).unwrap_or_default()
+            * Decimal::from(period_days)
+            / Decimal::from(DAYS_PER_YEAR);
+        let payment = if rate.is_zero() {
+            principal / Decimal::from(count)
+        } else {
+            let mut growth = Decimal::ONE;
+            for _ in 0..count {
+                growth *= Decimal::ONE + rate;
+            }
+            principal * rate * growth / (growth - Decimal::ONE)
+        };
+
+        let mut balance = principal;
+        (1..=count)
+            .map(|n| {
+                let interest = (balance * rate).round_dp(HC_SCALE);
+                let principal_part = if n == count {
+                    balance
+                } else {
+                    (payment - interest).round_dp(HC_SCALE).min(balance)
+                };
+                balance -= principal_part;
+                Installment {
+                    due_at: (start + (n * period_days) as i64 * DAY_MS).min(end),
+                    principal: principal_part,
+                    interest,
+                    paid: Decimal::ZERO,
+                }
+            })
+            .collect()
+    }
+
+    // ============ CREDIT ============
+
+    /// Record metered earnings of a DID at `at` (unix ms) for credit scoring
+    pub fn record_earnings(&self, did: &str, amount: Decimal, at: i64) {
+        self.earnings
+            .write()
+            .entry(did.to_string())
+            .or_default()
+            .record(at, amount, self.config.credit.earnings_window_days);
+    }
+
+    /// Credit score of a DID from trust, repayment history, earnings
+    /// volatility and outstanding debt
+    pub fn credit_score(&self, did: &str, now: i64) -> CreditScore {
+        let mut inputs = CreditInputs {
+            tau: self.get_trust(did),
+            ..Default::default()
+        };
+
+        for loan in self.loans.read().values().filter(|l| l.borrower_did == did) {
+            match loan.status {
+                LoanStatus::Repaid => inputs.loans_repaid += 1,
+                LoanStatus::Defaulted | LoanStatus::Liquidated => inputs.loans_defaulted += 1,
+                LoanStatus::Active => inputs.outstanding_debt += loan.outstanding,
+            }
+        }
+        inputs.outstanding_debt += self
+            .credit_lines
+            .read()
+            .values()
+            .filter(|l| l.borrower_did == did && l.status != CreditLineStatus::Closed)
+            .map(CreditLine::outstanding)
+            .sum::<Decimal>();
+
+        if let Some(history) = self.earnings.read().get(did) {
+            inputs.earnings = history.periods(now, self.config.credit.earnings_window_days);
+        }
+
+        LendPrimitive::credit_score(&inputs, &self.config.credit.weights)
+    }
+
+    /// Line limit supported by a credit score
+    pub fn credit_line_limit(&self, score: &CreditScore) -> Decimal {
+        LendPrimitive::calculate_credit_limit(
+            self.config.credit.base_line_limit,
+            score.as_trust_score(),
+        )
+        .round_dp(HC_SCALE)
+    }
+
+    /// Open a revolving credit line, locking the limit in the lender's wallet
+    pub fn open_credit_line(&self, request: CreditLineRequest) -> Result<CreditLine, DnaError> {
+        let lender_tau = self.get_trust(&request.lender_did);
+        if lender_tau < self.config.min_lend_tau {
+            return Err(DnaError::InsufficientTrust {
+                required: self.config.min_lend_tau,
+                current: lender_tau,
+            });
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let score = self.credit_score(&request.borrower_did, now);
+        if score.score < self.config.credit.min_open_score {
+            return Err(DnaError::CreditScoreTooLow {
+                required: self.config.credit.min_open_score,
+                current: score.score,
+            });
+        }
+
+        let sized = self.credit_line_limit(&score);
+        let limit = request.limit.map_or(sized, |limit| limit.min(sized));
+        if limit <= Decimal::ZERO {
+            return Err(DnaError::InvalidAmount { amount: limit });
+        }
+        self.lock_hc(&request.lender_did, limit)?;
+
+        let line = CreditLine {
+            line_id: Uuid::now_v7().to_string(),
+            lender_did: request.lender_did.clone(),
+            borrower_did: request.borrower_did.clone(),
+            limit,
+            drawn: Decimal::ZERO,
+            accrued_interest: Decimal::ZERO,
+            interest_rate: request
+                .interest_rate
+                .unwrap_or_else(|| self.config.pricing.interest_rate(score.score)),
+            status: CreditLineStatus::Open,
+            score: score.score,
+            opened_at: now,
+            reviewed_at: now,
+            last_accrued_at: now,
+        };
+
+        self.credit_lines
+            .write()
+            .insert(line.line_id.clone(), line.clone());
+        self.record_funding(
+            &request.lender_did,
+            &request.borrower_did,
+            FundingKind::Loan,
+        );
+
+        info!(
+            line_id = %line.line_id,
+            lender = %line.lender_did,
+            borrower = %line.borrower_did,
+            limit = %limit,
+            score = score.score,
+            "Credit line opened"
+        );
+
+        Ok(line)
+    }
+
+    /// Draw from a credit line into the borrower's available balance
+    ///
+    /// The borrower's score is checked first; a line whose borrower has
+    /// fallen below the freeze threshold is frozen and the draw refused.
+    pub fn draw_credit(&self, line_id: &str, amount: Decimal) -> Result<CreditLine, DnaError> {
+        if amount <= Decimal::ZERO {
+            return Err(DnaError::InvalidAmount { amount });
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let borrower = self
+            .credit_lines
+            .read()
+            .get(line_id)
+            .map(|l| l.borrower_did.clone())
+            .ok_or_else(|| DnaError::CreditLineNotFound {
+                line_id: line_id.to_string(),
+            })?;
+        let score = self.credit_score(&borrower, now);
+
+        let mut lines = self.credit_lines.write();
+        let line = lines
+            .get_mut(line_id)
+            .ok_or_else(|| DnaError::CreditLineNotFound {
+                line_id: line_id.to_string(),
+            })?;
+
+        line.score = score.score;
+        if line.status == CreditLineStatus::Open && score.score < self.config.credit.freeze_below {
+            line.status = CreditLineStatus::Frozen;
+            warn!(line_id = %line_id, score = score.score, "Credit line frozen");
+        }
+        match line.status {
+            CreditLineStatus::Open => {}
+            CreditLineStatus::Frozen => {
+                return Err(DnaError::CreditLineFrozen {
+                    line_id: line_id.to_string(),
+                })
+            }
+            CreditLineStatus::Closed => {
+                return Err(DnaError::CreditLineNotFound {
+                    line_id: line_id.to_string(),
+                })
+            }
+        }
+
+        if amount > line.available() {
+            return Err(DnaError::CreditLimitExceeded {
+                requested: amount,
+                available: line.available(),
+            });
+        }
+
+        Self::accrue_line(line, now);
+        self.apply_hc(
+            "credit_draw",
+            TrancheSource::Loan,
+            &[
+                (line.lender_did.as_str(), Decimal::ZERO, -amount),
+                (line.borrower_did.as_str(), amount, Decimal::ZERO),
+            ],
+        )?;
+        line.drawn += amount;
+
+        debug!(line_id = %line_id, drawn = %amount, utilization = line.utilization(), "Credit drawn");
+
+        Ok(line.clone())
+    }
+
+    /// Repay a credit line (interest first, then principal)
+    pub fn repay_credit(&self, line_id: &str, amount: Decimal) -> Result<CreditLine, DnaError> {
+        if amount <= Decimal::ZERO {
+            return Err(DnaError::InvalidAmount { amount });
+        }
+
+        let mut lines = self.credit_lines.write();
+        let line = lines
+            .get_mut(line_id)
+            .filter(|l| l.status != CreditLineStatus::Closed)
+            .ok_or_else(|| DnaError::CreditLineNotFound {
+                line_id: line_id.to_string(),
+            })?;
+
+        Self::accrue_line(line, chrono::Utc::now().timestamp_millis());
+        let paid = self.settle_line_payment(line, amount)?;
+
+        info!(
+            line_id = %line_id,
+            repaid = %paid,
+            outstanding = %line.outstanding(),
+            "Credit line repayment"
+        );
+
+        Ok(line.clone())
+    }
+
+    /// Close a fully repaid credit line, returning the commitment to the
+    /// lender
+    pub fn close_credit_line(
+        &self,
+        line_id: &str,
+        caller_did: &str,
+    ) -> Result<CreditLine, DnaError> {
+        let mut lines = self.credit_lines.write();
+        let line = lines
+            .get_mut(line_id)
+            .filter(|l| l.status != CreditLineStatus::Closed)
+            .ok_or_else(|| DnaError::CreditLineNotFound {
+                line_id: line_id.to_string(),
+            })?;
+
+        if caller_did != line.lender_did && caller_did != line.borrower_did {
+            return Err(DnaError::PermissionDenied {
+                action: "close credit line".to_string(),
+            });
+        }
+
+        Self::accrue_line(line, chrono::Utc::now().timestamp_millis());
+        if line.outstanding() > Decimal::ZERO {
+            return Err(DnaError::CreditLineOutstanding {
+                line_id: line_id.to_string(),
+                outstanding: line.outstanding(),
+            });
+        }
+
+        if line.undrawn() > Decimal::ZERO {
+            self.unlock_hc(&line.lender_did, line.undrawn())?;
+        }
+        line.status = CreditLineStatus::Closed;
+
+        info!(line_id = %line_id, closed_by = %caller_did, "Credit line closed");
+
+        Ok(line.clone())
+    }
+
+    /// Review all open and frozen credit lines
+    ///
+    /// Rescores each borrower, freezing lines below the freeze threshold and
+    /// reopening frozen ones that recovered. Open lines due for review are
+    /// resized by utilization: heavily used lines grow and idle ones shrink
+    /// by at most the review step, never above the score-sized limit nor
+    /// below the drawn amount. Returns the lines that changed.
+    pub fn review_credit_lines(&self, now: i64) -> Vec<LimitReview> {
+        let borrowers: HashSet<String> = self
+            .credit_lines
+            .read()
+            .values()
+            .filter(|l| l.status != CreditLineStatus::Closed)
+            .map(|l| l.borrower_did.clone())
+            .collect();
+        let scores: HashMap<String, CreditScore> = borrowers
+            .into_iter()
+            .map(|did| {
+                let score = self.credit_score(&did, now);
+                (did, score)
+            })
+            .collect();
+
+        let config = &self.config.credit;
+        let interval_ms = config.review_interval_days as i64 * DAY_MS;
+        let step = Decimal::try_from(config.review_step).unwrap_or_default();
+        let mut reviews = Vec::new();
+
+        let mut lines = self.credit_lines.write();
+        for line in lines
+            .values_mut()
+            .filter(|l| l.status != CreditLineStatus::Closed)
+        {
+            // Opened after scoring; picked up next pass
+            let Some(score) = scores.get(&line.borrower_did) else {
+                continue;
+            };
+
+            Self::accrue_line(line, now);
+            let old_limit = line.limit;
+            let old_status = line.status;
+            line.score = score.score;
+
+            match line.status {
+                CreditLineStatus::Open if score.score < config.freeze_below => {
+                    line.status = CreditLineStatus::Frozen;
+                }
+                CreditLineStatus::Frozen if score.score >= config.unfreeze_at => {
+                    line.status = CreditLineStatus::Open;
+                }
+                _ => {}
+            }
+
+            if line.status == CreditLineStatus::Open && now - line.reviewed_at >= interval_ms {
+                let utilization = line.utilization();
+                let mut new_limit = line.limit;
+                if utilization >= config.raise_utilization {
+                    new_limit = line.limit * (Decimal::ONE + step);
+                } else if utilization <= config.cut_utilization {
+                    new_limit = line.limit * (Decimal::ONE - step);
+                }
+                let new_limit = new_limit
+                    .min(self.credit_line_limit(score))
+                    .max(line.drawn)
+                    .round_dp(HC_SCALE);
+                self.resize_line(line, new_limit);
+                line.reviewed_at = now;
+            }
+
+            if line.limit != old_limit || line.status != old_status {
+                let review = LimitReview {
+                    line_id: line.line_id.clone(),
+                    score: score.score,
+                    utilization: line.utilization(),
+                    old_limit,
+                    new_limit: line.limit,
+                    old_status,
+                    new_status: line.status,
+                };
+                if review.new_status == CreditLineStatus::Frozen {
+                    warn!(line_id = %review.line_id, score = review.score, "Credit line frozen");
+                } else {
+                    info!(
+                        line_id = %review.line_id,
+                        score = review.score,
+                        old_limit = %review.old_limit,
+                        new_limit = %review.new_limit,
+                        status = ?review.new_status,
+                        "Credit line reviewed"
+                    );
+                }
+                reviews.push(review);
+            }
+        }
+
+        reviews
+    }
+
+    /// Get a credit line
+    pub fn get_credit_line(&self, line_id: &str) -> Option<CreditLine> {
+        self.credit_lines.read().get(line_id).cloned()
+    }
+
+    /// Get open and frozen credit lines of a borrower
+    pub fn get_borrower_credit_lines(&self, borrower_did: &str) -> Vec<CreditLine> {
+        self.credit_lines
+            .read()
+            .values()
+            .filter(|l| l.borrower_did == borrower_did && l.status != CreditLineStatus::Closed)
+            .cloned()
+            .collect()
+    }
+
+    /// Sweep up to `budget` of a borrower's earnings into their credit lines
+    fn sweep_credit_lines(&self, did: &str, mut budget: Decimal, now: i64) -> Decimal {
+        if budget <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        let mut swept = Decimal::ZERO;
+        let mut lines = self.credit_lines.write();
+        let mut owed: Vec<&mut CreditLine> = lines
+            .values_mut()
+            .filter(|l| l.borrower_did == did && l.status != CreditLineStatus::Closed)
+            .collect();
+        owed.sort_by_key(|l| l.opened_at);
+
+        for line in owed {
+            if budget <= Decimal::ZERO {
+                break;
+            }
+            Self::accrue_line(line, now);
+            if let Ok(paid) = self.settle_line_payment(line, budget) {
+                budget -= paid;
+                swept += paid;
+            }
+        }
+
+        swept
+    }
+
+    /// Accrue simple daily interest on the drawn amount up to `now`
+    fn accrue_line(line: &mut CreditLine, now: i64) -> Decimal {
+        let days = (now - line.last_accrued_at) / DAY_MS;
+        if days <= 0 {
+            return Decimal::ZERO;
+        }
+
+        let rate = Decimal::try_from(line.interest_rate).unwrap_or_default();
+        let interest = (line.drawn * rate * Decimal::from(days) / Decimal::from(DAYS_PER_YEAR))
+            .round_dp(HC_SCALE);
+
+        line.accrued_interest += interest;
+        line.last_accrued_at += days * DAY_MS;
+        interest
+    }
+
+    /// Move a payment from borrower to lender and apply it to the line
+    ///
+    /// Interest goes to the lender's available balance; repaid principal is
+    /// committed to the line again. Returns the amount actually paid.
+    fn settle_line_payment(
+        &self,
+        line: &mut CreditLine,
+        amount: Decimal,
+    ) -> Result<Decimal, DnaError> {
+        let payment = amount.min(line.outstanding());
+        if payment <= Decimal::ZERO {
+            return Ok(Decimal::ZERO);
+        }
+        let interest = payment.min(line.accrued_interest);
+        let principal = payment - interest;
+
+        self.apply_hc(
+            "credit_repayment",
+            TrancheSource::Earnings,
+            &[
+                (line.borrower_did.as_str(), -payment, Decimal::ZERO),
+                (line.lender_did.as_str(), interest, principal),
+            ],
+        )?;
+
+        line.accrued_interest -= interest;
+        line.drawn -= principal;
+        Ok(payment)
+    }
+
+    /// Change a line's limit, locking or releasing the lender commitment
+    ///
+    /// A raise the lender cannot fund leaves the limit unchanged.
+    fn resize_line(&self, line: &mut CreditLine, new_limit: Decimal) {
+        let delta = new_limit - line.limit;
+        let resized = if delta > Decimal::ZERO {
+            self.lock_hc(&line.lender_did, delta)
+        } else if delta < Decimal::ZERO {
+            self.unlock_hc(&line.lender_did, -delta)
+        } else {
+            return;
+        };
+
+        match resized {
+            Ok(()) => line.limit = new_limit,
+            Err(e) => {
+                debug!(line_id = %line.line_id, error = %e, "Credit line limit unchanged")
+            }
+        }
+    }
+
     // ============ INSURE ============
 
     /// Execute INSURE primitive
     pub fn insure(&self, request: InsureRequest) -> Result<InsurancePolicy, DnaError> {
         let insured_tau = self.get_trust(&request.insured_did);
-        let insurer_balance = self.get_balance(&request.insurer_did);
-
-        // Check insurer has enough to cover
-        if insurer_balance < request.coverage_amount {
-            return Err(DnaError::InsufficientFunds {
-                required: request.coverage_amount,
-                available: insurer_balance,
-            });
-        }
 
-        // Calculate premium (discount based on insured trust)
-        let premium_rate = request.premium_pct.unwrap_or_else(|| {
-            let discount = insured_tau * 10.0 * self.config.premium_discount_per_tau;
-            (self.config.base_premium_rate - discount).max(0.01)
-        });
-        let premium = request.coverage_amount * Decimal::try_from(premium_rate).unwrap();
+        // Calculate premium: explicit rate, actuarial quote, or flat rate
+        // priced on insured trust
+        let quote = match request.premium_pct {
+            Some(_) => None,
+            None => self.quote_premium(&request),
+        };
+        let (premium_rate, premium) = match &quote {
+            Some(quote) => (quote.premium_rate(), quote.premium),
+            None => {
+                let premium_rate = request
+                    .premium_pct
+                    .unwrap_or_else(|| self.config.pricing.premium_rate(insured_tau));
+                (
+                    premium_rate,
+                    self.config
+                        .pricing
+                        .premium_at(request.coverage_amount, premium_rate)?,
+                )
+            }
+        };
 
         // Check insured can pay premium
         let insured_balance = self.get_balance(&request.insured_did);
@@ -616,18 +2175,37 @@ impl ProtocolDna {
             });
         }
 
-        // Transfer premium
-        {
-            let mut balances = self.hc_balances.write();
-            if let Some(insured_bal) = balances.get_mut(&request.insured_did) {
-                *insured_bal -= premium;
-            }
-            let insurer_bal = balances.entry(request.insurer_did.clone()).or_insert(Decimal::ZERO);
-            *insurer_bal += premium;
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut policies = self.policies.write();
+
+        // Check the insurer can reserve capital against its new exposure
+        let mut exposure = self.exposure_of(&request.insurer_did, &policies, now);
+        exposure.add(&request.covered_actions, request.coverage_amount);
+        let required = self.required_reserve(&exposure);
+        let available = exposure.reserve_held + self.get_balance(&request.insurer_did) + premium;
+        if required > available {
+            return Err(DnaError::CapitalInsufficient {
+                required,
+                available,
+            });
         }
 
+        // Transfer premium; the insurer commits the coverage
+        self.commit_hc(
+            "premium",
+            TrancheSource::Earnings,
+            &[
+                (request.insured_did.as_str(), -premium, Decimal::ZERO),
+                (request.insurer_did.as_str(), premium, Decimal::ZERO),
+            ],
+            &[
+                (request.insurer_did.as_str(), request.coverage_amount),
+                (request.insured_did.as_str(), premium),
+            ],
+        )?;
+        self.set_reserve(&request.insurer_did, required)?;
+
         let policy_id = Uuid::now_v7().to_string();
-        let now = chrono::Utc::now().timestamp_millis();
         let duration_ms = (request.duration_days as i64) * 24 * 60 * 60 * 1000;
 
         let policy = InsurancePolicy {
@@ -643,9 +2221,12 @@ impl ProtocolDna {
             claims_made: 0,
             claims_paid: Decimal::ZERO,
             status: PolicyStatus::Active,
+            terms: request.terms,
+            quote,
+            cessions: Vec::new(),
         };
 
-        self.policies.write().insert(policy_id.clone(), policy.clone());
+        policies.insert(policy_id.clone(), policy.clone());
 
         info!(
             policy_id = %policy_id,
@@ -653,12 +2234,213 @@ impl ProtocolDna {
             insurer = %request.insurer_did,
             coverage = %request.coverage_amount,
             premium = %premium,
+            reserve = %required,
             "Insurance policy created"
         );
 
         Ok(policy)
     }
 
+    /// Cede a share of a policy to a reinsurer
+    ///
+    /// The reinsurer receives the same share of the premium, takes on that
+    /// share of exposure (reserving capital against it) and reimburses that
+    /// share of every later payout.
+    pub fn cede(
+        &self,
+        policy_id: &str,
+        reinsurer_did: &str,
+        share: f64,
+    ) -> Result<Cession, DnaError> {
+        if !(share > 0.0 && share <= 1.0) {
+            return Err(DnaError::CessionRejected {
+                reason: "Share must be in (0, 1]".to_string(),
+            });
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut policies = self.policies.write();
+        let policy = policies
+            .get(policy_id)
+            .filter(|p| p.is_in_force(now))
+            .ok_or_else(|| DnaError::PolicyNotFound {
+                policy_id: policy_id.to_string(),
+            })?;
+
+        if policy.insurer_did == reinsurer_did {
+            return Err(DnaError::CessionRejected {
+                reason: "Insurer cannot reinsure its own policy".to_string(),
+            });
+        }
+        if share > policy.retained_share() + f64::EPSILON {
+            return Err(DnaError::CessionRejected {
+                reason: format!(
+                    "Only {:.4} of the policy is retained",
+                    policy.retained_share()
+                ),
+            });
+        }
+
+        let share_dec = Decimal::try_from(share).unwrap_or_default();
+        let ceded_premium = (policy.premium_paid * share_dec).round_dp(HC_SCALE);
+        let ceded_coverage = (policy.remaining_coverage() * share_dec).round_dp(HC_SCALE);
+        let insurer_did = policy.insurer_did.clone();
+
+        // The reinsurer must be able to reserve against its new exposure
+        let mut exposure = self.exposure_of(reinsurer_did, &policies, now);
+        exposure.add(&policy.covered_actions, ceded_coverage);
+        let required = self.required_reserve(&exposure);
+        let available = exposure.reserve_held + self.get_balance(reinsurer_did) + ceded_premium;
+        if required > available {
+            return Err(DnaError::CapitalInsufficient {
+                required,
+                available,
+            });
+        }
+
+        self.transfer_hc(&insurer_did, reinsurer_did, ceded_premium)?;
+        self.set_reserve(reinsurer_did, required)?;
+
+        let cession = Cession {
+            reinsurer_did: reinsurer_did.to_string(),
+            share,
+            ceded_premium,
+            ceded_at: now,
+        };
+        if let Some(policy) = policies.get_mut(policy_id) {
+            policy.cessions.push(cession.clone());
+        }
+
+        // The insurer's retained exposure shrank
+        let insurer_required =
+            self.required_reserve(&self.exposure_of(&insurer_did, &policies, now));
+        if let Err(e) = self.set_reserve(&insurer_did, insurer_required) {
+            warn!(insurer = %insurer_did, error = %e, "Insurer reserve below requirement");
+        }
+
+        info!(
+            policy_id = %policy_id,
+            insurer = %insurer_did,
+            reinsurer = %reinsurer_did,
+            share = share,
+            premium = %ceded_premium,
+            "Policy share ceded"
+        );
+
+        Ok(cession)
+    }
+
+    /// Get an insurer's exposure across policies in force
+    pub fn insurer_exposure(&self, did: &str) -> InsurerExposure {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.exposure_of(did, &self.policies.read(), now)
+    }
+
+    /// Recompute an insurer's required reserve and lock or release the
+    /// difference
+    ///
+    /// Reserves shrink as policies expire or pay out; call this to release
+    /// them, or to top up a reserve drawn down by claims.
+    pub fn sync_reserve(&self, did: &str) -> Result<Decimal, DnaError> {
+        let required = self.required_reserve(&self.insurer_exposure(did));
+        self.set_reserve(did, required)?;
+        Ok(required)
+    }
+
+    /// Capital reserve currently locked by an insurer
+    pub fn reserve_of(&self, did: &str) -> Decimal {
+        self.capital_reserves
+            .read()
+            .get(did)
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Retained exposure of a DID as insurer or reinsurer
+    fn exposure_of(
+        &self,
+        did: &str,
+        policies: &HashMap<String, InsurancePolicy>,
+        now: i64,
+    ) -> InsurerExposure {
+        let mut exposure = InsurerExposure {
+            reserve_held: self.reserve_of(did),
+            ..Default::default()
+        };
+        for policy in policies.values().filter(|p| p.is_in_force(now)) {
+            let remaining = policy.remaining_coverage();
+            if policy.insurer_did == did {
+                let retained = Decimal::try_from(policy.retained_share()).unwrap_or_default();
+                exposure.add(
+                    &policy.covered_actions,
+                    (remaining * retained).round_dp(HC_SCALE),
+                );
+            }
+            for cession in policy.cessions.iter().filter(|c| c.reinsurer_did == did) {
+                let share = Decimal::try_from(cession.share).unwrap_or_default();
+                exposure.add(
+                    &policy.covered_actions,
+                    (remaining * share).round_dp(HC_SCALE),
+                );
+            }
+        }
+        exposure
+    }
+
+    /// Reserve required against an exposure
+    fn required_reserve(&self, exposure: &InsurerExposure) -> Decimal {
+        let ratio = |v: f64| Decimal::try_from(v).unwrap_or_default();
+        (exposure.total * ratio(self.config.capital.reserve_ratio)
+            + exposure.max_correlated() * ratio(self.config.capital.correlation_ratio))
+        .round_dp(HC_SCALE)
+    }
+
+    /// Lock or release HC so the reserve matches `required`
+    fn set_reserve(&self, did: &str, required: Decimal) -> Result<(), DnaError> {
+        let held = self.reserve_of(did);
+        if required > held {
+            self.lock_hc(did, required - held)
+                .map_err(|_| DnaError::CapitalInsufficient {
+                    required,
+                    available: held + self.get_balance(did),
+                })?;
+        } else if held > required {
+            self.unlock_hc(did, held - required)?;
+        }
+        self.capital_reserves
+            .write()
+            .insert(did.to_string(), required);
+        Ok(())
+    }
+
+    /// Release reserve so `amount` is available, if the balance falls short
+    fn draw_reserve(&self, did: &str, amount: Decimal) -> Result<(), DnaError> {
+        let shortfall = amount - self.get_balance(did);
+        if shortfall <= Decimal::ZERO {
+            return Ok(());
+        }
+        let drawn = shortfall.min(self.reserve_of(did));
+        self.unlock_hc(did, drawn)?;
+        *self
+            .capital_reserves
+            .write()
+            .entry(did.to_string())
+            .or_insert(Decimal::ZERO) -= drawn;
+        Ok(())
+    }
+
+    /// Quote an actuarial premium for a request, if a model is attached
+    pub fn quote_premium(&self, request: &InsureRequest) -> Option<PremiumQuote> {
+        self.actuarial.as_ref().map(|model| {
+            model.quote(
+                &request.insured_did,
+                &request.covered_actions,
+                request.coverage_amount,
+                request.duration_days,
+            )
+        })
+    }
+
     /// File insurance claim
     pub fn file_claim(
         &self,
@@ -691,7 +2473,7 @@ impl ProtocolDna {
         }
 
         // Check coverage
-        if amount > policy.coverage_amount - policy.claims_paid {
+        if amount > policy.remaining_coverage() {
             return Err(DnaError::ClaimDenied {
                 reason: "Claim exceeds remaining coverage".to_string(),
             });
@@ -710,6 +2492,8 @@ impl ProtocolDna {
             submitted_at: now,
             status: ClaimStatus::Pending,
             payout: None,
+            trigger: None,
+            resolution: None,
         };
 
         policy.claims_made += 1;
@@ -743,31 +2527,17 @@ impl ProtocolDna {
         }
 
         if approved {
-            let payout_amount = payout.unwrap_or(claim.claimed_amount);
-            claim.status = ClaimStatus::Approved;
-            claim.payout = Some(payout_amount);
-
-            // Update policy
             let mut policies = self.policies.write();
-            if let Some(policy) = policies.get_mut(&claim.policy_id) {
-                policy.claims_paid += payout_amount;
-
-                // Transfer payout
-                let mut balances = self.hc_balances.write();
-                if let Some(insurer_bal) = balances.get_mut(&policy.insurer_did) {
-                    *insurer_bal -= payout_amount;
-                }
-                let insured_bal = balances.entry(policy.insured_did.clone()).or_insert(Decimal::ZERO);
-                *insured_bal += payout_amount;
-
-                if policy.claims_paid >= policy.coverage_amount {
-                    policy.status = PolicyStatus::Expired;
-                } else {
-                    policy.status = PolicyStatus::Active;
-                }
-            }
-
-            claim.status = ClaimStatus::Paid;
+            let policy =
+                policies
+                    .get_mut(&claim.policy_id)
+                    .ok_or_else(|| DnaError::PolicyNotFound {
+                        policy_id: claim.policy_id.clone(),
+                    })?;
+            let payout_amount = payout
+                .unwrap_or(claim.claimed_amount)
+                .min(policy.remaining_coverage());
+            self.pay_claim(policy, claim, payout_amount)?;
         } else {
             claim.status = ClaimStatus::Denied;
 
@@ -790,6 +2560,124 @@ impl ProtocolDna {
         Ok(claim.clone())
     }
 
+    /// Adjudicate a pending claim from the outcome record of its action
+    ///
+    /// Failed verifications, missed SLAs and upheld disputes pay out per the
+    /// policy terms; ambiguous outcomes are left `UnderReview` for
+    /// [`process_claim`](Self::process_claim).
+    pub fn adjudicate_claim(
+        &self,
+        claim_id: &str,
+        record: &OutcomeRecord,
+    ) -> Result<InsuranceClaim, DnaError> {
+        let mut claims = self.claims.write();
+        let claim = claims
+            .get_mut(claim_id)
+            .ok_or_else(|| DnaError::ClaimDenied {
+                reason: "Claim not found".to_string(),
+            })?;
+
+        if claim.status != ClaimStatus::Pending {
+            return Err(DnaError::ClaimDenied {
+                reason: "Claim already processed".to_string(),
+            });
+        }
+
+        let mut policies = self.policies.write();
+        let policy =
+            policies
+                .get_mut(&claim.policy_id)
+                .ok_or_else(|| DnaError::PolicyNotFound {
+                    policy_id: claim.policy_id.clone(),
+                })?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        match parametric::adjudicate(policy, claim, record, now) {
+            Adjudication::Payout { trigger, amount } => {
+                self.pay_claim(policy, claim, amount)?;
+                claim.trigger = Some(trigger);
+            }
+            Adjudication::Deny { reason } => {
+                claim.status = ClaimStatus::Denied;
+                claim.resolution = Some(reason);
+                if policy.status == PolicyStatus::ClaimsPending {
+                    policy.status = PolicyStatus::Active;
+                }
+            }
+            Adjudication::Review { reason } => {
+                claim.status = ClaimStatus::UnderReview;
+                claim.resolution = Some(reason);
+            }
+        }
+
+        info!(
+            claim_id = %claim_id,
+            action_id = %claim.action_id,
+            status = ?claim.status,
+            trigger = ?claim.trigger,
+            payout = ?claim.payout,
+            "Claim adjudicated"
+        );
+
+        Ok(claim.clone())
+    }
+
+    /// Pay a claim from the insurer's balance
+    ///
+    /// Reinsurers reimburse their ceded share first. Capital reserves are
+    /// drawn on when available balances fall short; they are topped up again
+    /// by the next [`sync_reserve`](Self::sync_reserve) or policy written.
+    fn pay_claim(
+        &self,
+        policy: &mut InsurancePolicy,
+        claim: &mut InsuranceClaim,
+        amount: Decimal,
+    ) -> Result<(), DnaError> {
+        let recoveries: Vec<(&str, Decimal)> = policy
+            .cessions
+            .iter()
+            .map(|c| {
+                let share = Decimal::try_from(c.share).unwrap_or_default();
+                (c.reinsurer_did.as_str(), (amount * share).round_dp(HC_SCALE))
+            })
+            .collect();
+        let recovered: Decimal = recoveries.iter().map(|(_, due)| *due).sum();
+
+        // Check every party can pay before moving anything
+        let retained = amount - recovered;
+        for (did, due) in recoveries
+            .iter()
+            .copied()
+            .chain(std::iter::once((policy.insurer_did.as_str(), retained)))
+        {
+            let funds = self.get_balance(did) + self.reserve_of(did);
+            if funds < due {
+                return Err(DnaError::InsufficientFunds {
+                    required: due,
+                    available: funds,
+                });
+            }
+        }
+
+        for (reinsurer, due) in &recoveries {
+            self.draw_reserve(reinsurer, *due)?;
+            self.transfer_hc(reinsurer, &policy.insurer_did, *due)?;
+        }
+        self.draw_reserve(&policy.insurer_did, amount)?;
+        self.transfer_hc(&policy.insurer_did, &policy.insured_did, amount)?;
+
+        policy.claims_paid += amount;
+        policy.status = if policy.remaining_coverage() <= Decimal::ZERO {
+            PolicyStatus::Expired
+        } else {
+            PolicyStatus::Active
+        };
+
+        claim.payout = Some(amount);
+        claim.status = ClaimStatus::Paid;
+        Ok(())
+    }
+
     // ============ DELEGATE ============
 
     /// Execute DELEGATE primitive
@@ -823,7 +2711,23 @@ impl ProtocolDna {
             parent_delegation_id: None,
         };
 
+        // The delegate may spend up to the cap on the delegator's behalf; an
+        // uncapped delegation commits the delegator's whole balance
+        let committed = request
+            .max_total_hc
+            .unwrap_or_else(|| self.get_balance(&request.delegator_did));
+        self.commit_hc(
+            "delegate",
+            TrancheSource::Earnings,
+            &[],
+            &[(request.delegator_did.as_str(), committed)],
+        )?;
         self.delegations.write().insert(delegation_id.clone(), delegation.clone());
+        self.record_funding(
+            &request.delegator_did,
+            &request.delegate_did,
+            FundingKind::Delegation,
+        );
 
         info!(
             delegation_id = %delegation_id,
@@ -855,11 +2759,12 @@ impl ProtocolDna {
             if now > delegation.expires_at {
                 continue;
             }
+            if !Self::lineage_active(&delegations, delegation, now) {
+                continue;
+            }
 
             // Check action allowed
-            if !delegation.allowed_actions.is_empty()
-                && !delegation.allowed_actions.contains(&action.to_string())
-            {
+            if !delegation.allows_action(action) {
                 continue;
             }
 
@@ -905,41 +2810,212 @@ impl ProtocolDna {
             });
         }
 
-        delegation.hc_used += hc_amount;
+        delegation.hc_used += hc_amount;
+
+        debug!(
+            delegation_id = %delegation_id,
+            used = %hc_amount,
+            total_used = %delegation.hc_used,
+            "Delegation HC used"
+        );
+
+        Ok(())
+    }
+
+    /// Revoke delegation
+    pub fn revoke_delegation(&self, delegation_id: &str, revoker_did: &str) -> Result<(), DnaError> {
+        let mut delegations = self.delegations.write();
+        let delegation = delegations
+            .get_mut(delegation_id)
+            .ok_or_else(|| DnaError::DelegationNotFound {
+                delegation_id: delegation_id.to_string(),
+            })?;
+
+        if delegation.delegator_did != revoker_did {
+            return Err(DnaError::PermissionDenied {
+                action: "revoke delegation".to_string(),
+            });
+        }
+
+        delegation.revoked = true;
+
+        info!(
+            delegation_id = %delegation_id,
+            "Delegation revoked"
+        );
+
+        Ok(())
+    }
+
+    /// Sub-delegate part of a delegation's authority
+    ///
+    /// `request.delegator_did` must be the parent's delegate. The child may
+    /// only narrow the parent's scope; its expiry is clamped to the parent's.
+    pub fn subdelegate(
+        &self,
+        parent_delegation_id: &str,
+        request: DelegateRequest,
+    ) -> Result<Delegation, DnaError> {
+        let mut delegations = self.delegations.write();
+        let parent =
+            delegations
+                .get(parent_delegation_id)
+                .ok_or_else(|| DnaError::DelegationNotFound {
+                    delegation_id: parent_delegation_id.to_string(),
+                })?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        if parent.revoked || !Self::lineage_active(&delegations, parent, now) {
+            return Err(DnaError::DelegationNotFound {
+                delegation_id: parent_delegation_id.to_string(),
+            });
+        }
+        if now > parent.expires_at {
+            return Err(DnaError::DelegationExpired);
+        }
+        if parent.delegate_did != request.delegator_did {
+            return Err(DnaError::PermissionDenied {
+                action: "sub-delegate".to_string(),
+            });
+        }
+        if !parent.allow_subdelegation {
+            return Err(DnaError::InvalidDelegation {
+                reason: "Sub-delegation not allowed".to_string(),
+            });
+        }
+
+        let duration_ms = (request.duration_days as i64) * DAY_MS;
+        let delegation = Delegation {
+            delegation_id: Uuid::now_v7().to_string(),
+            delegator_did: request.delegator_did.clone(),
+            delegate_did: request.delegate_did.clone(),
+            allowed_actions: request.allowed_actions,
+            max_hc_per_action: request.max_hc_per_action,
+            max_total_hc: request.max_total_hc,
+            hc_used: Decimal::ZERO,
+            started_at: now,
+            expires_at: (now + duration_ms).min(parent.expires_at),
+            allow_subdelegation: request.allow_subdelegation,
+            revoked: false,
+            parent_delegation_id: Some(parent_delegation_id.to_string()),
+        };
+        delegation
+            .scope()
+            .attenuates(&parent.scope())
+            .map_err(|e| DnaError::InvalidDelegation {
+                reason: e.to_string(),
+            })?;
+
+        delegations.insert(delegation.delegation_id.clone(), delegation.clone());
+        drop(delegations);
+        self.record_funding(
+            &request.delegator_did,
+            &request.delegate_did,
+            FundingKind::Delegation,
+        );
 
-        debug!(
-            delegation_id = %delegation_id,
-            used = %hc_amount,
-            total_used = %delegation.hc_used,
-            "Delegation HC used"
+        info!(
+            delegation_id = %delegation.delegation_id,
+            parent = %parent_delegation_id,
+            delegate = %request.delegate_did,
+            "Sub-delegation created"
         );
 
-        Ok(())
+        Ok(delegation)
     }
 
-    /// Revoke delegation
-    pub fn revoke_delegation(&self, delegation_id: &str, revoker_did: &str) -> Result<(), DnaError> {
-        let mut delegations = self.delegations.write();
-        let delegation = delegations
-            .get_mut(delegation_id)
+    /// Issue a delegation as a signed capability token
+    ///
+    /// `signing_key` must belong to the delegator's did:key. Sub-delegations
+    /// need the token of their parent delegation so the chain links up. The
+    /// token carries the delegation ID, so revoking the delegation revokes
+    /// the token.
+    pub fn issue_capability(
+        &self,
+        delegation_id: &str,
+        signing_key: &SigningKey,
+        parent: Option<&CapabilityToken>,
+    ) -> Result<CapabilityToken, DnaError> {
+        let delegation = self
+            .delegations
+            .read()
+            .get(delegation_id)
+            .cloned()
             .ok_or_else(|| DnaError::DelegationNotFound {
                 delegation_id: delegation_id.to_string(),
             })?;
 
-        if delegation.delegator_did != revoker_did {
+        if delegation.revoked {
+            return Err(DnaError::DelegationNotFound {
+                delegation_id: delegation_id.to_string(),
+            });
+        }
+        let signer_did = encode_did_key(&signing_key.verifying_key().to_bytes());
+        if signer_did != delegation.delegator_did {
             return Err(DnaError::PermissionDenied {
-                action: "revoke delegation".to_string(),
+                action: "issue capability".to_string(),
+            });
+        }
+        if parent.map(|p| p.id.as_str()) != delegation.parent_delegation_id.as_deref() {
+            return Err(DnaError::InvalidDelegation {
+                reason: "Parent token does not match the parent delegation".to_string(),
             });
         }
 
-        delegation.revoked = true;
+        Ok(CapabilityToken::issue_with_id(
+            signing_key,
+            delegation.delegation_id.clone(),
+            delegation.delegate_did.clone(),
+            delegation.scope(),
+            parent,
+        )?)
+    }
 
-        info!(
-            delegation_id = %delegation_id,
-            "Delegation revoked"
-        );
+    /// IDs of revoked delegations, for distribution to offline verifiers
+    pub fn revoked_delegations(&self) -> HashSet<String> {
+        self.delegations
+            .read()
+            .values()
+            .filter(|d| d.revoked)
+            .map(|d| d.delegation_id.clone())
+            .collect()
+    }
 
-        Ok(())
+    /// Authorize an action from a capability chain against this ledger's
+    /// revocations
+    pub fn authorize_capability(
+        &self,
+        chain: &[CapabilityToken],
+        invoker_did: &str,
+        action: &str,
+        hc_amount: Decimal,
+    ) -> Result<AuthorizedCapability, DnaError> {
+        Ok(capability::verify_chain(
+            chain,
+            invoker_did,
+            action,
+            hc_amount,
+            chrono::Utc::now().timestamp_millis(),
+            &self.revoked_delegations(),
+        )?)
+    }
+
+    /// Whether every ancestor of a delegation is unrevoked and unexpired
+    fn lineage_active(
+        delegations: &HashMap<String, Delegation>,
+        delegation: &Delegation,
+        now: i64,
+    ) -> bool {
+        let mut parent_id = delegation.parent_delegation_id.as_ref();
+        while let Some(id) = parent_id {
+            match delegations.get(id) {
+                Some(parent) if !parent.revoked && now <= parent.expires_at => {
+                    parent_id = parent.parent_delegation_id.as_ref();
+                }
+                _ => return false,
+            }
+        }
+        true
     }
 
     /// Get active loans for a borrower
@@ -980,6 +3056,82 @@ impl Default for ProtocolDna {
     }
 }
 
+/// Trust recorded on the ledger drives the verifier's risk tiering
+impl TrustLookup for ProtocolDna {
+    fn trust_score(&self, did: &str, _action_type: &str) -> Option<u16> {
+        self.trust_scores
+            .read()
+            .get(did)
+            .map(|tau| (tau * 1000.0).round() as u16)
+    }
+}
+
+/// WASM contracts move HC through the same balances as the built-in primitives
+impl DnaLedger for ProtocolDna {
+    fn trust(&self, did: &str) -> u16 {
+        (self.get_trust(did) * 1000.0).round() as u16
+    }
+
+    fn balance(&self, did: &str) -> Decimal {
+        self.get_balance(did)
+    }
+
+    fn locked(&self, did: &str) -> Decimal {
+        self.get_locked(did)
+    }
+
+    fn escrowed(&self, contract: &str, did: &str) -> Decimal {
+        self.contract_escrow
+            .read()
+            .get(contract)
+            .and_then(|escrow| escrow.get(did))
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    fn apply_effects(&self, contract: &str, effects: &[LedgerEffect]) -> Result<(), LedgerError> {
+        // Held across apply_hc so escrow and balances change together
+        let mut escrows = self.contract_escrow.write();
+        let mut escrow = escrows.get(contract).cloned().unwrap_or_default();
+
+        let mut changes = Vec::with_capacity(effects.len() + 1);
+        for effect in effects {
+            match effect {
+                LedgerEffect::Lock { did, amount } => {
+                    *escrow.entry(did.clone()).or_insert(Decimal::ZERO) += amount;
+                    changes.push((did.as_str(), -*amount, *amount));
+                }
+                LedgerEffect::Unlock { did, amount } => {
+                    let held = escrow.entry(did.clone()).or_insert(Decimal::ZERO);
+                    if *held < *amount {
+                        return Err(LedgerError::Rejected(format!(
+                            "contract {} has {} HC escrowed for {}, cannot unlock {}",
+                            contract, held, did, amount
+                        )));
+                    }
+                    *held -= amount;
+                    changes.push((did.as_str(), *amount, -*amount));
+                }
+                LedgerEffect::Transfer { from, to, amount } => {
+                    changes.push((from.as_str(), -*amount, Decimal::ZERO));
+                    changes.push((to.as_str(), *amount, Decimal::ZERO));
+                }
+            }
+        }
+
+        self.apply_hc(&format!("contract:{}", contract), TrancheSource::Earnings, &changes)
+            .map_err(|e| LedgerError::Rejected(format!("contract {}: {}", contract, e)))?;
+
+        escrow.retain(|_, held| !held.is_zero());
+        if escrow.is_empty() {
+            escrows.remove(contract);
+        } else {
+            escrows.insert(contract.to_string(), escrow);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1013,6 +3165,191 @@ mod tests {
         assert_eq!(dna.get_balance("did:key:parent"), dec!(800));
     }
 
+    #[test]
+    fn test_spawn_rejects_existing_child() {
+        let dna = ProtocolDna::default();
+        dna.set_trust("did:key:parent", 0.8);
+        dna.set_balance("did:key:parent", dec!(1000));
+        dna.set_trust("did:key:established", 0.95);
+        dna.set_balance("did:key:funded", dec!(5));
+
+        let spawn = |child: &str| {
+            dna.spawn(SpawnRequest {
+                parent_did: "did:key:parent".to_string(),
+                child_did: child.to_string(),
+                initial_hc: dec!(100),
+                stake_amount: dec!(100),
+                metadata: HashMap::new(),
+            })
+        };
+
+        // Existing trust, wallets and spawns are not overwritten
+        for did in ["did:key:parent", "did:key:established", "did:key:funded"] {
+            assert!(matches!(spawn(did), Err(DnaError::AgentExists { .. })));
+        }
+        spawn("did:key:child").unwrap();
+        assert!(matches!(
+            spawn("did:key:child"),
+            Err(DnaError::AgentExists { .. })
+        ));
+
+        assert!((dna.get_trust("did:key:established") - 0.95).abs() < 1e-12);
+        assert_eq!(dna.get_balance("did:key:funded"), dec!(5));
+        assert_eq!(dna.get_balance("did:key:parent"), dec!(800));
+    }
+
+    fn spawn_child(dna: &ProtocolDna, parent: &str, child: &str) {
+        dna.spawn(SpawnRequest {
+            parent_did: parent.to_string(),
+            child_did: child.to_string(),
+            initial_hc: dec!(100),
+            stake_amount: dec!(100),
+            metadata: HashMap::new(),
+        })
+        .unwrap();
+    }
+
+    fn lineage_fixture() -> ProtocolDna {
+        // root -> a -> a1 -> a1x, root -> b
+        let dna = ProtocolDna::default();
+        dna.set_trust("did:key:root", 1.0);
+        dna.set_balance("did:key:root", dec!(1000));
+        spawn_child(&dna, "did:key:root", "did:key:a");
+        spawn_child(&dna, "did:key:root", "did:key:b");
+        for (parent, child) in [("did:key:a", "did:key:a1"), ("did:key:a1", "did:key:a1x")] {
+            dna.set_trust(parent, 0.9);
+            dna.set_balance(parent, dna.get_balance(parent) + dec!(300));
+            spawn_child(&dna, parent, child);
+        }
+        dna
+    }
+
+    #[test]
+    fn test_lineage_queries_and_misconduct() {
+        let dna = lineage_fixture();
+        assert_eq!(dna.get_locked("did:key:root"), dec!(200));
+
+        let resolved = dna.resolve_lineage("did:key:a1x", 0);
+        assert_eq!(
+            resolved.lineage,
+            vec!["did:key:a1x", "did:key:a1", "did:key:a", "did:key:root"]
+        );
+        assert_eq!(resolved.trust_inheritance.len(), 4);
+        assert!((resolved.trust_inheritance[2] - 0.09).abs() < 1e-12);
+        assert_eq!(dna.resolve_lineage("did:key:a1x", 1).lineage.len(), 2);
+
+        let aggregate = dna.subtree_aggregate("did:key:root");
+        assert_eq!(aggregate.descendants, 4);
+        assert_eq!(aggregate.depth, 3);
+        assert_eq!(aggregate.total_stake, dec!(400));
+
+        // Misconduct dents the parent most, ancestors less
+        let penalties = dna.cull_agent("did:key:a1x", true).unwrap();
+        assert_eq!(penalties.len(), 3);
+        assert!((dna.get_trust("did:key:a1") - 0.85).abs() < 1e-9);
+        assert!((dna.get_trust("did:key:a") - 0.875).abs() < 1e-9);
+        assert!((dna.get_trust("did:key:root") - 0.9875).abs() < 1e-9);
+        assert_eq!(dna.get_trust("did:key:a1x"), 0.0);
+        assert!(dna.cull_agent("did:key:a1x", true).is_err());
+
+        // Fraud slashes the stake the parent put up, paid to the victim
+        let slashed = dna
+            .slash_for_fraud("did:key:a1x", Some("did:key:victim"))
+            .unwrap();
+        assert_eq!(slashed, dec!(50));
+        assert_eq!(dna.get_balance("did:key:victim"), dec!(50));
+        assert_eq!(dna.get_locked("did:key:a1"), dec!(50));
+        assert_eq!(dna.lineage_node("did:key:a1x").unwrap().stake, dec!(50));
+    }
+
+    #[test]
+    fn test_recall_retires_subtree_and_returns_hc() {
+        let dna = lineage_fixture();
+        let before = dna.get_balance("did:key:root");
+
+        // Only ancestors may recall
+        assert!(matches!(
+            dna.recall("did:key:b", "did:key:a"),
+            Err(DnaError::PermissionDenied { .. })
+        ));
+
+        let report = dna.recall("did:key:root", "did:key:a").unwrap();
+        assert_eq!(
+            report.retired,
+            vec!["did:key:a", "did:key:a1", "did:key:a1x"]
+        );
+        // Spendable HC of a, a1 and a1x, plus the three spawn stakes
+        let spendable = dec!(200) + dec!(200) + dec!(100);
+        assert_eq!(report.hc_returned, spendable);
+        assert_eq!(report.stake_released, dec!(300));
+        assert_eq!(
+            dna.get_balance("did:key:root"),
+            before + spendable + dec!(300)
+        );
+        assert_eq!(dna.get_locked("did:key:root"), dec!(100));
+        assert_eq!(dna.get_locked("did:key:a"), Decimal::ZERO);
+
+        let aggregate = dna.subtree_aggregate("did:key:root");
+        assert_eq!(aggregate.recalled, 3);
+        assert_eq!(aggregate.active, 1);
+
+        // Retired agents can't spawn or be recalled again
+        dna.set_balance("did:key:a", dec!(1000));
+        dna.set_trust("did:key:a", 0.9);
+        assert!(matches!(
+            dna.spawn(SpawnRequest {
+                parent_did: "did:key:a".to_string(),
+                child_did: "did:key:a2".to_string(),
+                initial_hc: dec!(100),
+                stake_amount: dec!(100),
+                metadata: HashMap::new(),
+            }),
+            Err(DnaError::AgentRetired { .. })
+        ));
+        assert!(dna.recall("did:key:root", "did:key:a1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lineage_persists_through_store() {
+        use crate::verification::lineage_store::InMemoryLineageStore;
+
+        let store = Arc::new(InMemoryLineageStore::new());
+        let dna = ProtocolDna::default().with_lineage_store(store.clone());
+        dna.set_trust("did:key:root", 1.0);
+        dna.set_balance("did:key:root", dec!(1000));
+        spawn_child(&dna, "did:key:root", "did:key:a");
+        dna.persist_lineage().await.unwrap();
+
+        let restored = ProtocolDna::default().with_lineage_store(store);
+        assert!(restored.restore_lineage().await.unwrap());
+        assert_eq!(restored.ancestors("did:key:a"), vec!["did:key:root"]);
+    }
+
+    #[test]
+    fn test_lend_rejects_unpriceable_collateral() {
+        let dna = ProtocolDna::default();
+        dna.set_trust("did:key:lender", 0.9);
+        dna.set_balance("did:key:lender", dec!(1000));
+        dna.set_balance("did:key:borrower", dec!(200));
+
+        for collateral_pct in [f64::NAN, f64::INFINITY, -0.2] {
+            let result = dna.lend(LendRequest {
+                lender_did: "did:key:lender".to_string(),
+                borrower_did: "did:key:borrower".to_string(),
+                amount: dec!(500),
+                interest_rate: None,
+                collateral_pct,
+                duration_days: 30,
+                auto_repay: false,
+            });
+            assert!(matches!(
+                result,
+                Err(DnaError::Pricing(PricingError::InvalidRate(_)))
+            ));
+        }
+        assert_eq!(dna.get_balance("did:key:lender"), dec!(1000));
+    }
+
     #[test]
     fn test_lend_and_repay() {
         let dna = ProtocolDna::default();
@@ -1037,6 +3374,7 @@ mod tests {
         assert_eq!(loan.principal, dec!(500));
         assert_eq!(loan.collateral_amount, dec!(100));
         assert!(loan.interest_rate < 0.10); // Should have discount
+        assert_eq!(loan.interest_rate, DnaPricing::default().interest_rate(0.5));
 
         // Borrower balance should be initial + loan - collateral
         assert_eq!(dna.get_balance("did:key:borrower"), dec!(600)); // 200 + 500 - 100
@@ -1048,6 +3386,250 @@ mod tests {
 
         // Collateral returned
         assert_eq!(dna.get_balance("did:key:borrower"), dec!(200)); // 600 - 500 + 100 collateral
+
+        // Every move is a conserving machine event
+        let machine = dna.machine.read();
+        assert!(machine.is_conserved());
+        assert!(dna.hc_events().iter().any(|e| matches!(
+            e,
+            DnaEvent::Adjusted { memo, source: TrancheSource::Loan, .. } if memo == "loan"
+        )));
+        let replayed = DnaMachine::replay(DnaPricing::default(), dna.hc_events()).unwrap();
+        for did in ["did:key:lender", "did:key:borrower"] {
+            let held = |m: &DnaMachine| m.wallet(did).map(|w| (w.available, w.locked));
+            assert_eq!(held(&replayed), held(&machine));
+            machine.journal().verify(machine.wallet(did).unwrap()).unwrap();
+        }
+        drop(machine);
+
+        // The borrower's statement covers the loan from issue to repayment
+        let statement = dna.hc_statement("did:key:borrower", 0, i64::MAX);
+        assert!(statement.lines.iter().any(|l| l.reason == "loan"));
+        assert_eq!(statement.closing_available, dec!(200));
+        assert_eq!(statement.closing_locked, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_hc_changes_are_all_or_nothing() {
+        let dna = ProtocolDna::default();
+        dna.set_balance("did:key:a", dec!(100));
+        dna.set_balance("did:key:b", dec!(10));
+
+        // b cannot lock 70 even after a's payment arrives
+        let err = dna
+            .apply_hc(
+                "test",
+                TrancheSource::Earnings,
+                &[
+                    ("did:key:a", dec!(-50), Decimal::ZERO),
+                    ("did:key:b", dec!(50), Decimal::ZERO),
+                    ("did:key:b", dec!(-70), dec!(70)),
+                ],
+            )
+            .unwrap_err();
+        assert!(matches!(err, DnaError::InsufficientFunds { .. }));
+        assert_eq!(dna.get_balance("did:key:a"), dec!(100));
+        assert_eq!(dna.get_balance("did:key:b"), dec!(10));
+        assert_eq!(dna.get_locked("did:key:b"), Decimal::ZERO);
+
+        dna.apply_hc(
+            "test",
+            TrancheSource::Earnings,
+            &[
+                ("did:key:a", dec!(-50), Decimal::ZERO),
+                ("did:key:b", dec!(50), Decimal::ZERO),
+                ("did:key:b", dec!(-60), dec!(60)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(dna.get_balance("did:key:a"), dec!(50));
+        assert_eq!(dna.get_balance("did:key:b"), Decimal::ZERO);
+        assert_eq!(dna.get_locked("did:key:b"), dec!(60));
+    }
+
+    fn servicing_loan(dna: &ProtocolDna, collateral_pct: f64, auto_repay: bool) -> Loan {
+        dna.set_trust("did:key:lender", 0.8);
+        dna.set_trust("did:key:borrower", 0.5);
+        dna.set_balance("did:key:lender", dec!(1000));
+        dna.set_balance("did:key:borrower", dec!(200));
+
+        dna.lend(LendRequest {
+            lender_did: "did:key:lender".to_string(),
+            borrower_did: "did:key:borrower".to_string(),
+            amount: dec!(700),
+            interest_rate: Some(0.365), // 0.1% per day
+            collateral_pct,
+            duration_days: 28,
+            auto_repay,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_loan_schedule_and_accrual() {
+        let dna = ProtocolDna::default();
+        let loan = servicing_loan(&dna, 0.0, false);
+
+        // Weekly installments fully amortize the principal by expiry
+        let schedule = dna.payment_schedule(&loan.loan_id).unwrap();
+        assert_eq!(schedule.len(), 4);
+        assert_eq!(
+            schedule.iter().map(|i| i.principal).sum::<Decimal>(),
+            dec!(700)
+        );
+        assert_eq!(schedule.last().unwrap().due_at, loan.expires_at);
+
+        // Ten days at 0.1% per day
+        let report = dna.service_loans(loan.started_at + 10 * DAY_MS);
+        assert_eq!(report.interest_accrued, dec!(7));
+        assert!(report.auto_repaid.is_empty());
+
+        // Repayment covers interest before principal
+        let repaid = dna.repay_loan(&loan.loan_id, dec!(10)).unwrap();
+        assert_eq!(repaid.accrued_interest, Decimal::ZERO);
+        assert_eq!(repaid.principal_outstanding(), dec!(697));
+    }
+
+    #[test]
+    fn test_auto_repay_collects_due_installments_and_earnings() {
+        let dna = ProtocolDna::default();
+        let loan = servicing_loan(&dna, 0.0, true);
+        let installment = loan.schedule[0].amount();
+
+        let report = dna.service_loans(loan.started_at + 7 * DAY_MS);
+        assert_eq!(report.auto_repaid, vec![loan.loan_id.clone()]);
+
+        let serviced = dna.get_borrower_loans("did:key:borrower").remove(0);
+        assert_eq!(serviced.schedule[0].remaining(), Decimal::ZERO);
+        assert_eq!(serviced.outstanding, dec!(704.9) - installment);
+        assert_eq!(dna.get_balance("did:key:borrower"), dec!(900) - installment);
+
+        // Half of new earnings is swept into the loan
+        let swept = dna.credit_earnings("did:key:borrower", dec!(100));
+        assert_eq!(swept, dec!(50));
+        assert_eq!(dna.get_balance("did:key:borrower"), dec!(950) - installment);
+    }
+
+    #[test]
+    fn test_expired_loan_is_liquidated() {
+        let dna = ProtocolDna::default();
+        let loan = servicing_loan(&dna, 0.2, false);
+        assert_eq!(dna.get_locked("did:key:borrower"), dec!(140));
+
+        // Borrower spends everything and never repays
+        dna.set_balance("did:key:borrower", Decimal::ZERO);
+
+        // Still within the grace period
+        let report = dna.service_loans(loan.expires_at);
+        assert!(report.liquidated.is_empty());
+
+        let report = dna.service_loans(loan.expires_at + 2 * DAY_MS);
+        assert_eq!(report.liquidated, vec![loan.loan_id.clone()]);
+        assert!(dna.get_borrower_loans("did:key:borrower").is_empty());
+
+        // Collateral seized to the lender, trust penalty applied
+        assert_eq!(dna.get_locked("did:key:borrower"), Decimal::ZERO);
+        assert_eq!(dna.get_balance("did:key:lender"), dec!(440));
+        assert!((dna.get_trust("did:key:borrower") - 0.3).abs() < 1e-9);
+    }
+
+    fn credit_parties(dna: &ProtocolDna) {
+        dna.set_trust("did:key:lender", 0.9);
+        dna.set_balance("did:key:lender", dec!(5000));
+        dna.set_trust("did:key:borrower", 0.8);
+    }
+
+    #[test]
+    fn test_credit_line_draw_repay_and_sweep() {
+        let dna = ProtocolDna::default();
+        credit_parties(&dna);
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let score = dna.credit_score("did:key:borrower", now);
+        let line = dna
+            .open_credit_line(CreditLineRequest {
+                lender_did: "did:key:lender".to_string(),
+                borrower_did: "did:key:borrower".to_string(),
+                limit: None,
+                interest_rate: None,
+            })
+            .unwrap();
+        assert_eq!(line.limit, dna.credit_line_limit(&score));
+        assert_eq!(
+            line.interest_rate,
+            DnaPricing::default().interest_rate(score.score)
+        );
+        assert_eq!(dna.get_locked("did:key:lender"), line.limit);
+
+        let line = dna.draw_credit(&line.line_id, dec!(300)).unwrap();
+        assert_eq!(dna.get_balance("did:key:borrower"), dec!(300));
+        assert!(matches!(
+            dna.draw_credit(&line.line_id, line.limit),
+            Err(DnaError::CreditLimitExceeded { .. })
+        ));
+
+        // Half of the earnings are swept into the line
+        assert_eq!(
+            dna.credit_earnings("did:key:borrower", dec!(200)),
+            dec!(100)
+        );
+        assert_eq!(dna.get_credit_line(&line.line_id).unwrap().drawn, dec!(200));
+
+        // Overpayment only takes what is owed
+        let line = dna.repay_credit(&line.line_id, dec!(1000)).unwrap();
+        assert_eq!(line.outstanding(), Decimal::ZERO);
+        assert_eq!(dna.get_balance("did:key:borrower"), dec!(200));
+
+        assert!(matches!(
+            dna.close_credit_line(&line.line_id, "did:key:stranger"),
+            Err(DnaError::PermissionDenied { .. })
+        ));
+        let line = dna
+            .close_credit_line(&line.line_id, "did:key:borrower")
+            .unwrap();
+        assert_eq!(line.status, CreditLineStatus::Closed);
+        assert_eq!(dna.get_balance("did:key:lender"), dec!(5000));
+        assert_eq!(dna.get_locked("did:key:lender"), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_credit_line_review_and_freeze() {
+        let dna = ProtocolDna::default();
+        credit_parties(&dna);
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let line = dna
+            .open_credit_line(CreditLineRequest {
+                lender_did: "did:key:lender".to_string(),
+                borrower_did: "did:key:borrower".to_string(),
+                limit: Some(dec!(500)),
+                interest_rate: None,
+            })
+            .unwrap();
+        assert_eq!(line.limit, dec!(500));
+        dna.draw_credit(&line.line_id, dec!(450)).unwrap();
+
+        // 90% utilized: the review raises the limit by one step
+        let reviews = dna.review_credit_lines(now + 8 * DAY_MS);
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].new_limit, dec!(625));
+        assert_eq!(dna.get_locked("did:key:lender"), dec!(175));
+
+        // Score collapses: draws are refused but repayments still apply
+        dna.set_trust("did:key:borrower", 0.1);
+        assert!(matches!(
+            dna.draw_credit(&line.line_id, dec!(10)),
+            Err(DnaError::CreditLineFrozen { .. })
+        ));
+        let frozen = dna.repay_credit(&line.line_id, dec!(50)).unwrap();
+        assert_eq!(frozen.status, CreditLineStatus::Frozen);
+        assert_eq!(frozen.available(), Decimal::ZERO);
+
+        // Recovered score reopens the line
+        dna.set_trust("did:key:borrower", 0.8);
+        let reviews = dna.review_credit_lines(now);
+        assert_eq!(reviews[0].new_status, CreditLineStatus::Open);
+        assert!(dna.get_credit_line(&line.line_id).unwrap().available() > Decimal::ZERO);
     }
 
     #[test]
@@ -1067,6 +3649,7 @@ mod tests {
             premium_pct: None,
             duration_days: 30,
             covered_actions: vec![],
+            terms: PolicyTerms::default(),
         }).unwrap();
 
         assert!(policy.premium_paid > Decimal::ZERO);
@@ -1089,6 +3672,213 @@ mod tests {
         assert_eq!(processed.payout, Some(dec!(100)));
     }
 
+    #[test]
+    fn test_insure_rejects_unpriceable_premium_rates() {
+        let dna = ProtocolDna::default();
+        dna.set_balance("did:key:insured", dec!(100));
+        dna.set_balance("did:key:insurer", dec!(1000));
+
+        for rate in [f64::NAN, f64::INFINITY, -0.1, 1.5] {
+            let result = dna.insure(InsureRequest {
+                insured_did: "did:key:insured".to_string(),
+                insurer_did: "did:key:insurer".to_string(),
+                coverage_amount: dec!(500),
+                premium_pct: Some(rate),
+                duration_days: 30,
+                covered_actions: vec![],
+                terms: PolicyTerms::default(),
+            });
+            assert!(matches!(
+                result,
+                Err(DnaError::Pricing(PricingError::InvalidRate(_)))
+            ));
+        }
+        assert_eq!(dna.get_balance("did:key:insured"), dec!(100));
+    }
+
+    #[test]
+    fn test_parametric_claim_adjudication() {
+        use actoris_common::types::outcome_record::{FrostSignature, VerificationResult};
+
+        let dna = ProtocolDna::default();
+        dna.set_trust("did:key:insured", 0.7);
+        dna.set_balance("did:key:insured", dec!(100));
+        dna.set_balance("did:key:insurer", dec!(1000));
+
+        let policy = dna
+            .insure(InsureRequest {
+                insured_did: "did:key:insured".to_string(),
+                insurer_did: "did:key:insurer".to_string(),
+                coverage_amount: dec!(500),
+                premium_pct: Some(0.02),
+                duration_days: 30,
+                covered_actions: vec![],
+                terms: PolicyTerms::default(),
+            })
+            .unwrap();
+        let holdings = |did: &str| dna.get_balance(did) + dna.get_locked(did);
+        assert_eq!(holdings("did:key:insurer"), dec!(1010));
+
+        let outcome = |quorum_reached: bool| {
+            OutcomeRecord::new(
+                "did:key:insured".to_string(),
+                "did:key:client".to_string(),
+                "test.action".to_string(),
+                [1u8; 32],
+                [2u8; 32],
+                dec!(40),
+                VerificationResult::failure(3, quorum_reached, 100, vec![], "rejected".into()),
+                FrostSignature::new([0u8; 64], vec![], [0u8; 32], 3, 5),
+            )
+        };
+
+        // Quorum rejected the action: paid from the insurer per terms
+        let failed = outcome(true);
+        let claim = dna
+            .file_claim(
+                &policy.policy_id,
+                &failed.id.to_string(),
+                dec!(100),
+                "Verification failed",
+                &failed.canonical_hash(),
+            )
+            .unwrap();
+        let paid = dna.adjudicate_claim(&claim.claim_id, &failed).unwrap();
+        assert_eq!(paid.status, ClaimStatus::Paid);
+        assert_eq!(paid.trigger, Some(ClaimTrigger::VerificationFailed));
+        assert_eq!(paid.payout, Some(dec!(40)));
+        assert_eq!(holdings("did:key:insurer"), dec!(970));
+        assert_eq!(dna.get_balance("did:key:insured"), dec!(130));
+
+        // No quorum: ambiguous, settled by a reviewer
+        let unclear = outcome(false);
+        let claim = dna
+            .file_claim(
+                &policy.policy_id,
+                &unclear.id.to_string(),
+                dec!(25),
+                "Verification failed",
+                &unclear.canonical_hash(),
+            )
+            .unwrap();
+        let review = dna.adjudicate_claim(&claim.claim_id, &unclear).unwrap();
+        assert_eq!(review.status, ClaimStatus::UnderReview);
+        assert!(dna.adjudicate_claim(&claim.claim_id, &unclear).is_err());
+
+        let settled = dna.process_claim(&claim.claim_id, true, None).unwrap();
+        assert_eq!(settled.payout, Some(dec!(25)));
+        assert_eq!(
+            dna.get_insured_policies("did:key:insured")[0].claims_paid,
+            dec!(65)
+        );
+    }
+
+    fn policy_request(coverage: Decimal, action: &str) -> InsureRequest {
+        InsureRequest {
+            insured_did: "did:key:insured".to_string(),
+            insurer_did: "did:key:insurer".to_string(),
+            coverage_amount: coverage,
+            premium_pct: Some(0.05),
+            duration_days: 30,
+            covered_actions: vec![action.to_string()],
+            terms: PolicyTerms::default(),
+        }
+    }
+
+    #[test]
+    fn test_insurer_capital_limits() {
+        let dna = ProtocolDna::default();
+        dna.set_balance("did:key:insured", dec!(1000));
+        dna.set_balance("did:key:insurer", dec!(100));
+
+        // 10% of total plus 25% of the largest correlated exposure
+        dna.insure(policy_request(dec!(200), "a")).unwrap();
+        assert_eq!(dna.reserve_of("did:key:insurer"), dec!(70));
+        assert_eq!(dna.get_locked("did:key:insurer"), dec!(70));
+
+        // A second policy on the same action type is too concentrated
+        let err = dna.insure(policy_request(dec!(200), "a")).unwrap_err();
+        assert!(matches!(
+            err,
+            DnaError::CapitalInsufficient { required, .. } if required == dec!(140)
+        ));
+
+        // The same coverage on an uncorrelated action type fits
+        dna.insure(policy_request(dec!(200), "b")).unwrap();
+        let exposure = dna.insurer_exposure("did:key:insurer");
+        assert_eq!(exposure.total, dec!(400));
+        assert_eq!(exposure.max_correlated(), dec!(200));
+        assert_eq!(exposure.reserve_held, dec!(90));
+        assert_eq!(dna.get_balance("did:key:insurer"), dec!(30));
+    }
+
+    #[test]
+    fn test_reinsurance_shares_premium_exposure_and_payouts() {
+        let dna = ProtocolDna::default();
+        dna.set_balance("did:key:insured", dec!(1000));
+        dna.set_balance("did:key:insurer", dec!(100));
+        dna.set_balance("did:key:reinsurer", dec!(100));
+
+        let policy = dna.insure(policy_request(dec!(200), "a")).unwrap();
+        dna.insure(policy_request(dec!(200), "b")).unwrap();
+
+        let cession = dna
+            .cede(&policy.policy_id, "did:key:reinsurer", 0.5)
+            .unwrap();
+        assert_eq!(cession.ceded_premium, dec!(5));
+        assert_eq!(dna.reserve_of("did:key:reinsurer"), dec!(35));
+        assert_eq!(dna.get_balance("did:key:reinsurer"), dec!(70));
+
+        // The insurer's reserve shrinks with its retained exposure
+        assert_eq!(dna.reserve_of("did:key:insurer"), dec!(80));
+        assert_eq!(dna.get_balance("did:key:insurer"), dec!(35));
+
+        assert!(matches!(
+            dna.cede(&policy.policy_id, "did:key:reinsurer", 0.6),
+            Err(DnaError::CessionRejected { .. })
+        ));
+
+        // The reinsurer reimburses its share of the payout
+        let claim = dna
+            .file_claim(
+                &policy.policy_id,
+                "action",
+                dec!(50),
+                "failure",
+                b"evidence",
+            )
+            .unwrap();
+        dna.process_claim(&claim.claim_id, true, None).unwrap();
+        assert_eq!(dna.get_balance("did:key:reinsurer"), dec!(45));
+        assert_eq!(dna.get_balance("did:key:insurer"), dec!(10));
+        assert_eq!(dna.get_balance("did:key:insured"), dec!(1030));
+    }
+
+    #[test]
+    fn test_insure_priced_from_failure_history() {
+        let model = Arc::new(ActuarialModel::default());
+        let dna = ProtocolDna::default().with_actuarial(model.clone());
+        dna.set_balance("did:key:insured", dec!(100));
+        dna.set_balance("did:key:insurer", dec!(1000));
+
+        let request = InsureRequest {
+            insured_did: "did:key:insured".to_string(),
+            insurer_did: "did:key:insurer".to_string(),
+            coverage_amount: dec!(500),
+            premium_pct: None,
+            duration_days: 30,
+            covered_actions: vec![],
+            terms: PolicyTerms::default(),
+        };
+        let quote = dna.quote_premium(&request).unwrap();
+        assert_eq!(quote.sample_size, 0);
+
+        let policy = dna.insure(request).unwrap();
+        assert_eq!(policy.premium_paid, quote.premium);
+        assert_eq!(policy.quote, Some(quote));
+        assert_eq!(dna.get_balance("did:key:insured"), dec!(100) - policy.premium_paid);
+    }
+
     #[test]
     fn test_delegate() {
         let dna = ProtocolDna::default();
@@ -1127,4 +3917,266 @@ mod tests {
         let perms = dna.get_delegate_permissions("did:key:delegate");
         assert!(perms.is_empty());
     }
+
+    #[test]
+    fn test_org_spending_policy_limits_primitives() {
+        use actoris_common::types::principal::{MemberRole, PrincipalGraph};
+        use actoris_common::UnifiedID;
+        use actoris_protocol_dna::policy::SpendingPolicy;
+
+        let org = UnifiedID::new_organization("acme.example", [1u8; 32]);
+        let alice = UnifiedID::new_human_web("acme.example:users:alice", [2u8; 32]);
+        let (agent, _) = UnifiedID::new_agent(None);
+        let mut principals = PrincipalGraph::new();
+        for id in [&org, &alice, &agent] {
+            principals.register(id);
+        }
+        principals
+            .add_member(&org.did, &alice.did, MemberRole::Member)
+            .unwrap();
+        principals
+            .assign_owner(&agent.did, &alice.did, Some(&org.did))
+            .unwrap();
+        let policies = SpendingPolicies::new(principals).with_policy(
+            org.did.clone(),
+            SpendingPolicy::default()
+                .with_max_per_transition(dec!(300))
+                .with_budget(dec!(500)),
+        );
+
+        let dna = ProtocolDna::default().with_spending_policies(policies);
+        dna.set_trust(&agent.did, 0.9);
+        dna.set_balance(&agent.did, dec!(2000));
+        dna.set_balance("did:key:insured", dec!(1000));
+
+        // Spawn commits stake plus endowment
+        dna.spawn(SpawnRequest {
+            parent_did: agent.did.clone(),
+            child_did: "did:key:child".to_string(),
+            initial_hc: dec!(100),
+            stake_amount: dec!(100),
+            metadata: HashMap::new(),
+        })
+        .unwrap();
+        assert_eq!(dna.budget_spent(&org.did), dec!(200));
+
+        // A loan over the per-transition limit moves nothing
+        let loan = |amount| LendRequest {
+            lender_did: agent.did.clone(),
+            borrower_did: "did:key:insured".to_string(),
+            amount,
+            interest_rate: None,
+            collateral_pct: 0.0,
+            duration_days: 30,
+            auto_repay: false,
+        };
+        assert!(matches!(
+            dna.lend(loan(dec!(400))),
+            Err(DnaError::Ledger(TransitionError::OverTransitionLimit { .. }))
+        ));
+        assert_eq!(dna.get_balance(&agent.did), dec!(1800));
+        dna.lend(loan(dec!(250))).unwrap();
+        assert_eq!(dna.budget_spent(&org.did), dec!(450));
+
+        // Underwriting commits the coverage
+        let mut policy = policy_request(dec!(200), "a");
+        policy.insurer_did = agent.did.clone();
+        assert!(matches!(
+            dna.insure(policy.clone()),
+            Err(DnaError::Ledger(TransitionError::OverBudget { remaining, .. }))
+                if remaining == dec!(50)
+        ));
+        assert!(dna.get_insured_policies("did:key:insured").is_empty());
+
+        // Delegating commits the cap
+        let delegation = |max_total_hc| DelegateRequest {
+            delegator_did: agent.did.clone(),
+            delegate_did: "did:key:delegate".to_string(),
+            allowed_actions: Vec::new(),
+            max_hc_per_action: None,
+            max_total_hc,
+            duration_days: 7,
+            allow_subdelegation: false,
+        };
+        assert!(dna.delegate(delegation(Some(dec!(60)))).is_err());
+        assert!(dna.delegate(delegation(None)).is_err());
+        assert!(dna.get_delegate_permissions("did:key:delegate").is_empty());
+
+        // A new budget period admits them again
+        dna.reset_budget(&org.did);
+        dna.insure(policy).unwrap();
+        dna.delegate(delegation(Some(dec!(60)))).unwrap();
+        assert_eq!(dna.budget_spent(&org.did), dec!(260));
+
+        // Unaffiliated DIDs are unconstrained
+        dna.set_trust("did:key:insured", 0.9);
+        dna.lend(LendRequest {
+            lender_did: "did:key:insured".to_string(),
+            borrower_did: agent.did.clone(),
+            ..loan(dec!(900))
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_subdelegation_capability_chain() {
+        use rand::rngs::OsRng;
+
+        let dna = ProtocolDna::default();
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let alice_did = encode_did_key(&alice.verifying_key().to_bytes());
+        let bob_did = encode_did_key(&bob.verifying_key().to_bytes());
+        dna.set_trust(&alice_did, 0.8);
+
+        let root = dna
+            .delegate(DelegateRequest {
+                delegator_did: alice_did.clone(),
+                delegate_did: bob_did.clone(),
+                allowed_actions: vec!["compute".to_string(), "storage".to_string()],
+                max_hc_per_action: Some(dec!(100)),
+                max_total_hc: Some(dec!(1000)),
+                duration_days: 7,
+                allow_subdelegation: true,
+            })
+            .unwrap();
+
+        let narrow = |actions: Vec<String>, per_action| DelegateRequest {
+            delegator_did: bob_did.clone(),
+            delegate_did: "did:key:carol".to_string(),
+            allowed_actions: actions,
+            max_hc_per_action: per_action,
+            max_total_hc: Some(dec!(200)),
+            duration_days: 30,
+            allow_subdelegation: false,
+        };
+
+        // Widening the parent's scope is refused
+        assert!(matches!(
+            dna.subdelegate(&root.delegation_id, narrow(vec![], Some(dec!(50)))),
+            Err(DnaError::InvalidDelegation { .. })
+        ));
+        let child = dna
+            .subdelegate(
+                &root.delegation_id,
+                narrow(vec!["compute".to_string()], Some(dec!(50))),
+            )
+            .unwrap();
+        assert_eq!(child.expires_at, root.expires_at);
+        assert!(dna
+            .check_delegation("did:key:carol", "compute", dec!(40))
+            .is_ok());
+
+        // Tokens verify offline and carry the delegation IDs
+        let root_token = dna
+            .issue_capability(&root.delegation_id, &alice, None)
+            .unwrap();
+        assert!(matches!(
+            dna.issue_capability(&child.delegation_id, &alice, Some(&root_token)),
+            Err(DnaError::PermissionDenied { .. })
+        ));
+        let child_token = dna
+            .issue_capability(&child.delegation_id, &bob, Some(&root_token))
+            .unwrap();
+        let chain = vec![root_token, child_token];
+        let now = chrono::Utc::now().timestamp_millis();
+        let auth = capability::verify_chain(
+            &chain,
+            "did:key:carol",
+            "compute",
+            dec!(40),
+            now,
+            &HashSet::new(),
+        )
+        .unwrap();
+        assert_eq!(auth.root_issuer, alice_did);
+        assert_eq!(auth.max_total_hc, Some(dec!(200)));
+
+        // Revoking the root cuts off the whole chain, online and offline
+        dna.revoke_delegation(&root.delegation_id, &alice_did)
+            .unwrap();
+        assert!(dna
+            .check_delegation("did:key:carol", "compute", dec!(40))
+            .is_err());
+        assert!(matches!(
+            dna.authorize_capability(&chain, "did:key:carol", "compute", dec!(40)),
+            Err(DnaError::Capability(CapabilityError::Revoked(_)))
+        ));
+    }
+
+    #[test]
+    fn test_wasm_contract_uses_dna_balances() {
+        use actoris_protocol_dna::wasm::{ContractCall, WasmLimits, WasmRuntime};
+
+        // Escrows 2 HC from the client, then pays 1 HC to the agent
+        let wat = r#"
+            (module
+              (import "actoris" "lock" (func $lock (param i32 i32 i64) (result i32)))
+              (import "actoris" "transfer" (func $transfer (param i32 i32 i32 i32 i64) (result i32)))
+              (memory (export "memory") 1)
+              (data (i32.const 0) "did:key:client")
+              (data (i32.const 16) "did:key:agent")
+              (func (export "execute") (result i32)
+                (local $s i32)
+                (local.set $s (call $lock (i32.const 0) (i32.const 14) (i64.const 2000000)))
+                (if (local.get $s) (then (return (local.get $s))))
+                (call $transfer (i32.const 0) (i32.const 14) (i32.const 16) (i32.const 13) (i64.const 1000000))))
+        "#;
+
+        let dna = Arc::new(ProtocolDna::default());
+        dna.set_balance("did:key:client", dec!(10));
+
+        let runtime = WasmRuntime::new(WasmLimits::default()).unwrap();
+        let module_id = runtime.deploy(wat.as_bytes()).unwrap();
+        let call = ContractCall::new(module_id, "execute").authorized_by("did:key:client");
+        let outcome = runtime.execute(&call, dna.clone()).unwrap();
+
+        assert!(outcome.committed);
+        assert_eq!(dna.get_balance("did:key:client"), dec!(7));
+        assert_eq!(dna.get_locked("did:key:client"), dec!(2));
+        assert_eq!(dna.get_balance("did:key:agent"), dec!(1));
+        assert_eq!(dna.escrowed(&call.module_id, "did:key:client"), dec!(2));
+    }
+
+    #[test]
+    fn test_contract_effects_commit_atomically() {
+        let dna = ProtocolDna::default();
+        dna.set_balance("did:key:client", dec!(3));
+        dna.lock_hc("did:key:client", dec!(1)).unwrap();
+
+        // The transfer fits but the lock after it does not
+        let effects = vec![
+            LedgerEffect::Transfer {
+                from: "did:key:client".to_string(),
+                to: "did:key:agent".to_string(),
+                amount: dec!(1.5),
+            },
+            LedgerEffect::Lock {
+                did: "did:key:client".to_string(),
+                amount: dec!(1),
+            },
+        ];
+        assert!(dna.apply_effects("contract", &effects).is_err());
+        assert_eq!(dna.get_balance("did:key:client"), dec!(2));
+        assert_eq!(dna.get_balance("did:key:agent"), dec!(0));
+        assert_eq!(dna.escrowed("contract", "did:key:client"), dec!(0));
+
+        // HC locked by lock_hc is not the contract's to unlock
+        let unlock = vec![LedgerEffect::Unlock {
+            did: "did:key:client".to_string(),
+            amount: dec!(1),
+        }];
+        assert!(dna.apply_effects("contract", &unlock).is_err());
+        assert_eq!(dna.get_locked("did:key:client"), dec!(1));
+
+        let lock = vec![LedgerEffect::Lock {
+            did: "did:key:client".to_string(),
+            amount: dec!(1),
+        }];
+        dna.apply_effects("contract", &lock).unwrap();
+        assert!(dna.apply_effects("other", &unlock).is_err());
+        dna.apply_effects("contract", &unlock).unwrap();
+        assert_eq!(dna.get_locked("did:key:client"), dec!(1));
+        assert_eq!(dna.get_balance("did:key:client"), dec!(2));
+    }
 }
@@ -0,0 +1,248 @@
+//! Spawn lineage
+//!
+//! Every SPAWN adds a child→parent edge to the lineage graph together with
+//! the stake the parent put up for the child. The graph answers ancestor,
+//! descendant and subtree queries, backs `ResolveLineage` from
+//! `identity.proto`, and is what misconduct penalties, stake slashing and
+//! subtree recall walk along.
+
+use std::collections::{HashMap, VecDeque};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Lineage parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineageConfig {
+    /// Trust (τ) taken from the parent when a child is culled for misconduct
+    pub misconduct_penalty: f64,
+    /// Factor applied to the penalty at each further generation up
+    pub penalty_decay: f64,
+    /// Share of the parent's stake slashed for a child's fraud
+    pub fraud_slash_pct: f64,
+    /// Depth used by `resolve_lineage` when none is given
+    pub default_resolve_depth: u32,
+}
+
+impl Default for LineageConfig {
+    fn default() -> Self {
+        Self {
+            misconduct_penalty: 0.05,
+            penalty_decay: 0.5,
+            fraud_slash_pct: 0.5,
+            default_resolve_depth: 10,
+        }
+    }
+}
+
+/// Standing of a spawned agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineageStatus {
+    Active,
+    /// Removed by selection or for misconduct
+    Culled,
+    /// Retired by an ancestor's recall
+    Recalled,
+}
+
+/// A spawned agent in the lineage graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageNode {
+    pub did: String,
+    pub parent_did: String,
+    /// Generations from the root (children of a root are at depth 1)
+    pub depth: u32,
+    pub spawn_id: String,
+    pub spawned_at: i64,
+    /// Trust (τ) inherited at spawn
+    pub inherited_tau: f64,
+    /// Parent stake still locked for this child
+    pub stake: Decimal,
+    pub status: LineageStatus,
+    pub retired_at: Option<i64>,
+}
+
+impl LineageNode {
+    pub fn is_active(&self) -> bool {
+        self.status == LineageStatus::Active
+    }
+}
+
+/// Lineage of a DID as returned by `ResolveLineage`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedLineage {
+    /// DIDs from the requested DID up to the root (first is the requested DID)
+    pub lineage: Vec<String>,
+    /// Share of each entry's trust inherited by the requested DID
+    pub trust_inheritance: Vec<f64>,
+}
+
+/// Totals over the descendants of a DID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubtreeAggregate {
+    pub root_did: String,
+    pub descendants: usize,
+    pub active: usize,
+    pub culled: usize,
+    pub recalled: usize,
+    /// Generations below the root
+    pub depth: u32,
+    /// Stake locked across the subtree
+    pub total_stake: Decimal,
+    /// Available and locked HC held by descendants
+    pub total_hc: Decimal,
+    /// Mean trust of active descendants
+    pub mean_tau: f64,
+}
+
+/// Result of recalling a subtree
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecallReport {
+    /// Agents retired, top-down
+    pub retired: Vec<String>,
+    /// Available HC swept from retired agents
+    pub hc_returned: Decimal,
+    /// Spawn stakes released
+    pub stake_released: Decimal,
+}
+
+/// Child→parent graph of spawned agents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineageGraph {
+    /// When the graph was last snapshotted (unix ms)
+    pub taken_at: i64,
+    nodes: HashMap<String, LineageNode>,
+    children: HashMap<String, Vec<String>>,
+}
+
+impl LineageGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a spawned agent
+    pub fn insert(&mut self, node: LineageNode) {
+        self.children
+            .entry(node.parent_did.clone())
+            .or_default()
+            .push(node.did.clone());
+        self.nodes.insert(node.did.clone(), node);
+    }
+
+    /// Node of a spawned agent
+    pub fn node(&self, did: &str) -> Option<&LineageNode> {
+        self.nodes.get(did)
+    }
+
+    pub(crate) fn node_mut(&mut self, did: &str) -> Option<&mut LineageNode> {
+        self.nodes.get_mut(did)
+    }
+
+    /// Number of spawned agents
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Parent of a spawned agent
+    pub fn parent(&self, did: &str) -> Option<&str> {
+        self.nodes.get(did).map(|n| n.parent_did.as_str())
+    }
+
+    /// Generations from the root (0 for roots and unknown DIDs)
+    pub fn depth(&self, did: &str) -> u32 {
+        self.nodes.get(did).map(|n| n.depth).unwrap_or(0)
+    }
+
+    /// Direct children
+    pub fn children(&self, did: &str) -> &[String] {
+        self.children.get(did).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Ancestors from the parent up, at most `max_depth` of them
+    pub fn ancestors(&self, did: &str, max_depth: u32) -> Vec<String> {
+        let mut ancestors = Vec::new();
+        let mut current = did;
+        while ancestors.len() < max_depth as usize {
+            match self.parent(current) {
+                Some(parent) => {
+                    ancestors.push(parent.to_string());
+                    current = parent;
+                }
+                None => break,
+            }
+        }
+        ancestors
+    }
+
+    /// Whether `ancestor` is somewhere above `did`
+    pub fn is_ancestor(&self, ancestor: &str, did: &str) -> bool {
+        let mut current = did;
+        while let Some(parent) = self.parent(current) {
+            if parent == ancestor {
+                return true;
+            }
+            current = parent;
+        }
+        false
+    }
+
+    /// All descendants, breadth-first
+    pub fn descendants(&self, did: &str) -> Vec<String> {
+        let mut descendants = Vec::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([did]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.children(current) {
+                descendants.push(child.clone());
+                queue.push_back(child);
+            }
+        }
+        descendants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(did: &str, parent: &str, depth: u32) -> LineageNode {
+        LineageNode {
+            did: did.to_string(),
+            parent_did: parent.to_string(),
+            depth,
+            spawn_id: format!("spawn-{}", did),
+            spawned_at: 0,
+            inherited_tau: 0.1,
+            stake: Decimal::ONE,
+            status: LineageStatus::Active,
+            retired_at: None,
+        }
+    }
+
+    #[test]
+    fn test_ancestor_and_descendant_queries() {
+        let mut graph = LineageGraph::new();
+        graph.insert(node("a", "root", 1));
+        graph.insert(node("b", "root", 1));
+        graph.insert(node("a1", "a", 2));
+        graph.insert(node("a1x", "a1", 3));
+
+        assert_eq!(graph.ancestors("a1x", 10), vec!["a1", "a", "root"]);
+        assert_eq!(graph.ancestors("a1x", 2), vec!["a1", "a"]);
+        assert!(graph.is_ancestor("root", "a1x"));
+        assert!(!graph.is_ancestor("b", "a1x"));
+        assert_eq!(graph.descendants("root"), vec!["a", "b", "a1", "a1x"]);
+        assert_eq!(graph.descendants("a1x"), Vec::<String>::new());
+        assert_eq!(graph.depth("root"), 0);
+        assert_eq!(graph.depth("a1x"), 3);
+
+        // Round-trips through a snapshot
+        let restored: LineageGraph =
+            serde_json::from_str(&serde_json::to_string(&graph).unwrap()).unwrap();
+        assert_eq!(restored.descendants("a"), vec!["a1", "a1x"]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Durable storage for the spawn lineage graph
+//!
+//! Like SyRA state, the lineage graph is persisted as whole snapshots. The
+//! EventStoreDB backend appends each snapshot to a dedicated stream and
+//! restores from the latest.
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+
+use actoris_common::Result;
+
+use super::lineage::LineageGraph;
+use crate::ledger::eventstore::EventStoreClient;
+
+/// Storage backend for lineage snapshots
+#[async_trait]
+pub trait LineageStore: Send + Sync {
+    /// Save a snapshot, superseding earlier ones
+    async fn save(&self, graph: &LineageGraph) -> Result<()>;
+
+    /// Load the latest snapshot, if any
+    async fn load(&self) -> Result<Option<LineageGraph>>;
+}
+
+/// In-memory store (for testing and single-process deployments)
+#[derive(Default)]
+pub struct InMemoryLineageStore {
+    latest: RwLock<Option<LineageGraph>>,
+}
+
+impl InMemoryLineageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LineageStore for InMemoryLineageStore {
+    async fn save(&self, graph: &LineageGraph) -> Result<()> {
+        *self.latest.write() = Some(graph.clone());
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Option<LineageGraph>> {
+        Ok(self.latest.read().clone())
+    }
+}
+
+#[async_trait]
+impl LineageStore for EventStoreClient {
+    async fn save(&self, graph: &LineageGraph) -> Result<()> {
+        self.record_lineage_snapshot(graph).await.map(|_| ())
+    }
+
+    async fn load(&self) -> Result<Option<LineageGraph>> {
+        self.read_latest_lineage_snapshot().await
+    }
+}
@@ -0,0 +1,61 @@
+//! Loan servicing loop
+//!
+//! Periodically runs [`ProtocolDna::service_loans`] so interest accrues,
+//! auto-repay borrowers are charged their due installments, and loans left
+//! unpaid past expiry are defaulted without anyone having to poll for them.
+//! Each pass also reviews credit lines, resizing limits and freezing lines
+//! whose borrowers' scores have dropped.
+
+use crate::verification::dna::{ProtocolDna, ServicingReport};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Loan servicer configuration
+#[derive(Debug, Clone)]
+pub struct LoanServicerConfig {
+    /// Interval between servicing passes in milliseconds
+    pub interval_ms: u64,
+}
+
+impl Default for LoanServicerConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 60 * 60 * 1000, // hourly; interest accrues in whole days
+        }
+    }
+}
+
+/// Drives loan servicing for a [`ProtocolDna`] executor
+pub struct LoanServicer {
+    dna: Arc<ProtocolDna>,
+    config: LoanServicerConfig,
+}
+
+impl LoanServicer {
+    pub fn new(dna: Arc<ProtocolDna>, config: LoanServicerConfig) -> Self {
+        Self { dna, config }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &LoanServicerConfig {
+        &self.config
+    }
+
+    /// Run one servicing pass at the current time
+    pub fn run_once(&self) -> ServicingReport {
+        self.dna
+            .service_loans(chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Spawn the servicing loop on the current runtime
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.interval_ms));
+            loop {
+                interval.tick().await;
+                self.run_once();
+            }
+        })
+    }
+}
@@ -0,0 +1,82 @@
+//! Prometheus metrics for action verification
+//!
+//! Tracks verification latency against `TARGET_VERIFICATION_LATENCY_MS`, plus
+//! the timeouts, retries and escalations handled by the supervisor.
+
+use actoris_common::TARGET_VERIFICATION_LATENCY_MS;
+
+/// Prometheus metrics for the verifier and its supervisor
+pub struct VerificationMetrics {
+    pub latency_ms: prometheus::Histogram,
+    pub sla_misses: prometheus::IntCounter,
+    pub timeouts: prometheus::IntCounter,
+    pub retries: prometheus::IntCounter,
+    pub escalations: prometheus::IntCounter,
+    pub sweeps: prometheus::IntCounter,
+}
+
+impl VerificationMetrics {
+    pub fn new() -> Self {
+        Self {
+            latency_ms: prometheus::Histogram::with_opts(
+                prometheus::HistogramOpts::new(
+                    "actoris_verification_latency_ms",
+                    "Time from submission to final outcome in milliseconds",
+                )
+                .buckets(vec![
+                    50.0, 100.0, 250.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0, 30000.0,
+                ]),
+            )
+            .unwrap(),
+            sla_misses: prometheus::IntCounter::new(
+                "actoris_verification_sla_misses_total",
+                "Verifications that exceeded the target latency",
+            )
+            .unwrap(),
+            timeouts: prometheus::IntCounter::new(
+                "actoris_verification_timeouts_total",
+                "Verification attempts that timed out",
+            )
+            .unwrap(),
+            retries: prometheus::IntCounter::new(
+                "actoris_verification_retries_total",
+                "Timed-out verifications re-dispatched to a fresh oracle set",
+            )
+            .unwrap(),
+            escalations: prometheus::IntCounter::new(
+                "actoris_verification_escalations_total",
+                "Verifications escalated after exhausting retries",
+            )
+            .unwrap(),
+            sweeps: prometheus::IntCounter::new(
+                "actoris_verification_sweeps_total",
+                "Timeout sweeps run by the supervisor",
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn register(
+        &self,
+        registry: &prometheus::Registry,
+    ) -> std::result::Result<(), prometheus::Error> {
+        registry.register(Box::new(self.latency_ms.clone()))?;
+        registry.register(Box::new(self.sla_misses.clone()))?;
+        registry.register(Box::new(self.timeouts.clone()))?;
+        registry.register(Box::new(self.retries.clone()))?;
+        registry.register(Box::new(self.escalations.clone()))?;
+        registry.register(Box::new(self.sweeps.clone()))?;
+        Ok(())
+    }
+
+    /// Whether a latency misses the verification SLA
+    pub fn misses_sla(latency_ms: u64) -> bool {
+        latency_ms > TARGET_VERIFICATION_LATENCY_MS
+    }
+}
+
+impl Default for VerificationMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
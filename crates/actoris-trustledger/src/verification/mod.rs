@@ -2,13 +2,58 @@
 //!
 //! This module provides:
 //! - Action verification with oracle quorum
-//! - SyRA (Sybil Resistance Algorithm) protection
+//! - Risk-tiered verification policies (optimistic, sampled, full quorum)
+//! - Timeout supervision with retries, escalation and SLA metrics
+//! - SyRA (Sybil Resistance Algorithm) protection with durable state
+//! - Interaction graph Sybil cluster detection
 //! - Protocol DNA primitives (SPAWN, LEND, INSURE, DELEGATE)
+//! - Loan servicing (interest accrual, auto-repay, defaults)
+//! - Parametric insurance claims adjudicated from outcome records
+//! - Actuarial premium pricing from failure history
+//! - Insurer capital reserves, exposure limits and reinsurance
+//! - Spawn lineage with trust propagation, stake slashing and recall
+//! - Credit scoring and revolving credit lines
 
 pub mod syra;
+pub mod syra_store;
+pub mod sybil_graph;
 pub mod verifier;
 pub mod dna;
+pub mod policy;
+pub mod metrics;
+pub mod supervisor;
+pub mod loan_servicer;
+pub mod parametric;
+pub mod actuarial;
+pub mod lineage;
+pub mod lineage_store;
+pub mod credit;
 
-pub use verifier::ActionVerifier;
-pub use syra::{SyraGuard, SyraConfig, SyraError, SybilRiskAssessment, VerificationTier};
-pub use dna::{ProtocolDna, DnaPrimitive, SpawnRequest, LendRequest, InsureRequest, DelegateRequest};
+pub use verifier::{ActionVerifier, EscalationTier};
+pub use metrics::VerificationMetrics;
+pub use supervisor::{SupervisorConfig, SweepReport, VerificationSupervisor};
+pub use policy::{
+    ChallengeBonds, RiskFloor, RiskProfile, TrustLookup, VerificationDepth, VerificationPolicy,
+};
+pub use syra::{
+    ClusterFinding, SyraGuard, SyraConfig, SyraError, SyraSnapshot, SybilRiskAssessment,
+    VerificationTier,
+};
+pub use syra_store::{InMemorySyraStore, SyraStore};
+pub use sybil_graph::{ClusterDetectorConfig, DetectedCluster, FundingKind, InteractionGraph};
+pub use dna::{
+    CapitalConfig, Cession, DelegateRequest, DnaPrimitive, InsureRequest, InsurerExposure,
+    Installment, LendRequest, Loan, LoanServicingConfig, LoanStatus, ProtocolDna,
+    ServicingReport, SpawnRequest,
+};
+pub use loan_servicer::{LoanServicer, LoanServicerConfig};
+pub use parametric::{Adjudication, ClaimTrigger, PolicyTerms};
+pub use actuarial::{ActuarialConfig, ActuarialModel, FailureStats, PremiumQuote};
+pub use lineage::{
+    LineageConfig, LineageGraph, LineageNode, LineageStatus, RecallReport, ResolvedLineage,
+    SubtreeAggregate,
+};
+pub use lineage_store::{InMemoryLineageStore, LineageStore};
+pub use credit::{
+    CreditConfig, CreditLine, CreditLineRequest, CreditLineStatus, EarningsHistory, LimitReview,
+};
@@ -0,0 +1,276 @@
+//! Parametric claim adjudication
+//!
+//! Claims against an action are settled from the action's [`OutcomeRecord`]
+//! instead of a caller-supplied verdict. Each insured event pays a multiple of
+//! the action's HC cost set in the policy terms. Outcomes that neither match
+//! nor rule out an insured event are routed to manual review.
+
+use actoris_common::types::outcome_record::OutcomeRecord;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::dna::{InsuranceClaim, InsurancePolicy};
+
+/// Insured event triggering a payout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClaimTrigger {
+    /// Oracle quorum rejected the action
+    VerificationFailed,
+    /// Verification ran past the latency SLA
+    SlaMissed,
+    /// A challenge against an optimistic acceptance was upheld
+    DisputeUpheld,
+}
+
+impl ClaimTrigger {
+    /// Short name for logs and wire formats
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaimTrigger::VerificationFailed => "verification_failed",
+            ClaimTrigger::SlaMissed => "sla_missed",
+            ClaimTrigger::DisputeUpheld => "dispute_upheld",
+        }
+    }
+}
+
+/// Payout terms of a policy
+///
+/// Each multiple is applied to the HC cost of the affected action; zero
+/// leaves the event uncovered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyTerms {
+    pub verification_failed: f64,
+    pub sla_missed: f64,
+    pub dispute_upheld: f64,
+}
+
+impl Default for PolicyTerms {
+    fn default() -> Self {
+        Self {
+            verification_failed: 1.0,
+            sla_missed: 0.25,
+            dispute_upheld: 1.5,
+        }
+    }
+}
+
+impl PolicyTerms {
+    /// Payout multiple for a trigger
+    pub fn multiple(&self, trigger: ClaimTrigger) -> f64 {
+        match trigger {
+            ClaimTrigger::VerificationFailed => self.verification_failed,
+            ClaimTrigger::SlaMissed => self.sla_missed,
+            ClaimTrigger::DisputeUpheld => self.dispute_upheld,
+        }
+    }
+}
+
+/// Decision reached for a claim
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Adjudication {
+    /// Pay out for an insured event
+    Payout {
+        trigger: ClaimTrigger,
+        amount: Decimal,
+    },
+    /// The outcome rules out a payout
+    Deny { reason: String },
+    /// The outcome is ambiguous
+    Review { reason: String },
+}
+
+/// Evidence a claim must carry: the hash of the record's canonical hash
+///
+/// Claimants file `record.canonical_hash()` as evidence; `file_claim` stores
+/// its BLAKE3 hash.
+pub fn evidence_hash(record: &OutcomeRecord) -> [u8; 32] {
+    *blake3::hash(&record.canonical_hash()).as_bytes()
+}
+
+/// Adjudicate a claim against the outcome record of its action
+pub fn adjudicate(
+    policy: &InsurancePolicy,
+    claim: &InsuranceClaim,
+    record: &OutcomeRecord,
+    now: i64,
+) -> Adjudication {
+    let deny = |reason: &str| Adjudication::Deny {
+        reason: reason.to_string(),
+    };
+    let review = |reason: &str| Adjudication::Review {
+        reason: reason.to_string(),
+    };
+
+    if record.id.to_string() != claim.action_id {
+        return deny("Outcome record does not match the claimed action");
+    }
+    if claim.evidence_hash != evidence_hash(record) {
+        return deny("Evidence does not match the outcome record");
+    }
+    if record.actor_did != policy.insured_did {
+        return deny("Action was not performed by the insured");
+    }
+    if !policy.covered_actions.is_empty() && !policy.covered_actions.contains(&record.action_type) {
+        return deny("Action type is not covered");
+    }
+    if record.submitted_at < policy.started_at || record.submitted_at > policy.expires_at {
+        return deny("Action falls outside the coverage period");
+    }
+
+    // An optimistic acceptance can still be overturned
+    if record.verification_mode.challengeable_at(now) {
+        return review("Challenge window is still open");
+    }
+
+    let trigger = if record.verification.passed {
+        if record.verification.met_latency_sla() {
+            return deny("No insured event: action verified within SLA");
+        }
+        ClaimTrigger::SlaMissed
+    } else if record.dispute_upheld() {
+        ClaimTrigger::DisputeUpheld
+    } else if record.verification.quorum_reached {
+        ClaimTrigger::VerificationFailed
+    } else {
+        // Rejected without a quorum (timeouts, abstentions)
+        return review("Verification failed without an oracle quorum");
+    };
+
+    let multiple = Decimal::try_from(policy.terms.multiple(trigger)).unwrap_or_default();
+    let amount = (record.compute_hc * multiple)
+        .min(claim.claimed_amount)
+        .min(policy.remaining_coverage());
+    if amount <= Decimal::ZERO {
+        return deny(&format!("Nothing payable for {}", trigger.as_str()));
+    }
+
+    Adjudication::Payout { trigger, amount }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verification::dna::{ClaimStatus, PolicyStatus};
+    use actoris_common::types::outcome_record::{
+        FrostSignature, VerificationMode, VerificationResult,
+    };
+    use rust_decimal_macros::dec;
+
+    fn record(passed: bool, quorum_reached: bool, latency_ms: u32) -> OutcomeRecord {
+        let verification = if passed {
+            VerificationResult::success(3, latency_ms, vec![])
+        } else {
+            VerificationResult::failure(3, quorum_reached, latency_ms, vec![], "rejected".into())
+        };
+        OutcomeRecord::new(
+            "did:key:insured".to_string(),
+            "did:key:client".to_string(),
+            "test.action".to_string(),
+            [1u8; 32],
+            [2u8; 32],
+            dec!(40),
+            verification,
+            FrostSignature::new([0u8; 64], vec![], [0u8; 32], 3, 5),
+        )
+    }
+
+    fn policy() -> InsurancePolicy {
+        let now = chrono::Utc::now().timestamp_millis();
+        InsurancePolicy {
+            policy_id: "policy".to_string(),
+            insured_did: "did:key:insured".to_string(),
+            insurer_did: "did:key:insurer".to_string(),
+            coverage_amount: dec!(500),
+            premium_paid: dec!(10),
+            premium_rate: 0.02,
+            started_at: now - 1_000,
+            expires_at: now + 60_000,
+            covered_actions: vec![],
+            claims_made: 1,
+            claims_paid: Decimal::ZERO,
+            status: PolicyStatus::ClaimsPending,
+            terms: PolicyTerms::default(),
+            quote: None,
+            cessions: Vec::new(),
+        }
+    }
+
+    fn claim_for(record: &OutcomeRecord) -> InsuranceClaim {
+        InsuranceClaim {
+            claim_id: "claim".to_string(),
+            policy_id: "policy".to_string(),
+            action_id: record.id.to_string(),
+            claimed_amount: dec!(100),
+            reason: "failure".to_string(),
+            evidence_hash: evidence_hash(record),
+            submitted_at: record.submitted_at,
+            status: ClaimStatus::Pending,
+            payout: None,
+            trigger: None,
+            resolution: None,
+        }
+    }
+
+    fn decide(record: &OutcomeRecord) -> Adjudication {
+        adjudicate(
+            &policy(),
+            &claim_for(record),
+            record,
+            chrono::Utc::now().timestamp_millis(),
+        )
+    }
+
+    #[test]
+    fn test_triggers_pay_per_terms() {
+        assert_eq!(
+            decide(&record(false, true, 100)),
+            Adjudication::Payout {
+                trigger: ClaimTrigger::VerificationFailed,
+                amount: dec!(40),
+            }
+        );
+        assert_eq!(
+            decide(&record(true, true, 60_000)),
+            Adjudication::Payout {
+                trigger: ClaimTrigger::SlaMissed,
+                amount: dec!(10),
+            }
+        );
+
+        let upheld = record(false, true, 100).with_challenged(true);
+        assert_eq!(
+            decide(&upheld),
+            Adjudication::Payout {
+                trigger: ClaimTrigger::DisputeUpheld,
+                amount: dec!(60),
+            }
+        );
+    }
+
+    #[test]
+    fn test_denied_and_ambiguous_claims() {
+        assert!(matches!(
+            decide(&record(true, true, 100)),
+            Adjudication::Deny { .. }
+        ));
+        assert!(matches!(
+            decide(&record(false, false, 100)),
+            Adjudication::Review { .. }
+        ));
+
+        let open = record(true, false, 100).with_verification_mode(VerificationMode::Optimistic {
+            challenge_deadline: i64::MAX,
+        });
+        assert!(matches!(decide(&open), Adjudication::Review { .. }));
+
+        // Evidence must come from the record being claimed against
+        let failed = record(false, true, 100);
+        let mut claim = claim_for(&failed);
+        claim.evidence_hash = [0u8; 32];
+        assert!(matches!(
+            adjudicate(&policy(), &claim, &failed, 0),
+            Adjudication::Deny { .. }
+        ));
+    }
+}
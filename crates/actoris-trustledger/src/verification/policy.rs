@@ -0,0 +1,314 @@
+//! Risk-tiered verification policy
+//!
+//! Not every action needs a 3-of-5 oracle quorum. The policy picks a
+//! verification depth from the action's risk profile:
+//! - Optimistic: accepted immediately, open to challenge for a window
+//! - Sampled: sent to the quorum at a configurable rate
+//! - Full quorum: always verified by the oracle quorum
+//!
+//! Critical tasks, restricted data, high-value actions and low-trust actors
+//! always get a full quorum.
+//!
+//! Complexity and sensitivity are declared by the submitter, so they can only
+//! raise the server's floor for the action type. Only action types the server
+//! registers as low risk are accepted optimistically; anything else declared
+//! low risk is still sampled.
+
+use actoris_common::{DataSensitivity, TaskComplexity};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Verification depth chosen by the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationDepth {
+    Optimistic,
+    Sampled,
+    FullQuorum,
+}
+
+/// Risk inputs for choosing a verification depth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskProfile {
+    /// Task complexity classification
+    pub task_complexity: TaskComplexity,
+    /// Data sensitivity classification
+    pub data_sensitivity: DataSensitivity,
+    /// HC value of the action
+    pub compute_hc: Decimal,
+    /// Actor's current trust score (0-1000)
+    pub trust_score: u16,
+}
+
+impl RiskProfile {
+    /// Create a profile with default complexity and sensitivity
+    pub fn new(compute_hc: Decimal, trust_score: u16) -> Self {
+        Self {
+            task_complexity: TaskComplexity::default(),
+            data_sensitivity: DataSensitivity::default(),
+            compute_hc,
+            trust_score,
+        }
+    }
+
+    /// Set task complexity
+    pub fn with_complexity(mut self, complexity: TaskComplexity) -> Self {
+        self.task_complexity = complexity;
+        self
+    }
+
+    /// Set data sensitivity
+    pub fn with_sensitivity(mut self, sensitivity: DataSensitivity) -> Self {
+        self.data_sensitivity = sensitivity;
+        self
+    }
+}
+
+/// Server-side source of actor trust for risk tiering
+///
+/// Callers never supply their own trust score: a client that could declare
+/// high trust would skip the quorum.
+pub trait TrustLookup: Send + Sync {
+    /// Trust score (0-1000) for `did` performing `action_type`, if known
+    fn trust_score(&self, did: &str, action_type: &str) -> Option<u16>;
+}
+
+/// Server-side source of challenge bonds
+///
+/// Anyone other than the action's client must have posted a bond before
+/// challenging an optimistic acceptance.
+pub trait ChallengeBonds: Send + Sync {
+    /// HC currently bonded by `did` for challenges, if any
+    fn bonded_hc(&self, did: &str) -> Option<Decimal>;
+}
+
+/// Minimum risk the server assigns to an action type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskFloor {
+    /// Lowest task complexity the action type is treated as
+    pub task_complexity: TaskComplexity,
+    /// Lowest data sensitivity the action type is treated as
+    pub data_sensitivity: DataSensitivity,
+}
+
+/// Thresholds for choosing a verification depth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationPolicy {
+    /// Maximum HC value accepted optimistically
+    pub optimistic_max_hc: Decimal,
+    /// Minimum trust score for optimistic acceptance
+    pub optimistic_min_trust: u16,
+    /// Challenge window for optimistic acceptance (ms)
+    pub challenge_window_ms: u64,
+    /// Fraction of sampled actions sent to the quorum
+    pub sample_rate: f64,
+    /// Minimum trust score to skip a guaranteed quorum
+    pub sampling_min_trust: u16,
+    /// HC value at or above which a full quorum is required
+    pub full_quorum_min_hc: Decimal,
+    /// Bond a challenger other than the client must hold
+    pub challenge_bond_hc: Decimal,
+    /// Risk floors by action type; unlisted types are never optimistic
+    pub action_floors: HashMap<String, RiskFloor>,
+}
+
+impl Default for VerificationPolicy {
+    fn default() -> Self {
+        Self {
+            optimistic_max_hc: Decimal::new(5, 0),
+            optimistic_min_trust: 750,
+            challenge_window_ms: 300_000, // 5 minutes
+            sample_rate: 0.2,
+            sampling_min_trust: 500,
+            full_quorum_min_hc: Decimal::new(100, 0),
+            challenge_bond_hc: Decimal::new(10, 0),
+            action_floors: HashMap::new(),
+        }
+    }
+}
+
+impl VerificationPolicy {
+    /// Policy that always requires a full quorum
+    pub fn always_full_quorum() -> Self {
+        Self {
+            optimistic_max_hc: Decimal::ZERO,
+            optimistic_min_trust: u16::MAX,
+            sample_rate: 1.0,
+            sampling_min_trust: u16::MAX,
+            full_quorum_min_hc: Decimal::ZERO,
+            ..Default::default()
+        }
+    }
+
+    /// Set the risk floor for an action type
+    pub fn with_action_floor(
+        mut self,
+        action_type: impl Into<String>,
+        task_complexity: TaskComplexity,
+        data_sensitivity: DataSensitivity,
+    ) -> Self {
+        self.action_floors.insert(
+            action_type.into(),
+            RiskFloor {
+                task_complexity,
+                data_sensitivity,
+            },
+        );
+        self
+    }
+
+    /// Choose the verification depth for an action type and risk profile
+    ///
+    /// The declared complexity and sensitivity never go below the action
+    /// type's floor.
+    pub fn select(&self, action_type: &str, risk: &RiskProfile) -> VerificationDepth {
+        let floor = self.action_floors.get(action_type);
+        let task_complexity = floor.map_or(risk.task_complexity, |f| {
+            f.task_complexity.max(risk.task_complexity)
+        });
+        let data_sensitivity = floor.map_or(risk.data_sensitivity, |f| {
+            f.data_sensitivity.max(risk.data_sensitivity)
+        });
+
+        let full_quorum = task_complexity == TaskComplexity::Critical
+            || data_sensitivity == DataSensitivity::Restricted
+            || risk.compute_hc >= self.full_quorum_min_hc
+            || risk.trust_score < self.sampling_min_trust;
+        if full_quorum {
+            return VerificationDepth::FullQuorum;
+        }
+
+        let optimistic = floor.is_some()
+            && task_complexity == TaskComplexity::Low
+            && matches!(
+                data_sensitivity,
+                DataSensitivity::Public | DataSensitivity::Internal
+            )
+            && risk.compute_hc <= self.optimistic_max_hc
+            && risk.trust_score >= self.optimistic_min_trust;
+        if optimistic {
+            return VerificationDepth::Optimistic;
+        }
+
+        VerificationDepth::Sampled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_low_risk_is_optimistic() {
+        let policy = VerificationPolicy::default().with_action_floor(
+            "test.action",
+            TaskComplexity::Low,
+            DataSensitivity::Public,
+        );
+        let risk = RiskProfile::new(dec!(1), 900)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+
+        assert_eq!(
+            policy.select("test.action", &risk),
+            VerificationDepth::Optimistic
+        );
+    }
+
+    #[test]
+    fn test_moderate_risk_is_sampled() {
+        let policy = VerificationPolicy::default();
+
+        // Default complexity (medium) with a trusted actor
+        let risk = RiskProfile::new(dec!(10), 800);
+        assert_eq!(
+            policy.select("test.action", &risk),
+            VerificationDepth::Sampled
+        );
+
+        // Low complexity but not trusted enough for optimistic acceptance
+        let risk = RiskProfile::new(dec!(1), 600).with_complexity(TaskComplexity::Low);
+        assert_eq!(
+            policy.select("test.action", &risk),
+            VerificationDepth::Sampled
+        );
+    }
+
+    #[test]
+    fn test_high_risk_is_full_quorum() {
+        let policy = VerificationPolicy::default();
+
+        let critical = RiskProfile::new(dec!(1), 900).with_complexity(TaskComplexity::Critical);
+        assert_eq!(
+            policy.select("test.action", &critical),
+            VerificationDepth::FullQuorum
+        );
+
+        let restricted =
+            RiskProfile::new(dec!(1), 900).with_sensitivity(DataSensitivity::Restricted);
+        assert_eq!(
+            policy.select("test.action", &restricted),
+            VerificationDepth::FullQuorum
+        );
+
+        let high_value = RiskProfile::new(dec!(500), 900);
+        assert_eq!(
+            policy.select("test.action", &high_value),
+            VerificationDepth::FullQuorum
+        );
+
+        let low_trust = RiskProfile::new(dec!(1), 300).with_complexity(TaskComplexity::Low);
+        assert_eq!(
+            policy.select("test.action", &low_trust),
+            VerificationDepth::FullQuorum
+        );
+    }
+
+    #[test]
+    fn test_declared_risk_cannot_lower_the_floor() {
+        let policy = VerificationPolicy::default()
+            .with_action_floor("test.action", TaskComplexity::Low, DataSensitivity::Public)
+            .with_action_floor(
+                "payments.transfer",
+                TaskComplexity::Critical,
+                DataSensitivity::Restricted,
+            );
+        let low = RiskProfile::new(dec!(1), 900)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+
+        // An unregistered action type declared low risk is still sampled
+        assert_eq!(
+            policy.select("unknown.action", &low),
+            VerificationDepth::Sampled
+        );
+
+        // A high-risk action type ignores the low declaration
+        assert_eq!(
+            policy.select("payments.transfer", &low),
+            VerificationDepth::FullQuorum
+        );
+
+        // Declarations above the floor still raise the depth
+        let critical = low.clone().with_complexity(TaskComplexity::Critical);
+        assert_eq!(
+            policy.select("test.action", &critical),
+            VerificationDepth::FullQuorum
+        );
+    }
+
+    #[test]
+    fn test_always_full_quorum() {
+        let policy = VerificationPolicy::always_full_quorum();
+        let risk = RiskProfile::new(dec!(0), 1000)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+
+        assert_eq!(
+            policy.select("test.action", &risk),
+            VerificationDepth::FullQuorum
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Verification supervisor
+//!
+//! Periodically sweeps the verifier for requests that ran past their timeout.
+//! A timed-out committee request is re-dispatched to a freshly drawn
+//! committee up to `max_retries` times, then escalated to the configured
+//! fallback tier. Global quorum requests have no fresh oracle set to draw
+//! from and are escalated straight away. Requests that time out again after a
+//! larger-quorum escalation are parked for human review.
+
+use crate::verification::verifier::{ActionVerifier, EscalationTier};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Supervisor configuration
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Interval between timeout sweeps in milliseconds
+    pub sweep_interval_ms: u64,
+    /// Retries before a committee request is escalated
+    pub max_retries: u8,
+    /// Fallback once retries are exhausted
+    pub escalation: EscalationTier,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            sweep_interval_ms: 500,
+            max_retries: 2,
+            escalation: EscalationTier::HumanReview,
+        }
+    }
+}
+
+/// Outcome of a single sweep
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    /// Requests found past their timeout
+    pub timed_out: Vec<String>,
+    /// Requests re-dispatched to a fresh oracle set
+    pub retried: Vec<String>,
+    /// Requests escalated after exhausting retries, or with none to make
+    pub escalated: Vec<String>,
+}
+
+/// Drives timeout handling for an [`ActionVerifier`]
+pub struct VerificationSupervisor {
+    verifier: Arc<ActionVerifier>,
+    config: SupervisorConfig,
+}
+
+impl VerificationSupervisor {
+    pub fn new(verifier: Arc<ActionVerifier>, config: SupervisorConfig) -> Self {
+        Self { verifier, config }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &SupervisorConfig {
+        &self.config
+    }
+
+    /// Run one sweep: mark timeouts, then retry or escalate each of them
+    pub async fn sweep(&self) -> SweepReport {
+        if let Some(metrics) = self.verifier.metrics() {
+            metrics.sweeps.inc();
+        }
+
+        let mut report = SweepReport {
+            timed_out: self.verifier.sweep_timeouts().await,
+            ..Default::default()
+        };
+
+        for request_id in &report.timed_out {
+            let Some((retries, escalated)) = self.verifier.retry_state(request_id).await else {
+                continue;
+            };
+
+            let result = if escalated {
+                // An escalated quorum timed out too; hand over to a human
+                self.verifier
+                    .escalate(request_id, EscalationTier::HumanReview)
+                    .await
+                    .map(|_| report.escalated.push(request_id.clone()))
+            } else if retries < self.config.max_retries
+                && self.verifier.committee_seed(request_id).await.is_some()
+            {
+                self.verifier
+                    .retry(request_id)
+                    .await
+                    .map(|_| report.retried.push(request_id.clone()))
+            } else {
+                self.verifier
+                    .escalate(request_id, self.config.escalation)
+                    .await
+                    .map(|_| report.escalated.push(request_id.clone()))
+            };
+
+            if let Err(e) = result {
+                error!(request_id = %request_id, error = %e, "Failed to handle timed-out verification");
+            }
+        }
+
+        if !report.timed_out.is_empty() {
+            info!(
+                timed_out = report.timed_out.len(),
+                retried = report.retried.len(),
+                escalated = report.escalated.len(),
+                "Verification sweep completed"
+            );
+        }
+
+        report
+    }
+
+    /// Spawn the sweep loop on the current runtime
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.sweep_interval_ms));
+            loop {
+                interval.tick().await;
+                self.sweep().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::committee::CommitteeSelector;
+    use crate::verification::metrics::VerificationMetrics;
+    use crate::verification::verifier::{VerificationStatus, VerifierConfig};
+    use rust_decimal_macros::dec;
+
+    async fn timed_out_request(verifier: &ActionVerifier) -> String {
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        request_id
+    }
+
+    fn short_timeout_verifier() -> Arc<ActionVerifier> {
+        let config = VerifierConfig {
+            timeout_ms: 10,
+            quorum_threshold: 2,
+            oracle_count: 3,
+        };
+        Arc::new(
+            ActionVerifier::new(config)
+                .with_metrics(Arc::new(VerificationMetrics::new()))
+                .with_reviewers(["did:key:reviewer".to_string()]),
+        )
+    }
+
+    fn short_timeout_committee_verifier() -> Arc<ActionVerifier> {
+        let selector = Arc::new(CommitteeSelector::new(2));
+        for i in 0..4 {
+            selector.register_oracle(&format!("did:key:oracle{}", i), [i as u8; 32]);
+        }
+        let config = VerifierConfig {
+            timeout_ms: 10,
+            quorum_threshold: 2,
+            oracle_count: 4,
+        };
+        Arc::new(
+            ActionVerifier::new(config)
+                .with_committee(selector)
+                .with_metrics(Arc::new(VerificationMetrics::new()))
+                .with_reviewers(["did:key:reviewer".to_string()]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_retry_then_human_review() {
+        let verifier = short_timeout_committee_verifier();
+        let supervisor = VerificationSupervisor::new(
+            verifier.clone(),
+            SupervisorConfig {
+                max_retries: 1,
+                ..Default::default()
+            },
+        );
+
+        let request_id = timed_out_request(&verifier).await;
+
+        let report = supervisor.sweep().await;
+        assert_eq!(report.retried, vec![request_id.clone()]);
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Pending)
+        );
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let report = supervisor.sweep().await;
+        assert_eq!(report.escalated, vec![request_id.clone()]);
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Escalated)
+        );
+
+        let metrics = verifier.metrics().unwrap();
+        assert_eq!(metrics.timeouts.get(), 2);
+        assert_eq!(metrics.retries.get(), 1);
+        assert_eq!(metrics.escalations.get(), 1);
+        assert_eq!(metrics.sweeps.get(), 2);
+
+        // Only a configured reviewer settles the escalated request
+        assert!(verifier
+            .resolve_review(&request_id, "did:key:stranger", true, None)
+            .await
+            .is_err());
+        let status = verifier
+            .resolve_review(&request_id, "did:key:reviewer", true, None)
+            .await
+            .unwrap();
+        assert!(matches!(status, VerificationStatus::Completed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_global_quorum_escalates_without_retry() {
+        let verifier = short_timeout_verifier();
+        let supervisor = VerificationSupervisor::new(
+            verifier.clone(),
+            SupervisorConfig {
+                max_retries: 2,
+                ..Default::default()
+            },
+        );
+
+        let request_id = timed_out_request(&verifier).await;
+
+        // Re-opening to the pool that just timed out would not be a fresh set
+        let report = supervisor.sweep().await;
+        assert!(report.retried.is_empty());
+        assert_eq!(report.escalated, vec![request_id.clone()]);
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Escalated)
+        );
+
+        let metrics = verifier.metrics().unwrap();
+        assert_eq!(metrics.retries.get(), 0);
+        assert_eq!(metrics.escalations.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_larger_quorum_escalation() {
+        let verifier = short_timeout_verifier();
+        let supervisor = VerificationSupervisor::new(
+            verifier.clone(),
+            SupervisorConfig {
+                max_retries: 0,
+                escalation: EscalationTier::LargerQuorum { threshold: 3 },
+                ..Default::default()
+            },
+        );
+
+        let request_id = timed_out_request(&verifier).await;
+
+        // Re-opened to the whole pool under the raised threshold
+        supervisor.sweep().await;
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Pending)
+        );
+        assert_eq!(verifier.retry_state(&request_id).await, Some((0, true)));
+
+        // Timing out again falls back to human review
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        supervisor.sweep().await;
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Escalated)
+        );
+    }
+}
@@ -0,0 +1,553 @@
+//! Interaction-graph Sybil cluster detection
+//!
+//! Builds a weighted graph over identities from three kinds of evidence:
+//! - Shared counterparties: identities that keep dealing with the same DIDs
+//! - Funding flows: loans, delegations and spawns between identities
+//! - Temporal co-activity: identities acting in the same time windows
+//!
+//! Communities are found with weighted label propagation. Each detected
+//! cluster keeps per-kind link counts so a risk assessment can say *why*
+//! an identity was grouped, not just that its cluster is large.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Kind of funding flow between two identities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FundingKind {
+    Loan,
+    Delegation,
+    Spawn,
+}
+
+/// Thresholds for building the graph and accepting communities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterDetectorConfig {
+    /// Shared counterparties needed before two identities are linked
+    pub min_shared_counterparties: usize,
+    /// Shared activity windows needed before two identities are linked
+    pub min_co_active_windows: usize,
+    /// Weight added per funding relationship
+    pub funding_weight: f64,
+    /// Minimum total edge weight kept in the graph
+    pub min_edge_weight: f64,
+    /// Smallest community reported as a cluster
+    pub min_cluster_size: usize,
+    /// Activity windows retained per identity
+    pub activity_retention_windows: usize,
+    /// Label propagation iteration cap
+    pub max_iterations: usize,
+}
+
+impl Default for ClusterDetectorConfig {
+    fn default() -> Self {
+        Self {
+            min_shared_counterparties: 3,
+            min_co_active_windows: 3,
+            funding_weight: 1.0,
+            min_edge_weight: 0.5,
+            min_cluster_size: 3,
+            activity_retention_windows: 1440,
+            max_iterations: 20,
+        }
+    }
+}
+
+/// Evidence behind a single edge
+#[derive(Debug, Clone, Default, PartialEq)]
+struct EdgeEvidence {
+    /// Jaccard similarity of counterparty sets (0 if below the minimum)
+    shared_counterparties: f64,
+    /// Funding relationships in either direction
+    funding: usize,
+    /// Co-active windows over the smaller activity set (0 if below the minimum)
+    co_activity: f64,
+}
+
+impl EdgeEvidence {
+    fn weight(&self, config: &ClusterDetectorConfig) -> f64 {
+        self.shared_counterparties + self.funding as f64 * config.funding_weight + self.co_activity
+    }
+}
+
+/// Per-kind link counts within a cluster
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkEvidence {
+    /// Links from shared counterparties
+    pub shared_counterparty_links: usize,
+    /// Links from loans, delegations or spawns
+    pub funding_links: usize,
+    /// Links from temporal co-activity
+    pub co_activity_links: usize,
+}
+
+impl LinkEvidence {
+    fn add(&mut self, edge: &EdgeEvidence) {
+        if edge.shared_counterparties > 0.0 {
+            self.shared_counterparty_links += 1;
+        }
+        if edge.funding > 0 {
+            self.funding_links += 1;
+        }
+        if edge.co_activity > 0.0 {
+            self.co_activity_links += 1;
+        }
+    }
+
+    /// Human-readable reasons, one per kind of evidence present
+    pub fn explain(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.shared_counterparty_links > 0 {
+            reasons.push(format!(
+                "Shares counterparties with {} cluster member(s)",
+                self.shared_counterparty_links
+            ));
+        }
+        if self.funding_links > 0 {
+            reasons.push(format!(
+                "Funding flows with {} cluster member(s)",
+                self.funding_links
+            ));
+        }
+        if self.co_activity_links > 0 {
+            reasons.push(format!(
+                "Co-active with {} cluster member(s)",
+                self.co_activity_links
+            ));
+        }
+        reasons
+    }
+}
+
+/// A community found in the interaction graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedCluster {
+    /// Stable ID derived from the member set
+    pub cluster_id: String,
+    /// Member DIDs
+    pub members: BTreeSet<String>,
+    /// Link counts across all internal edges
+    pub evidence: LinkEvidence,
+    /// Link counts per member
+    pub member_evidence: BTreeMap<String, LinkEvidence>,
+    /// Internal edges over possible pairs (0.0 - 1.0)
+    pub density: f64,
+    /// Mean internal edge weight
+    pub mean_weight: f64,
+    /// Cohesion score (0.0 - 1.0)
+    pub score: f64,
+}
+
+impl DetectedCluster {
+    fn stable_id(members: &BTreeSet<String>) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for member in members {
+            hasher.update(member.as_bytes());
+            hasher.update(&[0]);
+        }
+        let hash = hasher.finalize();
+        let hex: String = hash.as_bytes()[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("graph_{}", hex)
+    }
+}
+
+/// Interaction graph over identities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InteractionGraph {
+    /// Counterparties each identity has interacted with
+    counterparties: HashMap<String, HashSet<String>>,
+    /// Funding flows keyed by (from, to)
+    funding: HashMap<String, HashMap<String, Vec<FundingKind>>>,
+    /// Activity window indices per identity
+    activity: HashMap<String, BTreeSet<i64>>,
+}
+
+impl InteractionGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an interaction between two identities
+    pub fn record_interaction(&mut self, did: &str, counterparty: &str) {
+        if did == counterparty {
+            return;
+        }
+        self.counterparties
+            .entry(did.to_string())
+            .or_default()
+            .insert(counterparty.to_string());
+        self.counterparties
+            .entry(counterparty.to_string())
+            .or_default()
+            .insert(did.to_string());
+    }
+
+    /// Record a funding flow from one identity to another
+    pub fn record_funding(&mut self, from: &str, to: &str, kind: FundingKind) {
+        if from == to {
+            return;
+        }
+        self.funding
+            .entry(from.to_string())
+            .or_default()
+            .entry(to.to_string())
+            .or_default()
+            .push(kind);
+    }
+
+    /// Record activity at `at_ms` in windows of `window_ms`
+    pub fn record_activity(&mut self, did: &str, at_ms: i64, window_ms: i64, retention: usize) {
+        let windows = self.activity.entry(did.to_string()).or_default();
+        windows.insert(at_ms / window_ms.max(1));
+        while windows.len() > retention.max(1) {
+            windows.pop_first();
+        }
+    }
+
+    /// Number of identities with any recorded evidence
+    pub fn node_count(&self) -> usize {
+        self.nodes().len()
+    }
+
+    fn nodes(&self) -> BTreeSet<&str> {
+        let mut nodes: BTreeSet<&str> = BTreeSet::new();
+        nodes.extend(self.counterparties.keys().map(String::as_str));
+        nodes.extend(self.activity.keys().map(String::as_str));
+        for (from, flows) in &self.funding {
+            nodes.insert(from);
+            nodes.extend(flows.keys().map(String::as_str));
+        }
+        nodes
+    }
+
+    fn funding_between(&self, a: &str, b: &str) -> usize {
+        let count = |x: &str, y: &str| {
+            self.funding
+                .get(x)
+                .and_then(|flows| flows.get(y))
+                .map(Vec::len)
+                .unwrap_or(0)
+        };
+        count(a, b) + count(b, a)
+    }
+
+    fn evidence(&self, a: &str, b: &str, config: &ClusterDetectorConfig) -> EdgeEvidence {
+        let mut edge = EdgeEvidence {
+            funding: self.funding_between(a, b),
+            ..Default::default()
+        };
+
+        if let (Some(ca), Some(cb)) = (self.counterparties.get(a), self.counterparties.get(b)) {
+            let shared = ca.intersection(cb).count();
+            if shared >= config.min_shared_counterparties {
+                edge.shared_counterparties = shared as f64 / ca.union(cb).count() as f64;
+            }
+        }
+
+        if let (Some(wa), Some(wb)) = (self.activity.get(a), self.activity.get(b)) {
+            let shared = wa.intersection(wb).count();
+            if shared >= config.min_co_active_windows {
+                edge.co_activity = shared as f64 / wa.len().min(wb.len()) as f64;
+            }
+        }
+
+        edge
+    }
+
+    /// Pairs that share at least one piece of evidence
+    fn candidate_pairs(&self) -> BTreeSet<(String, String)> {
+        let mut pairs = BTreeSet::new();
+        let mut add = |a: &str, b: &str| {
+            if a != b {
+                let (x, y) = if a < b { (a, b) } else { (b, a) };
+                pairs.insert((x.to_string(), y.to_string()));
+            }
+        };
+
+        // Identities that share a counterparty are neighbours of that counterparty
+        for neighbours in self.counterparties.values() {
+            let neighbours: Vec<&String> = neighbours.iter().collect();
+            for (i, a) in neighbours.iter().enumerate() {
+                for b in &neighbours[i + 1..] {
+                    add(a, b);
+                }
+            }
+        }
+
+        for (from, flows) in &self.funding {
+            for to in flows.keys() {
+                add(from, to);
+            }
+        }
+
+        let mut by_window: HashMap<i64, Vec<&str>> = HashMap::new();
+        for (did, windows) in &self.activity {
+            for window in windows {
+                by_window.entry(*window).or_default().push(did);
+            }
+        }
+        for dids in by_window.values() {
+            for (i, a) in dids.iter().enumerate() {
+                for b in &dids[i + 1..] {
+                    add(a, b);
+                }
+            }
+        }
+
+        pairs
+    }
+
+    fn edges(&self, config: &ClusterDetectorConfig) -> BTreeMap<(String, String), EdgeEvidence> {
+        self.candidate_pairs()
+            .into_iter()
+            .filter_map(|(a, b)| {
+                let edge = self.evidence(&a, &b, config);
+                (edge.weight(config) >= config.min_edge_weight).then_some(((a, b), edge))
+            })
+            .collect()
+    }
+
+    /// Detect communities with weighted label propagation
+    ///
+    /// Nodes are visited in DID order and ties go to the smallest label, so
+    /// the result is deterministic for a given graph.
+    pub fn detect_clusters(&self, config: &ClusterDetectorConfig) -> Vec<DetectedCluster> {
+        let edges = self.edges(config);
+
+        let mut adjacency: BTreeMap<&str, Vec<(&str, f64)>> = BTreeMap::new();
+        for ((a, b), edge) in &edges {
+            let weight = edge.weight(config);
+            adjacency
+                .entry(a.as_str())
+                .or_default()
+                .push((b.as_str(), weight));
+            adjacency
+                .entry(b.as_str())
+                .or_default()
+                .push((a.as_str(), weight));
+        }
+
+        let mut labels: HashMap<&str, &str> = adjacency.keys().map(|n| (*n, *n)).collect();
+        for _ in 0..config.max_iterations {
+            let mut changed = false;
+            for (node, neighbours) in &adjacency {
+                let mut scores: BTreeMap<&str, f64> = BTreeMap::new();
+                for (neighbour, weight) in neighbours {
+                    *scores.entry(labels[neighbour]).or_insert(0.0) += weight;
+                }
+
+                let current = labels[node];
+                let best = scores
+                    .iter()
+                    .fold(
+                        None,
+                        |best: Option<(&str, f64)>, (label, score)| match best {
+                            Some((_, s)) if *score <= s => best,
+                            _ => Some((*label, *score)),
+                        },
+                    )
+                    .map(|(label, score)| {
+                        // Keep the current label when it ties with the best
+                        if scores.get(&current).copied().unwrap_or(0.0) >= score {
+                            current
+                        } else {
+                            label
+                        }
+                    })
+                    .unwrap_or(current);
+
+                if best != current {
+                    labels.insert(*node, best);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut communities: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (node, label) in &labels {
+            communities
+                .entry(*label)
+                .or_default()
+                .insert(node.to_string());
+        }
+
+        communities
+            .into_values()
+            .filter(|members| members.len() >= config.min_cluster_size.max(2))
+            .map(|members| Self::summarize(members, &edges, config))
+            .collect()
+    }
+
+    fn summarize(
+        members: BTreeSet<String>,
+        edges: &BTreeMap<(String, String), EdgeEvidence>,
+        config: &ClusterDetectorConfig,
+    ) -> DetectedCluster {
+        let mut evidence = LinkEvidence::default();
+        let mut member_evidence: BTreeMap<String, LinkEvidence> = members
+            .iter()
+            .map(|m| (m.clone(), LinkEvidence::default()))
+            .collect();
+        let mut internal = 0usize;
+        let mut total_weight = 0.0;
+
+        for ((a, b), edge) in edges {
+            if members.contains(a) && members.contains(b) {
+                internal += 1;
+                total_weight += edge.weight(config);
+                evidence.add(edge);
+                if let Some(e) = member_evidence.get_mut(a) {
+                    e.add(edge);
+                }
+                if let Some(e) = member_evidence.get_mut(b) {
+                    e.add(edge);
+                }
+            }
+        }
+
+        let n = members.len() as f64;
+        let possible = n * (n - 1.0) / 2.0;
+        let density = if possible > 0.0 {
+            internal as f64 / possible
+        } else {
+            0.0
+        };
+        let mean_weight = if internal > 0 {
+            total_weight / internal as f64
+        } else {
+            0.0
+        };
+
+        // Dense, strongly linked communities score highest
+        let score = (0.6 * density + 0.4 * mean_weight.min(1.0)).clamp(0.0, 1.0);
+
+        DetectedCluster {
+            cluster_id: DetectedCluster::stable_id(&members),
+            members,
+            evidence,
+            member_evidence,
+            density,
+            mean_weight,
+            score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(graph: &mut InteractionGraph, prefix: &str, size: usize, clients: &[&str]) {
+        for i in 0..size {
+            let did = format!("did:key:{}{}", prefix, i);
+            for client in clients {
+                graph.record_interaction(&did, client);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_counterparties_form_cluster() {
+        let mut graph = InteractionGraph::new();
+        ring(
+            &mut graph,
+            "sybil",
+            4,
+            &["did:key:c1", "did:key:c2", "did:key:c3"],
+        );
+
+        // An honest agent with a single overlapping client stays out
+        graph.record_interaction("did:key:honest", "did:key:c1");
+        graph.record_interaction("did:key:honest", "did:key:other");
+
+        let clusters = graph.detect_clusters(&ClusterDetectorConfig::default());
+        let cluster = clusters
+            .iter()
+            .find(|c| c.members.contains("did:key:sybil0"))
+            .expect("sybil ring detected");
+
+        assert!((0..4).all(|i| cluster.members.contains(&format!("did:key:sybil{}", i))));
+        assert!(!cluster.members.contains("did:key:honest"));
+        assert!(cluster.evidence.shared_counterparty_links >= 6);
+        assert_eq!(cluster.density, 1.0);
+        assert!(cluster.score > 0.85);
+        assert!(!cluster.member_evidence["did:key:sybil0"]
+            .explain()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_funding_and_co_activity_links() {
+        let config = ClusterDetectorConfig::default();
+        let mut graph = InteractionGraph::new();
+
+        graph.record_funding("did:key:a", "did:key:b", FundingKind::Loan);
+        graph.record_funding("did:key:a", "did:key:c", FundingKind::Delegation);
+
+        // b and c act in the same three 60s windows
+        for t in [0, 60_000, 120_000] {
+            graph.record_activity("did:key:b", t, 60_000, 100);
+            graph.record_activity("did:key:c", t + 1_000, 60_000, 100);
+        }
+
+        let clusters = graph.detect_clusters(&config);
+        assert_eq!(clusters.len(), 1);
+
+        let cluster = &clusters[0];
+        assert_eq!(cluster.members.len(), 3);
+        assert_eq!(cluster.evidence.funding_links, 2);
+        assert_eq!(cluster.evidence.co_activity_links, 1);
+        assert_eq!(cluster.member_evidence["did:key:a"].funding_links, 2);
+    }
+
+    #[test]
+    fn test_separate_communities_and_stable_ids() {
+        let mut graph = InteractionGraph::new();
+        ring(
+            &mut graph,
+            "x",
+            3,
+            &["did:key:c1", "did:key:c2", "did:key:c3"],
+        );
+        ring(
+            &mut graph,
+            "y",
+            3,
+            &["did:key:d1", "did:key:d2", "did:key:d3"],
+        );
+
+        let config = ClusterDetectorConfig::default();
+        let first = graph.detect_clusters(&config);
+        let second = graph.detect_clusters(&config);
+
+        let sybil_clusters: Vec<_> = first
+            .iter()
+            .filter(|c| c.members.iter().any(|m| m.starts_with("did:key:x")))
+            .collect();
+        assert_eq!(sybil_clusters.len(), 1);
+        assert!(sybil_clusters[0]
+            .members
+            .iter()
+            .all(|m| !m.starts_with("did:key:y")));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_weak_evidence_is_ignored() {
+        let mut graph = InteractionGraph::new();
+        ring(&mut graph, "z", 5, &["did:key:c1", "did:key:c2"]);
+
+        // Two shared counterparties are below the default minimum of three
+        let clusters = graph.detect_clusters(&ClusterDetectorConfig::default());
+        assert!(clusters
+            .iter()
+            .all(|c| !c.members.iter().any(|m| m.starts_with("did:key:z"))));
+    }
+}
@@ -19,20 +19,38 @@
 //!    - Slashing for malicious behavior
 //!
 //! 4. **Network Analysis**:
-//!    - Identity graph clustering detection
+//!    - Interaction graph community detection (see [`super::sybil_graph`])
 //!    - Temporal correlation analysis
 //!    - IP/device fingerprinting
+//!
+//! All state is kept against wall-clock timestamps so it can be snapshotted
+//! to a [`SyraStore`] and restored after a restart.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 
+use actoris_common::crypto::credential::{
+    CredentialClaim, CredentialError, CredentialIssuer, StatusList, VerifiableCredential,
+};
+use actoris_common::TrustScore;
+use actoris_protocol_dna::pricing::risk_factor;
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
+use super::sybil_graph::{
+    ClusterDetectorConfig, DetectedCluster, FundingKind, InteractionGraph, LinkEvidence,
+};
+use super::syra_store::SyraStore;
+
+/// Current wall-clock time in unix milliseconds
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
 /// SyRA errors
 #[derive(Debug, Error)]
 pub enum SyraError {
@@ -60,6 +78,9 @@ pub enum SyraError {
 
     #[error("Cooling off period: {remaining_secs}s remaining")]
     CoolingOffPeriod { remaining_secs: u64 },
+
+    #[error("Invalid deposit rate: {0}")]
+    InvalidDepositRate(f64),
 }
 
 /// Identity verification tier
@@ -81,6 +102,27 @@ impl Default for VerificationTier {
     }
 }
 
+impl VerificationTier {
+    /// Tier from its numeric level
+    pub fn from_level(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(Self::Tier0),
+            1 => Some(Self::Tier1),
+            2 => Some(Self::Tier2),
+            3 => Some(Self::Tier3),
+            _ => None,
+        }
+    }
+}
+
+impl From<VerificationTier> for CredentialClaim {
+    fn from(tier: VerificationTier) -> Self {
+        CredentialClaim::VerificationTier {
+            verification_tier: tier as u8,
+        }
+    }
+}
+
 /// Configuration for SyRA
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyraConfig {
@@ -110,6 +152,10 @@ pub struct SyraConfig {
 
     /// Time window for temporal correlation analysis (seconds)
     pub temporal_window_secs: u64,
+
+    /// Interaction graph cluster detection
+    #[serde(default)]
+    pub clustering: ClusterDetectorConfig,
 }
 
 impl Default for SyraConfig {
@@ -136,32 +182,35 @@ impl Default for SyraConfig {
             max_cluster_size: 50,
             sybil_similarity_threshold: 0.85,
             temporal_window_secs: 60,
+            clustering: ClusterDetectorConfig::default(),
         }
     }
 }
 
 /// Rate limiter using sliding window
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct RateLimiter {
-    window: Duration,
+    window_ms: i64,
     max_count: u32,
-    timestamps: VecDeque<Instant>,
+    /// Unix ms timestamps inside the window
+    timestamps: VecDeque<i64>,
 }
 
 impl RateLimiter {
-    fn new(window: Duration, max_count: u32) -> Self {
+    fn new(window_secs: u64, max_count: u32) -> Self {
         Self {
-            window,
+            window_ms: window_secs as i64 * 1000,
             max_count,
             timestamps: VecDeque::new(),
         }
     }
 
     fn allow(&mut self) -> bool {
-        let now = Instant::now();
+        let now = now_ms();
 
         // Remove expired entries
         while let Some(ts) = self.timestamps.front() {
-            if now.duration_since(*ts) > self.window {
+            if now - *ts > self.window_ms {
                 self.timestamps.pop_front();
             } else {
                 break;
@@ -182,20 +231,20 @@ impl RateLimiter {
 }
 
 /// Behavioral pattern for analysis
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BehavioralPattern {
     /// DIDs this identity has interacted with
     interactions: HashSet<String>,
-    /// Timestamps of actions
-    action_timestamps: VecDeque<Instant>,
+    /// Timestamps of actions (unix ms)
+    action_timestamps: VecDeque<i64>,
     /// Request patterns (action type -> count)
     request_patterns: HashMap<String, u32>,
-    /// Last action time
-    last_action: Option<Instant>,
+    /// Last action time (unix ms)
+    last_action: Option<i64>,
     /// Flagged for suspicious behavior
     flagged: bool,
-    /// Cooling off until
-    cooling_off_until: Option<Instant>,
+    /// Cooling off until (unix ms)
+    cooling_off_until: Option<i64>,
 }
 
 impl Default for BehavioralPattern {
@@ -212,14 +261,30 @@ impl Default for BehavioralPattern {
 }
 
 /// Identity cluster for Sybil detection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct IdentityCluster {
     cluster_id: String,
     members: HashSet<String>,
-    created_at: Instant,
+    /// Creation time (unix ms)
+    created_at: i64,
     flagged_as_sybil: bool,
 }
 
+/// Serializable copy of all SyRA state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyraSnapshot {
+    /// When the snapshot was taken (unix ms)
+    pub taken_at: i64,
+    rate_limiters: HashMap<String, RateLimiter>,
+    spawn_limiters: HashMap<String, RateLimiter>,
+    verification_tiers: HashMap<String, VerificationTier>,
+    patterns: HashMap<String, BehavioralPattern>,
+    clusters: HashMap<String, IdentityCluster>,
+    staked_amounts: HashMap<String, Decimal>,
+    graph: InteractionGraph,
+    graph_clusters: Vec<DetectedCluster>,
+}
+
 /// Sybil Resistance Algorithm implementation
 pub struct SyraGuard {
     config: SyraConfig,
@@ -241,6 +306,15 @@ pub struct SyraGuard {
 
     /// Staked amounts per DID
     staked_amounts: Arc<RwLock<HashMap<String, Decimal>>>,
+
+    /// Interaction graph for cluster detection
+    graph: Arc<RwLock<InteractionGraph>>,
+
+    /// Clusters found by the last graph detection run
+    graph_clusters: Arc<RwLock<Vec<DetectedCluster>>>,
+
+    /// Durable store for snapshots
+    store: Option<Arc<dyn SyraStore>>,
 }
 
 impl SyraGuard {
@@ -254,9 +328,74 @@ impl SyraGuard {
             patterns: Arc::new(RwLock::new(HashMap::new())),
             clusters: Arc::new(RwLock::new(HashMap::new())),
             staked_amounts: Arc::new(RwLock::new(HashMap::new())),
+            graph: Arc::new(RwLock::new(InteractionGraph::new())),
+            graph_clusters: Arc::new(RwLock::new(Vec::new())),
+            store: None,
+        }
+    }
+
+    /// Rebuild a guard from a snapshot
+    pub fn from_snapshot(config: SyraConfig, snapshot: SyraSnapshot) -> Self {
+        Self {
+            config,
+            rate_limiters: Arc::new(RwLock::new(snapshot.rate_limiters)),
+            spawn_limiters: Arc::new(RwLock::new(snapshot.spawn_limiters)),
+            verification_tiers: Arc::new(RwLock::new(snapshot.verification_tiers)),
+            patterns: Arc::new(RwLock::new(snapshot.patterns)),
+            clusters: Arc::new(RwLock::new(snapshot.clusters)),
+            staked_amounts: Arc::new(RwLock::new(snapshot.staked_amounts)),
+            graph: Arc::new(RwLock::new(snapshot.graph)),
+            graph_clusters: Arc::new(RwLock::new(snapshot.graph_clusters)),
+            store: None,
+        }
+    }
+
+    /// Load the latest snapshot from a store, or start empty
+    pub async fn restore(
+        config: SyraConfig,
+        store: Arc<dyn SyraStore>,
+    ) -> actoris_common::Result<Self> {
+        let guard = match store.load().await? {
+            Some(snapshot) => {
+                info!(taken_at = snapshot.taken_at, "Restored SyRA state");
+                Self::from_snapshot(config, snapshot)
+            }
+            None => Self::new(config),
+        };
+        Ok(guard.with_store(store))
+    }
+
+    /// Persist snapshots to a durable store
+    pub fn with_store(mut self, store: Arc<dyn SyraStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Take a snapshot of all state
+    pub fn snapshot(&self) -> SyraSnapshot {
+        SyraSnapshot {
+            taken_at: now_ms(),
+            rate_limiters: self.rate_limiters.read().clone(),
+            spawn_limiters: self.spawn_limiters.read().clone(),
+            verification_tiers: self.verification_tiers.read().clone(),
+            patterns: self.patterns.read().clone(),
+            clusters: self.clusters.read().clone(),
+            staked_amounts: self.staked_amounts.read().clone(),
+            graph: self.graph.read().clone(),
+            graph_clusters: self.graph_clusters.read().clone(),
         }
     }
 
+    /// Write a snapshot to the store (no-op without a store)
+    pub async fn persist(&self) -> actoris_common::Result<()> {
+        if let Some(store) = &self.store {
+            let snapshot = self.snapshot();
+            store.save(&snapshot).await?;
+            debug!(taken_at = snapshot.taken_at, "SyRA state persisted");
+        }
+        Ok(())
+    }
+
     /// Set verification tier for a DID
     pub fn set_verification_tier(&self, did: &str, tier: VerificationTier) {
         self.verification_tiers.write().insert(did.to_string(), tier);
@@ -272,6 +411,29 @@ impl SyraGuard {
             .unwrap_or_default()
     }
 
+    /// Attest the current verification tier of a DID as a credential
+    ///
+    /// Lets other platforms check KYC level without calling SyRA. Pass the
+    /// issuer's status list so the credential can be revoked if the tier is
+    /// later lowered.
+    pub fn issue_tier_credential(
+        &self,
+        issuer: &CredentialIssuer,
+        did: &str,
+        valid_until: DateTime<Utc>,
+        status_list: Option<&mut StatusList>,
+    ) -> Result<VerifiableCredential, CredentialError> {
+        let tier = self.get_verification_tier(did);
+        let credential = issuer.issue(did, tier.into(), valid_until, status_list)?;
+        info!(
+            did = %did,
+            tier = ?tier,
+            credential = %credential.id,
+            "Verification tier credential issued"
+        );
+        Ok(credential)
+    }
+
     /// Record stake for a DID
     pub fn record_stake(&self, did: &str, amount: Decimal) {
         let mut stakes = self.staked_amounts.write();
@@ -284,8 +446,9 @@ impl SyraGuard {
         // Check cooling off period
         if let Some(pattern) = self.patterns.read().get(did) {
             if let Some(until) = pattern.cooling_off_until {
-                if Instant::now() < until {
-                    let remaining = until.duration_since(Instant::now()).as_secs();
+                let now = now_ms();
+                if now < until {
+                    let remaining = ((until - now) / 1000) as u64;
                     return Err(SyraError::CoolingOffPeriod {
                         remaining_secs: remaining,
                     });
@@ -300,7 +463,7 @@ impl SyraGuard {
         let mut limiters = self.rate_limiters.write();
         let limiter = limiters
             .entry(did.to_string())
-            .or_insert_with(|| RateLimiter::new(Duration::from_secs(3600), limit));
+            .or_insert_with(|| RateLimiter::new(3600, limit));
 
         if !limiter.allow() {
             return Err(SyraError::RateLimitExceeded {
@@ -329,20 +492,7 @@ impl SyraGuard {
             });
         }
 
-        let mut limiters = self.spawn_limiters.write();
-        let limiter = limiters
-            .entry(parent_did.to_string())
-            .or_insert_with(|| RateLimiter::new(Duration::from_secs(86400), limit));
-
-        if !limiter.allow() {
-            return Err(SyraError::RateLimitExceeded {
-                operation: "spawn".to_string(),
-                limit,
-                window_secs: 86400,
-            });
-        }
-
-        // Check stake requirement
+        // Check stake requirement before spending a rate-limit slot
         let staked = self
             .staked_amounts
             .read()
@@ -357,8 +507,22 @@ impl SyraGuard {
             });
         }
 
+        let mut limiters = self.spawn_limiters.write();
+        let limiter = limiters
+            .entry(parent_did.to_string())
+            .or_insert_with(|| RateLimiter::new(86400, limit));
+
+        if !limiter.allow() {
+            return Err(SyraError::RateLimitExceeded {
+                operation: "spawn".to_string(),
+                limit,
+                window_secs: 86400,
+            });
+        }
+
         // Add child to parent's cluster
         self.add_to_cluster(parent_did, child_did)?;
+        self.record_funding(parent_did, child_did, FundingKind::Spawn);
 
         info!(
             parent = %parent_did,
@@ -375,6 +539,35 @@ impl SyraGuard {
         did: &str,
         value: Decimal,
         available_hc: Decimal,
+    ) -> Result<Decimal, SyraError> {
+        self.high_value_deposit(did, value, available_hc, self.config.high_value_deposit_pct)
+    }
+
+    /// Check a high-value action against the actor's trust in its domain
+    ///
+    /// The deposit scales with the DNA risk factor of the domain tau: an
+    /// actor fully trusted for `action_type` pays the configured rate, an
+    /// untrusted one up to twice that.
+    pub fn check_high_value_action(
+        &self,
+        did: &str,
+        action_type: &str,
+        trust: &TrustScore,
+        value: Decimal,
+        available_hc: Decimal,
+    ) -> Result<Decimal, SyraError> {
+        let tau = trust.domain_tau(action_type);
+        let deposit_pct = self.config.high_value_deposit_pct * risk_factor(tau);
+        debug!(did = %did, action_type = %action_type, tau, "Domain-scaled deposit");
+        self.high_value_deposit(did, value, available_hc, deposit_pct)
+    }
+
+    fn high_value_deposit(
+        &self,
+        did: &str,
+        value: Decimal,
+        available_hc: Decimal,
+        deposit_pct: f64,
     ) -> Result<Decimal, SyraError> {
         if value < self.config.high_value_threshold {
             return Ok(Decimal::ZERO);
@@ -389,7 +582,11 @@ impl SyraGuard {
         }
 
         // Calculate required deposit
-        let deposit_pct = Decimal::try_from(self.config.high_value_deposit_pct).unwrap();
+        let deposit_pct = Decimal::try_from(deposit_pct)
+            .ok()
+            .filter(|pct| !pct.is_sign_negative())
+            .ok_or(SyraError::InvalidDepositRate(deposit_pct))?
+            .round_dp(6);
         let required_deposit = value * deposit_pct;
 
         if available_hc < required_deposit {
@@ -409,7 +606,7 @@ impl SyraGuard {
             .entry(did.to_string())
             .or_insert_with(BehavioralPattern::default);
 
-        let now = Instant::now();
+        let now = now_ms();
 
         // Record timestamp
         pattern.action_timestamps.push_back(now);
@@ -419,20 +616,28 @@ impl SyraGuard {
         *pattern.request_patterns.entry(action_type.to_string()).or_insert(0) += 1;
 
         // Trim old timestamps
-        let window = Duration::from_secs(self.config.temporal_window_secs);
+        let window_ms = self.config.temporal_window_secs as i64 * 1000;
         while let Some(ts) = pattern.action_timestamps.front() {
-            if now.duration_since(*ts) > window {
+            if now - *ts > window_ms {
                 pattern.action_timestamps.pop_front();
             } else {
                 break;
             }
         }
 
+        // Feed temporal co-activity into the interaction graph
+        self.graph.write().record_activity(
+            did,
+            now,
+            window_ms,
+            self.config.clustering.activity_retention_windows,
+        );
+
         // Check for burst behavior (possible automation)
         if pattern.action_timestamps.len() > 10 {
             let first = pattern.action_timestamps.front().unwrap();
-            let duration = now.duration_since(*first);
-            let rate = pattern.action_timestamps.len() as f64 / duration.as_secs_f64();
+            let duration_secs = (now - *first) as f64 / 1000.0;
+            let rate = pattern.action_timestamps.len() as f64 / duration_secs;
 
             if rate > 5.0 {
                 // More than 5 actions per second
@@ -456,7 +661,7 @@ impl SyraGuard {
                 let mut cluster = IdentityCluster {
                     cluster_id: id.clone(),
                     members: HashSet::new(),
-                    created_at: Instant::now(),
+                    created_at: now_ms(),
                     flagged_as_sybil: false,
                 };
                 cluster.members.insert(parent_did.to_string());
@@ -476,6 +681,46 @@ impl SyraGuard {
         Ok(())
     }
 
+    /// Record an interaction between an identity and a counterparty
+    pub fn record_interaction(&self, did: &str, counterparty: &str) {
+        self.graph.write().record_interaction(did, counterparty);
+
+        let mut patterns = self.patterns.write();
+        patterns
+            .entry(did.to_string())
+            .or_default()
+            .interactions
+            .insert(counterparty.to_string());
+    }
+
+    /// Record a funding flow (loan, delegation or spawn) between identities
+    pub fn record_funding(&self, from: &str, to: &str, kind: FundingKind) {
+        self.graph.write().record_funding(from, to, kind);
+        debug!(from = %from, to = %to, kind = ?kind, "Funding flow recorded");
+    }
+
+    /// Run community detection over the interaction graph
+    ///
+    /// Replaces the previous detection results. Clusters scoring at or above
+    /// `sybil_similarity_threshold` are logged as Sybil clusters.
+    pub fn detect_clusters(&self) -> Vec<DetectedCluster> {
+        let detected = self.graph.read().detect_clusters(&self.config.clustering);
+
+        for cluster in &detected {
+            if cluster.score >= self.config.sybil_similarity_threshold {
+                warn!(
+                    cluster_id = %cluster.cluster_id,
+                    members = cluster.members.len(),
+                    score = cluster.score,
+                    "Sybil cluster detected in interaction graph"
+                );
+            }
+        }
+
+        *self.graph_clusters.write() = detected.clone();
+        detected
+    }
+
     /// Analyze identity for Sybil behavior
     pub fn analyze_sybil_risk(&self, did: &str) -> SybilRiskAssessment {
         let pattern = self.patterns.read().get(did).cloned();
@@ -532,12 +777,34 @@ impl SyraGuard {
             }
         }
 
+        // Interaction graph community membership
+        let graph_cluster = self
+            .graph_clusters
+            .read()
+            .iter()
+            .find(|c| c.members.contains(did))
+            .map(|c| ClusterFinding {
+                cluster_id: c.cluster_id.clone(),
+                size: c.members.len(),
+                score: c.score,
+                links: c.member_evidence.get(did).cloned().unwrap_or_default(),
+            });
+        if let Some(finding) = &graph_cluster {
+            risk_score += finding.score;
+            risk_factors.push(format!(
+                "Member of interaction cluster {} ({} members, cohesion {:.2})",
+                finding.cluster_id, finding.size, finding.score
+            ));
+            risk_factors.extend(finding.links.explain());
+        }
+
         SybilRiskAssessment {
             did: did.to_string(),
             risk_score: risk_score.min(1.0),
             risk_factors,
             verification_tier: tier,
             is_sybil: risk_score >= self.config.sybil_similarity_threshold,
+            cluster: graph_cluster,
         }
     }
 
@@ -549,8 +816,7 @@ impl SyraGuard {
             .or_insert_with(BehavioralPattern::default);
 
         pattern.flagged = true;
-        pattern.cooling_off_until =
-            Some(Instant::now() + Duration::from_secs(self.config.cooling_off_secs));
+        pattern.cooling_off_until = Some(now_ms() + self.config.cooling_off_secs as i64 * 1000);
 
         warn!(did = %did, reason = %reason, "Identity flagged for suspicious behavior");
     }
@@ -584,6 +850,7 @@ impl SyraGuard {
             flagged_identities: patterns.values().filter(|p| p.flagged).count(),
             sybil_clusters: clusters.values().filter(|c| c.flagged_as_sybil).count(),
             total_clusters: clusters.len(),
+            graph_clusters: self.graph_clusters.read().len(),
         }
     }
 }
@@ -596,6 +863,20 @@ pub struct SybilRiskAssessment {
     pub risk_factors: Vec<String>,
     pub verification_tier: VerificationTier,
     pub is_sybil: bool,
+    /// Interaction graph cluster the identity belongs to, if any
+    #[serde(default)]
+    pub cluster: Option<ClusterFinding>,
+}
+
+/// Why an identity was grouped into an interaction graph cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterFinding {
+    pub cluster_id: String,
+    pub size: usize,
+    /// Cluster cohesion score (0.0 - 1.0)
+    pub score: f64,
+    /// The identity's own links inside the cluster
+    pub links: LinkEvidence,
 }
 
 /// SyRA statistics
@@ -606,6 +887,7 @@ pub struct SyraStats {
     pub flagged_identities: usize,
     pub sybil_clusters: usize,
     pub total_clusters: usize,
+    pub graph_clusters: usize,
 }
 
 impl Default for SyraGuard {
@@ -617,6 +899,8 @@ impl Default for SyraGuard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::verification::syra_store::InMemorySyraStore;
+    use actoris_common::TrustModel;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -726,5 +1010,133 @@ mod tests {
         // High value with sufficient HC
         let deposit = guard.check_high_value(did, dec!(2000), dec!(1000)).unwrap();
         assert_eq!(deposit, dec!(200)); // 10% of 2000
+
+        // A misconfigured rate is an error, not a panic
+        for rate in [f64::NAN, f64::INFINITY, -0.1] {
+            let guard = SyraGuard::new(SyraConfig {
+                high_value_deposit_pct: rate,
+                ..Default::default()
+            });
+            guard.set_verification_tier(did, VerificationTier::Tier2);
+            let result = guard.check_high_value(did, dec!(2000), dec!(1000));
+            assert!(matches!(result, Err(SyraError::InvalidDepositRate(_))));
+        }
+    }
+
+    #[test]
+    fn test_high_value_deposit_uses_domain_trust() {
+        let guard = SyraGuard::default();
+        let did = "did:key:reviewer";
+        guard.set_verification_tier(did, VerificationTier::Tier2);
+
+        let model = TrustModel::default();
+        let mut trust = TrustScore::new();
+        for i in 0..40 {
+            trust.record_outcome_at("code.review", true, 1_700_000_000_000 + i, &model);
+        }
+        for i in 0..40 {
+            trust.record_outcome_at("finance.reconcile", false, 1_700_000_000_100 + i, &model);
+        }
+
+        let trusted = guard
+            .check_high_value_action(did, "code.review.rust", &trust, dec!(2000), dec!(1000))
+            .unwrap();
+        let untrusted = guard
+            .check_high_value_action(did, "finance.reconcile", &trust, dec!(2000), dec!(1000))
+            .unwrap();
+        assert!(trusted < untrusted);
+        assert!(trusted > dec!(200) && untrusted <= dec!(400));
+    }
+
+    #[test]
+    fn test_graph_cluster_is_explained() {
+        let guard = SyraGuard::default();
+
+        // Four agents working the same three clients
+        for i in 0..4 {
+            let did = format!("did:key:ring{}", i);
+            for client in ["did:key:c1", "did:key:c2", "did:key:c3"] {
+                guard.record_interaction(&did, client);
+            }
+        }
+
+        let detected = guard.detect_clusters();
+        assert!(detected.iter().any(|c| c.members.contains("did:key:ring0")));
+
+        let assessment = guard.analyze_sybil_risk("did:key:ring0");
+        let finding = assessment.cluster.as_ref().expect("cluster finding");
+        assert_eq!(finding.size, 4);
+        assert_eq!(finding.links.shared_counterparty_links, 3);
+        assert!(assessment.is_sybil);
+        assert!(assessment
+            .risk_factors
+            .iter()
+            .any(|f| f.starts_with("Shares counterparties")));
+
+        // Unrelated identities carry no cluster finding
+        assert!(guard.analyze_sybil_risk("did:key:other").cluster.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_state_survives_restart() {
+        let store: Arc<dyn SyraStore> = Arc::new(InMemorySyraStore::new());
+        let parent = "did:key:parent";
+
+        {
+            let guard = SyraGuard::restore(SyraConfig::default(), store.clone())
+                .await
+                .unwrap();
+            guard.set_verification_tier(parent, VerificationTier::Tier2);
+            guard.record_stake(parent, dec!(500));
+            guard.check_spawn(parent, "did:key:child").unwrap();
+            guard.flag_suspicious("did:key:bad", "Test reason");
+            guard.persist().await.unwrap();
+        }
+
+        let guard = SyraGuard::restore(SyraConfig::default(), store)
+            .await
+            .unwrap();
+        assert_eq!(guard.get_verification_tier(parent), VerificationTier::Tier2);
+        assert!(matches!(
+            guard.check_operation("did:key:bad", "test.action"),
+            Err(SyraError::CoolingOffPeriod { .. })
+        ));
+
+        let stats = guard.stats();
+        assert_eq!(stats.total_clusters, 1);
+        assert_eq!(stats.flagged_identities, 1);
+
+        // Spawn lineage is part of the restored interaction graph
+        assert_eq!(guard.graph.read().node_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tier_credential() {
+        use actoris_common::crypto::{CredentialVerifier, UniversalResolver};
+        use ed25519_dalek::SigningKey;
+
+        let guard = SyraGuard::default();
+        let did = "did:key:kyc";
+        guard.set_verification_tier(did, VerificationTier::Tier3);
+
+        let issuer = CredentialIssuer::from_did_key(SigningKey::generate(&mut rand::rngs::OsRng));
+        let credential = guard
+            .issue_tier_credential(&issuer, did, Utc::now() + chrono::Duration::days(90), None)
+            .unwrap();
+
+        let verifier = CredentialVerifier::new(
+            Arc::new(UniversalResolver::new()),
+            Arc::new(HashMap::<String, VerifiableCredential>::new()),
+        )
+        .with_trusted_issuer(issuer.did());
+        let subject = verifier.verify(&credential, Utc::now()).await.unwrap();
+        assert_eq!(subject.id, did);
+        let CredentialClaim::VerificationTier { verification_tier } = subject.claim else {
+            panic!("expected a verification tier claim");
+        };
+        assert_eq!(
+            VerificationTier::from_level(verification_tier),
+            Some(VerificationTier::Tier3)
+        );
     }
 }
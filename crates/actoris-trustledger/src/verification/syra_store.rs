@@ -0,0 +1,57 @@
+//! Durable storage for SyRA state
+//!
+//! SyRA state is persisted as whole snapshots. The EventStoreDB backend
+//! appends each snapshot to a dedicated stream and restores from the latest.
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+
+use actoris_common::Result;
+
+use super::syra::SyraSnapshot;
+use crate::ledger::eventstore::EventStoreClient;
+
+/// Storage backend for SyRA snapshots
+#[async_trait]
+pub trait SyraStore: Send + Sync {
+    /// Save a snapshot, superseding earlier ones
+    async fn save(&self, snapshot: &SyraSnapshot) -> Result<()>;
+
+    /// Load the latest snapshot, if any
+    async fn load(&self) -> Result<Option<SyraSnapshot>>;
+}
+
+/// In-memory store (for testing and single-process deployments)
+#[derive(Default)]
+pub struct InMemorySyraStore {
+    latest: RwLock<Option<SyraSnapshot>>,
+}
+
+impl InMemorySyraStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SyraStore for InMemorySyraStore {
+    async fn save(&self, snapshot: &SyraSnapshot) -> Result<()> {
+        *self.latest.write() = Some(snapshot.clone());
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Option<SyraSnapshot>> {
+        Ok(self.latest.read().clone())
+    }
+}
+
+#[async_trait]
+impl SyraStore for EventStoreClient {
+    async fn save(&self, snapshot: &SyraSnapshot) -> Result<()> {
+        self.record_syra_snapshot(snapshot).await.map(|_| ())
+    }
+
+    async fn load(&self) -> Result<Option<SyraSnapshot>> {
+        self.read_latest_syra_snapshot().await
+    }
+}
@@ -1,22 +1,34 @@
 //! Action Verification Engine
 //!
 //! Coordinates the verification process:
-//! 1. Receives action submissions
-//! 2. Dispatches to oracle quorum
+//! 1. Receives action submissions and picks a verification depth
+//!    (optimistic, sampled or full quorum) from the action's risk profile
+//! 2. Dispatches to oracle quorum (or a per-request VRF committee)
 //! 3. Collects votes and signatures
 //! 4. Aggregates FROST signature
 //! 5. Records to EventStoreDB
 
-use crate::consensus::{OracleNode, QuorumManager};
+use crate::consensus::{CommitteeSeed, CommitteeSelector, QuorumManager, TallyOutcome};
 use crate::ledger::eventstore::{EventStoreClient, OutcomeRecordData};
+use crate::verification::metrics::VerificationMetrics;
+use crate::verification::policy::{
+    ChallengeBonds, RiskProfile, VerificationDepth, VerificationPolicy,
+};
 use actoris_common::{
-    crypto::{frost, merkle::MerkleTree},
+    crypto::{
+        frost::{FrostCoordinator, SignatureShare, SigningCommitment},
+        merkle::MerkleTree,
+        vrf::{VrfOutput, VrfProof},
+    },
     error::VerificationError,
-    types::outcome_record::{FrostSignature, OracleVote, OutcomeRecord, VerificationResult},
+    types::outcome_record::{
+        verdict_message, FrostSignature, OracleVote, OutcomeRecord, VerificationMode,
+        VerificationResult,
+    },
     ActorisError, Result,
 };
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
@@ -31,6 +43,34 @@ pub enum VerificationStatus {
     Completed(Box<OutcomeRecord>),
     Failed(String),
     Timeout,
+    /// Retries exhausted; awaiting human review
+    Escalated,
+}
+
+/// Fallback for verifications that exhausted their retries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationTier {
+    /// Park the request for a human reviewer
+    HumanReview,
+    /// Re-open the request to the whole oracle pool with a higher threshold
+    LargerQuorum { threshold: u8 },
+}
+
+impl EscalationTier {
+    /// Short name for logs and events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EscalationTier::HumanReview => "human_review",
+            EscalationTier::LargerQuorum { .. } => "larger_quorum",
+        }
+    }
+}
+
+/// An oracle's FROST round-2 share and the round-1 commitment it signed under
+#[derive(Debug, Clone)]
+pub struct OracleShare {
+    pub commitment: SigningCommitment,
+    pub share: SignatureShare,
 }
 
 /// Pending verification request
@@ -43,12 +83,96 @@ struct PendingVerification {
     input_hash: [u8; 32],
     output_hash: [u8; 32],
     compute_hc: Decimal,
+    /// Original submission time (SLA latency is measured from here)
+    first_submitted_at: Instant,
+    /// Start of the current dispatch attempt (timeouts are measured from here)
     submitted_at: Instant,
+    /// Retries made after timeouts
+    retries: u8,
+    /// Approvals required (raised when escalated to a larger quorum)
+    threshold: u8,
+    /// Whether the request was escalated after exhausting retries
+    escalated: bool,
+    /// Whether an SLA miss was already counted for this request
+    sla_missed: bool,
     votes: Vec<OracleVote>,
-    partial_signatures: Vec<frost::PartialSignature>,
+    /// Signature shares, in vote order
+    shares: Vec<OracleShare>,
+    /// Committee seed when a per-request committee is sampled
+    committee: Option<CommitteeSeed>,
+    /// Verified sortition tickets for the committee seed
+    tickets: Vec<(String, VrfOutput, VrfProof)>,
+    /// Committee fixed from the lowest tickets
+    members: Option<Vec<String>>,
+    /// Members of committees that timed out; barred from re-draws
+    excluded: Vec<String>,
+    /// Verification depth applied to this request
+    mode: VerificationMode,
+    /// Whether an optimistic acceptance was challenged
+    challenged: bool,
     status: VerificationStatus,
 }
 
+impl PendingVerification {
+    /// Whether the request is still collecting votes
+    fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            VerificationStatus::Pending | VerificationStatus::InProgress { .. }
+        )
+    }
+
+    /// Start a new dispatch attempt, discarding votes and tickets from the previous one
+    fn reopen(&mut self) {
+        self.submitted_at = Instant::now();
+        self.votes.clear();
+        self.shares.clear();
+        self.tickets.clear();
+        self.members = None;
+        self.status = VerificationStatus::Pending;
+    }
+}
+
+/// EventStore append decided under the pending lock
+///
+/// Appends are made only after the lock is released, so a slow EventStore
+/// stalls the request being persisted rather than every verification.
+#[derive(Debug)]
+enum PendingAppend {
+    Outcome {
+        record: Box<OutcomeRecord>,
+        quorum_reached: bool,
+        signature: [u8; 64],
+    },
+    TimedOut {
+        approvals: u8,
+        rejections: u8,
+        elapsed_ms: u64,
+    },
+    Challenged {
+        challenger_did: String,
+        reason: String,
+    },
+    Retried {
+        attempt: u8,
+        committee_seed: Option<[u8; 32]>,
+    },
+    Escalated {
+        tier: &'static str,
+        retries: u8,
+    },
+}
+
+impl PendingAppend {
+    fn outcome(record: &OutcomeRecord, quorum_reached: bool, signature: [u8; 64]) -> Self {
+        PendingAppend::Outcome {
+            record: Box::new(record.clone()),
+            quorum_reached,
+            signature,
+        }
+    }
+}
+
 /// Configuration for the verifier
 #[derive(Debug, Clone)]
 pub struct VerifierConfig {
@@ -83,6 +207,18 @@ pub struct ActionVerifier {
     merkle_tree: Arc<RwLock<MerkleTree>>,
     /// Group public key for FROST verification
     group_public_key: [u8; 32],
+    /// Aggregates oracle shares (None = quorum outcomes cannot be signed)
+    coordinator: Option<Arc<FrostCoordinator>>,
+    /// Per-request committee selector (None = global quorum)
+    committee: Option<Arc<CommitteeSelector>>,
+    /// Risk-tiered verification policy
+    policy: VerificationPolicy,
+    /// Bonds backing challenges from anyone but the client
+    bonds: Option<Arc<dyn ChallengeBonds>>,
+    /// Prometheus metrics
+    metrics: Option<Arc<VerificationMetrics>>,
+    /// DIDs allowed to resolve escalated requests
+    reviewers: HashSet<String>,
 }
 
 impl ActionVerifier {
@@ -101,6 +237,12 @@ impl ActionVerifier {
             eventstore: None,
             merkle_tree: Arc::new(RwLock::new(MerkleTree::new())),
             group_public_key: group_key,
+            coordinator: None,
+            committee: None,
+            policy: VerificationPolicy::default(),
+            bonds: None,
+            metrics: None,
+            reviewers: HashSet::new(),
         }
     }
 
@@ -110,7 +252,59 @@ impl ActionVerifier {
         self
     }
 
-    /// Submit an action for verification
+    /// Aggregate oracle shares with the group key from DKG
+    pub fn with_frost(mut self, coordinator: Arc<FrostCoordinator>) -> Self {
+        self.group_public_key = coordinator.group_public_key();
+        self.coordinator = Some(coordinator);
+        self
+    }
+
+    /// Sample a per-request oracle committee instead of the global quorum
+    pub fn with_committee(mut self, selector: Arc<CommitteeSelector>) -> Self {
+        self.committee = Some(selector);
+        self
+    }
+
+    /// Get the committee selector, if per-request committees are enabled
+    pub fn committee(&self) -> Option<&Arc<CommitteeSelector>> {
+        self.committee.as_ref()
+    }
+
+    /// Set the risk-tiered verification policy
+    pub fn with_policy(mut self, policy: VerificationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the verification policy
+    pub fn policy(&self) -> &VerificationPolicy {
+        &self.policy
+    }
+
+    /// Let bonded challengers other than the client challenge
+    pub fn with_challenge_bonds(mut self, bonds: Arc<dyn ChallengeBonds>) -> Self {
+        self.bonds = Some(bonds);
+        self
+    }
+
+    /// Record latency, SLA misses, timeouts and retries
+    pub fn with_metrics(mut self, metrics: Arc<VerificationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Allow these DIDs to resolve requests escalated to human review
+    pub fn with_reviewers(mut self, reviewers: impl IntoIterator<Item = String>) -> Self {
+        self.reviewers.extend(reviewers);
+        self
+    }
+
+    /// Get the metrics, if enabled
+    pub fn metrics(&self) -> Option<&Arc<VerificationMetrics>> {
+        self.metrics.as_ref()
+    }
+
+    /// Submit an action for full quorum verification
     #[instrument(skip(self, input, output))]
     pub async fn submit_action(
         &self,
@@ -120,6 +314,74 @@ impl ActionVerifier {
         input: &[u8],
         output: &[u8],
         compute_hc: Decimal,
+    ) -> Result<String> {
+        self.submit(
+            actor_did,
+            client_did,
+            action_type,
+            input,
+            output,
+            compute_hc,
+            VerificationMode::FullQuorum,
+        )
+        .await
+    }
+
+    /// Submit an action, choosing the verification depth from its risk profile
+    ///
+    /// Optimistic and unsampled actions complete immediately without oracle
+    /// votes; everything else waits for the quorum.
+    #[instrument(skip(self, input, output))]
+    pub async fn submit_action_with_risk(
+        &self,
+        actor_did: &str,
+        client_did: &str,
+        action_type: &str,
+        input: &[u8],
+        output: &[u8],
+        risk: &RiskProfile,
+    ) -> Result<String> {
+        let mode = self.choose_mode(action_type, risk);
+        self.submit(
+            actor_did,
+            client_did,
+            action_type,
+            input,
+            output,
+            risk.compute_hc,
+            mode,
+        )
+        .await
+    }
+
+    /// Resolve the policy's depth into a concrete mode for one request
+    fn choose_mode(&self, action_type: &str, risk: &RiskProfile) -> VerificationMode {
+        match self.policy.select(action_type, risk) {
+            VerificationDepth::Optimistic => VerificationMode::Optimistic {
+                challenge_deadline: chrono::Utc::now().timestamp_millis()
+                    + self.policy.challenge_window_ms as i64,
+            },
+            VerificationDepth::Sampled => {
+                let rate = self.policy.sample_rate.clamp(0.0, 1.0);
+                VerificationMode::Sampled {
+                    rate,
+                    sampled: rand::Rng::gen::<f64>(&mut rand::rngs::OsRng) < rate,
+                }
+            }
+            VerificationDepth::FullQuorum => VerificationMode::FullQuorum,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn submit(
+        &self,
+        actor_did: &str,
+        client_did: &str,
+        action_type: &str,
+        input: &[u8],
+        output: &[u8],
+        compute_hc: Decimal,
+        mode: VerificationMode,
     ) -> Result<String> {
         let request_id = Uuid::now_v7().to_string();
 
@@ -127,6 +389,10 @@ impl ActionVerifier {
         let input_hash = *blake3::hash(input).as_bytes();
         let output_hash = *blake3::hash(output).as_bytes();
 
+        // Fix the committee seed against the latest committed block
+        let committee = self.committee.as_ref().map(|c| c.draw(&request_id));
+
+        let now = Instant::now();
         let pending = PendingVerification {
             request_id: request_id.clone(),
             actor_did: actor_did.to_string(),
@@ -135,18 +401,23 @@ impl ActionVerifier {
             input_hash,
             output_hash,
             compute_hc,
-            submitted_at: Instant::now(),
+            first_submitted_at: now,
+            submitted_at: now,
+            retries: 0,
+            threshold: self.config.quorum_threshold,
+            escalated: false,
+            sla_missed: false,
             votes: Vec::new(),
-            partial_signatures: Vec::new(),
+            shares: Vec::new(),
+            committee,
+            tickets: Vec::new(),
+            members: None,
+            excluded: Vec::new(),
+            mode,
+            challenged: false,
             status: VerificationStatus::Pending,
         };
 
-        // Store pending verification
-        {
-            let mut pending_map = self.pending.write().await;
-            pending_map.insert(request_id.clone(), pending);
-        }
-
         // Record to EventStore if available
         if let Some(es) = &self.eventstore {
             es.record_action_submitted(
@@ -159,14 +430,235 @@ impl ActionVerifier {
                 &compute_hc.to_string(),
             )
             .await?;
+
+            es.record_verification_started(
+                &request_id,
+                self.config.quorum_threshold,
+                committee.map(|c| c.seed),
+            )
+            .await?;
+        }
+
+        // Store pending verification, accepting low-risk actions immediately
+        {
+            let mut pending = pending;
+            if !mode.quorum_checked() {
+                let record = self.accept_without_quorum(&mut pending).await?;
+                pending.status = VerificationStatus::Completed(Box::new(record));
+            }
+
+            let mut pending_map = self.pending.write().await;
+            pending_map.insert(request_id.clone(), pending);
         }
 
-        info!(request_id = %request_id, actor = %actor_did, "Action submitted for verification");
+        info!(
+            request_id = %request_id,
+            actor = %actor_did,
+            mode = mode.as_str(),
+            "Action submitted for verification"
+        );
 
         Ok(request_id)
     }
 
+    /// Finalize an optimistic or unsampled action without oracle votes
+    async fn accept_without_quorum(
+        &self,
+        verification: &mut PendingVerification,
+    ) -> Result<OutcomeRecord> {
+        let latency_ms = self.observe_outcome(verification);
+
+        let result = VerificationResult {
+            passed: true,
+            oracle_count: 0,
+            quorum_reached: false,
+            latency_ms,
+            votes: Vec::new(),
+            failure_reason: None,
+        };
+
+        // No oracle signed this record
+        let frost_sig = FrostSignature::new(
+            [0u8; 64],
+            Vec::new(),
+            self.group_public_key,
+            self.config.quorum_threshold,
+            self.config.oracle_count,
+        );
+
+        let record = OutcomeRecord::new(
+            verification.actor_did.clone(),
+            verification.client_did.clone(),
+            verification.action_type.clone(),
+            verification.input_hash,
+            verification.output_hash,
+            verification.compute_hc,
+            result,
+            frost_sig,
+        )
+        .with_verification_mode(verification.mode);
+
+        let record = self.seal_record(record).await;
+        self.persist(
+            &verification.request_id,
+            &PendingAppend::outcome(&record, false, [0u8; 64]),
+        )
+        .await?;
+        Ok(record)
+    }
+
+    /// Append a record to the Merkle tree, attaching its inclusion proof
+    async fn seal_record(&self, mut record: OutcomeRecord) -> OutcomeRecord {
+        let mut tree = self.merkle_tree.write().await;
+        let canonical_hash = record.canonical_hash();
+        let index = tree.append(canonical_hash);
+        tree.commit();
+
+        if let Some(proof) = tree.generate_proof(index) {
+            record.set_merkle_proof(proof.siblings, proof.root, index);
+        }
+        record
+    }
+
+    /// Make an append decided under the pending lock
+    ///
+    /// Must be called after the lock is released.
+    async fn persist(&self, request_id: &str, append: &PendingAppend) -> Result<()> {
+        let Some(es) = &self.eventstore else {
+            return Ok(());
+        };
+        match append {
+            PendingAppend::Outcome {
+                record,
+                quorum_reached,
+                signature,
+            } => {
+                es.record_verification_completed(
+                    request_id,
+                    record.verification.passed,
+                    *quorum_reached,
+                    record.verification.latency_ms,
+                    signature,
+                )
+                .await?;
+                es.record_outcome_finalized(record).await?;
+            }
+            PendingAppend::TimedOut {
+                approvals,
+                rejections,
+                elapsed_ms,
+            } => {
+                es.record_verification_timed_out(request_id, *approvals, *rejections, *elapsed_ms)
+                    .await?;
+            }
+            PendingAppend::Challenged {
+                challenger_did,
+                reason,
+            } => {
+                es.record_verification_challenged(request_id, challenger_did, reason)
+                    .await?;
+            }
+            PendingAppend::Retried {
+                attempt,
+                committee_seed,
+            } => {
+                es.record_verification_retried(request_id, *attempt, *committee_seed)
+                    .await?;
+            }
+            PendingAppend::Escalated { tier, retries } => {
+                es.record_verification_escalated(request_id, tier, *retries)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Challenge an optimistically accepted action within its window
+    ///
+    /// The action is reopened for full quorum verification; its earlier
+    /// acceptance is superseded by the quorum's outcome. Only the action's
+    /// client, or a challenger holding at least the policy's bond, may
+    /// challenge.
+    #[instrument(skip(self))]
+    pub async fn challenge(
+        &self,
+        request_id: &str,
+        challenger_did: &str,
+        reason: &str,
+    ) -> Result<()> {
+        let mut pending_map = self.pending.write().await;
+
+        let verification =
+            pending_map
+                .get_mut(request_id)
+                .ok_or_else(|| VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        if !verification.mode.challengeable_at(now) {
+            return Err(VerificationError::ChallengeRejected {
+                reason: format!(
+                    "{} verification is not open to challenge",
+                    verification.mode.as_str()
+                ),
+            }
+            .into());
+        }
+
+        let bonded = || {
+            self.bonds
+                .as_ref()
+                .and_then(|bonds| bonds.bonded_hc(challenger_did))
+                .is_some_and(|bond| bond >= self.policy.challenge_bond_hc)
+        };
+        if challenger_did != verification.client_did && !bonded() {
+            return Err(VerificationError::ChallengeRejected {
+                reason: format!(
+                    "{} is neither the client nor a bonded challenger",
+                    challenger_did
+                ),
+            }
+            .into());
+        }
+
+        verification.mode = VerificationMode::FullQuorum;
+        verification.challenged = true;
+        verification.reopen();
+        drop(pending_map);
+
+        warn!(
+            request_id = %request_id,
+            challenger = %challenger_did,
+            reason = %reason,
+            "Optimistic acceptance challenged, escalating to full quorum"
+        );
+
+        self.persist(
+            request_id,
+            &PendingAppend::Challenged {
+                challenger_did: challenger_did.to_string(),
+                reason: reason.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Register an oracle allowed to vote on global-quorum requests
+    pub fn register_oracle(&self, oracle_did: &str) -> bool {
+        self.quorum.register(oracle_did)
+    }
+
+    /// Remove an oracle from the global quorum
+    pub fn deregister_oracle(&self, oracle_did: &str) -> bool {
+        self.quorum.deregister(oracle_did)
+    }
+
     /// Record an oracle vote
+    ///
+    /// Each registered oracle votes at most once. The request passes as soon
+    /// as approvals reach the threshold and fails as soon as the remaining
+    /// eligible voters can no longer get it there.
     #[instrument(skip(self))]
     pub async fn record_vote(
         &self,
@@ -174,185 +666,756 @@ impl ActionVerifier {
         oracle_did: &str,
         approved: bool,
         reason: Option<String>,
-        partial_sig: frost::PartialSignature,
+        share: OracleShare,
+        vrf_proof: Option<VrfProof>,
     ) -> Result<VerificationStatus> {
         let mut pending_map = self.pending.write().await;
 
-        let verification = pending_map
-            .get_mut(request_id)
-            .ok_or_else(|| ActorisError::Verification(VerificationError::DuplicateAction {
-                action_id: request_id.to_string(),
-            }))?;
+        let mut verification =
+            pending_map
+                .get_mut(request_id)
+                .ok_or_else(|| VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })?;
+
+        // Actions accepted without a quorum, and decided requests, take no votes
+        if !verification.mode.quorum_checked() || !verification.is_open() {
+            return Ok(verification.status.clone());
+        }
 
         // Check for timeout
         if verification.submitted_at.elapsed() > Duration::from_millis(self.config.timeout_ms) {
-            verification.status = VerificationStatus::Timeout;
+            let timed_out = self.mark_timed_out(request_id, verification);
+            drop(pending_map);
+            self.persist(request_id, &timed_out).await?;
             return Ok(VerificationStatus::Timeout);
         }
 
+        // Only registered oracles vote; committee requests only take the lowest-ticket members
+        let unknown_oracle = || VerificationError::UnknownOracle {
+            oracle_did: oracle_did.to_string(),
+        };
+        let mut ticket = None;
+        match (&self.committee, &verification.committee) {
+            (Some(selector), Some(_)) => {
+                if !selector.is_registered(oracle_did) {
+                    return Err(unknown_oracle().into());
+                }
+                if let Some(proof) = &vrf_proof {
+                    Self::add_ticket(selector, verification, oracle_did, proof)?;
+                }
+                Self::close_ticket_window(selector, verification);
+
+                let members = verification.members.as_ref().ok_or_else(|| {
+                    VerificationError::CommitteePending {
+                        request_id: request_id.to_string(),
+                    }
+                })?;
+                if !members.iter().any(|m| m == oracle_did) {
+                    return Err(VerificationError::NotInCommittee {
+                        oracle_did: oracle_did.to_string(),
+                    }
+                    .into());
+                }
+                ticket = verification
+                    .tickets
+                    .iter()
+                    .find(|(did, _, _)| did == oracle_did)
+                    .map(|(_, _, proof)| *proof);
+            }
+            _ => {
+                if !self.quorum.is_member(oracle_did) {
+                    return Err(unknown_oracle().into());
+                }
+            }
+        }
+
+        if verification
+            .votes
+            .iter()
+            .any(|v| v.oracle_did == oracle_did)
+        {
+            return Err(VerificationError::DuplicateVote {
+                oracle_did: oracle_did.to_string(),
+            }
+            .into());
+        }
+
+        if let Some(es) = &self.eventstore {
+            // Persist without holding the pending map; re-check the request after
+            let attempt = verification.submitted_at;
+            drop(pending_map);
+            es.record_oracle_vote(request_id, oracle_did, approved, reason.as_deref())
+                .await?;
+            pending_map = self.pending.write().await;
+            verification = pending_map
+                .get_mut(request_id)
+                .ok_or_else(|| VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })?;
+            if !verification.is_open() {
+                return Ok(verification.status.clone());
+            }
+            if verification.submitted_at != attempt {
+                return Err(ActorisError::Validation(format!(
+                    "request {} was re-dispatched while the vote was recorded",
+                    request_id
+                )));
+            }
+            if verification
+                .votes
+                .iter()
+                .any(|v| v.oracle_did == oracle_did)
+            {
+                return Err(VerificationError::DuplicateVote {
+                    oracle_did: oracle_did.to_string(),
+                }
+                .into());
+            }
+        }
+
         // Record vote
         verification.votes.push(OracleVote {
             oracle_did: oracle_did.to_string(),
             approved,
             reason,
             timestamp: chrono::Utc::now().timestamp_millis(),
+            vrf_proof: ticket.map(|p| p.to_bytes().to_vec()),
         });
 
-        // Record partial signature
-        verification.partial_signatures.push(partial_sig);
+        // Record the signature share
+        verification.shares.push(share);
 
         let votes_received = verification.votes.len() as u8;
-        let votes_required = self.config.quorum_threshold;
+        let votes_required = verification.threshold;
+        let approvals = verification.votes.iter().filter(|v| v.approved).count();
+        let rejections = verification.votes.len() - approvals;
+
+        // Committee requests can only hear from the members actually selected
+        let eligible = match &verification.members {
+            Some(members) => members.len(),
+            None => self.quorum.member_count(),
+        };
 
         debug!(
             request_id = %request_id,
             oracle = %oracle_did,
             approved = approved,
-            votes = votes_received,
+            approvals = approvals,
+            rejections = rejections,
             required = votes_required,
             "Oracle vote recorded"
         );
 
-        // Check if quorum reached
-        if votes_received >= votes_required {
-            // Count approvals
-            let approvals = verification.votes.iter().filter(|v| v.approved).count() as u8;
-            let passed = approvals >= votes_required;
-
-            // Aggregate signature
-            let signature = frost::aggregate_signatures(
-                &verification.input_hash,
-                &verification.partial_signatures,
-                &self.group_public_key,
-            )?;
-
-            // Calculate latency
-            let latency_ms = verification.submitted_at.elapsed().as_millis() as u32;
-
-            // Build verification result
-            let result = VerificationResult {
-                passed,
-                oracle_count: votes_received,
-                quorum_reached: true,
-                latency_ms,
-                votes: verification.votes.clone(),
-                failure_reason: if passed {
-                    None
-                } else {
-                    Some("Quorum rejected action".to_string())
-                },
-            };
-
-            // Build FROST signature
-            let frost_sig = FrostSignature::new(
-                signature,
-                verification
-                    .votes
-                    .iter()
-                    .map(|v| v.oracle_did.clone())
-                    .collect(),
-                self.group_public_key,
-                self.config.quorum_threshold,
-                self.config.oracle_count,
-            );
+        match QuorumManager::tally_at(verification.threshold, approvals, rejections, eligible) {
+            TallyOutcome::Pending => {
+                verification.status = VerificationStatus::InProgress {
+                    votes_received,
+                    votes_required,
+                };
+                Ok(verification.status.clone())
+            }
+            outcome => {
+                let (record, outcome) = self
+                    .finalize_quorum(request_id, verification, outcome == TallyOutcome::Passed)
+                    .await?;
+                drop(pending_map);
+                self.persist(request_id, &outcome).await?;
+                Ok(VerificationStatus::Completed(Box::new(record)))
+            }
+        }
+    }
 
-            // Create outcome record
-            let mut record = OutcomeRecord::new(
-                verification.actor_did.clone(),
-                verification.client_did.clone(),
-                verification.action_type.clone(),
-                verification.input_hash,
-                verification.output_hash,
-                verification.compute_hc,
-                result,
-                frost_sig,
-            );
+    /// Submit an oracle's sortition ticket for a committee request
+    ///
+    /// Returns the committee once it is fixed: when every oracle registered at
+    /// seed time has a ticket in, or the ticket window has closed.
+    #[instrument(skip(self, proof))]
+    pub async fn submit_ticket(
+        &self,
+        request_id: &str,
+        oracle_did: &str,
+        proof: &VrfProof,
+    ) -> Result<Option<Vec<String>>> {
+        let selector = self.committee.as_ref().ok_or_else(|| {
+            ActorisError::Validation("per-request committees are not enabled".to_string())
+        })?;
 
-            // Add to Merkle tree
-            {
-                let mut tree = self.merkle_tree.write().await;
-                let canonical_hash = record.canonical_hash();
-                let index = tree.append(canonical_hash);
-                tree.commit();
+        let mut pending_map = self.pending.write().await;
+        let verification =
+            pending_map
+                .get_mut(request_id)
+                .ok_or_else(|| VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })?;
 
-                if let Some(proof) = tree.generate_proof(index) {
-                    record.set_merkle_proof(proof.siblings, proof.root, index);
-                }
-            }
+        if !verification.is_open() || verification.committee.is_none() {
+            return Err(ActorisError::Validation(format!(
+                "request {} is not selecting a committee",
+                request_id
+            )));
+        }
 
-            // Record to EventStore
-            if let Some(es) = &self.eventstore {
-                es.record_verification_completed(
-                    request_id,
-                    passed,
-                    true,
-                    latency_ms,
-                    &signature,
-                )
-                .await?;
+        Self::add_ticket(selector, verification, oracle_did, proof)?;
+        Self::close_ticket_window(selector, verification);
+        Ok(verification.members.clone())
+    }
+
+    /// Committee members for a request, once fixed
+    pub async fn committee_members(&self, request_id: &str) -> Option<Vec<String>> {
+        let mut pending_map = self.pending.write().await;
+        let verification = pending_map.get_mut(request_id)?;
+        if let Some(selector) = &self.committee {
+            Self::close_ticket_window(selector, verification);
+        }
+        verification.members.clone()
+    }
 
-                es.record_outcome_finalized(&record).await?;
+    /// Verify and store a ticket, fixing the committee once the pool is in
+    fn add_ticket(
+        selector: &CommitteeSelector,
+        verification: &mut PendingVerification,
+        oracle_did: &str,
+        proof: &VrfProof,
+    ) -> Result<()> {
+        let Some(seed) = verification.committee else {
+            return Ok(());
+        };
+        if verification.excluded.iter().any(|did| did == oracle_did) {
+            return Err(VerificationError::NotInCommittee {
+                oracle_did: oracle_did.to_string(),
             }
+            .into());
+        }
 
-            verification.status = VerificationStatus::Completed(Box::new(record.clone()));
+        if verification.members.is_some()
+            || verification.tickets.iter().any(|(did, _, _)| did == oracle_did)
+        {
+            return Ok(());
+        }
 
-            info!(
-                request_id = %request_id,
-                passed = passed,
-                latency_ms = latency_ms,
-                "Verification completed"
-            );
+        let output = selector.verify_ticket(oracle_did, &seed, proof)?;
+        verification
+            .tickets
+            .push((oracle_did.to_string(), output, *proof));
 
-            Ok(VerificationStatus::Completed(Box::new(record)))
-        } else {
-            verification.status = VerificationStatus::InProgress {
-                votes_received,
-                votes_required,
-            };
-            Ok(verification.status.clone())
+        let eligible = seed.pool_size.saturating_sub(verification.excluded.len());
+        if verification.tickets.len() >= eligible {
+            Self::fix_committee(selector, verification);
         }
+        Ok(())
     }
 
-    /// Get verification status
-    pub async fn get_status(&self, request_id: &str) -> Option<VerificationStatus> {
-        let pending_map = self.pending.read().await;
-        pending_map.get(request_id).map(|v| v.status.clone())
+    /// Fix the committee from the tickets in once the window has closed
+    fn close_ticket_window(selector: &CommitteeSelector, verification: &mut PendingVerification) {
+        if verification.members.is_none()
+            && !verification.tickets.is_empty()
+            && verification.submitted_at.elapsed() >= selector.ticket_window()
+        {
+            Self::fix_committee(selector, verification);
+        }
     }
 
-    /// Clean up timed out verifications
-    #[instrument(skip(self))]
-    pub async fn cleanup_timeouts(&self) -> usize {
-        let mut pending_map = self.pending.write().await;
-        let timeout = Duration::from_millis(self.config.timeout_ms);
-
-        let timed_out: Vec<String> = pending_map
+    fn fix_committee(selector: &CommitteeSelector, verification: &mut PendingVerification) {
+        let tickets: Vec<(String, VrfOutput)> = verification
+            .tickets
             .iter()
-            .filter(|(_, v)| {
-                v.submitted_at.elapsed() > timeout
-                    && !matches!(v.status, VerificationStatus::Completed(_))
-            })
-            .map(|(k, _)| k.clone())
+            .map(|(did, output, _)| (did.clone(), *output))
             .collect();
+        let members = selector.select(&tickets);
 
-        for request_id in &timed_out {
-            if let Some(v) = pending_map.get_mut(request_id) {
-                v.status = VerificationStatus::Timeout;
-                warn!(request_id = %request_id, "Verification timed out");
+        info!(
+            request_id = %verification.request_id,
+            tickets = tickets.len(),
+            members = members.len(),
+            "Committee fixed"
+        );
+        verification.members = Some(members);
+    }
+
+    /// Build and sign the outcome once the tally is decided, returning the
+    /// append that persists it
+    async fn finalize_quorum(
+        &self,
+        request_id: &str,
+        verification: &mut PendingVerification,
+        passed: bool,
+    ) -> Result<(OutcomeRecord, PendingAppend)> {
+        // The side that decided the outcome signs the record
+        let (signers, shares): (Vec<String>, Vec<OracleShare>) = verification
+            .votes
+            .iter()
+            .zip(verification.shares.iter())
+            .filter(|(vote, _)| vote.approved == passed)
+            .map(|(vote, share)| (vote.oracle_did.clone(), share.clone()))
+            .unzip();
+
+        // A failure decided before a rejecting quorum formed carries no signature
+        let quorum_reached = signers.len() >= verification.threshold as usize;
+        let signature = if quorum_reached {
+            match self.aggregate(verification, passed, &shares) {
+                Ok(signature) => signature,
+                Err(e) => {
+                    error!(request_id = %request_id, error = %e, "FROST aggregation failed");
+                    verification.status =
+                        VerificationStatus::Failed(format!("signature aggregation failed: {}", e));
+                    return Err(e);
+                }
             }
-        }
+        } else {
+            [0u8; 64]
+        };
+
+        // Calculate latency
+        let latency_ms = self.observe_outcome(verification);
+
+        let approvals = verification.votes.iter().filter(|v| v.approved).count();
+
+        // Build verification result
+        let result = VerificationResult {
+            passed,
+            oracle_count: verification.votes.len() as u8,
+            quorum_reached,
+            latency_ms,
+            votes: verification.votes.clone(),
+            failure_reason: if passed {
+                None
+            } else {
+                Some(format!(
+                    "Quorum rejected action ({} of {} required approvals)",
+                    approvals, verification.threshold
+                ))
+            },
+        };
+
+        // Build FROST signature
+        let frost_sig = FrostSignature::new(
+            signature,
+            signers,
+            self.group_public_key,
+            verification.threshold,
+            verification
+                .members
+                .as_ref()
+                .map(|m| m.len().min(u8::MAX as usize) as u8)
+                .unwrap_or(self.config.oracle_count),
+        );
+
+        // Create outcome record
+        let record = OutcomeRecord::new(
+            verification.actor_did.clone(),
+            verification.client_did.clone(),
+            verification.action_type.clone(),
+            verification.input_hash,
+            verification.output_hash,
+            verification.compute_hc,
+            result,
+            frost_sig,
+        )
+        .with_verification_mode(verification.mode)
+        .with_challenged(verification.challenged);
+
+        let record = self.seal_record(record).await;
+        verification.status = VerificationStatus::Completed(Box::new(record.clone()));
+
+        info!(
+            request_id = %request_id,
+            passed = passed,
+            latency_ms = latency_ms,
+            "Verification completed"
+        );
 
-        timed_out.len()
+        let outcome = PendingAppend::outcome(&record, quorum_reached, signature);
+        Ok((record, outcome))
     }
 
-    /// Get group public key
-    pub fn group_public_key(&self) -> [u8; 32] {
-        self.group_public_key
+    /// Aggregate the deciding side's shares over the verdict message
+    fn aggregate(
+        &self,
+        verification: &PendingVerification,
+        passed: bool,
+        shares: &[OracleShare],
+    ) -> Result<[u8; 64]> {
+        let coordinator = self.coordinator.as_ref().ok_or_else(|| {
+            ActorisError::Config("no FROST coordinator configured".to_string())
+        })?;
+
+        let message = Self::pending_verdict_message(verification, passed);
+        let commitments: Vec<SigningCommitment> =
+            shares.iter().map(|s| s.commitment.clone()).collect();
+        let shares: Vec<SignatureShare> = shares.iter().map(|s| s.share.clone()).collect();
+
+        Ok(coordinator
+            .aggregate(&message, &commitments, &shares)?
+            .to_bytes())
     }
 
-    /// Get current Merkle root
-    pub async fn merkle_root(&self) -> Option<[u8; 32]> {
-        let tree = self.merkle_tree.read().await;
-        tree.root()
+    fn pending_verdict_message(verification: &PendingVerification, passed: bool) -> [u8; 32] {
+        verdict_message(
+            &verification.actor_did,
+            &verification.client_did,
+            &verification.action_type,
+            &verification.input_hash,
+            &verification.output_hash,
+            &verification.compute_hc,
+            passed,
+        )
+    }
+
+    /// Message an oracle signs when voting `approved` on a request
+    pub async fn verdict_message(&self, request_id: &str, approved: bool) -> Option<[u8; 32]> {
+        let pending_map = self.pending.read().await;
+        pending_map
+            .get(request_id)
+            .map(|v| Self::pending_verdict_message(v, approved))
+    }
+
+    /// Observe end-to-end latency for a final outcome, returning it in ms
+    fn observe_outcome(&self, verification: &mut PendingVerification) -> u32 {
+        let latency_ms = verification.first_submitted_at.elapsed().as_millis() as u64;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.latency_ms.observe(latency_ms as f64);
+            if !verification.sla_missed && VerificationMetrics::misses_sla(latency_ms) {
+                verification.sla_missed = true;
+                metrics.sla_misses.inc();
+            }
+        }
+
+        latency_ms.min(u32::MAX as u64) as u32
+    }
+
+    /// Mark a request as timed out, returning the append that persists it
+    fn mark_timed_out(
+        &self,
+        request_id: &str,
+        verification: &mut PendingVerification,
+    ) -> PendingAppend {
+        let approvals = verification.votes.iter().filter(|v| v.approved).count() as u8;
+        let rejections = verification.votes.len() as u8 - approvals;
+        let elapsed_ms = verification.submitted_at.elapsed().as_millis() as u64;
+
+        verification.status = VerificationStatus::Timeout;
+        warn!(
+            request_id = %request_id,
+            approvals = approvals,
+            rejections = rejections,
+            elapsed_ms = elapsed_ms,
+            retries = verification.retries,
+            "Verification timed out"
+        );
+
+        if let Some(metrics) = &self.metrics {
+            metrics.timeouts.inc();
+            let total_ms = verification.first_submitted_at.elapsed().as_millis() as u64;
+            if !verification.sla_missed && VerificationMetrics::misses_sla(total_ms) {
+                verification.sla_missed = true;
+                metrics.sla_misses.inc();
+            }
+        }
+
+        PendingAppend::TimedOut {
+            approvals,
+            rejections,
+            elapsed_ms,
+        }
+    }
+
+    /// Get the committee seed oracles evaluate for a request
+    pub async fn committee_seed(&self, request_id: &str) -> Option<CommitteeSeed> {
+        let pending_map = self.pending.read().await;
+        pending_map.get(request_id).and_then(|v| v.committee)
+    }
+
+    /// Get verification status
+    pub async fn get_status(&self, request_id: &str) -> Option<VerificationStatus> {
+        let pending_map = self.pending.read().await;
+        pending_map.get(request_id).map(|v| v.status.clone())
+    }
+
+    /// Clean up timed out verifications
+    #[instrument(skip(self))]
+    pub async fn cleanup_timeouts(&self) -> usize {
+        self.sweep_timeouts().await.len()
+    }
+
+    /// Mark open requests past their timeout, returning their IDs
+    #[instrument(skip(self))]
+    pub async fn sweep_timeouts(&self) -> Vec<String> {
+        let mut pending_map = self.pending.write().await;
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+
+        let timed_out: Vec<String> = pending_map
+            .iter()
+            .filter(|(_, v)| v.is_open() && v.submitted_at.elapsed() > timeout)
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        let appends: Vec<(&String, PendingAppend)> = timed_out
+            .iter()
+            .filter_map(|request_id| {
+                let v = pending_map.get_mut(request_id)?;
+                Some((request_id, self.mark_timed_out(request_id, v)))
+            })
+            .collect();
+        drop(pending_map);
+
+        for (request_id, append) in &appends {
+            if let Err(e) = self.persist(request_id, append).await {
+                error!(request_id = %request_id, error = %e, "Failed to persist timeout");
+            }
+        }
+
+        timed_out
+    }
+
+    /// Number of retries made and whether the request was escalated
+    pub async fn retry_state(&self, request_id: &str) -> Option<(u8, bool)> {
+        let pending_map = self.pending.read().await;
+        pending_map
+            .get(request_id)
+            .map(|v| (v.retries, v.escalated))
+    }
+
+    /// Re-dispatch a timed-out request to a fresh oracle set
+    ///
+    /// Committee requests draw a new committee salted with the retry count,
+    /// excluding every oracle that sat on a committee that timed out. Global
+    /// quorum requests have no other oracle set to turn to, so retrying them
+    /// fails and leaves the request timed out for escalation. Returns the
+    /// number of retries made so far.
+    #[instrument(skip(self))]
+    pub async fn retry(&self, request_id: &str) -> Result<u8> {
+        let mut pending_map = self.pending.write().await;
+        let verification = Self::timed_out_mut(&mut pending_map, request_id)?;
+
+        let selector = match (&self.committee, verification.committee) {
+            (Some(selector), Some(_)) => selector,
+            _ => {
+                return Err(ActorisError::Validation(format!(
+                    "request {} has no committee to re-draw; escalate it instead",
+                    request_id
+                )))
+            }
+        };
+
+        verification.retries = verification.retries.saturating_add(1);
+        if let Some(members) = verification.members.take() {
+            verification.excluded.extend(members);
+        }
+        let salted = format!("{}#retry{}", request_id, verification.retries);
+        verification.committee = Some(selector.draw(&salted));
+        verification.reopen();
+        let retries = verification.retries;
+        let committee_seed = verification.committee.map(|c| c.seed);
+        drop(pending_map);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.retries.inc();
+        }
+
+        info!(
+            request_id = %request_id,
+            retries = retries,
+            "Verification re-dispatched"
+        );
+
+        self.persist(
+            request_id,
+            &PendingAppend::Retried {
+                attempt: retries,
+                committee_seed,
+            },
+        )
+        .await?;
+        Ok(retries)
+    }
+
+    /// Escalate a timed-out request to a fallback tier
+    #[instrument(skip(self))]
+    pub async fn escalate(&self, request_id: &str, tier: EscalationTier) -> Result<()> {
+        let mut pending_map = self.pending.write().await;
+        let verification = Self::timed_out_mut(&mut pending_map, request_id)?;
+
+        verification.escalated = true;
+        match tier {
+            EscalationTier::HumanReview => {
+                verification.status = VerificationStatus::Escalated;
+            }
+            EscalationTier::LargerQuorum { threshold } => {
+                // The whole registered pool votes under the raised threshold
+                verification.threshold = threshold.max(verification.threshold);
+                verification.committee = None;
+                verification.reopen();
+            }
+        }
+
+        let retries = verification.retries;
+        drop(pending_map);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.escalations.inc();
+        }
+
+        warn!(
+            request_id = %request_id,
+            tier = tier.as_str(),
+            retries = retries,
+            "Verification escalated"
+        );
+
+        self.persist(
+            request_id,
+            &PendingAppend::Escalated {
+                tier: tier.as_str(),
+                retries,
+            },
+        )
+        .await
+    }
+
+    /// Resolve a request escalated to human review
+    #[instrument(skip(self))]
+    pub async fn resolve_review(
+        &self,
+        request_id: &str,
+        reviewer_did: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<VerificationStatus> {
+        if !self.reviewers.contains(reviewer_did) {
+            return Err(VerificationError::UnknownReviewer {
+                reviewer_did: reviewer_did.to_string(),
+            }
+            .into());
+        }
+
+        // Check the request, persist the review without holding the pending
+        // map, then check again in case another reviewer got there first
+        let awaiting_review = |pending_map: &mut HashMap<String, PendingVerification>| {
+            let verification = pending_map.get_mut(request_id).ok_or_else(|| {
+                ActorisError::from(VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })
+            })?;
+            if verification.status != VerificationStatus::Escalated {
+                return Err(ActorisError::Validation(format!(
+                    "request {} is not awaiting review",
+                    request_id
+                )));
+            }
+            Ok(())
+        };
+        awaiting_review(&mut *self.pending.write().await)?;
+        if let Some(es) = &self.eventstore {
+            es.record_oracle_vote(request_id, reviewer_did, approved, reason.as_deref())
+                .await?;
+        }
+
+        let mut pending_map = self.pending.write().await;
+        awaiting_review(&mut pending_map)?;
+        let verification = pending_map
+            .get_mut(request_id)
+            .expect("checked to be awaiting review");
+
+        verification.votes.push(OracleVote {
+            oracle_did: reviewer_did.to_string(),
+            approved,
+            reason: reason.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            vrf_proof: None,
+        });
+
+        let latency_ms = self.observe_outcome(verification);
+
+        let result = VerificationResult {
+            passed: approved,
+            oracle_count: verification.votes.len() as u8,
+            quorum_reached: false,
+            latency_ms,
+            votes: verification.votes.clone(),
+            failure_reason: if approved {
+                None
+            } else {
+                Some(format!(
+                    "Rejected on human review: {}",
+                    reason.unwrap_or_default()
+                ))
+            },
+        };
+
+        // The reviewer is recorded as the sole signer; there is no threshold signature
+        let frost_sig = FrostSignature::new(
+            [0u8; 64],
+            vec![reviewer_did.to_string()],
+            self.group_public_key,
+            verification.threshold,
+            self.config.oracle_count,
+        );
+
+        let record = OutcomeRecord::new(
+            verification.actor_did.clone(),
+            verification.client_did.clone(),
+            verification.action_type.clone(),
+            verification.input_hash,
+            verification.output_hash,
+            verification.compute_hc,
+            result,
+            frost_sig,
+        )
+        .with_verification_mode(verification.mode)
+        .with_challenged(verification.challenged);
+
+        let record = self.seal_record(record).await;
+        verification.status = VerificationStatus::Completed(Box::new(record.clone()));
+        drop(pending_map);
+
+        info!(
+            request_id = %request_id,
+            reviewer = %reviewer_did,
+            approved = approved,
+            "Escalated verification resolved by review"
+        );
+
+        self.persist(
+            request_id,
+            &PendingAppend::outcome(&record, false, [0u8; 64]),
+        )
+        .await?;
+        Ok(VerificationStatus::Completed(Box::new(record)))
+    }
+
+    /// Look up a request that must currently be timed out
+    fn timed_out_mut<'a>(
+        pending_map: &'a mut HashMap<String, PendingVerification>,
+        request_id: &str,
+    ) -> Result<&'a mut PendingVerification> {
+        let verification =
+            pending_map
+                .get_mut(request_id)
+                .ok_or_else(|| VerificationError::RequestNotFound {
+                    request_id: request_id.to_string(),
+                })?;
+
+        if verification.status != VerificationStatus::Timeout {
+            return Err(ActorisError::Validation(format!(
+                "request {} has not timed out",
+                request_id
+            )));
+        }
+
+        Ok(verification)
+    }
+
+    /// Get group public key
+    pub fn group_public_key(&self) -> [u8; 32] {
+        self.group_public_key
+    }
+
+    /// Get current Merkle root
+    pub async fn merkle_root(&self) -> Option<[u8; 32]> {
+        let tree = self.merkle_tree.read().await;
+        tree.root()
     }
 }
 
@@ -365,6 +1428,7 @@ impl Default for ActionVerifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use actoris_common::crypto::frost::{FrostKeyShare, FrostSigner};
     use rust_decimal_macros::dec;
 
     #[tokio::test]
@@ -389,14 +1453,73 @@ mod tests {
         assert!(matches!(status, Some(VerificationStatus::Pending)));
     }
 
-    #[tokio::test]
-    async fn test_quorum_completion() {
+    /// 2-of-3 FROST keys from a trusted dealer
+    fn test_keys() -> (Arc<FrostCoordinator>, Vec<FrostKeyShare>) {
+        let (shares, public_key_package) = frost_ed25519::keys::generate_with_dealer(
+            3,
+            2,
+            frost_ed25519::keys::IdentifierList::Default,
+            rand::rngs::OsRng,
+        )
+        .unwrap();
+        let keys = shares
+            .into_iter()
+            .map(|(id, share)| {
+                let key_package = frost_ed25519::keys::KeyPackage::try_from(share).unwrap();
+                FrostKeyShare::new(id, key_package, public_key_package.clone())
+            })
+            .collect();
+        (Arc::new(FrostCoordinator::new(public_key_package, 2)), keys)
+    }
+
+    /// Run both FROST rounds among `keys` over `message`
+    async fn sign_shares(keys: &[&FrostKeyShare], message: &[u8]) -> Vec<OracleShare> {
+        let signers: Vec<FrostSigner> = keys
+            .iter()
+            .map(|key| FrostSigner::new((*key).clone()))
+            .collect();
+
+        let mut commitments = Vec::new();
+        for signer in &signers {
+            commitments.push(signer.start_signing("test", message).await.unwrap());
+        }
+        for signer in &signers {
+            for commitment in &commitments {
+                signer
+                    .add_commitment("test", commitment.clone())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let mut shares = Vec::new();
+        for (signer, commitment) in signers.iter().zip(commitments) {
+            shares.push(OracleShare {
+                commitment,
+                share: signer.sign("test").await.unwrap(),
+            });
+        }
+        shares
+    }
+
+    /// A well-formed share from `keys[i]` for votes that won't be aggregated
+    async fn unused_share(keys: &[FrostKeyShare], i: usize) -> OracleShare {
+        let other = &keys[(i + 1) % keys.len()];
+        sign_shares(&[&keys[i], other], b"unused").await.remove(0)
+    }
+
+    /// Verifier with a 2-of-3 quorum of registered oracles and one pending request
+    async fn verifier_with_request() -> (ActionVerifier, String, Vec<FrostKeyShare>) {
         let config = VerifierConfig {
             timeout_ms: 5000,
             quorum_threshold: 2,
             oracle_count: 3,
         };
-        let verifier = ActionVerifier::new(config);
+        let (coordinator, keys) = test_keys();
+        let verifier = ActionVerifier::new(config).with_frost(coordinator);
+        for i in 0..3 {
+            verifier.register_oracle(&format!("did:key:oracle{}", i));
+        }
 
         let request_id = verifier
             .submit_action(
@@ -410,27 +1533,746 @@ mod tests {
             .await
             .unwrap();
 
-        // Simulate oracle votes
-        let key_shares = frost::generate_test_key_shares(2, 3).unwrap();
+        (verifier, request_id, keys)
+    }
 
-        for (i, share) in key_shares.iter().take(2).enumerate() {
-            let mut signer = frost::FrostSigner::new(share.clone());
-            let partial = signer.start_signing(b"input").unwrap();
+    #[tokio::test]
+    async fn test_quorum_completion() {
+        let (verifier, request_id, keys) = verifier_with_request().await;
+
+        let message = verifier.verdict_message(&request_id, true).await.unwrap();
+        let shares = sign_shares(&[&keys[0], &keys[1]], &message).await;
 
+        for (i, share) in shares.into_iter().enumerate() {
             let status = verifier
                 .record_vote(
                     &request_id,
                     &format!("did:key:oracle{}", i),
                     true,
                     None,
-                    partial,
+                    share,
+                    None,
                 )
                 .await
                 .unwrap();
 
             if i == 1 {
-                assert!(matches!(status, VerificationStatus::Completed(_)));
+                let record = match status {
+                    VerificationStatus::Completed(record) => record,
+                    other => panic!("expected completed, got {:?}", other),
+                };
+                assert!(record.is_verified());
+                assert!(record.verify_signature());
+                assert_eq!(record.signature.group_key, verifier.group_public_key());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quorum_without_coordinator_fails() {
+        let verifier = ActionVerifier::new(VerifierConfig {
+            timeout_ms: 5000,
+            quorum_threshold: 2,
+            oracle_count: 3,
+        });
+        for i in 0..3 {
+            verifier.register_oracle(&format!("did:key:oracle{}", i));
+        }
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+
+        let (_, keys) = test_keys();
+        let message = verifier.verdict_message(&request_id, true).await.unwrap();
+        let mut shares = sign_shares(&[&keys[0], &keys[1]], &message).await;
+
+        verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle0",
+                true,
+                None,
+                shares.remove(0),
+                None,
+            )
+            .await
+            .unwrap();
+        let result = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle1",
+                true,
+                None,
+                shares.remove(0),
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(ActorisError::Config(_))));
+        assert!(matches!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Failed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_vote_validation_errors() {
+        let (verifier, request_id, keys) = verifier_with_request().await;
+        let share = unused_share(&keys, 0).await;
+
+        // Unknown request
+        let result = verifier
+            .record_vote(
+                "missing",
+                "did:key:oracle0",
+                true,
+                None,
+                share.clone(),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ActorisError::Verification(
+                VerificationError::RequestNotFound { .. }
+            ))
+        ));
+
+        // Unregistered oracle
+        let result = verifier
+            .record_vote(
+                &request_id,
+                "did:key:intruder",
+                true,
+                None,
+                share.clone(),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ActorisError::Verification(
+                VerificationError::UnknownOracle { .. }
+            ))
+        ));
+
+        // One vote per oracle
+        verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle0",
+                true,
+                None,
+                share.clone(),
+                None,
+            )
+            .await
+            .unwrap();
+        let result = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle0",
+                true,
+                None,
+                share.clone(),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ActorisError::Verification(
+                VerificationError::DuplicateVote { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejections_fail_early() {
+        let (verifier, request_id, keys) = verifier_with_request().await;
+
+        // The rejecting side signs the failed verdict
+        let message = verifier.verdict_message(&request_id, false).await.unwrap();
+        let mut shares = sign_shares(&[&keys[0], &keys[1]], &message).await;
+
+        let status = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle0",
+                false,
+                None,
+                shares.remove(0),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(status, VerificationStatus::InProgress { .. }));
+
+        // Two rejections out of three leave at most one approval
+        let status = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle1",
+                false,
+                None,
+                shares.remove(0),
+                None,
+            )
+            .await
+            .unwrap();
+        let record = match status {
+            VerificationStatus::Completed(record) => record,
+            other => panic!("expected completed, got {:?}", other),
+        };
+        assert!(!record.verification.passed);
+        assert!(!record.is_verified());
+        assert!(record.verification.quorum_reached);
+        assert!(record.verify_signature());
+        assert_eq!(record.verification.oracle_count, 2);
+
+        // Late votes don't change the outcome
+        let status = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle2",
+                true,
+                None,
+                unused_share(&keys, 2).await,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(status, VerificationStatus::Completed(r) if !r.verification.passed));
+    }
+
+    #[tokio::test]
+    async fn test_mixed_votes_pass_on_approvals() {
+        let (verifier, request_id, keys) = verifier_with_request().await;
+
+        let message = verifier.verdict_message(&request_id, true).await.unwrap();
+        let mut approvals = sign_shares(&[&keys[0], &keys[2]], &message).await;
+        let mut shares = vec![approvals.remove(0), unused_share(&keys, 1).await];
+
+        let votes = [("did:key:oracle0", true), ("did:key:oracle1", false)];
+        for (oracle, approved) in votes.iter() {
+            let status = verifier
+                .record_vote(
+                    &request_id,
+                    oracle,
+                    *approved,
+                    None,
+                    shares.remove(0),
+                    None,
+                )
+                .await
+                .unwrap();
+            assert!(matches!(status, VerificationStatus::InProgress { .. }));
+        }
+
+        let status = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle2",
+                true,
+                None,
+                approvals.remove(0),
+                None,
+            )
+            .await
+            .unwrap();
+        let record = match status {
+            VerificationStatus::Completed(record) => record,
+            other => panic!("expected completed, got {:?}", other),
+        };
+        assert!(record.verification.passed);
+        assert!(record.verify_signature());
+        assert_eq!(
+            record.signature.signers,
+            vec!["did:key:oracle0".to_string(), "did:key:oracle2".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_optimistic_acceptance_and_challenge() {
+        use actoris_common::{DataSensitivity, TaskComplexity};
+
+        let policy = VerificationPolicy::default().with_action_floor(
+            "test.action",
+            TaskComplexity::Low,
+            DataSensitivity::Public,
+        );
+        let verifier = ActionVerifier::default().with_policy(policy);
+        let risk = RiskProfile::new(dec!(1), 900)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+
+        let request_id = verifier
+            .submit_action_with_risk(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                &risk,
+            )
+            .await
+            .unwrap();
+
+        // Accepted immediately without oracle votes
+        let record = match verifier.get_status(&request_id).await {
+            Some(VerificationStatus::Completed(record)) => record,
+            other => panic!("expected completed, got {:?}", other),
+        };
+        assert!(matches!(
+            record.verification_mode,
+            VerificationMode::Optimistic { .. }
+        ));
+        assert!(record.is_verified());
+        assert!(record.verification.votes.is_empty());
+
+        // Strangers without a bond cannot challenge
+        assert!(matches!(
+            verifier
+                .challenge(&request_id, "did:key:stranger", "output is wrong")
+                .await,
+            Err(ActorisError::Verification(
+                VerificationError::ChallengeRejected { .. }
+            ))
+        ));
+
+        // Challenge reopens the action for a full quorum
+        verifier
+            .challenge(&request_id, "did:key:client", "output is wrong")
+            .await
+            .unwrap();
+        assert!(matches!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Pending)
+        ));
+
+        // A second challenge is rejected
+        assert!(verifier
+            .challenge(&request_id, "did:key:client", "again")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bonded_challenger() {
+        use actoris_common::{DataSensitivity, TaskComplexity};
+
+        struct Bonds;
+        impl ChallengeBonds for Bonds {
+            fn bonded_hc(&self, did: &str) -> Option<Decimal> {
+                match did {
+                    "did:key:watcher" => Some(dec!(10)),
+                    "did:key:underbonded" => Some(dec!(1)),
+                    _ => None,
+                }
             }
         }
+
+        let policy = VerificationPolicy::default().with_action_floor(
+            "test.action",
+            TaskComplexity::Low,
+            DataSensitivity::Public,
+        );
+        let verifier = ActionVerifier::default()
+            .with_policy(policy)
+            .with_challenge_bonds(Arc::new(Bonds));
+        let risk = RiskProfile::new(dec!(1), 900)
+            .with_complexity(TaskComplexity::Low)
+            .with_sensitivity(DataSensitivity::Public);
+        let request_id = verifier
+            .submit_action_with_risk(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                &risk,
+            )
+            .await
+            .unwrap();
+
+        // A bond below the policy minimum does not qualify
+        assert!(verifier
+            .challenge(&request_id, "did:key:underbonded", "disputed")
+            .await
+            .is_err());
+
+        verifier
+            .challenge(&request_id, "did:key:watcher", "disputed")
+            .await
+            .unwrap();
+        assert!(matches!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Pending)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sampling_and_full_quorum_modes() {
+        // Rate 0: sampled actions are never sent to the quorum
+        let policy = VerificationPolicy {
+            sample_rate: 0.0,
+            ..Default::default()
+        };
+        let verifier = ActionVerifier::default().with_policy(policy);
+
+        let sampled = RiskProfile::new(dec!(10), 800);
+        let request_id = verifier
+            .submit_action_with_risk(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"in",
+                b"out",
+                &sampled,
+            )
+            .await
+            .unwrap();
+        match verifier.get_status(&request_id).await {
+            Some(VerificationStatus::Completed(record)) => assert_eq!(
+                record.verification_mode,
+                VerificationMode::Sampled {
+                    rate: 0.0,
+                    sampled: false
+                }
+            ),
+            other => panic!("expected completed, got {:?}", other),
+        }
+
+        // Optimistic-only challenges
+        assert!(verifier
+            .challenge(&request_id, "did:key:client", "disputed")
+            .await
+            .is_err());
+
+        // High-value actions wait for the quorum
+        let high_value = RiskProfile::new(dec!(500), 800);
+        let request_id = verifier
+            .submit_action_with_risk(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"in",
+                b"out",
+                &high_value,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Pending)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_committee_formed_from_lowest_tickets() {
+        use ed25519_dalek::SigningKey;
+        use rand::rngs::OsRng;
+
+        // Two of four oracles serve; the window only closes once all tickets are in
+        let selector = Arc::new(
+            CommitteeSelector::new(2).with_ticket_window(Duration::from_secs(60)),
+        );
+        let oracle_keys: Vec<SigningKey> =
+            (0..4).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        for (i, key) in oracle_keys.iter().enumerate() {
+            selector.register_oracle(
+                &format!("did:key:oracle{}", i),
+                key.verifying_key().to_bytes(),
+            );
+        }
+        selector.set_block_hash([9u8; 32]);
+
+        let config = VerifierConfig {
+            timeout_ms: 5000,
+            quorum_threshold: 2,
+            oracle_count: 4,
+        };
+        let verifier = ActionVerifier::new(config).with_committee(selector.clone());
+
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+
+        let seed = verifier.committee_seed(&request_id).await.unwrap();
+        assert_eq!(seed.block_hash, [9u8; 32]);
+
+        let (_, keys) = test_keys();
+        let partial = unused_share(&keys, 0).await;
+
+        // No votes before the committee is fixed
+        let result = verifier
+            .record_vote(
+                &request_id,
+                "did:key:oracle0",
+                true,
+                None,
+                partial.clone(),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ActorisError::Verification(VerificationError::CommitteePending { .. }))
+        ));
+
+        // Another oracle's ticket is rejected
+        let wrong = selector.sortition(&oracle_keys[1], &seed);
+        assert!(verifier
+            .submit_ticket(&request_id, "did:key:oracle0", &wrong)
+            .await
+            .is_err());
+
+        let mut members = None;
+        for (i, key) in oracle_keys.iter().enumerate() {
+            let ticket = selector.sortition(key, &seed);
+            members = verifier
+                .submit_ticket(&request_id, &format!("did:key:oracle{}", i), &ticket)
+                .await
+                .unwrap();
+        }
+        let members = members.unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(verifier.committee_members(&request_id).await, Some(members.clone()));
+
+        // Outsiders can't vote
+        let outsider = (0..4)
+            .map(|i| format!("did:key:oracle{}", i))
+            .find(|did| !members.contains(did))
+            .unwrap();
+        let result = verifier
+            .record_vote(&request_id, &outsider, true, None, partial.clone(), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(ActorisError::Verification(VerificationError::NotInCommittee { .. }))
+        ));
+
+        // Members vote with their ticket attached
+        let status = verifier
+            .record_vote(&request_id, &members[0], true, None, partial, None)
+            .await
+            .unwrap();
+        assert!(matches!(
+            status,
+            VerificationStatus::InProgress {
+                votes_received: 1,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_excludes_timed_out_committee() {
+        use ed25519_dalek::SigningKey;
+        use rand::rngs::OsRng;
+
+        let selector = Arc::new(
+            CommitteeSelector::new(2).with_ticket_window(Duration::from_secs(60)),
+        );
+        let oracle_keys: Vec<SigningKey> =
+            (0..4).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        for (i, key) in oracle_keys.iter().enumerate() {
+            selector.register_oracle(
+                &format!("did:key:oracle{}", i),
+                key.verifying_key().to_bytes(),
+            );
+        }
+
+        let config = VerifierConfig {
+            timeout_ms: 10,
+            quorum_threshold: 2,
+            oracle_count: 4,
+        };
+        let verifier = ActionVerifier::new(config).with_committee(selector.clone());
+
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+
+        let seed = verifier.committee_seed(&request_id).await.unwrap();
+        let mut first = None;
+        for (i, key) in oracle_keys.iter().enumerate() {
+            let ticket = selector.sortition(key, &seed);
+            first = verifier
+                .submit_ticket(&request_id, &format!("did:key:oracle{}", i), &ticket)
+                .await
+                .unwrap();
+        }
+        let first = first.unwrap();
+
+        // The first committee never votes
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(verifier.sweep_timeouts().await, vec![request_id.clone()]);
+        verifier.retry(&request_id).await.unwrap();
+
+        let seed = verifier.committee_seed(&request_id).await.unwrap();
+        let mut second = None;
+        for (i, key) in oracle_keys.iter().enumerate() {
+            let did = format!("did:key:oracle{}", i);
+            let ticket = selector.sortition(key, &seed);
+            let result = verifier.submit_ticket(&request_id, &did, &ticket).await;
+            if first.contains(&did) {
+                assert!(matches!(
+                    result,
+                    Err(ActorisError::Verification(VerificationError::NotInCommittee { .. }))
+                ));
+            } else {
+                second = result.unwrap();
+            }
+        }
+
+        // The re-draw is fixed once every eligible oracle has a ticket in
+        let second = second.unwrap();
+        assert_eq!(second.len(), 2);
+        assert!(second.iter().all(|did| !first.contains(did)));
+    }
+
+    #[tokio::test]
+    async fn test_global_quorum_retry_fails() {
+        let config = VerifierConfig {
+            timeout_ms: 10,
+            quorum_threshold: 2,
+            oracle_count: 3,
+        };
+        let verifier = ActionVerifier::new(config);
+        for i in 0..3 {
+            verifier.register_oracle(&format!("did:key:oracle{}", i));
+        }
+
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(verifier.sweep_timeouts().await, vec![request_id.clone()]);
+
+        // The pool that just timed out is the only one there is
+        assert!(matches!(
+            verifier.retry(&request_id).await,
+            Err(ActorisError::Validation(_))
+        ));
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Timeout)
+        );
+        assert_eq!(verifier.retry_state(&request_id).await, Some((0, false)));
+
+        verifier
+            .escalate(&request_id, EscalationTier::HumanReview)
+            .await
+            .unwrap();
+        assert_eq!(
+            verifier.get_status(&request_id).await,
+            Some(VerificationStatus::Escalated)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_committee_failure_counts_members_not_pool() {
+        use ed25519_dalek::SigningKey;
+        use rand::rngs::OsRng;
+
+        // Three of five oracles serve with a 2-of-3 threshold
+        let selector = Arc::new(CommitteeSelector::new(3));
+        let oracle_keys: Vec<SigningKey> =
+            (0..5).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        for (i, key) in oracle_keys.iter().enumerate() {
+            selector.register_oracle(
+                &format!("did:key:oracle{}", i),
+                key.verifying_key().to_bytes(),
+            );
+        }
+
+        let (coordinator, keys) = test_keys();
+        let config = VerifierConfig {
+            timeout_ms: 5000,
+            quorum_threshold: 2,
+            oracle_count: 5,
+        };
+        let verifier = ActionVerifier::new(config)
+            .with_committee(selector.clone())
+            .with_frost(coordinator);
+
+        let request_id = verifier
+            .submit_action(
+                "did:key:actor",
+                "did:key:client",
+                "test.action",
+                b"input",
+                b"output",
+                dec!(5),
+            )
+            .await
+            .unwrap();
+
+        let seed = verifier.committee_seed(&request_id).await.unwrap();
+        let mut members = None;
+        for (i, key) in oracle_keys.iter().enumerate() {
+            members = verifier
+                .submit_ticket(
+                    &request_id,
+                    &format!("did:key:oracle{}", i),
+                    &selector.sortition(key, &seed),
+                )
+                .await
+                .unwrap();
+        }
+        let members = members.unwrap();
+
+        let message = verifier.verdict_message(&request_id, false).await.unwrap();
+        let mut shares = sign_shares(&[&keys[0], &keys[1]], &message).await;
+
+        let status = verifier
+            .record_vote(&request_id, &members[0], false, None, shares.remove(0), None)
+            .await
+            .unwrap();
+        assert!(matches!(status, VerificationStatus::InProgress { .. }));
+
+        // Two rejections of three members leave at most one approval
+        let status = verifier
+            .record_vote(&request_id, &members[1], false, None, shares.remove(0), None)
+            .await
+            .unwrap();
+        let record = match status {
+            VerificationStatus::Completed(record) => record,
+            other => panic!("expected completed, got {:?}", other),
+        };
+        assert!(!record.verification.passed);
+        assert_eq!(record.signature.total_oracles, 3);
     }
 }